@@ -4,22 +4,30 @@
 //
 
 use anyhow::*;
-use attestation_agent::{AttestationAPIs, AttestationAgent, RuntimeMeasurement};
+use attestation_agent::{
+    config::Config,
+    proto::grpc::{evidence_metrics_response, platform_report_response},
+    AttestationAPIs, AttestationAgent, RuntimeMeasurement,
+};
 use log::{debug, error};
 use protos::grpc::aa::attestation_agent::{
     attestation_agent_service_server::{AttestationAgentService, AttestationAgentServiceServer},
-    BindInitDataRequest, BindInitDataResponse, ExtendRuntimeMeasurementRequest,
-    ExtendRuntimeMeasurementResponse, GetAdditionalEvidenceRequest, GetEvidenceRequest,
-    GetEvidenceResponse, GetTeeTypeRequest, GetTeeTypeResponse, GetTokenRequest, GetTokenResponse,
-    RuntimeMeasurementResult,
+    BindInitDataRequest, BindInitDataResponse, DeriveSealingKeyRequest, DeriveSealingKeyResponse,
+    ExtendRuntimeMeasurementRequest, ExtendRuntimeMeasurementResponse,
+    ExtendRuntimeMeasurementsRequest, GetAdditionalEvidenceRequest, GetEventlogRequest,
+    GetEventlogResponse, GetEvidenceMetricsRequest, GetEvidenceMetricsResponse, GetEvidenceRequest,
+    GetEvidenceResponse, GetInitdataRequest, GetInitdataResponse, GetPlatformReportRequest,
+    GetPlatformReportResponse, GetTeeTypeRequest, GetTeeTypeResponse, GetTokenRequest,
+    GetTokenResponse, RegisterReplayResult, RuntimeMeasurementResult, UpdateConfigRequest,
+    UpdateConfigResponse, VerifyEventlogRequest, VerifyEventlogResponse,
 };
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 use tonic::{transport::Server, Request, Response, Status};
 
 pub const AGENT_NAME: &str = "attestation-agent";
 
 pub struct AA {
-    inner: AttestationAgent,
+    inner: Arc<AttestationAgent>,
 }
 
 #[tonic::async_trait]
@@ -32,14 +40,14 @@ impl AttestationAgentService for AA {
 
         debug!("AA (grpc): get token ...");
 
-        let token = self
-            .inner
-            .get_token(&request.token_type)
-            .await
-            .map_err(|e| {
-                error!("AA (grpc): get token failed:\n{e:?}");
-                Status::internal(format!("[ERROR:{AGENT_NAME}] AA get token failed"))
-            })?;
+        let token = match request.refresh {
+            true => self.inner.refresh_token(&request.token_type).await,
+            false => self.inner.get_token(&request.token_type).await,
+        }
+        .map_err(|e| {
+            error!("AA (grpc): get token failed:\n{e:?}");
+            Status::internal(format!("[ERROR:{AGENT_NAME}] AA get token failed"))
+        })?;
 
         debug!("AA (grpc): Get token successfully!");
 
@@ -135,6 +143,50 @@ impl AttestationAgentService for AA {
         Result::Ok(Response::new(reply))
     }
 
+    async fn extend_runtime_measurements(
+        &self,
+        request: Request<ExtendRuntimeMeasurementsRequest>,
+    ) -> Result<Response<ExtendRuntimeMeasurementResponse>, Status> {
+        let request = request.into_inner();
+
+        debug!("AA (grpc): extend runtime measurements ...");
+
+        let events = request
+            .events
+            .into_iter()
+            .map(attestation_agent::RuntimeMeasurementEvent::try_from)
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| {
+                error!("AA (grpc): invalid runtime measurement event:\n{e:?}");
+                Status::invalid_argument(format!(
+                    "[ERROR:{AGENT_NAME}] AA invalid runtime measurement event"
+                ))
+            })?;
+
+        let res = self
+            .inner
+            .extend_runtime_measurements(events)
+            .await
+            .map_err(|e| {
+                error!("AA (grpc): extend runtime measurements failed:\n{e:?}");
+                Status::internal(format!(
+                    "[ERROR:{AGENT_NAME}] AA extend runtime measurements failed"
+                ))
+            })?;
+
+        debug!("AA (grpc): extend runtime measurements succeeded.");
+
+        let reply = ExtendRuntimeMeasurementResponse {
+            result: match res {
+                RuntimeMeasurement::Ok => RuntimeMeasurementResult::Ok.into(),
+                RuntimeMeasurement::NotSupported => RuntimeMeasurementResult::NotSupported.into(),
+                RuntimeMeasurement::NotEnabled => RuntimeMeasurementResult::NotEnabled.into(),
+            },
+        };
+
+        Result::Ok(Response::new(reply))
+    }
+
     async fn bind_init_data(
         &self,
         request: Request<BindInitDataRequest>,
@@ -158,6 +210,30 @@ impl AttestationAgentService for AA {
         Result::Ok(Response::new(reply))
     }
 
+    async fn derive_sealing_key(
+        &self,
+        request: Request<DeriveSealingKeyRequest>,
+    ) -> Result<Response<DeriveSealingKeyResponse>, Status> {
+        let request = request.into_inner();
+
+        debug!("AA (grpc): derive sealing key ...");
+
+        let key = self
+            .inner
+            .derive_sealing_key(&request.purpose)
+            .await
+            .map_err(|e| {
+                error!("AA (grpc): derive sealing key failed:\n{e:?}");
+                Status::internal(format!("[ERROR:{AGENT_NAME}] AA derive sealing key failed"))
+            })?;
+
+        debug!("AA (grpc): Derive sealing key successfully!");
+
+        let reply = DeriveSealingKeyResponse { key };
+
+        Result::Ok(Response::new(reply))
+    }
+
     async fn get_tee_type(
         &self,
         _request: Request<GetTeeTypeRequest>,
@@ -180,9 +256,169 @@ impl AttestationAgentService for AA {
 
         Result::Ok(Response::new(reply))
     }
+
+    async fn get_eventlog(
+        &self,
+        _request: Request<GetEventlogRequest>,
+    ) -> Result<Response<GetEventlogResponse>, Status> {
+        debug!("AA (grpc): get eventlog ...");
+
+        let eventlog = self.inner.get_eventlog().await.map_err(|e| {
+            error!("AA (grpc): get eventlog failed:\n{e:?}");
+            Status::internal(format!("[ERROR:{AGENT_NAME}] AA get eventlog failed"))
+        })?;
+
+        debug!("AA (grpc): get eventlog succeeded.");
+
+        let reply = GetEventlogResponse { eventlog };
+
+        Result::Ok(Response::new(reply))
+    }
+
+    async fn verify_eventlog(
+        &self,
+        _request: Request<VerifyEventlogRequest>,
+    ) -> Result<Response<VerifyEventlogResponse>, Status> {
+        debug!("AA (grpc): verify eventlog ...");
+
+        let verification = self.inner.verify_eventlog().await.map_err(|e| {
+            error!("AA (grpc): verify eventlog failed:\n{e:?}");
+            Status::internal(format!("[ERROR:{AGENT_NAME}] AA verify eventlog failed"))
+        })?;
+
+        let hash_algorithm = serde_json::to_string(&verification.hash_algorithm)
+            .map_err(|e| {
+                error!("AA (grpc): verify eventlog failed:\n{e:?}");
+                Status::internal(format!("[ERROR:{AGENT_NAME}] AA verify eventlog failed"))
+            })?
+            .trim_end_matches('"')
+            .trim_start_matches('"')
+            .to_string();
+
+        debug!("AA (grpc): verify eventlog succeeded.");
+
+        let reply = VerifyEventlogResponse {
+            verified: verification.verified(),
+            hash_algorithm,
+            registers: verification
+                .registers
+                .into_iter()
+                .map(|register| RegisterReplayResult {
+                    register_index: register.register_index,
+                    matched: register.matched(),
+                    replayed: register.replayed,
+                    actual: register.actual,
+                })
+                .collect(),
+        };
+
+        Result::Ok(Response::new(reply))
+    }
+
+    async fn get_initdata(
+        &self,
+        _request: Request<GetInitdataRequest>,
+    ) -> Result<Response<GetInitdataResponse>, Status> {
+        debug!("AA (grpc): get initdata ...");
+
+        let initdata = self.inner.get_initdata().await.map_err(|e| {
+            error!("AA (grpc): get initdata failed:\n{e:?}");
+            Status::internal(format!("[ERROR:{AGENT_NAME}] AA get initdata failed"))
+        })?;
+
+        let mut reply = GetInitdataResponse::default();
+        if let Some(initdata) = initdata {
+            let algorithm = serde_json::to_string(&initdata.initdata.algorithm)
+                .map_err(|e| {
+                    error!("AA (grpc): get initdata failed:\n{e:?}");
+                    Status::internal(format!("[ERROR:{AGENT_NAME}] AA get initdata failed"))
+                })?
+                .trim_end_matches('"')
+                .trim_start_matches('"')
+                .to_string();
+
+            reply.toml = initdata.toml;
+            reply.digest = initdata.digest;
+            reply.algorithm = algorithm;
+        }
+
+        debug!("AA (grpc): get initdata succeeded.");
+
+        Result::Ok(Response::new(reply))
+    }
+
+    async fn update_config(
+        &self,
+        request: Request<UpdateConfigRequest>,
+    ) -> Result<Response<UpdateConfigResponse>, Status> {
+        let request = request.into_inner();
+
+        debug!("AA (grpc): update config ...");
+
+        let config = Config::from_toml_str(&request.config).map_err(|e| {
+            error!("AA (grpc): invalid config:\n{e:?}");
+            Status::invalid_argument(format!("[ERROR:{AGENT_NAME}] AA invalid config"))
+        })?;
+
+        self.inner.update_config(config).await.map_err(|e| {
+            error!("AA (grpc): update config failed:\n{e:?}");
+            Status::invalid_argument(format!("[ERROR:{AGENT_NAME}] AA update config failed"))
+        })?;
+
+        debug!("AA (grpc): update config succeeded.");
+
+        Result::Ok(Response::new(UpdateConfigResponse {}))
+    }
+
+    async fn get_platform_report(
+        &self,
+        _request: Request<GetPlatformReportRequest>,
+    ) -> Result<Response<GetPlatformReportResponse>, Status> {
+        debug!("AA (grpc): get platform report ...");
+
+        let reply = self
+            .inner
+            .get_platform_report()
+            .await
+            .and_then(|report| platform_report_response(report, self.inner.get_tee_type()))
+            .map_err(|e| {
+                error!(
+                    "AA (grpc): get platform report failed:
+{e:?}"
+                );
+                Status::internal(format!(
+                    "[ERROR:{AGENT_NAME}] AA get platform report failed"
+                ))
+            })?;
+
+        debug!("AA (grpc): get platform report succeeded.");
+
+        Result::Ok(Response::new(reply))
+    }
+
+    async fn get_evidence_metrics(
+        &self,
+        _request: Request<GetEvidenceMetricsRequest>,
+    ) -> Result<Response<GetEvidenceMetricsResponse>, Status> {
+        debug!("AA (grpc): get evidence metrics ...");
+
+        let reply = evidence_metrics_response(self.inner.evidence_metrics()).map_err(|e| {
+            error!(
+                "AA (grpc): get evidence metrics failed:
+{e:?}"
+            );
+            Status::internal(format!(
+                "[ERROR:{AGENT_NAME}] AA get evidence metrics failed"
+            ))
+        })?;
+
+        debug!("AA (grpc): get evidence metrics succeeded.");
+
+        Result::Ok(Response::new(reply))
+    }
 }
 
-pub async fn start_grpc_service(socket: SocketAddr, aa: AttestationAgent) -> Result<()> {
+pub async fn start_grpc_service(socket: SocketAddr, aa: Arc<AttestationAgent>) -> Result<()> {
     let service = AA { inner: aa };
     Server::builder()
         .add_service(AttestationAgentServiceServer::new(service))
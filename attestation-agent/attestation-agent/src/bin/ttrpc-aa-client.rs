@@ -8,8 +8,10 @@ use clap::{Args, Parser, Subcommand};
 use const_format::concatcp;
 use protos::ttrpc::aa::{
     attestation_agent::{
-        ExtendRuntimeMeasurementRequest, GetEvidenceRequest, GetTeeTypeRequest, GetTokenRequest,
-        RuntimeMeasurementResult,
+        ExtendRuntimeMeasurementRequest, GetEventlogRequest, GetEvidenceMetricsRequest,
+        GetEvidenceRequest, GetInitdataRequest, GetPlatformReportRequest, GetTeeTypeRequest,
+        GetTokenRequest, PlatformDiagnosis, RuntimeMeasurementResult, UpdateConfigRequest,
+        VerifyEventlogRequest,
     },
     attestation_agent_ttrpc::AttestationAgentServiceClient,
 };
@@ -57,6 +59,24 @@ enum Operation {
 
     /// Extend runtime measurement
     ExtendRuntimeMeasurement(ExtendRuntimeMeasurementArgs),
+
+    /// Get the eventlog (base64 encoded)
+    GetEventlog,
+
+    /// Replay the eventlog and compare it with the runtime measurement registers
+    VerifyEventlog,
+
+    /// Get the initdata given to AA at launch
+    GetInitdata,
+
+    /// Replace the configuration of AA
+    UpdateConfig(UpdateConfigArgs),
+
+    /// Explain why each platform was or was not detected
+    GetPlatformReport,
+
+    /// Get the evidence generations, failures and cache hits of each attester
+    GetEvidenceMetrics,
 }
 
 #[derive(Args)]
@@ -73,6 +93,10 @@ struct GetTokenArgs {
     /// token type
     #[arg(short, long)]
     token_type: String,
+
+    /// get a new token rather than the cached one
+    #[arg(long)]
+    refresh: bool,
 }
 
 #[derive(Args)]
@@ -95,6 +119,14 @@ struct ExtendRuntimeMeasurementArgs {
     pcr: Option<u64>,
 }
 
+#[derive(Args)]
+#[command(author, version, about, long_about = None)]
+struct UpdateConfigArgs {
+    /// path to the new configuration file in TOML format
+    #[arg(short, long)]
+    config_file: String,
+}
+
 #[tokio::main]
 pub async fn main() {
     let args = Cli::parse();
@@ -131,6 +163,7 @@ pub async fn main() {
         Operation::GetToken(get_token_args) => {
             let req = GetTokenRequest {
                 TokenType: get_token_args.token_type,
+                Refresh: get_token_args.refresh,
                 ..Default::default()
             };
             let res = client
@@ -167,5 +200,132 @@ pub async fn main() {
                 ),
             }
         }
+        Operation::GetEventlog => {
+            let req = GetEventlogRequest {
+                ..Default::default()
+            };
+            let res = client
+                .get_eventlog(context::with_timeout(TIMEOUT), &req)
+                .await
+                .expect("request to AA");
+            println!(
+                "{}",
+                base64::engine::general_purpose::STANDARD.encode(res.Eventlog)
+            );
+        }
+        Operation::VerifyEventlog => {
+            let req = VerifyEventlogRequest {
+                ..Default::default()
+            };
+            let res = client
+                .verify_eventlog(context::with_timeout(TIMEOUT), &req)
+                .await
+                .expect("request to AA");
+            for register in &res.Registers {
+                let actual = register
+                    .Actual
+                    .as_ref()
+                    .map(hex::encode)
+                    .unwrap_or_else(|| "unavailable".to_string());
+                println!(
+                    "register {}: replayed {}, actual {actual}, matched: {}",
+                    register.RegisterIndex,
+                    hex::encode(&register.Replayed),
+                    register.Matched
+                );
+            }
+            if res.Verified {
+                println!("Eventlog verified with {}.", res.HashAlgorithm);
+            } else if res
+                .Registers
+                .iter()
+                .all(|register| register.Actual.is_none())
+            {
+                println!("Eventlog not verified, no register could be read.");
+            } else {
+                println!("Eventlog does not match the registers.");
+            }
+        }
+        Operation::GetInitdata => {
+            let req = GetInitdataRequest {
+                ..Default::default()
+            };
+            let res = client
+                .get_initdata(context::with_timeout(TIMEOUT), &req)
+                .await
+                .expect("request to AA");
+            if res.Toml.is_empty() {
+                println!("No initdata is given.");
+            } else {
+                println!("{} digest: {}", res.Algorithm, hex::encode(res.Digest));
+                println!("{}", res.Toml);
+            }
+        }
+        Operation::UpdateConfig(args) => {
+            let config =
+                std::fs::read_to_string(&args.config_file).expect("read configuration file");
+            let req = UpdateConfigRequest {
+                Config: config,
+                ..Default::default()
+            };
+            client
+                .update_config(context::with_timeout(TIMEOUT), &req)
+                .await
+                .expect("request to AA");
+            println!("Configuration updated.");
+        }
+        Operation::GetPlatformReport => {
+            let req = GetPlatformReportRequest {
+                ..Default::default()
+            };
+            let res = client
+                .get_platform_report(context::with_timeout(TIMEOUT), &req)
+                .await
+                .expect("request to AA");
+            println!("Primary attester: {}", res.PrimaryTee);
+            match res.SampleFallback {
+                true => println!("Detected platform: none, falling back to sample"),
+                false => println!("Detected platform: {}", res.Tee),
+            }
+            println!("Platforms:");
+            res.Platforms.iter().for_each(print_diagnosis);
+            println!("Devices:");
+            res.Devices.iter().for_each(print_diagnosis);
+            if !res.NotCompiledIn.is_empty() {
+                println!("Not compiled in: {}", res.NotCompiledIn.join(", "));
+            }
+        }
+        Operation::GetEvidenceMetrics => {
+            let req = GetEvidenceMetricsRequest {
+                ..Default::default()
+            };
+            let res = client
+                .get_evidence_metrics(context::with_timeout(TIMEOUT), &req)
+                .await
+                .expect("request to AA");
+            for metrics in &res.Attesters {
+                println!(
+                    "{}: {} generated, {} failed, {} cache hits, {} ms total latency, {} ms max latency",
+                    metrics.Tee,
+                    metrics.Generations,
+                    metrics.Failures,
+                    metrics.CacheHits,
+                    metrics.TotalLatencyMs,
+                    metrics.MaxLatencyMs
+                );
+            }
+        }
+    }
+}
+
+fn print_diagnosis(diagnosis: &PlatformDiagnosis) {
+    match (&diagnosis.Failure, diagnosis.Probed) {
+        (_, false) => println!("  {}: not probed", diagnosis.Tee),
+        (None, true) => println!("  {}: detected", diagnosis.Tee),
+        (Some(failure), true) => println!("  {}: not detected, {failure}", diagnosis.Tee),
+    }
+    for checked in &diagnosis.Checked {
+        let state = if checked.Exists { "exists" } else { "missing" };
+        println!("    {} {state}", checked.Path);
     }
 }
@@ -4,12 +4,20 @@
 //
 
 use anyhow::*;
-use attestation_agent::{initdata::Initdata, AttestationAPIs, AttestationAgent};
+use attestation_agent::{
+    config::{DEFAULT_AA_CONFIG_PATH, DEFAULT_CONFIG_WATCH_INTERVAL_SECS},
+    AttestationAPIs, AttestationAgent,
+};
 use base64::Engine;
 use clap::Parser;
 use const_format::concatcp;
 use log::{debug, info};
-use std::{collections::HashMap, path::Path, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 use tokio::signal::unix::{signal, SignalKind};
 use ttrpc::asynchronous::{Server, Service};
 use ttrpc_dep::server::AA;
@@ -71,9 +79,25 @@ struct Cli {
     /// `--initdata_toml /path/to/initdata.toml`
     #[arg(short = 't', long)]
     initdata_toml: Option<String>,
+
+    /// Watch the configuration file and reload the configuration once it
+    /// changes. The file given by `--config-file` is watched, or
+    /// `/etc/attestation-agent.conf` if not given.
+    ///
+    /// Example:
+    /// `--watch-config`
+    #[arg(short, long)]
+    watch_config: bool,
+
+    /// Seconds between two checks of the watched configuration file.
+    ///
+    /// Example:
+    /// `--config-watch-interval 5`
+    #[arg(long, default_value_t = DEFAULT_CONFIG_WATCH_INTERVAL_SECS)]
+    config_watch_interval: u64,
 }
 
-pub fn start_ttrpc_service(aa: AttestationAgent) -> Result<HashMap<String, Service>> {
+pub fn start_ttrpc_service(aa: Arc<AttestationAgent>) -> Result<HashMap<String, Service>> {
     let service = AA { inner: aa };
     let service = Arc::new(service);
     let get_resource_service = create_attestation_agent_service(service);
@@ -99,8 +123,9 @@ pub async fn main() -> Result<()> {
         info!("Initdata TOML file is given by parameter");
         let initdata_toml =
             std::fs::read_to_string(&initdata_toml_path).context("read initdata toml file")?;
-        let (_, digest) = Initdata::parse_and_get_digest(&initdata_toml)?;
-        aa.set_initdata_toml(initdata_toml);
+        let digest = aa
+            .set_initdata_toml(initdata_toml)
+            .context("invalid initdata toml")?;
         initdata_digest = Some(digest);
     } else if let Some(initdata) = cli.initdata_digest {
         info!("Initdata digest is given by parameter");
@@ -124,6 +149,15 @@ pub async fn main() -> Result<()> {
     }
 
     aa.init().await.context("init AA")?;
+    let aa = Arc::new(aa);
+    if cli.watch_config {
+        let config_path =
+            PathBuf::from(cli.config_file.as_deref().unwrap_or(DEFAULT_AA_CONFIG_PATH));
+        let interval = Duration::from_secs(cli.config_watch_interval);
+        let aa = aa.clone();
+        tokio::spawn(async move { aa.watch_config(&config_path, interval).await });
+    }
+
     let att = start_ttrpc_service(aa)?;
 
     let mut atts = Server::new()
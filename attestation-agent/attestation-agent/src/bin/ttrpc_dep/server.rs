@@ -5,15 +5,25 @@
 
 use ::ttrpc::proto::Code;
 use async_trait::async_trait;
-use attestation_agent::{AttestationAPIs, AttestationAgent, RuntimeMeasurement};
+use attestation_agent::{
+    config::Config,
+    proto::ttrpc::{evidence_metrics_response, platform_report_response},
+    AttestationAPIs, AttestationAgent, RuntimeMeasurement,
+};
+use std::sync::Arc;
 
 use log::{debug, error};
 
 use protos::ttrpc::aa::{
     attestation_agent::{
-        ExtendRuntimeMeasurementRequest, ExtendRuntimeMeasurementResponse,
-        GetAdditionalEvidenceRequest, GetEvidenceRequest, GetEvidenceResponse, GetTeeTypeRequest,
-        GetTeeTypeResponse, GetTokenRequest, GetTokenResponse, RuntimeMeasurementResult,
+        DeriveSealingKeyRequest, DeriveSealingKeyResponse, ExtendRuntimeMeasurementRequest,
+        ExtendRuntimeMeasurementResponse, ExtendRuntimeMeasurementsRequest,
+        GetAdditionalEvidenceRequest, GetEventlogRequest, GetEventlogResponse,
+        GetEvidenceMetricsRequest, GetEvidenceMetricsResponse, GetEvidenceRequest,
+        GetEvidenceResponse, GetInitdataRequest, GetInitdataResponse, GetPlatformReportRequest,
+        GetPlatformReportResponse, GetTeeTypeRequest, GetTeeTypeResponse, GetTokenRequest,
+        GetTokenResponse, RegisterReplayResult, RuntimeMeasurementResult, UpdateConfigRequest,
+        UpdateConfigResponse, VerifyEventlogRequest, VerifyEventlogResponse,
     },
     attestation_agent_ttrpc::AttestationAgentService,
 };
@@ -23,7 +33,7 @@ pub const AGENT_NAME: &str = "attestation-agent";
 
 #[allow(dead_code)]
 pub struct AA {
-    pub(crate) inner: AttestationAgent,
+    pub(crate) inner: Arc<AttestationAgent>,
 }
 
 #[async_trait]
@@ -35,7 +45,11 @@ impl AttestationAgentService for AA {
     ) -> ::ttrpc::Result<GetTokenResponse> {
         debug!("AA (ttrpc): get token ...");
 
-        let token = self.inner.get_token(&req.TokenType).await.map_err(|e| {
+        let token = match req.Refresh {
+            true => self.inner.refresh_token(&req.TokenType).await,
+            false => self.inner.get_token(&req.TokenType).await,
+        }
+        .map_err(|e| {
             error!("AA (ttrpc): get token failed\n {e:?}");
             let mut error_status = ::ttrpc::proto::Status::new();
             error_status.set_code(Code::INTERNAL);
@@ -107,6 +121,34 @@ impl AttestationAgentService for AA {
         ::ttrpc::Result::Ok(reply)
     }
 
+    async fn derive_sealing_key(
+        &self,
+        _ctx: &::ttrpc::r#async::TtrpcContext,
+        req: DeriveSealingKeyRequest,
+    ) -> ::ttrpc::Result<DeriveSealingKeyResponse> {
+        debug!("AA (ttrpc): derive sealing key ...");
+
+        let key = self
+            .inner
+            .derive_sealing_key(&req.Purpose)
+            .await
+            .map_err(|e| {
+                error!("AA (ttrpc): derive sealing key failed:\n {e:?}");
+                let mut error_status = ::ttrpc::proto::Status::new();
+                error_status.set_code(Code::INTERNAL);
+                error_status
+                    .set_message(format!("[ERROR:{AGENT_NAME}] AA derive sealing key failed"));
+                ::ttrpc::Error::RpcStatus(error_status)
+            })?;
+
+        debug!("AA (ttrpc): Derive sealing key successfully!");
+
+        let mut reply = DeriveSealingKeyResponse::new();
+        reply.Key = key;
+
+        ::ttrpc::Result::Ok(reply)
+    }
+
     async fn extend_runtime_measurement(
         &self,
         _ctx: &::ttrpc::r#async::TtrpcContext,
@@ -143,6 +185,52 @@ impl AttestationAgentService for AA {
         ::ttrpc::Result::Ok(reply)
     }
 
+    async fn extend_runtime_measurements(
+        &self,
+        _ctx: &::ttrpc::r#async::TtrpcContext,
+        req: ExtendRuntimeMeasurementsRequest,
+    ) -> ::ttrpc::Result<ExtendRuntimeMeasurementResponse> {
+        debug!("AA (ttrpc): extend runtime measurements ...");
+
+        let events = req
+            .Events
+            .into_iter()
+            .map(attestation_agent::RuntimeMeasurementEvent::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|e| {
+                error!("AA (ttrpc): invalid runtime measurement event:\n {e:?}");
+                let mut error_status = ::ttrpc::proto::Status::new();
+                error_status.set_code(Code::INVALID_ARGUMENT);
+                error_status.set_message(format!(
+                    "[ERROR:{AGENT_NAME}] AA invalid runtime measurement event"
+                ));
+                ::ttrpc::Error::RpcStatus(error_status)
+            })?;
+
+        let res = self
+            .inner
+            .extend_runtime_measurements(events)
+            .await
+            .map_err(|e| {
+                error!("AA (ttrpc): extend runtime measurements failed:\n {e:?}");
+                let mut error_status = ::ttrpc::proto::Status::new();
+                error_status.set_code(Code::INTERNAL);
+                error_status.set_message(format!(
+                    "[ERROR:{AGENT_NAME}] AA extend runtime measurements failed"
+                ));
+                ::ttrpc::Error::RpcStatus(error_status)
+            })?;
+
+        debug!("AA (ttrpc): extend runtime measurements succeeded.");
+        let mut reply = ExtendRuntimeMeasurementResponse::new();
+        reply.Result = match res {
+            RuntimeMeasurement::Ok => RuntimeMeasurementResult::OK.into(),
+            RuntimeMeasurement::NotSupported => RuntimeMeasurementResult::NOT_SUPPORTED.into(),
+            RuntimeMeasurement::NotEnabled => RuntimeMeasurementResult::NOT_ENABLED.into(),
+        };
+        ::ttrpc::Result::Ok(reply)
+    }
+
     async fn get_tee_type(
         &self,
         _ctx: &::ttrpc::r#async::TtrpcContext,
@@ -169,4 +257,184 @@ impl AttestationAgentService for AA {
         reply.tee = res;
         ::ttrpc::Result::Ok(reply)
     }
+
+    async fn get_eventlog(
+        &self,
+        _ctx: &::ttrpc::r#async::TtrpcContext,
+        _req: GetEventlogRequest,
+    ) -> ::ttrpc::Result<GetEventlogResponse> {
+        debug!("AA (ttrpc): get eventlog ...");
+
+        let eventlog = self.inner.get_eventlog().await.map_err(|e| {
+            error!("AA (ttrpc): get eventlog failed:\n {e:?}");
+            let mut error_status = ::ttrpc::proto::Status::new();
+            error_status.set_code(Code::INTERNAL);
+            error_status.set_message(format!("[ERROR:{AGENT_NAME}] AA get eventlog failed"));
+            ::ttrpc::Error::RpcStatus(error_status)
+        })?;
+
+        debug!("AA (ttrpc): get eventlog succeeded.");
+        let mut reply = GetEventlogResponse::new();
+        reply.Eventlog = eventlog;
+        ::ttrpc::Result::Ok(reply)
+    }
+
+    async fn verify_eventlog(
+        &self,
+        _ctx: &::ttrpc::r#async::TtrpcContext,
+        _req: VerifyEventlogRequest,
+    ) -> ::ttrpc::Result<VerifyEventlogResponse> {
+        debug!("AA (ttrpc): verify eventlog ...");
+
+        let verification = self.inner.verify_eventlog().await.map_err(|e| {
+            error!("AA (ttrpc): verify eventlog failed:\n {e:?}");
+            let mut error_status = ::ttrpc::proto::Status::new();
+            error_status.set_code(Code::INTERNAL);
+            error_status.set_message(format!("[ERROR:{AGENT_NAME}] AA verify eventlog failed"));
+            ::ttrpc::Error::RpcStatus(error_status)
+        })?;
+
+        let hash_algorithm = serde_json::to_string(&verification.hash_algorithm)
+            .map_err(|e| {
+                error!("AA (ttrpc): verify eventlog failed:\n {e:?}");
+                let mut error_status = ::ttrpc::proto::Status::new();
+                error_status.set_code(Code::INTERNAL);
+                error_status.set_message(format!("[ERROR:{AGENT_NAME}] AA verify eventlog failed"));
+                ::ttrpc::Error::RpcStatus(error_status)
+            })?
+            .trim_end_matches('"')
+            .trim_start_matches('"')
+            .to_string();
+
+        debug!("AA (ttrpc): verify eventlog succeeded.");
+        let mut reply = VerifyEventlogResponse::new();
+        reply.Verified = verification.verified();
+        reply.HashAlgorithm = hash_algorithm;
+        reply.Registers = verification
+            .registers
+            .into_iter()
+            .map(|register| {
+                let mut result = RegisterReplayResult::new();
+                result.RegisterIndex = register.register_index;
+                result.Matched = register.matched();
+                result.Replayed = register.replayed;
+                result.Actual = register.actual;
+                result
+            })
+            .collect();
+        ::ttrpc::Result::Ok(reply)
+    }
+
+    async fn get_initdata(
+        &self,
+        _ctx: &::ttrpc::r#async::TtrpcContext,
+        _req: GetInitdataRequest,
+    ) -> ::ttrpc::Result<GetInitdataResponse> {
+        debug!("AA (ttrpc): get initdata ...");
+
+        let initdata = self.inner.get_initdata().await.map_err(|e| {
+            error!("AA (ttrpc): get initdata failed:\n {e:?}");
+            let mut error_status = ::ttrpc::proto::Status::new();
+            error_status.set_code(Code::INTERNAL);
+            error_status.set_message(format!("[ERROR:{AGENT_NAME}] AA get initdata failed"));
+            ::ttrpc::Error::RpcStatus(error_status)
+        })?;
+
+        let mut reply = GetInitdataResponse::new();
+        if let Some(initdata) = initdata {
+            let algorithm = serde_json::to_string(&initdata.initdata.algorithm)
+                .map_err(|e| {
+                    error!("AA (ttrpc): get initdata failed:\n {e:?}");
+                    let mut error_status = ::ttrpc::proto::Status::new();
+                    error_status.set_code(Code::INTERNAL);
+                    error_status
+                        .set_message(format!("[ERROR:{AGENT_NAME}] AA get initdata failed"));
+                    ::ttrpc::Error::RpcStatus(error_status)
+                })?
+                .trim_end_matches('"')
+                .trim_start_matches('"')
+                .to_string();
+
+            reply.Toml = initdata.toml;
+            reply.Digest = initdata.digest;
+            reply.Algorithm = algorithm;
+        }
+
+        debug!("AA (ttrpc): get initdata succeeded.");
+        ::ttrpc::Result::Ok(reply)
+    }
+
+    async fn update_config(
+        &self,
+        _ctx: &::ttrpc::r#async::TtrpcContext,
+        req: UpdateConfigRequest,
+    ) -> ::ttrpc::Result<UpdateConfigResponse> {
+        debug!("AA (ttrpc): update config ...");
+
+        let config = Config::from_toml_str(&req.Config).map_err(|e| {
+            error!("AA (ttrpc): invalid config:\n {e:?}");
+            let mut error_status = ::ttrpc::proto::Status::new();
+            error_status.set_code(Code::INVALID_ARGUMENT);
+            error_status.set_message(format!("[ERROR:{AGENT_NAME}] AA invalid config"));
+            ::ttrpc::Error::RpcStatus(error_status)
+        })?;
+
+        self.inner.update_config(config).await.map_err(|e| {
+            error!("AA (ttrpc): update config failed:\n {e:?}");
+            let mut error_status = ::ttrpc::proto::Status::new();
+            error_status.set_code(Code::INVALID_ARGUMENT);
+            error_status.set_message(format!("[ERROR:{AGENT_NAME}] AA update config failed"));
+            ::ttrpc::Error::RpcStatus(error_status)
+        })?;
+
+        debug!("AA (ttrpc): update config succeeded.");
+        ::ttrpc::Result::Ok(UpdateConfigResponse::new())
+    }
+
+    async fn get_platform_report(
+        &self,
+        _ctx: &::ttrpc::r#async::TtrpcContext,
+        _req: GetPlatformReportRequest,
+    ) -> ::ttrpc::Result<GetPlatformReportResponse> {
+        debug!("AA (ttrpc): get platform report ...");
+
+        let reply = self
+            .inner
+            .get_platform_report()
+            .await
+            .and_then(|report| platform_report_response(report, self.inner.get_tee_type()))
+            .map_err(|e| {
+                error!("AA (ttrpc): get platform report failed:\n {e:?}");
+                let mut error_status = ::ttrpc::proto::Status::new();
+                error_status.set_code(Code::INTERNAL);
+                error_status.set_message(format!(
+                    "[ERROR:{AGENT_NAME}] AA get platform report failed"
+                ));
+                ::ttrpc::Error::RpcStatus(error_status)
+            })?;
+
+        debug!("AA (ttrpc): get platform report succeeded.");
+        ::ttrpc::Result::Ok(reply)
+    }
+
+    async fn get_evidence_metrics(
+        &self,
+        _ctx: &::ttrpc::r#async::TtrpcContext,
+        _req: GetEvidenceMetricsRequest,
+    ) -> ::ttrpc::Result<GetEvidenceMetricsResponse> {
+        debug!("AA (ttrpc): get evidence metrics ...");
+
+        let reply = evidence_metrics_response(self.inner.evidence_metrics()).map_err(|e| {
+            error!("AA (ttrpc): get evidence metrics failed:\n {e:?}");
+            let mut error_status = ::ttrpc::proto::Status::new();
+            error_status.set_code(Code::INTERNAL);
+            error_status.set_message(format!(
+                "[ERROR:{AGENT_NAME}] AA get evidence metrics failed"
+            ));
+            ::ttrpc::Error::RpcStatus(error_status)
+        })?;
+
+        debug!("AA (ttrpc): get evidence metrics succeeded.");
+        ::ttrpc::Result::Ok(reply)
+    }
 }
@@ -9,4 +9,16 @@ use serde::Deserialize;
 pub struct CoCoASConfig {
     /// URL Address of Attestation Service.
     pub url: String,
+
+    /// IDs of the policies the AS verifies the evidence against. If not
+    /// given, the AS uses its default policy.
+    #[serde(default)]
+    pub policy_ids: Vec<String>,
+
+    /// Return the token together with the TEE key pair bound into it, as a
+    /// JSON object `{"token": ..., "tee_keypair": ...}`, instead of the bare
+    /// token. The private key lets the caller decrypt the resources the AS
+    /// or a relying party wraps for the TEE key.
+    #[serde(default)]
+    pub with_tee_keypair: bool,
 }
@@ -0,0 +1,37 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// The request body sent to the verifier if `request_template` is not given.
+pub const DEFAULT_EAT_REQUEST_TEMPLATE: &str = r#"{"tee":"${tee}","evidence":"${evidence}"}"#;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct EatConfig {
+    /// URL of the verifier endpoint the evidence is posted to.
+    pub url: String,
+
+    /// JSON request body template. The following placeholders are replaced
+    /// with JSON-escaped values:
+    /// - `${tee}`: the TEE type, e.g. `tdx`
+    /// - `${evidence}`: base64url encoded evidence of the primary attester
+    /// - `${initdata}`: the plaintext initdata toml, empty if not given
+    #[serde(default = "default_request_template")]
+    pub request_template: String,
+
+    /// Extra HTTP headers sent to the verifier, e.g. an API key.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// JSON pointer to the token inside the response, e.g. `/token`.
+    /// If not given, the whole response body is the token.
+    pub token_pointer: Option<String>,
+}
+
+fn default_request_template() -> String {
+    DEFAULT_EAT_REQUEST_TEMPLATE.to_string()
+}
@@ -8,13 +8,45 @@ use serde::Deserialize;
 
 use super::aa_kbc_params::AaKbcParams;
 
-#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 pub struct KbsConfig {
     /// URL Address of KBS.
     pub url: String,
 
     /// Cert of KBS
     pub cert: Option<String>,
+
+    /// Client certificate (PEM format) to authenticate to the KBS with
+    /// mutual TLS. Must be given together with `client_key`.
+    #[serde(default)]
+    pub client_cert: Option<String>,
+
+    /// PKCS#8 private key (PEM format) of the client certificate.
+    #[serde(default)]
+    pub client_key: Option<String>,
+
+    /// SHA-256 hashes of the SubjectPublicKeyInfo of the KBS certificate, in
+    /// `sha256/<base64>` format. If any is given, a KBS whose certificate
+    /// matches none of them is rejected.
+    #[serde(default)]
+    pub spki_pins: Vec<String>,
+
+    /// Pin the KBS certificate by the `kbs_spki_pins` entry of the initdata
+    /// rather than by `spki_pins`.
+    #[serde(default)]
+    pub attested_tls: bool,
+
+    /// Algorithm of the TEE key the KBS wraps the resources to, one of
+    /// `rsa`, `ec-p256` or `ec-p384`. Defaults to `ec-p256`.
+    #[serde(default)]
+    pub tee_key_algorithm: Option<String>,
+
+    /// File persisting the session with the KBS, so it is resumed after a
+    /// restart while its token is valid. The session is sealed with a key
+    /// derived by the primary attester, and not persisted if the attester
+    /// cannot derive one.
+    #[serde(default)]
+    pub session_file: Option<String>,
 }
 
 impl KbsConfig {
@@ -28,7 +60,7 @@ impl KbsConfig {
         }
         Ok(Self {
             url: aa_kbc_params.uri,
-            cert: None,
+            ..Default::default()
         })
     }
 }
@@ -4,6 +4,9 @@
 //
 
 use anyhow::Result;
+use attester::{cache::EvidenceCacheOptions, AttesterOptions};
+use kbs_protocol::ProxyConfig;
+use kbs_types::Tee;
 use serde::Deserialize;
 
 /// Default PCR index used by AA. `17` is selected for its usage of dynamic root of trust for measurement.
@@ -19,10 +22,22 @@ pub mod coco_as;
 #[cfg(feature = "kbs")]
 pub mod kbs;
 
+#[cfg(feature = "eat")]
+pub mod eat;
+
 pub const DEFAULT_AA_CONFIG_PATH: &str = "/etc/attestation-agent.conf";
 
 pub const DEFAULT_EVENTLOG_HASH: &str = "sha384";
 
+/// AA's eventlog and its WAL cache are put into this directory by default.
+pub const DEFAULT_EVENTLOG_DIR: &str = "/run/attestation-agent";
+
+/// By default, a cached token is refreshed 60 seconds before it expires.
+pub const DEFAULT_TOKEN_REFRESH_AHEAD_SECS: u64 = 60;
+
+/// By default, a watched configuration file is checked for changes every 5 seconds.
+pub const DEFAULT_CONFIG_WATCH_INTERVAL_SECS: u64 = 5;
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Default)]
 pub struct Config {
     /// configs about token
@@ -31,6 +46,10 @@ pub struct Config {
 
     /// configs about eventlog
     pub eventlog_config: EventlogConfig,
+
+    /// configs about the attesters to use
+    #[serde(default)]
+    pub attesters: AttestersConfig,
 }
 
 impl Config {
@@ -38,6 +57,7 @@ impl Config {
         Config {
             token_configs: TokenConfigs::from_kernel_cmdline(),
             eventlog_config: EventlogConfig::default(),
+            attesters: AttestersConfig::default(),
         }
     }
 }
@@ -49,6 +69,9 @@ pub struct EventlogConfig {
 
     /// Flag whether enable eventlog recording
     pub enable_eventlog: bool,
+
+    /// Directory to store the eventlog and its WAL cache
+    pub eventlog_dir: String,
 }
 
 impl Default for EventlogConfig {
@@ -56,10 +79,35 @@ impl Default for EventlogConfig {
         Self {
             init_pcr: DEFAULT_PCR_INDEX,
             enable_eventlog: false,
+            eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
         }
     }
 }
 
+/// Attesters to use. Platforms and devices are only detected if not
+/// configured here.
+#[derive(Clone, Debug, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct AttestersConfig {
+    /// The primary attester, e.g. `tdx` or `sample`.
+    pub primary: Option<Tee>,
+
+    /// Additional attesters to use, e.g. `["nvidia"]`. Attesters of devices
+    /// are only used if listed here.
+    pub allow: Option<Vec<Tee>>,
+
+    /// Additional attesters never to use, even if the device is detected.
+    pub deny: Vec<Tee>,
+
+    /// Per-attester options, e.g. `[attesters.tpm]`.
+    #[serde(flatten)]
+    pub options: AttesterOptions,
+
+    /// Caching of the evidence and limits of the evidence generations of
+    /// each attester.
+    pub evidence_cache: EvidenceCacheOptions,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Default)]
 pub struct TokenConfigs {
     /// This config item is used when `coco_as` feature is enabled.
@@ -69,6 +117,38 @@ pub struct TokenConfigs {
     /// This config item is used when `kbs` feature is enabled.
     #[cfg(feature = "kbs")]
     pub kbs: Option<kbs::KbsConfig>,
+
+    /// This config item is used when `eat` feature is enabled.
+    #[cfg(feature = "eat")]
+    pub eat: Option<eat::EatConfig>,
+
+    /// Proxies used by all the token getters to reach the verifiers
+    #[serde(default)]
+    pub proxy: Option<ProxyConfig>,
+
+    /// configs about token caching
+    #[serde(default)]
+    pub cache: TokenCacheConfig,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TokenCacheConfig {
+    /// Flag whether to cache tokens and refresh them in background
+    pub enable: bool,
+
+    /// How many seconds before the cached token expires to refresh it, at
+    /// most a quarter of the token lifetime
+    pub refresh_ahead_secs: u64,
+}
+
+impl Default for TokenCacheConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            refresh_ahead_secs: DEFAULT_TOKEN_REFRESH_AHEAD_SECS,
+        }
+    }
 }
 
 impl TokenConfigs {
@@ -81,27 +161,55 @@ impl TokenConfigs {
             coco_as: None,
             #[cfg(feature = "kbs")]
             kbs,
+            #[cfg(feature = "eat")]
+            eat: None,
+            proxy: None,
+            cache: TokenCacheConfig::default(),
         }
     }
 }
 
-impl TryFrom<&str> for Config {
-    type Error = config::ConfigError;
-    fn try_from(config_path: &str) -> Result<Self, Self::Error> {
+impl Config {
+    fn from_source<T>(source: T) -> Result<Self, config::ConfigError>
+    where
+        T: config::Source + Send + Sync + 'static,
+    {
         let c = config::Config::builder()
-            .add_source(config::File::with_name(config_path))
+            .add_source(source)
             .set_default("eventlog_config.init_pcr", DEFAULT_PCR_INDEX)?
             .set_default("eventlog_config.enable_eventlog", "false")?
+            .set_default("eventlog_config.eventlog_dir", DEFAULT_EVENTLOG_DIR)?
             .build()?;
 
         let cfg = c.try_deserialize()?;
         Ok(cfg)
     }
+
+    /// Parse the configuration from a TOML string, e.g. the `aa.toml`
+    /// section of initdata.
+    pub fn from_toml_str(config: &str) -> Result<Self, config::ConfigError> {
+        Self::from_source(config::File::from_str(config, config::FileFormat::Toml))
+    }
+}
+
+impl TryFrom<&str> for Config {
+    type Error = config::ConfigError;
+    fn try_from(config_path: &str) -> Result<Self, Self::Error> {
+        Self::from_source(config::File::with_name(config_path))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::config::{EventlogConfig, TokenConfigs};
+    use attester::{
+        cache::EvidenceCacheOptions, AttesterOptions, NvidiaOptions, TdxOptions, TdxQuoteProvider,
+        TpmOptions, TpmPcrBank,
+    };
+    use kbs_types::Tee;
+
+    use crate::config::{
+        AttestersConfig, EventlogConfig, TokenCacheConfig, TokenConfigs, DEFAULT_EVENTLOG_DIR,
+    };
 
     use super::Config;
 
@@ -112,6 +220,8 @@ mod tests {
             #[cfg(feature = "coco_as")]
             coco_as: Some(crate::config::coco_as::CoCoASConfig {
                 url: "http://127.0.0.1:8000".to_string(),
+                policy_ids: vec![],
+                with_tee_keypair: false,
             }),
             #[cfg(feature = "kbs")]
             kbs: Some(crate::config::kbs::KbsConfig {
@@ -139,11 +249,18 @@ DYEbQvoZIkUTc1gBUWDcAUS5ztbJg9LCb9WVtvUTqTP2lGuNymOvdsuXq+sAZh9b
 M9QaC1mzQ/OStg==
 -----END CERTIFICATE-----
 ".to_string()),
-            })
+                ..Default::default()
+            }),
+            #[cfg(feature = "eat")]
+            eat: None,
+            proxy: None,
+            cache: TokenCacheConfig::default(),
         },
+        attesters: AttestersConfig::default(),
         eventlog_config: EventlogConfig {
             init_pcr: 17,
             enable_eventlog: false,
+            eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
         }
     })]
     #[case("config.example.json",
@@ -152,6 +269,8 @@ M9QaC1mzQ/OStg==
             #[cfg(feature = "coco_as")]
             coco_as: Some(crate::config::coco_as::CoCoASConfig {
                 url: "http://127.0.0.1:8000".to_string(),
+                policy_ids: vec![],
+                with_tee_keypair: false,
             }),
             #[cfg(feature = "kbs")]
             kbs: Some(crate::config::kbs::KbsConfig {
@@ -179,11 +298,18 @@ DYEbQvoZIkUTc1gBUWDcAUS5ztbJg9LCb9WVtvUTqTP2lGuNymOvdsuXq+sAZh9b
 M9QaC1mzQ/OStg==
 -----END CERTIFICATE-----
 ".to_string()),
-            })
+                ..Default::default()
+            }),
+            #[cfg(feature = "eat")]
+            eat: None,
+            proxy: None,
+            cache: TokenCacheConfig::default(),
         },
+        attesters: AttestersConfig::default(),
         eventlog_config: EventlogConfig {
             init_pcr: 17,
             enable_eventlog: false,
+            eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
         }
     })]
     #[case(
@@ -193,16 +319,25 @@ M9QaC1mzQ/OStg==
             #[cfg(feature = "coco_as")]
             coco_as: Some(crate::config::coco_as::CoCoASConfig {
                 url: "http://127.0.0.1:8000".to_string(),
+                policy_ids: vec![],
+                with_tee_keypair: false,
             }),
             #[cfg(feature = "kbs")]
             kbs: Some(crate::config::kbs::KbsConfig {
                 url: "https://127.0.0.1:8080".to_string(),
                 cert: Some("cert".to_string()),
-            })
+                ..Default::default()
+            }),
+            #[cfg(feature = "eat")]
+            eat: None,
+            proxy: None,
+            cache: TokenCacheConfig::default(),
         },
+        attesters: AttestersConfig::default(),
         eventlog_config: EventlogConfig {
             init_pcr: 17,
             enable_eventlog: false,
+            eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
         }
     })]
     #[case(
@@ -215,11 +350,18 @@ M9QaC1mzQ/OStg==
             kbs: Some(crate::config::kbs::KbsConfig {
                 url: "https://127.0.0.1:8080".to_string(),
                 cert: Some("cert".to_string()),
-            })
+                ..Default::default()
+            }),
+            #[cfg(feature = "eat")]
+            eat: None,
+            proxy: None,
+            cache: TokenCacheConfig::default(),
         },
+        attesters: AttestersConfig::default(),
         eventlog_config: EventlogConfig {
             init_pcr: 17,
             enable_eventlog: false,
+            eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
         }
     })]
     #[case(
@@ -229,16 +371,25 @@ M9QaC1mzQ/OStg==
             #[cfg(feature = "coco_as")]
             coco_as: Some(crate::config::coco_as::CoCoASConfig {
                 url: "http://127.0.0.1:8000".to_string(),
+                policy_ids: vec![],
+                with_tee_keypair: false,
             }),
             #[cfg(feature = "kbs")]
             kbs: Some(crate::config::kbs::KbsConfig {
                 url: "https://127.0.0.1:8080".to_string(),
                 cert: None,
-            })
+                ..Default::default()
+            }),
+            #[cfg(feature = "eat")]
+            eat: None,
+            proxy: None,
+            cache: TokenCacheConfig::default(),
         },
+        attesters: AttestersConfig::default(),
         eventlog_config: EventlogConfig {
             init_pcr: 17,
             enable_eventlog: false,
+            eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
         }
     })]
     #[case(
@@ -249,10 +400,16 @@ M9QaC1mzQ/OStg==
             coco_as: None,
             #[cfg(feature = "kbs")]
             kbs: None,
+            #[cfg(feature = "eat")]
+            eat: None,
+            proxy: None,
+            cache: TokenCacheConfig::default(),
         },
+        attesters: AttestersConfig::default(),
         eventlog_config: EventlogConfig {
             init_pcr: 17,
             enable_eventlog: false,
+            eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
         }
     })]
     #[case(
@@ -263,10 +420,16 @@ M9QaC1mzQ/OStg==
             coco_as: None,
             #[cfg(feature = "kbs")]
             kbs: None,
+            #[cfg(feature = "eat")]
+            eat: None,
+            proxy: None,
+            cache: TokenCacheConfig::default(),
         },
+        attesters: AttestersConfig::default(),
         eventlog_config: EventlogConfig {
             init_pcr: 17,
             enable_eventlog: false,
+            eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
         }
     })]
     #[case(
@@ -277,10 +440,187 @@ M9QaC1mzQ/OStg==
                 coco_as: None,
                 #[cfg(feature = "kbs")]
                 kbs: None,
+                #[cfg(feature = "eat")]
+                eat: None,
+                proxy: None,
+                cache: TokenCacheConfig::default(),
+            },
+            attesters: AttestersConfig::default(),
+            eventlog_config: EventlogConfig {
+                init_pcr: 17,
+                enable_eventlog: false,
+                eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
+            }
+        })]
+    #[case(
+        "test/config7.toml",
+        Config {
+            token_configs: TokenConfigs {
+                #[cfg(feature = "coco_as")]
+                coco_as: None,
+                #[cfg(feature = "kbs")]
+                kbs: Some(crate::config::kbs::KbsConfig {
+                    url: "https://127.0.0.1:8080".to_string(),
+                    cert: None,
+                    ..Default::default()
+                }),
+                #[cfg(feature = "eat")]
+                eat: None,
+                proxy: None,
+                cache: TokenCacheConfig {
+                    enable: false,
+                    refresh_ahead_secs: 120,
+                },
+            },
+            attesters: AttestersConfig::default(),
+            eventlog_config: EventlogConfig {
+                init_pcr: 17,
+                enable_eventlog: false,
+                eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
+            }
+        })]
+    #[case(
+        "test/config8.toml",
+        Config {
+            token_configs: TokenConfigs {
+                #[cfg(feature = "coco_as")]
+                coco_as: None,
+                #[cfg(feature = "kbs")]
+                kbs: None,
+                #[cfg(feature = "eat")]
+                eat: Some(crate::config::eat::EatConfig {
+                    url: "https://127.0.0.1:8443/attest".to_string(),
+                    request_template: r#"{"evidence":"${evidence}"}"#.to_string(),
+                    headers: [("x-api-key".to_string(), "key".to_string())].into(),
+                    token_pointer: Some("/token".to_string()),
+                }),
+                proxy: None,
+                cache: TokenCacheConfig::default(),
+            },
+            attesters: AttestersConfig::default(),
+            eventlog_config: EventlogConfig {
+                init_pcr: 17,
+                enable_eventlog: false,
+                eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
+            }
+        })]
+    #[case(
+        "test/config9.toml",
+        Config {
+            token_configs: TokenConfigs {
+                #[cfg(feature = "coco_as")]
+                coco_as: None,
+                #[cfg(feature = "kbs")]
+                kbs: None,
+                #[cfg(feature = "eat")]
+                eat: None,
+                proxy: None,
+                cache: TokenCacheConfig::default(),
+            },
+            attesters: AttestersConfig::default(),
+            eventlog_config: EventlogConfig {
+                init_pcr: 17,
+                enable_eventlog: true,
+                eventlog_dir: "/run/attestation-agent/instance-1".to_string(),
+            }
+        })]
+    #[case(
+        "test/config10.toml",
+        Config {
+            token_configs: TokenConfigs {
+                #[cfg(feature = "coco_as")]
+                coco_as: None,
+                #[cfg(feature = "kbs")]
+                kbs: None,
+                #[cfg(feature = "eat")]
+                eat: None,
+                proxy: None,
+                cache: TokenCacheConfig::default(),
+            },
+            attesters: AttestersConfig {
+                primary: Some(Tee::Tdx),
+                allow: Some(vec![Tee::Nvidia]),
+                deny: vec![Tee::Tpm],
+                options: AttesterOptions {
+                    tpm: TpmOptions {
+                        device: Some("/dev/tpmrm0".to_string()),
+                        pcrs: Some(vec![0, 7, 8]),
+                        pcr_banks: Some(vec![TpmPcrBank::Sha256, TpmPcrBank::Sha384]),
+                        init_data_pcr: Some(9),
+                        provision_ak: true,
+                    },
+                    tdx: TdxOptions {
+                        quote_provider: Some(TdxQuoteProvider::Tsm),
+                    },
+                    nvidia: NvidiaOptions {
+                        device_indexes: Some(vec![0, 2]),
+                    },
+                },
+                evidence_cache: EvidenceCacheOptions {
+                    enable: true,
+                    ttl_secs: 2,
+                    max_concurrent_generations: 0,
+                },
+            },
+            eventlog_config: EventlogConfig {
+                init_pcr: 17,
+                enable_eventlog: false,
+                eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
+            }
+        })]
+    #[case(
+        "test/config11.toml",
+        Config {
+            token_configs: TokenConfigs {
+                #[cfg(feature = "coco_as")]
+                coco_as: None,
+                #[cfg(feature = "kbs")]
+                kbs: Some(crate::config::kbs::KbsConfig {
+                    url: "https://127.0.0.1:8080".to_string(),
+                    cert: None,
+                    client_cert: Some("client cert".to_string()),
+                    client_key: Some("client key".to_string()),
+                    spki_pins: vec![
+                        "sha256/47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=".to_string()
+                    ],
+                    attested_tls: true,
+                    tee_key_algorithm: Some("ec-p384".to_string()),
+                    session_file: None,
+                }),
+                #[cfg(feature = "eat")]
+                eat: None,
+                proxy: None,
+                cache: TokenCacheConfig::default(),
+            },
+            attesters: AttestersConfig::default(),
+            eventlog_config: EventlogConfig {
+                init_pcr: 17,
+                enable_eventlog: false,
+                eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
+            }
+        })]
+    #[case(
+        "test/config12.toml",
+        Config {
+            token_configs: TokenConfigs {
+                #[cfg(feature = "coco_as")]
+                coco_as: None,
+                #[cfg(feature = "kbs")]
+                kbs: None,
+                #[cfg(feature = "eat")]
+                eat: None,
+                proxy: Some(ProxyConfig {
+                    https_proxy: Some("http://proxy.example.com:3128".to_string()),
+                    http_proxy: None,
+                    no_proxy: Some("localhost,.svc.cluster.local".to_string()),
+                }),
+                cache: TokenCacheConfig::default(),
             },
+            attesters: AttestersConfig::default(),
             eventlog_config: EventlogConfig {
                 init_pcr: 17,
                 enable_eventlog: false,
+                eventlog_dir: DEFAULT_EVENTLOG_DIR.to_string(),
             }
         })]
     fn parse_configs(#[case] config: &str, #[case] expected: Config) {
@@ -0,0 +1,268 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Well-known Confidential Containers runtime measurement events, following
+//! <https://github.com/confidential-containers/trustee/blob/main/kbs/docs/confidential-containers-eventlog.md#confidential-containers-event-spec>
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Domain of the well-known Confidential Containers events.
+pub const COCO_EVENT_DOMAIN: &str = "github.com/confidential-containers";
+
+/// An image is pulled.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PullImage {
+    /// Reference of the image, e.g. `docker.io/library/busybox:latest`.
+    pub image: String,
+
+    /// Digest of the image manifest, e.g. `sha256:...`.
+    pub digest: String,
+}
+
+/// A secure storage is mounted.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SecureMount {
+    /// Type of the storage, e.g. `block-device`.
+    pub volume_type: String,
+
+    /// Absolute path where the storage is mounted.
+    pub mount_point: String,
+}
+
+/// A sealed secret is unsealed.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UnsealSecret {
+    /// Id of the key used to unseal the secret.
+    pub key_id: String,
+}
+
+/// A confidential resource is got from the KBS.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GetResource {
+    /// The resource uri, e.g. `kbs:///default/key/1`.
+    pub resource_uri: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CocoEvent {
+    PullImage(PullImage),
+    SecureMount(SecureMount),
+    UnsealSecret(UnsealSecret),
+    GetResource(GetResource),
+}
+
+fn validate_digest(digest: &str) -> Result<()> {
+    let Some((algorithm, hex_digest)) = digest.split_once(':') else {
+        bail!("digest `{digest}` is not in `<algorithm>:<hex>` format");
+    };
+
+    let len = match algorithm {
+        "sha256" => 32,
+        "sha384" => 48,
+        "sha512" => 64,
+        _ => bail!("unsupported digest algorithm `{algorithm}`"),
+    };
+
+    let decoded = hex::decode(hex_digest).context("digest is not hex encoded")?;
+    if decoded.len() != len {
+        bail!("{algorithm} digest must be {len} bytes");
+    }
+
+    Ok(())
+}
+
+impl CocoEvent {
+    /// The operation of the event.
+    pub fn operation(&self) -> &'static str {
+        match self {
+            CocoEvent::PullImage(_) => "PullImage",
+            CocoEvent::SecureMount(_) => "SecureMount",
+            CocoEvent::UnsealSecret(_) => "UnsealSecret",
+            CocoEvent::GetResource(_) => "GetResource",
+        }
+    }
+
+    /// The content of the event, i.e. the event as JSON.
+    pub fn content(&self) -> Result<String> {
+        let content = match self {
+            CocoEvent::PullImage(event) => serde_json::to_string(event),
+            CocoEvent::SecureMount(event) => serde_json::to_string(event),
+            CocoEvent::UnsealSecret(event) => serde_json::to_string(event),
+            CocoEvent::GetResource(event) => serde_json::to_string(event),
+        };
+
+        content.context("serialize event content")
+    }
+
+    /// Parse the content of a well-known operation. `None` is returned if
+    /// the operation is not a well-known one.
+    pub fn parse(operation: &str, content: &str) -> Result<Option<Self>> {
+        let event = match operation {
+            "PullImage" => CocoEvent::PullImage(serde_json::from_str(content)?),
+            "SecureMount" => CocoEvent::SecureMount(serde_json::from_str(content)?),
+            "UnsealSecret" => CocoEvent::UnsealSecret(serde_json::from_str(content)?),
+            "GetResource" => CocoEvent::GetResource(serde_json::from_str(content)?),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(event))
+    }
+
+    /// Check the fields of the event.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            CocoEvent::PullImage(event) => {
+                if event.image.is_empty() {
+                    bail!("PullImage event: empty image");
+                }
+                validate_digest(&event.digest).context("PullImage event")?;
+            }
+            CocoEvent::SecureMount(event) => {
+                if event.volume_type.is_empty() {
+                    bail!("SecureMount event: empty volume type");
+                }
+                if !Path::new(&event.mount_point).is_absolute() {
+                    bail!("SecureMount event: mount point must be an absolute path");
+                }
+            }
+            CocoEvent::UnsealSecret(event) => {
+                if event.key_id.is_empty() {
+                    bail!("UnsealSecret event: empty key id");
+                }
+            }
+            CocoEvent::GetResource(event) => {
+                if !event.resource_uri.starts_with("kbs://") {
+                    bail!("GetResource event: resource uri must start with `kbs://`");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A runtime measurement event to be extended, owning its fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuntimeMeasurementEvent {
+    pub domain: String,
+    pub operation: String,
+    pub content: String,
+
+    /// The PCR to extend. If not given, the `init_pcr` of the eventlog
+    /// configuration is used.
+    pub register_index: Option<u64>,
+}
+
+impl RuntimeMeasurementEvent {
+    pub fn new(domain: &str, operation: &str, content: &str, register_index: Option<u64>) -> Self {
+        Self {
+            domain: domain.to_string(),
+            operation: operation.to_string(),
+            content: content.to_string(),
+            register_index,
+        }
+    }
+
+    /// Create an event of a well-known Confidential Containers operation.
+    pub fn coco(event: CocoEvent, register_index: Option<u64>) -> Result<Self> {
+        event.validate()?;
+        Ok(Self {
+            domain: COCO_EVENT_DOMAIN.to_string(),
+            operation: event.operation().to_string(),
+            content: event.content()?,
+            register_index,
+        })
+    }
+
+    /// Events of well-known Confidential Containers operations must carry
+    /// valid contents. Other events are not checked.
+    pub fn validate(&self) -> Result<()> {
+        if self.domain != COCO_EVENT_DOMAIN {
+            return Ok(());
+        }
+
+        let Some(event) = CocoEvent::parse(&self.operation, &self.content)
+            .with_context(|| format!("invalid {} event content", self.operation))?
+        else {
+            return Ok(());
+        };
+
+        event.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("PullImage", r#"{"image":"docker.io/library/busybox:latest","digest":"sha256:0000000000000000000000000000000000000000000000000000000000000000"}"#, true)]
+    #[case(
+        "PullImage",
+        r#"{"image":"docker.io/library/busybox:latest", "digest":"sha256:00"}"#,
+        false
+    )]
+    #[case("PullImage", r#"{"image":"", "digest":"sha256:0000000000000000000000000000000000000000000000000000000000000000"}"#, false)]
+    #[case("PullImage", "not a json", false)]
+    #[case(
+        "SecureMount",
+        r#"{"volume_type":"block-device","mount_point":"/mnt/data"}"#,
+        true
+    )]
+    #[case(
+        "SecureMount",
+        r#"{"volume_type":"block-device","mount_point":"mnt"}"#,
+        false
+    )]
+    #[case("UnsealSecret", r#"{"key_id":"kbs:///default/key/1"}"#, true)]
+    #[case("UnsealSecret", r#"{"key_id":"","extra":1}"#, false)]
+    #[case("GetResource", r#"{"resource_uri":"kbs:///default/key/1"}"#, true)]
+    #[case("GetResource", r#"{"resource_uri":"https://example.com/key"}"#, false)]
+    #[case("Unknown", "free-form content", true)]
+    fn test_validate_coco_event(
+        #[case] operation: &str,
+        #[case] content: &str,
+        #[case] valid: bool,
+    ) {
+        let event = RuntimeMeasurementEvent::new(COCO_EVENT_DOMAIN, operation, content, None);
+        assert_eq!(event.validate().is_ok(), valid);
+
+        // Events of other domains are not checked
+        let event = RuntimeMeasurementEvent::new("example.com", operation, content, None);
+        assert!(event.validate().is_ok());
+    }
+
+    #[test]
+    fn test_coco_event_content() {
+        let event = CocoEvent::PullImage(PullImage {
+            image: "docker.io/library/busybox:latest".into(),
+            digest: format!("sha256:{}", "ab".repeat(32)),
+        });
+        let event = RuntimeMeasurementEvent::coco(event, Some(17)).unwrap();
+        assert_eq!(event.domain, COCO_EVENT_DOMAIN);
+        assert_eq!(event.operation, "PullImage");
+        assert_eq!(
+            CocoEvent::parse(&event.operation, &event.content)
+                .unwrap()
+                .unwrap()
+                .operation(),
+            "PullImage"
+        );
+
+        let event = CocoEvent::GetResource(GetResource {
+            resource_uri: "file:///etc/passwd".into(),
+        });
+        assert!(RuntimeMeasurementEvent::coco(event, None).is_err());
+    }
+}
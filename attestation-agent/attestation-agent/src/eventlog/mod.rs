@@ -3,44 +3,58 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
+pub mod coco;
+pub mod replay;
 pub mod tcg2;
 
 use std::{
+    collections::HashMap,
     fmt::Display,
     fs::{remove_file, File},
     io::{Read, Seek, SeekFrom, Write},
-    path::Path,
-    sync::Arc,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 
 use anyhow::{anyhow, bail, Context, Result};
 use attester::BoxedAttester;
-use const_format::concatcp;
 
 use kbs_types::HashAlgorithm;
 use log::warn;
 
 use crate::eventlog::tcg2::Tcg2EventEntry;
 
-/// AA's eventlog will be put into this parent directory
-pub const EVENTLOG_PARENT_DIR_PATH: &str = "/run/attestation-agent";
+/// AA's eventlog will be stored inside this file of the eventlog directory
+pub const EVENTLOG_FILE_NAME: &str = "eventlog";
 
-/// AA's eventlog will be stored inside the file
-pub const EVENTLOG_PATH: &str = concatcp!(EVENTLOG_PARENT_DIR_PATH, "/eventlog");
+/// A new log entry will be cached in this file of the eventlog directory
+/// before writing to the eventlog file.
+pub const WAL_CACHE_FILE_NAME: &str = ".wal_event_entry";
 
-/// A new log entry will be cached in this file before writing to the eventlog file.
-pub const WAL_CACHE: &str = concatcp!(EVENTLOG_PARENT_DIR_PATH, "/.wal_event_entry");
+/// Leading bytes of the versioned WAL cache. The unversioned WAL cache of
+/// earlier versions of AA starts with the big endian event offset, which
+/// never has these leading bytes.
+const WAL_CACHE_MAGIC: [u8; 4] = *b"AAWL";
+
+/// Version of the WAL cache format holding several entries.
+const WAL_CACHE_VERSION: u32 = 2;
 
 pub struct EventLog {
     writer: Box<dyn Writer>,
     rtmr_extender: Arc<BoxedAttester>,
     alg: HashAlgorithm,
     pcr: u64,
+    wal_cache: PathBuf,
 }
 
-trait Writer: Sync + Send {
+/// The storage of the AAEL entries.
+pub trait Writer: Sync + Send {
+    /// Write the data at the current position.
     fn write(&mut self, data: &[u8]) -> Result<()>;
+
+    /// Move the current position to `pos`.
     fn seek(&mut self, pos: u64) -> Result<()>;
+
     fn current_pos(&self) -> u64;
 }
 
@@ -49,9 +63,29 @@ pub struct FileWriter {
     pos: u64,
 }
 
+impl FileWriter {
+    /// Open the eventlog file, or create it if not exists. The position is
+    /// set to the end of the file.
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .context("open AAEL file")?;
+        let pos = file.seek(SeekFrom::End(0))?;
+        Ok(Self { file, pos })
+    }
+}
+
 impl Writer for FileWriter {
     fn write(&mut self, data: &[u8]) -> Result<()> {
-        self.file.write(data).context("failed to write log")?;
+        // Drop any partially written entry after the current position
+        self.file
+            .set_len(self.pos)
+            .context("failed to truncate log")?;
+        self.file.write_all(data).context("failed to write log")?;
         self.file
             .sync_data()
             .context("failed to flush log to I/O media")?;
@@ -72,158 +106,386 @@ impl Writer for FileWriter {
     }
 }
 
-/// Write Ahead Log
-struct WalCache {
-    /// The target PCR value after the log is written.
+/// A [`Writer`] keeping the eventlog in memory. Writing at a position
+/// drops everything after it, like rewriting the tail of a log file.
+#[derive(Clone, Default)]
+pub struct MemoryWriter {
+    content: Arc<Mutex<Vec<u8>>>,
+    pos: u64,
+}
+
+impl MemoryWriter {
+    /// Create a writer with the given existing eventlog, positioned at its end.
+    pub fn with_content(content: Vec<u8>) -> Self {
+        let pos = content.len() as u64;
+        Self {
+            content: Arc::new(Mutex::new(content)),
+            pos,
+        }
+    }
+
+    /// Get a copy of the eventlog. Clones of the writer share the content.
+    pub fn content(&self) -> Vec<u8> {
+        self.content.lock().expect("poisoned lock").clone()
+    }
+}
+
+impl Writer for MemoryWriter {
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        let mut content = self.content.lock().expect("poisoned lock");
+        content.truncate(self.pos as usize);
+        content.extend_from_slice(data);
+        self.pos += data.len() as u64;
+        Ok(())
+    }
+
+    fn seek(&mut self, pos: u64) -> Result<()> {
+        let len = self.content.lock().expect("poisoned lock").len() as u64;
+        if pos > len {
+            bail!("seek position {pos} is beyond the log length {len}");
+        }
+
+        self.pos = pos;
+        Ok(())
+    }
+
+    fn current_pos(&self) -> u64 {
+        self.pos
+    }
+}
+
+/// An event to be extended, as recorded in the WAL cache.
+struct WalEntry {
+    /// The PCR to be extended.
+    pcr: u64,
+
+    /// The target PCR value after the event is extended.
     expected_pcr: Vec<u8>,
 
     /// The event data to be written.
     event_data: String,
+}
 
+/// Write Ahead Log
+struct WalCache {
     /// The offset of the event data in the event log.
     event_offset: u64,
+
+    /// The events to be extended in order.
+    entries: Vec<WalEntry>,
+}
+
+fn read_u32(file: &mut File) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    file.read_exact(&mut buf)?;
+    Ok(u32::from_be_bytes(buf))
+}
+
+fn read_u64(file: &mut File) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    file.read_exact(&mut buf)?;
+    Ok(u64::from_be_bytes(buf))
+}
+
+/// Turn an AAEL event into a TCG2 entry targeting the CC measurement register
+/// of `pcr`. Returns the encoded entry and its digest.
+fn tcg2_entry(
+    rtmr_extender: &BoxedAttester,
+    alg: HashAlgorithm,
+    event: Event<'_>,
+    pcr: u64,
+) -> (Vec<u8>, Vec<u8>) {
+    let rtmr = rtmr_extender.pcr_to_ccmr(pcr);
+    let (tcg2_event, event_digest) = Into::<Tcg2EventEntry>::into(event)
+        .with_target_measurement_register(rtmr as u32)
+        .digest(alg);
+    (tcg2_event.to_le_bytes(), event_digest)
 }
 
 impl EventLog {
-    pub async fn new(rtmr_extender: Arc<BoxedAttester>, pcr: u64) -> Result<Self> {
-        tokio::fs::create_dir_all(EVENTLOG_PARENT_DIR_PATH)
+    /// Open the eventlog file under `eventlog_dir`, recovering from a
+    /// previous crash if needed.
+    pub async fn new(
+        rtmr_extender: Arc<BoxedAttester>,
+        pcr: u64,
+        eventlog_dir: &Path,
+    ) -> Result<Self> {
+        tokio::fs::create_dir_all(eventlog_dir)
             .await
             .context("create eventlog parent dir")?;
-        let mut file = File::options()
-            .append(true)
-            .create(true)
-            .open(EVENTLOG_PATH)
-            .context("open AAEL file")?;
-        let pos = file.stream_position()?;
+        let writer = FileWriter::open(&eventlog_dir.join(EVENTLOG_FILE_NAME))?;
+
+        Self::with_writer(
+            Box::new(writer),
+            rtmr_extender,
+            pcr,
+            eventlog_dir.join(WAL_CACHE_FILE_NAME),
+        )
+        .await
+    }
 
-        let mut writer = Box::new(FileWriter { file, pos });
+    /// Create an eventlog backed by the given [`Writer`]. `wal_cache` is the
+    /// path of the WAL cache file. If it exists, the entries cached in it are
+    /// recovered first.
+    pub async fn with_writer(
+        mut writer: Box<dyn Writer>,
+        rtmr_extender: Arc<BoxedAttester>,
+        pcr: u64,
+        wal_cache: PathBuf,
+    ) -> Result<Self> {
         let alg = rtmr_extender.ccel_hash_algorithm();
         // if any WAL cache file exists, we should handle recovering from crash
-        match Self::read_wal_cache(alg.digest_len()) {
-            Ok(Some(wal_cache)) => {
+        match Self::read_wal_cache(&wal_cache, alg.digest_len(), pcr) {
+            Ok(Some(cache)) => {
                 warn!("Recover from a previous crash.");
-                let current_pcr = rtmr_extender.get_runtime_measurement(pcr).await.context("get runtime measurement")?;
-                let aael_event = Event::try_from(&wal_cache.event_data[..])?;
-                let (tcg2_event, tcg2_event_digest) = Into::<Tcg2EventEntry>::into(aael_event).digest(alg);
-                let tcg2_event_data = tcg2_event.to_le_bytes();
-
-                // if the PCR has not been extended yet, we should just write eventlog
-                if current_pcr != wal_cache.expected_pcr {
-                    let mut pcr_status = current_pcr.clone();
-                    let mut tcg2_event_digest_clone = tcg2_event_digest.clone();
-                    pcr_status.append(&mut tcg2_event_digest_clone);
-                    let digest_to_be_updated = alg.digest(&pcr_status);
-
-                    if digest_to_be_updated != wal_cache.expected_pcr {
-                        bail!("fatal error when recovering. The eventlog file {EVENTLOG_PATH} is probably corrupted, or other process has extend the target PCR {pcr}.")
-                    }
+                Self::recover(writer.as_mut(), &rtmr_extender, alg, cache).await?;
+                Self::clean_wal_cache(&wal_cache)?;
+            }
+            Err(_) => bail!("Failed to read wal cache. This is a significant error caused by a previous crash. Please try delete `{}` and restart the attestation agent.", wal_cache.display()),
+            Ok(None) => {}
+        }
+
+        Ok(Self {
+            writer,
+            rtmr_extender,
+            alg,
+            pcr,
+            wal_cache,
+        })
+    }
+
+    /// Finish the transaction recorded in the WAL cache. For each CC measurement
+    /// register, the entries which have not been extended yet are extended. Then
+    /// all the entries are written to the eventlog.
+    async fn recover(
+        writer: &mut dyn Writer,
+        rtmr_extender: &BoxedAttester,
+        alg: HashAlgorithm,
+        cache: WalCache,
+    ) -> Result<()> {
+        let mut log = Vec::new();
+        let mut registers: Vec<(u64, Vec<(u64, Vec<u8>, Vec<u8>)>)> = Vec::new();
+        for entry in cache.entries {
+            let aael_event = Event::try_from(&entry.event_data[..])?;
+            let (tcg2_event_data, event_digest) =
+                tcg2_entry(rtmr_extender, alg, aael_event, entry.pcr);
+            log.extend_from_slice(&tcg2_event_data);
 
-                    // else, update the PCR
-                    rtmr_extender.extend_runtime_measurement(tcg2_event_digest, pcr).await?;
+            let rtmr = rtmr_extender.pcr_to_ccmr(entry.pcr);
+            let item = (entry.pcr, event_digest, entry.expected_pcr);
+            match registers.iter_mut().find(|(r, _)| *r == rtmr) {
+                Some((_, items)) => items.push(item),
+                None => registers.push((rtmr, vec![item])),
+            }
+        }
+
+        for (_, items) in registers {
+            let (first_pcr, first_digest, first_expected) = &items[0];
+            let current_pcr = rtmr_extender
+                .get_runtime_measurement(*first_pcr)
+                .await
+                .context("get runtime measurement")?;
+
+            // The entries up to the one whose expected value is the current
+            // value have been extended.
+            let extended = match items
+                .iter()
+                .rposition(|(_, _, expected)| *expected == current_pcr)
+            {
+                Some(index) => index + 1,
+                None => {
+                    let mut pcr_status = current_pcr;
+                    pcr_status.extend_from_slice(first_digest);
+                    if alg.digest(&pcr_status) != *first_expected {
+                        bail!("fatal error when recovering. The eventlog is probably corrupted, or other process has extend the target PCR {first_pcr}.")
+                    }
+                    0
                 }
+            };
 
-                writer.seek(wal_cache.event_offset)?;
-                writer.write(&tcg2_event_data)?;
-                Self::clean_wal_cache()?;
-                Ok(Self {
-                    writer,
-                    rtmr_extender,
-                    alg,
-                    pcr,
-                })
+            for (pcr, event_digest, _) in items.into_iter().skip(extended) {
+                rtmr_extender
+                    .extend_runtime_measurement(event_digest, pcr)
+                    .await?;
             }
-            Err(_) => bail!("Failed to read wal cache. This is a significant error caused by a previous crash. Please try delete `{WAL_CACHE}` and restart the attestation agent."),
-            Ok(None) => Ok(Self {
-                writer,
-                rtmr_extender,
-                alg,
-                pcr,
-            })
         }
+
+        writer.seek(cache.event_offset)?;
+        writer.write(&log)?;
+        Ok(())
     }
 
-    /// Record the event and the target digest into cache file before write, this would do
+    /// Record the events and the target digests into cache file before write, this would do
     /// help when there is a crash between extending PCR and logging event.
-    fn write_wal_cache(&self, wal_cache: WalCache) -> Result<()> {
-        let mut file = File::create(WAL_CACHE)?;
+    ///
+    /// The layout is [`WAL_CACHE_MAGIC`], [`WAL_CACHE_VERSION`] (u32), the
+    /// event offset (u64), the number of entries (u32), then for each entry
+    /// the PCR (u64), the expected PCR value, the event data length (u32) and
+    /// the event data. Integers are in big endian, like the event offset of
+    /// the unversioned WAL cache.
+    fn write_wal_cache(path: &Path, wal_cache: WalCache) -> Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(&WAL_CACHE_MAGIC)?;
+        file.write_all(&WAL_CACHE_VERSION.to_be_bytes())?;
         file.write_all(&wal_cache.event_offset.to_be_bytes())?;
-        file.write_all(wal_cache.expected_pcr.as_ref())?;
-        file.write_all(wal_cache.event_data.as_ref())?;
+        file.write_all(&(wal_cache.entries.len() as u32).to_be_bytes())?;
+        for entry in wal_cache.entries {
+            file.write_all(&entry.pcr.to_be_bytes())?;
+            file.write_all(entry.expected_pcr.as_ref())?;
+            file.write_all(&(entry.event_data.len() as u32).to_be_bytes())?;
+            file.write_all(entry.event_data.as_ref())?;
+        }
         file.sync_data()?;
         Ok(())
     }
 
     /// Remove the wal cache file.
-    pub fn clean_wal_cache() -> Result<()> {
-        remove_file(WAL_CACHE)?;
+    fn clean_wal_cache(path: &Path) -> Result<()> {
+        remove_file(path)?;
         Ok(())
     }
 
-    /// Try to read the wal cache file.
-    fn read_wal_cache(digest_len: usize) -> Result<Option<WalCache>> {
-        if !Path::new(WAL_CACHE).exists() {
+    /// Try to read the wal cache file. A WAL cache left by earlier versions
+    /// of AA is read as well, so that it is recovered and then replaced by
+    /// the versioned format on the next write.
+    fn read_wal_cache(path: &Path, digest_len: usize, pcr: u64) -> Result<Option<WalCache>> {
+        if !path.exists() {
             return Ok(None);
         }
-        let mut file = File::open(WAL_CACHE)?;
-        let mut event_offset = [0u8; 8];
-        file.read_exact(&mut event_offset)?;
-        let event_offset = u64::from_le_bytes(event_offset);
+        let mut file = File::open(path)?;
+        let mut magic = [0u8; 4];
+        file.read_exact(&mut magic)?;
+        if magic != WAL_CACHE_MAGIC {
+            file.rewind()?;
+            return Self::read_unversioned_wal_cache(file, digest_len, pcr).map(Some);
+        }
+
+        let version = read_u32(&mut file)?;
+        if version != WAL_CACHE_VERSION {
+            bail!("unsupported wal cache version {version}");
+        }
+
+        let event_offset = read_u64(&mut file)?;
+        let count = read_u32(&mut file)?;
+
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let pcr = read_u64(&mut file)?;
+
+            let mut expected_pcr = vec![0u8; digest_len];
+            file.read_exact(&mut expected_pcr)?;
+
+            let mut event_data = vec![0u8; read_u32(&mut file)? as usize];
+            file.read_exact(&mut event_data)?;
+            let event_data = String::from_utf8(event_data)?;
+
+            entries.push(WalEntry {
+                pcr,
+                expected_pcr,
+                event_data,
+            });
+        }
+
+        if entries.is_empty() {
+            bail!("no entry in wal cache");
+        }
+
+        Ok(Some(WalCache {
+            event_offset,
+            entries,
+        }))
+    }
+
+    /// Read the single entry WAL cache of earlier versions of AA: the event
+    /// offset (u64), the expected PCR value and the event data up to the end
+    /// of the file. The PCR was not recorded, the entry was recovered to the
+    /// default `pcr` of the eventlog.
+    fn read_unversioned_wal_cache(mut file: File, digest_len: usize, pcr: u64) -> Result<WalCache> {
+        let event_offset = read_u64(&mut file)?;
 
         let mut expected_pcr = vec![0u8; digest_len];
         file.read_exact(&mut expected_pcr)?;
 
         let mut event_data = String::new();
-        let _ = file.read_to_string(&mut event_data)?;
+        file.read_to_string(&mut event_data)?;
 
-        Ok(Some(WalCache {
-            expected_pcr,
-            event_data,
+        Ok(WalCache {
             event_offset,
-        }))
+            entries: vec![WalEntry {
+                pcr,
+                expected_pcr,
+                event_data,
+            }],
+        })
     }
 
     /// This an atomic operation, which will both extend the PCR and write the eventlog to disk.
-    /// We should bring in a little transection mechanism (WAL, Write Ahead Log) to make sure the atomicity.
+    /// See [`EventLog::extend_entries`].
+    #[cfg(test)]
+    pub async fn extend_entry(&mut self, log_entry: Event<'_>, pcr: u64) -> Result<()> {
+        self.extend_entries(vec![(log_entry, pcr)]).await
+    }
+
+    /// This an atomic operation, which will both extend the PCRs with all the given entries
+    /// and write them to the eventlog on disk. We should bring in a little transection
+    /// mechanism (WAL, Write Ahead Log) to make sure the atomicity.
     ///
     /// The logical order:
     ///
-    /// 1. write expected PCR and AAEL event data to WAL cache file on filesystem (memory backend)
-    /// 2. Extend PCR
-    /// 3. Write eventlog entry to AAEL file on filesystem (memory backend)
+    /// 1. write expected PCRs and AAEL event data to WAL cache file on filesystem (memory backend)
+    /// 2. Extend PCRs
+    /// 3. Write eventlog entries to AAEL file on filesystem (memory backend)
     /// 4. delete WAL cache file on filesystem (memory backend)
     ///
     /// Once kernel provides a more robust way (atomic) to maintain an eventlog,
     /// we can remove the WAL cache file mechanism.
-    pub async fn extend_entry(&mut self, log_entry: Event<'_>, pcr: u64) -> Result<()> {
-        let aael_event_data = log_entry.to_string();
-        let rtmr = self.rtmr_extender.pcr_to_ccmr(self.pcr);
-        let (tcg2_event, event_digest) = Into::<Tcg2EventEntry>::into(log_entry)
-            .with_target_measurement_register(rtmr as u32)
-            .digest(self.alg);
+    pub async fn extend_entries(&mut self, log_entries: Vec<(Event<'_>, u64)>) -> Result<()> {
+        if log_entries.is_empty() {
+            return Ok(());
+        }
 
-        let tcg2_event_data = tcg2_event.to_le_bytes();
-        let mut current_pcr = self.rtmr_extender.get_runtime_measurement(pcr).await?;
+        // The expected values of the CC measurement registers after each entry
+        let mut registers: HashMap<u64, Vec<u8>> = HashMap::new();
+        let mut entries = Vec::new();
+        let mut digests = Vec::new();
+        let mut log = Vec::new();
+        for (log_entry, pcr) in log_entries {
+            let event_data = log_entry.to_string();
+            let (tcg2_event_data, event_digest) =
+                tcg2_entry(&self.rtmr_extender, self.alg, log_entry, pcr);
+            log.extend_from_slice(&tcg2_event_data);
 
-        current_pcr.extend_from_slice(&event_digest);
-        let expected_pcr = self.alg.digest(&current_pcr);
+            let rtmr = self.rtmr_extender.pcr_to_ccmr(pcr);
+            let mut current_pcr = match registers.remove(&rtmr) {
+                Some(value) => value,
+                None => self.rtmr_extender.get_runtime_measurement(pcr).await?,
+            };
+            current_pcr.extend_from_slice(&event_digest);
+            let expected_pcr = self.alg.digest(&current_pcr);
+            registers.insert(rtmr, expected_pcr.clone());
+
+            entries.push(WalEntry {
+                pcr,
+                expected_pcr,
+                event_data,
+            });
+            digests.push((event_digest, pcr));
+        }
 
-        let event_offset = self.writer.current_pos();
         let wal_cache = WalCache {
-            expected_pcr,
-            event_data: aael_event_data,
-            event_offset,
+            event_offset: self.writer.current_pos(),
+            entries,
         };
-        self.write_wal_cache(wal_cache)
-            .context("write wal cache file failed")?;
+        Self::write_wal_cache(&self.wal_cache, wal_cache).context("write wal cache file failed")?;
 
-        self.rtmr_extender
-            .extend_runtime_measurement(event_digest, pcr)
-            .await?;
-        self.writer
-            .write(&tcg2_event_data)
-            .context("write log entry")?;
+        for (event_digest, pcr) in digests {
+            self.rtmr_extender
+                .extend_runtime_measurement(event_digest, pcr)
+                .await?;
+        }
+        self.writer.write(&log).context("write log entries")?;
 
-        Self::clean_wal_cache().context("remove wal cache file failed")?;
+        Self::clean_wal_cache(&self.wal_cache).context("remove wal cache file failed")?;
         Ok(())
     }
 }
@@ -291,31 +553,76 @@ impl Display for Event<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use attester::detect_tee_type;
+    use crate::config::DEFAULT_EVENTLOG_DIR;
+    use crate::eventlog::replay::{parse_eventlog, tests::FakeAttester};
+    use attester::{detect_tee_type, utils::EL_HEADER};
     use rstest::rstest;
-    use std::sync::{Arc, Mutex};
 
-    struct TestWriter {
-        content: Arc<Mutex<Vec<u8>>>,
-        pos: u64,
+    const TEST_PCR: u64 = 17;
+
+    fn entry(event: &str, pcr: u64, attester: &BoxedAttester) -> (Vec<u8>, Vec<u8>) {
+        tcg2_entry(
+            attester,
+            attester.ccel_hash_algorithm(),
+            Event::try_from(event).unwrap(),
+            pcr,
+        )
     }
 
-    impl Writer for TestWriter {
-        fn write(&mut self, data: &[u8]) -> Result<()> {
-            self.content.lock().unwrap().extend_from_slice(data);
-            Ok(())
+    /// Leave a WAL cache file as if AA crashed while extending `events`,
+    /// after the first `extended` of them were extended.
+    async fn crash_while_extending(
+        events: &[(&str, u64)],
+        extended: usize,
+        attester: &BoxedAttester,
+        event_offset: u64,
+        wal_cache: &Path,
+    ) {
+        let alg = attester.ccel_hash_algorithm();
+        let mut registers = HashMap::new();
+        let mut entries = Vec::new();
+        for (event, pcr) in events {
+            let (_, digest) = entry(event, *pcr, attester);
+            let rtmr = attester.pcr_to_ccmr(*pcr);
+            let mut expected_pcr = match registers.remove(&rtmr) {
+                Some(value) => value,
+                None => attester.get_runtime_measurement(*pcr).await.unwrap(),
+            };
+            expected_pcr.extend_from_slice(&digest);
+            let expected_pcr = alg.digest(&expected_pcr);
+            registers.insert(rtmr, expected_pcr.clone());
+            entries.push(WalEntry {
+                pcr: *pcr,
+                expected_pcr,
+                event_data: event.to_string(),
+            });
         }
 
-        fn seek(&mut self, pos: u64) -> Result<()> {
-            self.pos = pos;
-            Ok(())
-        }
+        let cache = WalCache {
+            event_offset,
+            entries,
+        };
+        EventLog::write_wal_cache(wal_cache, cache).unwrap();
 
-        fn current_pos(&self) -> u64 {
-            self.pos
+        for (event, pcr) in &events[..extended] {
+            let (_, digest) = entry(event, *pcr, attester);
+            attester
+                .extend_runtime_measurement(digest, *pcr)
+                .await
+                .unwrap();
         }
     }
 
+    fn aael_events(content: &[u8]) -> Vec<String> {
+        let mut raw = EL_HEADER.to_vec();
+        raw.extend_from_slice(content);
+        parse_eventlog(&raw)
+            .unwrap()
+            .iter()
+            .filter_map(|entry| entry.aael_event())
+            .collect()
+    }
+
     #[test]
     fn test_content() {
         let a_str = "hello";
@@ -325,51 +632,356 @@ mod tests {
         assert!(content.is_err());
     }
 
-    /// The eventlog will influence the underlying hardware
-    #[ignore]
+    #[test]
+    fn test_memory_writer() {
+        let mut writer = MemoryWriter::with_content(b"abc".to_vec());
+        assert_eq!(writer.current_pos(), 3);
+        writer.write(b"def").unwrap();
+        assert_eq!(writer.content(), b"abcdef");
+
+        writer.seek(2).unwrap();
+        writer.write(b"x").unwrap();
+        assert_eq!(writer.content(), b"abx");
+        assert_eq!(writer.current_pos(), 3);
+        assert!(writer.seek(4).is_err());
+    }
+
     #[tokio::test]
-    #[serial_test::serial]
     async fn test_log_events() {
-        std::fs::create_dir_all(EVENTLOG_PARENT_DIR_PATH).unwrap();
-        let lines = Arc::new(Mutex::new(vec![]));
-        let tw = TestWriter {
-            content: lines.clone(),
-            pos: 0,
-        };
-        let tee = detect_tee_type();
-        let rtmr_extender =
-            BoxedAttester::try_from(tee).expect("Failed to create BoxedAttester from Tee type");
-
-        let mut el = EventLog {
-            writer: Box::new(tw),
-            pcr: 17,
-            rtmr_extender: Arc::new(rtmr_extender),
-            alg: HashAlgorithm::Sha384,
-        };
+        let dir = tempfile::tempdir().unwrap();
+        let wal_cache = dir.path().join(WAL_CACHE_FILE_NAME);
+        let writer = MemoryWriter::default();
+        let attester: Arc<BoxedAttester> = Arc::new(Box::new(FakeAttester::default()));
 
-        el.extend_entry(
-            Event {
-                domain: "domain",
-                operation: "operation",
-                content: "content1".try_into().unwrap(),
-            },
-            17,
+        let mut eventlog = EventLog::with_writer(
+            Box::new(writer.clone()),
+            attester.clone(),
+            TEST_PCR,
+            wal_cache.clone(),
         )
         .await
         .unwrap();
-        el.extend_entry(
-            Event {
-                domain: "domain",
-                operation: "operation",
-                content: "content2".try_into().unwrap(),
-            },
-            17,
+        for event in ["domain operation content1", "domain operation content2"] {
+            eventlog
+                .extend_entry(Event::try_from(event).unwrap(), TEST_PCR)
+                .await
+                .unwrap();
+        }
+
+        assert!(!wal_cache.exists());
+        assert_eq!(
+            aael_events(&writer.content()),
+            vec!["domain operation content1", "domain operation content2"]
+        );
+
+        let mut expected = vec![0u8; 48];
+        for event in ["domain operation content1", "domain operation content2"] {
+            expected.extend_from_slice(&entry(event, TEST_PCR, &attester).1);
+            expected = HashAlgorithm::Sha384.digest(&expected);
+        }
+        assert_eq!(
+            attester.get_runtime_measurement(TEST_PCR).await.unwrap(),
+            expected
+        );
+    }
+
+    /// The eventlog is encoded the same as by the earlier versions of AA.
+    #[tokio::test]
+    async fn test_aael_golden() {
+        let dir = tempfile::tempdir().unwrap();
+        let writer = MemoryWriter::default();
+        let attester: Arc<BoxedAttester> = Arc::new(Box::new(FakeAttester::default()));
+
+        let mut eventlog = EventLog::with_writer(
+            Box::new(writer.clone()),
+            attester,
+            TEST_PCR,
+            dir.path().join(WAL_CACHE_FILE_NAME),
         )
         .await
         .unwrap();
+        for event in ["domain operation content1", "domain operation content2"] {
+            eventlog
+                .extend_entry(Event::try_from(event).unwrap(), TEST_PCR)
+                .await
+                .unwrap();
+        }
 
         let expected = tokio::fs::read("./test/aael.bin").await.unwrap();
-        assert_eq!(expected, lines.lock().unwrap().to_vec());
+        assert_eq!(expected, writer.content());
+    }
+
+    /// The entry is logged against and extended to the register of the given
+    /// PCR, not the one of the default PCR of the eventlog.
+    #[tokio::test]
+    async fn test_extend_entry_to_given_pcr() {
+        let dir = tempfile::tempdir().unwrap();
+        let writer = MemoryWriter::default();
+        let attester: Arc<BoxedAttester> = Arc::new(Box::new(FakeAttester::default()));
+        let pcr = 8;
+        assert_ne!(attester.pcr_to_ccmr(pcr), attester.pcr_to_ccmr(TEST_PCR));
+
+        let mut eventlog = EventLog::with_writer(
+            Box::new(writer.clone()),
+            attester.clone(),
+            TEST_PCR,
+            dir.path().join(WAL_CACHE_FILE_NAME),
+        )
+        .await
+        .unwrap();
+        eventlog
+            .extend_entry(Event::try_from("domain operation content").unwrap(), pcr)
+            .await
+            .unwrap();
+
+        let mut raw = EL_HEADER.to_vec();
+        raw.extend_from_slice(&writer.content());
+        let entries = parse_eventlog(&raw).unwrap();
+        let logged = entries
+            .iter()
+            .find(|entry| entry.aael_event().is_some())
+            .unwrap();
+        assert_eq!(
+            logged.target_measurement_register() as u64,
+            attester.pcr_to_ccmr(pcr)
+        );
+
+        let mut expected = vec![0u8; 48];
+        expected.extend_from_slice(&entry("domain operation content", pcr, &attester).1);
+        assert_eq!(
+            attester.get_runtime_measurement(pcr).await.unwrap(),
+            HashAlgorithm::Sha384.digest(&expected)
+        );
+        assert_eq!(
+            attester.get_runtime_measurement(TEST_PCR).await.unwrap(),
+            vec![0u8; 48]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extend_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal_cache = dir.path().join(WAL_CACHE_FILE_NAME);
+        let writer = MemoryWriter::default();
+        let attester: Arc<BoxedAttester> = Arc::new(Box::new(FakeAttester::default()));
+
+        let mut eventlog = EventLog::with_writer(
+            Box::new(writer.clone()),
+            attester.clone(),
+            TEST_PCR,
+            wal_cache.clone(),
+        )
+        .await
+        .unwrap();
+        let events = vec![
+            (Event::try_from("domain operation content1").unwrap(), 17),
+            (Event::try_from("domain operation content2").unwrap(), 8),
+            (Event::try_from("domain operation content3").unwrap(), 18),
+        ];
+        eventlog.extend_entries(events).await.unwrap();
+
+        assert!(!wal_cache.exists());
+        assert_eq!(
+            aael_events(&writer.content()),
+            vec![
+                "domain operation content1",
+                "domain operation content2",
+                "domain operation content3"
+            ]
+        );
+
+        let mut raw = EL_HEADER.to_vec();
+        raw.extend_from_slice(&writer.content());
+        let verification = crate::eventlog::replay::verify_eventlog(&attester, &raw)
+            .await
+            .unwrap();
+        assert_eq!(verification.registers.len(), 2);
+        assert!(verification.verified());
+    }
+
+    #[rstest]
+    #[case::pcr_not_extended(&[("domain operation content2", 17)], 0)]
+    #[case::pcr_extended(&[("domain operation content2", 17)], 1)]
+    #[case::batch_not_extended(&[("domain operation content2", 17), ("domain operation content3", 8), ("domain operation content4", 18)], 0)]
+    #[case::batch_partially_extended(&[("domain operation content2", 17), ("domain operation content3", 8), ("domain operation content4", 18)], 2)]
+    #[case::batch_extended(&[("domain operation content2", 17), ("domain operation content3", 8), ("domain operation content4", 18)], 3)]
+    #[tokio::test]
+    async fn test_recover_from_crash(#[case] events: &[(&str, u64)], #[case] extended: usize) {
+        let dir = tempfile::tempdir().unwrap();
+        let wal_cache = dir.path().join(WAL_CACHE_FILE_NAME);
+        let attester: Arc<BoxedAttester> = Arc::new(Box::new(FakeAttester::default()));
+
+        let (first, first_digest) = entry("domain operation content1", TEST_PCR, &attester);
+        attester
+            .extend_runtime_measurement(first_digest, TEST_PCR)
+            .await
+            .unwrap();
+
+        // The crash happened after a partial entry was written
+        let mut content = first.clone();
+        content.extend_from_slice(b"garbage");
+        let writer = MemoryWriter::with_content(content);
+
+        crash_while_extending(events, extended, &attester, first.len() as u64, &wal_cache).await;
+
+        let eventlog = EventLog::with_writer(
+            Box::new(writer.clone()),
+            attester.clone(),
+            TEST_PCR,
+            wal_cache.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!wal_cache.exists());
+        assert_eq!(eventlog.writer.current_pos(), writer.content().len() as u64);
+        let mut expected = vec!["domain operation content1"];
+        expected.extend(events.iter().map(|(event, _)| *event));
+        assert_eq!(aael_events(&writer.content()), expected);
+
+        let mut raw = EL_HEADER.to_vec();
+        raw.extend_from_slice(&writer.content());
+        let verification = crate::eventlog::replay::verify_eventlog(&attester, &raw)
+            .await
+            .unwrap();
+        assert!(verification.verified());
+    }
+
+    #[tokio::test]
+    async fn test_recover_pcr_changed_by_others() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal_cache = dir.path().join(WAL_CACHE_FILE_NAME);
+        let attester: Arc<BoxedAttester> = Arc::new(Box::new(FakeAttester::default()));
+
+        crash_while_extending(
+            &[("domain operation content", TEST_PCR)],
+            0,
+            &attester,
+            0,
+            &wal_cache,
+        )
+        .await;
+        attester
+            .extend_runtime_measurement(vec![0xab; 48], TEST_PCR)
+            .await
+            .unwrap();
+
+        let res = EventLog::with_writer(
+            Box::new(MemoryWriter::default()),
+            attester,
+            TEST_PCR,
+            wal_cache.clone(),
+        )
+        .await;
+        assert!(res.is_err());
+        assert!(wal_cache.exists());
+    }
+
+    #[rstest]
+    #[case::pcr_not_extended(false)]
+    #[case::pcr_extended(true)]
+    #[tokio::test]
+    async fn test_recover_unversioned_wal_cache(#[case] pcr_extended: bool) {
+        let dir = tempfile::tempdir().unwrap();
+        let wal_cache = dir.path().join(WAL_CACHE_FILE_NAME);
+        let attester: Arc<BoxedAttester> = Arc::new(Box::new(FakeAttester::default()));
+
+        let (first, first_digest) = entry("domain operation content1", TEST_PCR, &attester);
+        attester
+            .extend_runtime_measurement(first_digest, TEST_PCR)
+            .await
+            .unwrap();
+        let writer = MemoryWriter::with_content(first.clone());
+
+        // The WAL cache written by earlier versions of AA
+        let event = "domain operation content2";
+        let (_, digest) = entry(event, TEST_PCR, &attester);
+        let mut expected_pcr = attester.get_runtime_measurement(TEST_PCR).await.unwrap();
+        expected_pcr.extend_from_slice(&digest);
+        let mut cache = (first.len() as u64).to_be_bytes().to_vec();
+        cache.extend_from_slice(&HashAlgorithm::Sha384.digest(&expected_pcr));
+        cache.extend_from_slice(event.as_bytes());
+        std::fs::write(&wal_cache, cache).unwrap();
+        if pcr_extended {
+            attester
+                .extend_runtime_measurement(digest, TEST_PCR)
+                .await
+                .unwrap();
+        }
+
+        EventLog::with_writer(
+            Box::new(writer.clone()),
+            attester.clone(),
+            TEST_PCR,
+            wal_cache.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!wal_cache.exists());
+        assert_eq!(
+            aael_events(&writer.content()),
+            vec!["domain operation content1", event]
+        );
+
+        let mut raw = EL_HEADER.to_vec();
+        raw.extend_from_slice(&writer.content());
+        let verification = crate::eventlog::replay::verify_eventlog(&attester, &raw)
+            .await
+            .unwrap();
+        assert!(verification.verified());
+    }
+
+    #[tokio::test]
+    async fn test_recover_corrupted_wal_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal_cache = dir.path().join(WAL_CACHE_FILE_NAME);
+        std::fs::write(&wal_cache, [0u8; 3]).unwrap();
+        let attester: Arc<BoxedAttester> = Arc::new(Box::new(FakeAttester::default()));
+
+        let res = EventLog::with_writer(
+            Box::new(MemoryWriter::default()),
+            attester,
+            TEST_PCR,
+            wal_cache,
+        )
+        .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_eventlog_in_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let attester: Arc<BoxedAttester> = Arc::new(Box::new(FakeAttester::default()));
+
+        let mut eventlog = EventLog::new(attester.clone(), TEST_PCR, dir.path())
+            .await
+            .unwrap();
+        eventlog
+            .extend_entry(
+                Event::try_from("domain operation content1").unwrap(),
+                TEST_PCR,
+            )
+            .await
+            .unwrap();
+        drop(eventlog);
+
+        // Reopening the eventlog appends to the existing one
+        let mut eventlog = EventLog::new(attester, TEST_PCR, dir.path()).await.unwrap();
+        eventlog
+            .extend_entry(
+                Event::try_from("domain operation content2").unwrap(),
+                TEST_PCR,
+            )
+            .await
+            .unwrap();
+
+        let content = std::fs::read(dir.path().join(EVENTLOG_FILE_NAME)).unwrap();
+        assert_eq!(
+            aael_events(&content),
+            vec!["domain operation content1", "domain operation content2"]
+        );
+        assert!(!dir.path().join(WAL_CACHE_FILE_NAME).exists());
     }
 
     #[rstest]
@@ -405,13 +1017,20 @@ mod tests {
     #[tokio::test]
     #[serial_test::serial]
     async fn test_eventlog_from_nothing() {
-        if std::path::Path::new(EVENTLOG_PATH).exists() {
-            std::fs::remove_file(EVENTLOG_PATH).unwrap();
+        let eventlog_path = Path::new(DEFAULT_EVENTLOG_DIR).join(EVENTLOG_FILE_NAME);
+        if eventlog_path.exists() {
+            std::fs::remove_file(&eventlog_path).unwrap();
         }
         let tee = detect_tee_type();
         let rtmr_extender =
             BoxedAttester::try_from(tee).expect("Failed to create BoxedAttester from Tee type");
-        let mut eventlog = EventLog::new(Arc::new(rtmr_extender), 17).await.unwrap();
+        let mut eventlog = EventLog::new(
+            Arc::new(rtmr_extender),
+            TEST_PCR,
+            Path::new(DEFAULT_EVENTLOG_DIR),
+        )
+        .await
+        .unwrap();
         eventlog
             .extend_entry(
                 Event {
@@ -419,12 +1038,12 @@ mod tests {
                     operation: "operation",
                     content: "content".try_into().unwrap(),
                 },
-                17,
+                TEST_PCR,
             )
             .await
             .unwrap();
         drop(eventlog);
-        std::fs::remove_file(EVENTLOG_PATH).unwrap();
+        std::fs::remove_file(&eventlog_path).unwrap();
     }
 
     // skip this test because it will depend/influence the underlying hardware
@@ -432,14 +1051,13 @@ mod tests {
     #[tokio::test]
     #[serial_test::serial]
     async fn test_eventlog_from_empty_file() {
-        if !Path::new(EVENTLOG_PARENT_DIR_PATH).exists() {
-            std::fs::create_dir_all(EVENTLOG_PARENT_DIR_PATH).unwrap();
-        }
+        let eventlog_path = Path::new(DEFAULT_EVENTLOG_DIR).join(EVENTLOG_FILE_NAME);
+        std::fs::create_dir_all(DEFAULT_EVENTLOG_DIR).unwrap();
         let f = std::fs::File::options()
             .create(true)
             .truncate(true)
             .write(true)
-            .open(EVENTLOG_PATH)
+            .open(&eventlog_path)
             .unwrap();
         f.sync_all().unwrap();
         drop(f);
@@ -447,7 +1065,13 @@ mod tests {
         let tee = detect_tee_type();
         let rtmr_extender =
             BoxedAttester::try_from(tee).expect("Failed to create BoxedAttester from Tee type");
-        let mut eventlog = EventLog::new(Arc::new(rtmr_extender), 17).await.unwrap();
+        let mut eventlog = EventLog::new(
+            Arc::new(rtmr_extender),
+            TEST_PCR,
+            Path::new(DEFAULT_EVENTLOG_DIR),
+        )
+        .await
+        .unwrap();
         eventlog
             .extend_entry(
                 Event {
@@ -455,11 +1079,11 @@ mod tests {
                     operation: "operation",
                     content: "content".try_into().unwrap(),
                 },
-                17,
+                TEST_PCR,
             )
             .await
             .unwrap();
         drop(eventlog);
-        std::fs::remove_file(EVENTLOG_PATH).unwrap();
+        std::fs::remove_file(&eventlog_path).unwrap();
     }
 }
@@ -0,0 +1,350 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Read back the CCEL and AAEL of the platform, replay them and compare
+//! the result with the runtime measurement registers.
+
+use std::{collections::BTreeMap, path::Path};
+
+use anyhow::{bail, Context, Result};
+use attester::BoxedAttester;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use kbs_types::HashAlgorithm;
+use log::warn;
+use serde::Serialize;
+
+use super::tcg2::{Tcg2EventEntry, TcgAlgorithm};
+
+/// Size of the SHA-1 digest in the legacy `TCG_PCR_EVENT` header entry.
+const LEGACY_DIGEST_SIZE: usize = 20;
+
+/// PCR indexes probed to find the PCR mapped to a CC measurement register.
+const MAX_PCR_INDEX: u64 = 23;
+
+/// Read the eventlog of the platform, i.e. the CCEL followed by the AAEL
+/// entries at `aael_path`. Returns an empty vector if neither of them exists.
+pub async fn read_raw_eventlog(aael_path: &Path) -> Result<Vec<u8>> {
+    let Some(eventlog) = attester::utils::read_eventlog_from(aael_path)
+        .await
+        .context("read eventlog")?
+    else {
+        return Ok(vec![]);
+    };
+
+    STANDARD.decode(eventlog).context("base64 decode eventlog")
+}
+
+/// Parse the eventlog into TCG2 event entries. The leading `TCG_PCR_EVENT`
+/// header entry in legacy format is skipped.
+pub fn parse_eventlog(raw: &[u8]) -> Result<Vec<Tcg2EventEntry>> {
+    if raw.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // pcr index, event type, SHA-1 digest and event data size
+    let header_size = size_of::<u32>() * 2 + LEGACY_DIGEST_SIZE;
+    let Some(event_size) = raw.get(header_size..header_size + size_of::<u32>()) else {
+        bail!("invalid eventlog: no header entry");
+    };
+    let event_size = u32::from_le_bytes(event_size.try_into().expect("slice must be 4 bytes"));
+    let mut offset = header_size + size_of::<u32>() + event_size as usize;
+
+    let mut entries = Vec::new();
+    loop {
+        let Some(next) = raw.get(offset..offset + size_of::<u64>()) else {
+            break;
+        };
+        if next.iter().all(|b| *b == 0xFF) || next.iter().all(|b| *b == 0x00) {
+            break;
+        }
+
+        let (entry, size) = Tcg2EventEntry::from_le_bytes(&raw[offset..])
+            .with_context(|| format!("invalid eventlog entry at offset {offset}"))?;
+        entries.push(entry);
+        offset += size;
+    }
+
+    Ok(entries)
+}
+
+/// Replay the entries per CC measurement register. Every register starts
+/// with zeros and is extended with the digest of each entry targeting it.
+pub fn replay(entries: &[Tcg2EventEntry], alg: HashAlgorithm) -> Result<BTreeMap<u32, Vec<u8>>> {
+    let tcg_alg = TcgAlgorithm::from(alg);
+    let mut registers = BTreeMap::new();
+    for entry in entries.iter().filter(|entry| !entry.is_no_action()) {
+        let Some(digest) = entry.digests().iter().find(|d| d.alg == tcg_alg) else {
+            bail!(
+                "eventlog entry for register {} has no {tcg_alg:?} digest",
+                entry.target_measurement_register()
+            );
+        };
+
+        let register = registers
+            .entry(entry.target_measurement_register())
+            .or_insert_with(|| vec![0u8; tcg_alg.digest_len()]);
+        register.extend_from_slice(&digest.digest);
+        *register = alg.digest(register);
+    }
+
+    Ok(registers)
+}
+
+/// The replay result of a single CC measurement register.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct RegisterReplay {
+    /// The CC measurement register index as recorded in the eventlog.
+    pub register_index: u32,
+
+    /// The register value calculated from the eventlog.
+    pub replayed: Vec<u8>,
+
+    /// The register value read from the platform. `None` if no PCR maps
+    /// to the register, e.g. TDX MRTD.
+    pub actual: Option<Vec<u8>>,
+}
+
+impl RegisterReplay {
+    pub fn matched(&self) -> bool {
+        self.actual.as_ref() == Some(&self.replayed)
+    }
+}
+
+/// The result of replaying the eventlog against the platform registers.
+#[derive(Clone, Debug, Serialize)]
+pub struct EventlogVerification {
+    /// The hash algorithm used to replay the eventlog.
+    pub hash_algorithm: HashAlgorithm,
+
+    /// Number of entries in the eventlog.
+    pub entries: usize,
+
+    /// One result per register extended by the eventlog.
+    pub registers: Vec<RegisterReplay>,
+}
+
+impl EventlogVerification {
+    /// Whether every register that can be read from the platform matches
+    /// the replayed value. A replay which compared no register, e.g. as none
+    /// can be read from the platform, is not verified.
+    pub fn verified(&self) -> bool {
+        let mut compared = self
+            .registers
+            .iter()
+            .filter(|register| register.actual.is_some())
+            .peekable();
+        compared.peek().is_some() && compared.all(RegisterReplay::matched)
+    }
+}
+
+/// Replay the given raw eventlog with the CCEL hash algorithm of the
+/// attester and compare the result with its runtime measurement registers.
+pub async fn verify_eventlog(attester: &BoxedAttester, raw: &[u8]) -> Result<EventlogVerification> {
+    if !attester.supports_runtime_measurement() {
+        bail!("The attester does not support runtime measurement");
+    }
+
+    let hash_algorithm = attester.ccel_hash_algorithm();
+    let entries = parse_eventlog(raw)?;
+    let replayed = replay(&entries, hash_algorithm)?;
+
+    let mut registers = Vec::new();
+    for (register_index, replayed) in replayed {
+        let pcr =
+            (0..=MAX_PCR_INDEX).find(|pcr| attester.pcr_to_ccmr(*pcr) == register_index as u64);
+        let actual = match pcr {
+            Some(pcr) => Some(
+                attester
+                    .get_runtime_measurement(pcr)
+                    .await
+                    .with_context(|| format!("get runtime measurement of PCR {pcr}"))?,
+            ),
+            None => {
+                warn!("No PCR maps to CC measurement register {register_index}, skip it");
+                None
+            }
+        };
+
+        registers.push(RegisterReplay {
+            register_index,
+            replayed,
+            actual,
+        });
+    }
+
+    Ok(EventlogVerification {
+        hash_algorithm,
+        entries: entries.len(),
+        registers,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{collections::HashMap, sync::Mutex};
+
+    use attester::{
+        utils::{EL_END_FLAG, EL_HEADER},
+        Attester, TeeEvidence,
+    };
+
+    use super::*;
+    use crate::eventlog::Event;
+
+    /// An attester keeping its registers in memory, indexed by CC
+    /// measurement register.
+    #[derive(Default)]
+    pub(crate) struct FakeAttester {
+        registers: Mutex<HashMap<u64, Vec<u8>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Attester for FakeAttester {
+        async fn get_evidence(&self, _report_data: Vec<u8>) -> Result<TeeEvidence> {
+            Ok(serde_json::Value::Null)
+        }
+
+        fn supports_runtime_measurement(&self) -> bool {
+            true
+        }
+
+        async fn extend_runtime_measurement(
+            &self,
+            event_digest: Vec<u8>,
+            register_index: u64,
+        ) -> Result<()> {
+            let mut registers = self.registers.lock().unwrap();
+            let register = registers
+                .entry(self.pcr_to_ccmr(register_index))
+                .or_insert_with(|| vec![0u8; 48]);
+            register.extend_from_slice(&event_digest);
+            *register = HashAlgorithm::Sha384.digest(register);
+            Ok(())
+        }
+
+        async fn get_runtime_measurement(&self, pcr_index: u64) -> Result<Vec<u8>> {
+            let registers = self.registers.lock().unwrap();
+            Ok(registers
+                .get(&self.pcr_to_ccmr(pcr_index))
+                .cloned()
+                .unwrap_or_else(|| vec![0u8; 48]))
+        }
+
+        fn pcr_to_ccmr(&self, pcr_index: u64) -> u64 {
+            match pcr_index {
+                1 | 7 => 1,
+                2..=6 => 2,
+                8..=15 => 3,
+                _ => 4,
+            }
+        }
+
+        fn ccel_hash_algorithm(&self) -> HashAlgorithm {
+            HashAlgorithm::Sha384
+        }
+    }
+
+    fn aael(events: &[(&str, u32)]) -> Vec<u8> {
+        let mut raw = EL_HEADER.to_vec();
+        for (event, register) in events {
+            let event = Event::try_from(*event).unwrap();
+            let (entry, _) = Tcg2EventEntry::from(event)
+                .with_target_measurement_register(*register)
+                .digest(HashAlgorithm::Sha384);
+            raw.extend_from_slice(&entry.to_le_bytes());
+        }
+        raw.extend_from_slice(&EL_END_FLAG);
+        raw
+    }
+
+    #[test]
+    fn test_parse_eventlog() {
+        let raw = aael(&[
+            ("domain operation content1", 4),
+            ("domain operation content2", 3),
+        ]);
+
+        let entries = parse_eventlog(&raw).unwrap();
+        let events: Vec<_> = entries.iter().filter_map(|e| e.aael_event()).collect();
+        assert_eq!(
+            events,
+            vec!["domain operation content1", "domain operation content2"]
+        );
+        assert_eq!(entries[1].target_measurement_register(), 3);
+
+        assert!(parse_eventlog(&[]).unwrap().is_empty());
+        assert!(parse_eventlog(&EL_HEADER[..20]).is_err());
+    }
+
+    #[test]
+    fn test_replay() {
+        let raw = aael(&[("a b c", 4), ("d e f", 4), ("g h i", 3)]);
+        let entries = parse_eventlog(&raw).unwrap();
+        assert_eq!(entries.len(), 3);
+
+        let registers = replay(&entries, HashAlgorithm::Sha384).unwrap();
+        assert_eq!(registers.keys().copied().collect::<Vec<_>>(), vec![3, 4]);
+
+        let mut expected = vec![0u8; 48];
+        for entry in &entries[..2] {
+            expected.extend_from_slice(&entries_digest(entry));
+            expected = HashAlgorithm::Sha384.digest(&expected);
+        }
+        assert_eq!(registers[&4], expected);
+
+        assert!(replay(&entries, HashAlgorithm::Sha256).is_err());
+    }
+
+    fn entries_digest(entry: &Tcg2EventEntry) -> Vec<u8> {
+        entry.digests()[0].digest.clone()
+    }
+
+    #[tokio::test]
+    async fn test_verify_eventlog() {
+        let fake = FakeAttester::default();
+        let raw = aael(&[("a b c", 4), ("d e f", 3)]);
+        for entry in parse_eventlog(&raw).unwrap() {
+            let pcr = match entry.target_measurement_register() {
+                3 => 8,
+                _ => 17,
+            };
+            fake.extend_runtime_measurement(entries_digest(&entry), pcr)
+                .await
+                .unwrap();
+        }
+
+        let attester: BoxedAttester = Box::new(fake);
+        let verification = verify_eventlog(&attester, &raw).await.unwrap();
+        assert_eq!(verification.entries, 2);
+        assert_eq!(verification.registers.len(), 2);
+        assert!(verification.verified());
+
+        // An entry which has not been extended into the register
+        let raw = aael(&[("a b c", 4), ("d e f", 3), ("x y z", 3)]);
+        let verification = verify_eventlog(&attester, &raw).await.unwrap();
+        assert!(!verification.verified());
+        assert!(verification
+            .registers
+            .iter()
+            .any(|register| register.register_index == 3 && !register.matched()));
+    }
+
+    #[tokio::test]
+    async fn test_verify_eventlog_without_registers() {
+        let attester: BoxedAttester = Box::new(FakeAttester::default());
+
+        // No PCR maps to the register, so nothing is compared
+        let raw = aael(&[("a b c", 5)]);
+        let verification = verify_eventlog(&attester, &raw).await.unwrap();
+        assert_eq!(verification.registers.len(), 1);
+        assert_eq!(verification.registers[0].actual, None);
+        assert!(!verification.verified());
+
+        let raw = aael(&[]);
+        let verification = verify_eventlog(&attester, &raw).await.unwrap();
+        assert!(verification.registers.is_empty());
+        assert!(!verification.verified());
+    }
+}
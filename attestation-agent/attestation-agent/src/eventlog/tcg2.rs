@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
+use anyhow::{bail, Context, Result};
 use kbs_types::HashAlgorithm;
 use serde::Serialize;
 
@@ -35,10 +36,54 @@ impl From<HashAlgorithm> for TcgAlgorithm {
     }
 }
 
+impl TryFrom<u16> for TcgAlgorithm {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u16) -> Result<Self> {
+        match value {
+            0xB => Ok(TcgAlgorithm::Sha256),
+            0xC => Ok(TcgAlgorithm::Sha384),
+            0xD => Ok(TcgAlgorithm::Sha512),
+            0x12 => Ok(TcgAlgorithm::Sm3),
+            other => bail!("unsupported TCG digest algorithm {other:#x}"),
+        }
+    }
+}
+
 impl TcgAlgorithm {
     pub const fn to_le_bytes(self) -> [u8; 2] {
         (self as u16).to_le_bytes()
     }
+
+    pub const fn digest_len(self) -> usize {
+        match self {
+            TcgAlgorithm::Sha256 | TcgAlgorithm::Sm3 => 32,
+            TcgAlgorithm::Sha384 => 48,
+            TcgAlgorithm::Sha512 => 64,
+        }
+    }
+}
+
+/// Read a little-endian u32 at `offset` of `bytes` and move the offset forward.
+fn read_u32(bytes: &[u8], offset: &mut usize) -> Result<u32> {
+    let end = *offset + size_of::<u32>();
+    let value = bytes
+        .get(*offset..end)
+        .context("unexpected end of eventlog")?;
+    *offset = end;
+    Ok(u32::from_le_bytes(
+        value.try_into().expect("slice must be 4 bytes"),
+    ))
+}
+
+/// Read `len` bytes at `offset` of `bytes` and move the offset forward.
+fn read_bytes<'a>(bytes: &'a [u8], offset: &mut usize, len: usize) -> Result<&'a [u8]> {
+    let end = *offset + len;
+    let value = bytes
+        .get(*offset..end)
+        .context("unexpected end of eventlog")?;
+    *offset = end;
+    Ok(value)
 }
 
 #[derive(Debug, Clone)]
@@ -60,6 +105,7 @@ impl ElDigest {
 /// This is a wrapper of AAEL.
 ///
 /// The event data section is the AAEL plaintext.
+#[derive(Debug, Clone)]
 pub struct Tcg2EventEntry {
     /// The target measurement register number.
     target_measurement_register: u32,
@@ -96,6 +142,74 @@ impl Tcg2EventEntry {
         bytes.extend_from_slice(&self.event_data);
         bytes
     }
+
+    /// Parse a crypto agile event entry from the beginning of `bytes`.
+    /// Returns the entry and the number of bytes it occupies.
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<(Self, usize)> {
+        let mut offset = 0;
+        let target_measurement_register = read_u32(bytes, &mut offset)?;
+        let event_type_num = read_u32(bytes, &mut offset)?;
+        let digest_count = read_u32(bytes, &mut offset)?;
+
+        let mut digest = Vec::new();
+        for _ in 0..digest_count {
+            let alg = read_bytes(bytes, &mut offset, size_of::<u16>())?;
+            let alg = TcgAlgorithm::try_from(u16::from_le_bytes(
+                alg.try_into().expect("slice must be 2 bytes"),
+            ))?;
+            let value = read_bytes(bytes, &mut offset, alg.digest_len())?;
+            digest.push(ElDigest {
+                alg,
+                digest: value.to_vec(),
+            });
+        }
+
+        let event_data_length = read_u32(bytes, &mut offset)?;
+        let event_data = read_bytes(bytes, &mut offset, event_data_length as usize)?.to_vec();
+
+        Ok((
+            Self {
+                target_measurement_register,
+                event_type_num,
+                digest_count,
+                digest,
+                event_data_length,
+                event_data,
+            },
+            offset,
+        ))
+    }
+
+    pub fn target_measurement_register(&self) -> u32 {
+        self.target_measurement_register
+    }
+
+    pub fn digests(&self) -> &[ElDigest] {
+        &self.digest
+    }
+
+    /// Whether the event is only informative and not extended into any register.
+    pub fn is_no_action(&self) -> bool {
+        self.event_type_num == EV_NO_ACTION_TYPE
+    }
+
+    /// The AAEL plaintext carried in the entry, if it is an AAEL tagged event.
+    #[cfg(test)]
+    pub fn aael_event(&self) -> Option<&str> {
+        if self.event_type_num != EV_EVENT_TAG_TYPE {
+            return None;
+        }
+
+        let mut offset = 0;
+        let tagged_id = read_u32(&self.event_data, &mut offset).ok()?;
+        if tagged_id != AAEL_TAGGED_EVENT_ID {
+            return None;
+        }
+
+        let size = read_u32(&self.event_data, &mut offset).ok()?;
+        let data = read_bytes(&self.event_data, &mut offset, size as usize).ok()?;
+        std::str::from_utf8(data).ok()
+    }
 }
 
 pub struct TaggedEvent {
@@ -119,6 +233,9 @@ impl TaggedEvent {
     }
 }
 
+/// Informative event type ID, not extended into any register
+const EV_NO_ACTION_TYPE: u32 = 0x3;
+
 /// Tagged event type ID
 const EV_EVENT_TAG_TYPE: u32 = 0x6;
 
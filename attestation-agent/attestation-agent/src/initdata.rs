@@ -5,14 +5,30 @@
 
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 
 use kbs_types::HashAlgorithm;
 use serde::Deserialize;
 
+use crate::config::Config;
+
+/// The initdata version supported by AA.
+pub const INITDATA_VERSION: &str = "0.1.0";
+
+/// Key of the AA configuration section in `data`, in the same format as
+/// the AA configuration file.
+pub const AA_CONFIG_SECTION: &str = "aa.toml";
+
+/// Key of the CDH configuration section in `data`, in the same format as
+/// the CDH configuration file.
+pub const CDH_CONFIG_SECTION: &str = "cdh.toml";
+
+/// Key of the kata-agent policy section in `data`.
+pub const POLICY_SECTION: &str = "policy.rego";
+
 /// Initdata defined in
 /// <https://github.com/confidential-containers/trustee/blob/47d7a2338e0be76308ac19be5c0c172c592780aa/kbs/docs/initdata.md>
-#[derive(Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct Initdata {
     pub version: String,
     pub algorithm: HashAlgorithm,
@@ -20,10 +36,214 @@ pub struct Initdata {
 }
 
 impl Initdata {
-    /// Create a new Initdata instance from a TOML string.
+    /// Create a new Initdata instance from a TOML string. The version and
+    /// the well-known sections are validated.
     pub fn parse_and_get_digest(toml: &str) -> Result<(Self, Vec<u8>)> {
-        let initdata: Initdata = toml::de::from_str(toml)?;
+        let initdata: Initdata = toml::de::from_str(toml).context("parse initdata toml")?;
+        initdata.validate()?;
         let digest = initdata.algorithm.digest(toml.as_bytes());
         Ok((initdata, digest))
     }
+
+    /// Check the version and the well-known sections of the initdata.
+    /// Unknown sections are kept as they are.
+    pub fn validate(&self) -> Result<()> {
+        if self.version != INITDATA_VERSION {
+            bail!(
+                "unsupported initdata version `{}`, only `{INITDATA_VERSION}` is supported",
+                self.version
+            );
+        }
+
+        self.aa_config()?;
+        self.cdh_config()?;
+        Ok(())
+    }
+
+    /// The AA configuration carried by the initdata, if any.
+    pub fn aa_config(&self) -> Result<Option<Config>> {
+        let Some(aa_config) = self.data.get(AA_CONFIG_SECTION) else {
+            return Ok(None);
+        };
+
+        let config = Config::from_toml_str(aa_config)
+            .with_context(|| format!("invalid `{AA_CONFIG_SECTION}` in initdata"))?;
+        Ok(Some(config))
+    }
+
+    /// The CDH configuration carried by the initdata, if any. AA does not
+    /// interpret it but only checks it is valid TOML, as it is parsed by CDH.
+    pub fn cdh_config(&self) -> Result<Option<&str>> {
+        let Some(cdh_config) = self.data.get(CDH_CONFIG_SECTION) else {
+            return Ok(None);
+        };
+
+        toml::de::from_str::<toml::Table>(cdh_config)
+            .with_context(|| format!("invalid `{CDH_CONFIG_SECTION}` in initdata"))?;
+        Ok(Some(cdh_config))
+    }
+
+    /// The kata-agent policy carried by the initdata, if any.
+    pub fn policy(&self) -> Option<&str> {
+        self.data.get(POLICY_SECTION).map(String::as_str)
+    }
+}
+
+/// The initdata given to AA at launch. Its digest is bound to the platform
+/// before AA starts serving, so other components can fetch it from AA
+/// instead of parsing it again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundInitdata {
+    /// The plaintext initdata TOML.
+    pub toml: String,
+
+    /// The digest of the TOML, calculated with the initdata algorithm.
+    pub digest: Vec<u8>,
+
+    pub initdata: Initdata,
+}
+
+impl BoundInitdata {
+    pub fn new(toml: String) -> Result<Self> {
+        let (initdata, digest) = Initdata::parse_and_get_digest(&toml)?;
+        Ok(Self {
+            toml,
+            digest,
+            initdata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(
+        r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"aa.toml" = '''
+[token_configs]
+[token_configs.kbs]
+url = "http://127.0.0.1:8080"
+'''
+
+"cdh.toml" = '''
+socket = "unix:///run/confidential-containers/cdh.sock"
+
+[kbc]
+name = "cc_kbc"
+url = "http://127.0.0.1:8080"
+'''
+
+"policy.rego" = '''
+package agent_policy
+default AllowRequestsFailingPolicy := true
+'''
+"#,
+        true
+    )]
+    #[case(
+        r#"
+version = "0.1.0"
+algorithm = "sha256"
+
+[data]
+"unknown" = "kept as it is"
+"#,
+        true
+    )]
+    #[case(
+        r#"
+version = "0.2.0"
+algorithm = "sha384"
+
+[data]
+"#,
+        false
+    )]
+    #[case(
+        r#"
+version = "0.1.0"
+algorithm = "md5"
+
+[data]
+"#,
+        false
+    )]
+    #[case(
+        r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"aa.toml" = '''
+[eventlog_config]
+init_pcr = "not a number"
+'''
+"#,
+        false
+    )]
+    #[case(
+        r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"cdh.toml" = "[kbc"
+"#,
+        false
+    )]
+    fn test_parse_initdata(#[case] toml: &str, #[case] valid: bool) {
+        assert_eq!(Initdata::parse_and_get_digest(toml).is_ok(), valid);
+    }
+
+    #[test]
+    fn test_initdata_sections() {
+        let toml = r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"aa.toml" = '''
+[token_configs.proxy]
+https_proxy = "http://proxy.example.com:3128"
+
+[eventlog_config]
+init_pcr = 18
+enable_eventlog = true
+'''
+
+"cdh.toml" = '''
+socket = "unix:///run/confidential-containers/cdh.sock"
+'''
+
+"policy.rego" = "package agent_policy"
+"#;
+        let bound = BoundInitdata::new(toml.to_string()).unwrap();
+        assert_eq!(bound.digest, HashAlgorithm::Sha384.digest(toml.as_bytes()));
+
+        let aa_config = bound.initdata.aa_config().unwrap().unwrap();
+        assert_eq!(aa_config.eventlog_config.init_pcr, 18);
+        assert!(aa_config.eventlog_config.enable_eventlog);
+        assert_eq!(
+            aa_config
+                .token_configs
+                .proxy
+                .unwrap()
+                .https_proxy
+                .as_deref(),
+            Some("http://proxy.example.com:3128")
+        );
+        assert_eq!(
+            bound.initdata.cdh_config().unwrap(),
+            Some("socket = \"unix:///run/confidential-containers/cdh.sock\"\n")
+        );
+        assert_eq!(bound.initdata.policy(), Some("package agent_policy"));
+    }
 }
@@ -3,25 +3,52 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
-use attester::{detect_attestable_devices, detect_tee_type, BoxedAttester};
+use attester::{
+    cache::{CachingAttester, EvidenceMetricsHandle},
+    detect_attestable_devices, detect_tee_type,
+    diagnostics::platform_report,
+    new_attester, BoxedAttester,
+};
 use kbs_types::Tee;
-use std::{collections::HashMap, str::FromStr, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 use tokio::sync::{Mutex, RwLock};
 
-pub use attester::InitDataResult;
+pub use attester::{
+    cache::EvidenceMetrics,
+    diagnostics::{PlatformDiagnosis, PlatformReport},
+    InitDataResult,
+};
+pub use eventlog::{
+    coco::{self, CocoEvent, RuntimeMeasurementEvent},
+    replay::{EventlogVerification, RegisterReplay},
+    Event, EventLog, FileWriter, MemoryWriter, Writer,
+};
 
 pub mod config;
 mod eventlog;
 pub mod initdata;
+#[cfg(any(feature = "grpc", feature = "ttrpc"))]
+pub mod proto;
 pub mod token;
 
-use eventlog::EventLog;
+use eventlog::EVENTLOG_FILE_NAME;
 use log::{debug, info, warn};
-use token::*;
+use token::{
+    cache::{TokenCache, TokenCacheStatus, TokenFetcher, TokenFuture},
+    TokenGetter, TokenGetterRegistry,
+};
 
-use crate::{config::Config, eventlog::Event};
+use crate::{
+    config::{Config, EventlogConfig},
+    initdata::{BoundInitdata, AA_CONFIG_SECTION},
+};
 
 pub enum RuntimeMeasurement {
     /// The runtime measurement is extended successfully.
@@ -44,9 +71,18 @@ pub enum RuntimeMeasurement {
 /// current hardware runtime measurement register (if any) or PCR for (v)TPM (under
 /// development) platforms
 /// with a runtime event.
+/// - `extend_runtime_measurements`: extend several runtime events atomically.
 /// - `bind_init_data`: bind the given data slice to the current confidential
 /// computing environment. This can be a verify operation or an extension of the TEE
 /// evidence
+/// - `get_eventlog`: get the CCEL and AAEL of the current platform.
+/// - `verify_eventlog`: replay the eventlog and compare the result with the
+/// runtime measurement registers.
+/// - `get_initdata`: get the initdata given to AA at launch.
+/// - `update_config`: replace the configuration of a running AA.
+/// - `get_platform_report`: explain why the platform was or was not detected.
+/// - `evidence_metrics`: get the evidence generations, failures and cache hits
+/// of each attester.
 ///
 /// # Example
 ///
@@ -69,6 +105,10 @@ pub trait AttestationAPIs {
     /// Get attestation Token
     async fn get_token(&self, token_type: &str) -> Result<Vec<u8>>;
 
+    /// Get a new attestation token rather than the cached one, e.g. once the
+    /// relying party rejected it.
+    async fn refresh_token(&self, token_type: &str) -> Result<Vec<u8>>;
+
     /// Get TEE hardware evidence from the primary attester with runtime
     /// data included.
     async fn get_evidence(&self, runtime_data: &[u8]) -> Result<Vec<u8>>;
@@ -86,29 +126,86 @@ pub trait AttestationAPIs {
         register_index: Option<u64>,
     ) -> Result<RuntimeMeasurement>;
 
+    /// Extend runtime measurement registers with several events atomically.
+    /// Either all of them or none of them are recorded.
+    async fn extend_runtime_measurements(
+        &self,
+        events: Vec<RuntimeMeasurementEvent>,
+    ) -> Result<RuntimeMeasurement>;
+
     /// Bind initdata
     async fn bind_init_data(&self, init_data: &[u8]) -> Result<InitDataResult>;
 
+    /// Derive a key bound to the TEE and the guest measurement from the
+    /// primary attester, e.g. to seal data persisted by the guest.
+    async fn derive_sealing_key(&self, purpose: &str) -> Result<Vec<u8>>;
+
     fn get_tee_type(&self) -> Tee;
+
+    /// Get the eventlog, i.e. the CCEL followed by the AAEL entries in
+    /// TCG2 crypto agile format.
+    async fn get_eventlog(&self) -> Result<Vec<u8>>;
+
+    /// Replay the eventlog and compare the result with the runtime
+    /// measurement registers of the primary attester.
+    async fn verify_eventlog(&self) -> Result<EventlogVerification>;
+
+    /// Get the initdata given to AA at launch, whose digest has been bound
+    /// to the platform. `None` if no initdata is given.
+    async fn get_initdata(&self) -> Result<Option<BoundInitdata>>;
+
+    /// Replace the configuration. The update is rejected if it conflicts
+    /// with the running eventlog.
+    async fn update_config(&self, config: Config) -> Result<()>;
+
+    /// Probe the platforms the way they are detected when no primary
+    /// attester is configured, reporting what was checked for each one and
+    /// why it was not detected.
+    async fn get_platform_report(&self) -> Result<PlatformReport>;
 }
 
 /// Attestation agent to provide attestation service.
 pub struct AttestationAgent {
-    primary_tee: Tee,
     config: RwLock<Config>,
     eventlog: Option<Mutex<EventLog>>,
-    initdata: Option<String>,
-    primary_attester: Arc<BoxedAttester>,
-    additional_attesters: HashMap<Tee, BoxedAttester>,
+    initdata: Option<BoundInitdata>,
+    attesters: Attesters,
+    evidence_metrics: HashMap<Tee, EvidenceMetricsHandle>,
+    token_getters: TokenGetterRegistry,
+    token_cache: TokenCache,
+}
+
+/// The attesters created from the configuration, each one wrapped by a
+/// [`CachingAttester`]. They are given to the token getters as well, so
+/// that the evidence of a token comes from the configured attesters.
+#[derive(Clone)]
+pub struct Attesters {
+    /// The configured or detected primary platform.
+    pub primary_tee: Tee,
+    pub primary: Arc<BoxedAttester>,
+
+    /// The attesters of the additional devices, in the order they are
+    /// configured or detected.
+    pub additional: Vec<(Tee, Arc<BoxedAttester>)>,
+}
+
+/// The AAEL the attesters put into the evidence, if the eventlog is enabled.
+fn attester_aael_path(config: &Config) -> Option<PathBuf> {
+    let eventlog = &config.eventlog_config;
+    eventlog
+        .enable_eventlog
+        .then(|| Path::new(&eventlog.eventlog_dir).join(EVENTLOG_FILE_NAME))
 }
 
 impl AttestationAgent {
     pub async fn init(&mut self) -> Result<()> {
         let config = self.config.read().await;
         if config.eventlog_config.enable_eventlog {
+            let eventlog_dir = Path::new(&config.eventlog_config.eventlog_dir);
             let eventlog = EventLog::new(
-                self.primary_attester.clone(),
+                self.attesters.primary.clone(),
                 config.eventlog_config.init_pcr,
+                eventlog_dir,
             )
             .await?;
 
@@ -118,6 +215,60 @@ impl AttestationAgent {
         Ok(())
     }
 
+    /// Poll the configuration file every `interval` and update the
+    /// configuration once the file changes. Invalid or conflicting updates
+    /// are logged and skipped. This never returns, so it is supposed to be
+    /// spawned as a background task.
+    pub async fn watch_config(&self, config_path: &Path, interval: Duration) {
+        info!("Watching AA config file: {}", config_path.display());
+        let mut last = tokio::fs::read(config_path).await.ok();
+        loop {
+            tokio::time::sleep(interval).await;
+
+            let content = tokio::fs::read(config_path).await.ok();
+            if content == last {
+                continue;
+            }
+            last = content;
+
+            if last.is_none() {
+                warn!("AA config file removed, keep the current configuration");
+                continue;
+            }
+
+            let config = match Config::try_from(&*config_path.to_string_lossy()) {
+                Ok(config) => config,
+                Err(e) => {
+                    warn!("Failed to parse the changed AA config file: {e:?}");
+                    continue;
+                }
+            };
+
+            match self.update_config(config).await {
+                Ok(()) => info!("AA config reloaded"),
+                Err(e) => warn!("Failed to reload AA config: {e:?}"),
+            }
+        }
+    }
+
+    /// Check that the eventlog configuration can be switched to `new`.
+    /// Eventlog settings only take effect in [`AttestationAgent::init`].
+    fn check_eventlog_config(&self, current: &EventlogConfig, new: &EventlogConfig) -> Result<()> {
+        if self.eventlog.is_some() {
+            if new != current {
+                bail!("eventlog configuration cannot be changed while the eventlog is running");
+            }
+        } else if new.enable_eventlog {
+            bail!("eventlog cannot be enabled at runtime");
+        }
+
+        Ok(())
+    }
+
+    async fn aael_path(&self) -> PathBuf {
+        Path::new(&self.config.read().await.eventlog_config.eventlog_dir).join(EVENTLOG_FILE_NAME)
+    }
+
     /// Create a new instance of [AttestationAgent].
     pub fn new(config_path: Option<&str>) -> Result<Self> {
         let config = match config_path {
@@ -131,79 +282,194 @@ impl AttestationAgent {
             }
         };
         debug!("Using config: {config:#?}");
+        let token_cache = TokenCache::new(Duration::from_secs(
+            config.token_configs.cache.refresh_ahead_secs,
+        ));
+        let (attesters, evidence_metrics) = Self::create_attesters(&config)?;
         let config = RwLock::new(config);
 
-        let primary_tee = detect_tee_type();
-        let additional_tees = detect_attestable_devices();
-
-        let mut additional_attesters = HashMap::new();
-        for tee in additional_tees {
-            additional_attesters.insert(tee, tee.try_into()?);
-        }
-
         Ok(AttestationAgent {
-            primary_tee,
             config,
             eventlog: None,
             initdata: None,
-            additional_attesters,
-            primary_attester: Arc::new(primary_tee.try_into()?),
+            attesters,
+            evidence_metrics,
+            token_getters: TokenGetterRegistry::default(),
+            token_cache,
         })
     }
 
-    /// Set initdata toml as status of current AA instance.
-    pub fn set_initdata_toml(&mut self, initdata_toml: String) {
-        self.initdata = Some(initdata_toml);
+    /// Create the configured attesters and the handles of their metrics.
+    /// The primary platform and the additional devices are detected if not
+    /// configured.
+    fn create_attesters(
+        config: &Config,
+    ) -> Result<(Attesters, HashMap<Tee, EvidenceMetricsHandle>)> {
+        let mut options = config.attesters.options.clone();
+        options.aael_path = attester_aael_path(config);
+        let config = &config.attesters;
+        let primary_tee = match config.primary {
+            Some(tee) => {
+                info!("Using configured primary attester: {tee:?}");
+                tee
+            }
+            None => detect_tee_type(),
+        };
+        if config.deny.contains(&primary_tee) {
+            bail!("primary attester {primary_tee:?} is denied");
+        }
+
+        let mut metrics = HashMap::new();
+        let mut with_cache = |tee: Tee, attester: BoxedAttester| -> Arc<BoxedAttester> {
+            let attester = CachingAttester::new(attester, &config.evidence_cache);
+            metrics.insert(tee, attester.metrics());
+            Arc::new(Box::new(attester))
+        };
+
+        let primary = new_attester(primary_tee, &options)
+            .with_context(|| format!("create primary attester {primary_tee:?}"))?;
+        let primary = with_cache(primary_tee, primary);
+
+        let additional_tees = match &config.allow {
+            Some(allow) => allow.clone(),
+            None => detect_attestable_devices(),
+        };
+
+        let mut additional = Vec::new();
+        for tee in additional_tees {
+            if tee == primary_tee
+                || config.deny.contains(&tee)
+                || additional.iter().any(|(added, _)| *added == tee)
+            {
+                debug!("Skip additional attester {tee:?}");
+                continue;
+            }
+
+            let attester = new_attester(tee, &options)
+                .with_context(|| format!("create additional attester {tee:?}"))?;
+            additional.push((tee, with_cache(tee, attester)));
+        }
+
+        let attesters = Attesters {
+            primary_tee,
+            primary,
+            additional,
+        };
+        Ok((attesters, metrics))
+    }
+
+    /// Set initdata toml as status of current AA instance and return its
+    /// digest to be bound to the platform.
+    ///
+    /// If the initdata carries an `aa.toml` section, it overrides the
+    /// configuration given to [`AttestationAgent::new`]. Thus this must be
+    /// called before [`AttestationAgent::init`].
+    pub fn set_initdata_toml(&mut self, initdata_toml: String) -> Result<Vec<u8>> {
+        let initdata = BoundInitdata::new(initdata_toml)?;
+        if let Some(config) = initdata.initdata.aa_config()? {
+            info!("Using AA config from initdata");
+            debug!("Using config: {config:#?}");
+            self.token_cache = TokenCache::new(Duration::from_secs(
+                config.token_configs.cache.refresh_ahead_secs,
+            ));
+            let current = self.config.get_mut();
+            if config.attesters != current.attesters
+                || attester_aael_path(&config) != attester_aael_path(current)
+            {
+                (self.attesters, self.evidence_metrics) = Self::create_attesters(&config)?;
+            }
+            *self.config.get_mut() = config;
+        }
+
+        let digest = initdata.digest.clone();
+        self.initdata = Some(initdata);
+        Ok(digest)
+    }
+
+    /// Whether the configuration is given by the `aa.toml` section of the
+    /// bound initdata.
+    fn config_from_initdata(&self) -> bool {
+        self.initdata
+            .as_ref()
+            .is_some_and(|initdata| initdata.initdata.data.contains_key(AA_CONFIG_SECTION))
+    }
+
+    /// Get the state of the cached attestation tokens, one entry per token type.
+    pub async fn token_cache_status(&self) -> Vec<TokenCacheStatus> {
+        self.token_cache.status().await
+    }
+
+    /// Get the metrics of the evidence generations, one entry per attester.
+    pub fn evidence_metrics(&self) -> HashMap<Tee, EvidenceMetrics> {
+        self.evidence_metrics
+            .iter()
+            .map(|(tee, metrics)| (*tee, metrics.get()))
+            .collect()
+    }
+
+    /// Register a token type besides the ones enabled by cargo features,
+    /// so that it can be requested by `get_token`.
+    pub fn register_token_getter<F>(&mut self, token_type: &str, factory: F) -> Result<()>
+    where
+        F: Fn(&config::TokenConfigs, &Attesters) -> Result<Arc<dyn TokenGetter>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.token_getters.register(token_type, factory)
+    }
+
+    /// Get the token of the type, from the cache if enabled unless
+    /// `refresh` is set.
+    async fn token(&self, token_type: &str, refresh: bool) -> Result<Vec<u8>> {
+        let (getter, cache_enabled) = {
+            let config = self.config.read().await;
+            (
+                self.token_getters.get_getter(
+                    token_type,
+                    &config.token_configs,
+                    &self.attesters,
+                )?,
+                config.token_configs.cache.enable,
+            )
+        };
+
+        let initdata = self.initdata.as_ref().map(|initdata| initdata.toml.clone());
+        let fetcher: TokenFetcher = Arc::new(move || -> TokenFuture {
+            let getter = getter.clone();
+            let initdata = initdata.clone();
+            Box::pin(async move { getter.get_token(initdata.as_deref()).await })
+        });
+
+        if !cache_enabled {
+            let reply = fetcher().await?;
+            return Ok(reply.content);
+        }
+
+        if refresh {
+            self.token_cache.invalidate(token_type).await;
+        }
+
+        self.token_cache.get_token(token_type, fetcher).await
     }
 }
 
 #[async_trait]
 impl AttestationAPIs for AttestationAgent {
     async fn get_token(&self, token_type: &str) -> Result<Vec<u8>> {
-        let token_type = TokenType::from_str(token_type).context("Unsupported token type")?;
-
-        match token_type {
-            #[cfg(feature = "kbs")]
-            token::TokenType::Kbs => {
-                token::kbs::KbsTokenGetter::new(
-                    self.config
-                        .read()
-                        .await
-                        .token_configs
-                        .kbs
-                        .as_ref()
-                        .ok_or(anyhow::anyhow!(
-                            "kbs token config not configured in config file"
-                        ))?,
-                )
-                .get_token(self.initdata.as_deref())
-                .await
-            }
-            // TODO: add initdata plaintext for CoCoAS token
-            #[cfg(feature = "coco_as")]
-            token::TokenType::CoCoAS => {
-                token::coco_as::CoCoASTokenGetter::new(
-                    self.config
-                        .read()
-                        .await
-                        .token_configs
-                        .coco_as
-                        .as_ref()
-                        .ok_or(anyhow::anyhow!(
-                            "coco_as token config not configured in config file"
-                        ))?,
-                )
-                .get_token()
-                .await
-            }
-        }
+        self.token(token_type, false).await
+    }
+
+    async fn refresh_token(&self, token_type: &str) -> Result<Vec<u8>> {
+        self.token(token_type, true).await
     }
 
     /// Get TEE hardware evidence from the primary attester with runtime
     /// data included.
     async fn get_evidence(&self, runtime_data: &[u8]) -> Result<Vec<u8>> {
         let evidence = self
-            .primary_attester
+            .attesters
+            .primary
             .get_evidence(runtime_data.to_vec())
             .await?;
         Ok(evidence.to_string().into_bytes())
@@ -214,7 +480,7 @@ impl AttestationAPIs for AttestationAgent {
     async fn get_additional_evidence(&self, runtime_data: &[u8]) -> Result<Vec<u8>> {
         let mut evidence = HashMap::new();
 
-        for (tee, attester) in &self.additional_attesters {
+        for (tee, attester) in &self.attesters.additional {
             evidence.insert(*tee, attester.get_evidence(runtime_data.to_vec()).await?);
         }
 
@@ -240,30 +506,47 @@ impl AttestationAPIs for AttestationAgent {
         operation: &str,
         content: &str,
         register_index: Option<u64>,
+    ) -> Result<RuntimeMeasurement> {
+        let event = RuntimeMeasurementEvent::new(domain, operation, content, register_index);
+        self.extend_runtime_measurements(vec![event]).await
+    }
+
+    /// Extend runtime measurement registers with several events in one WAL
+    /// transaction. Events of well-known Confidential Containers operations
+    /// are validated first, and nothing is extended if any event is invalid.
+    async fn extend_runtime_measurements(
+        &self,
+        events: Vec<RuntimeMeasurementEvent>,
     ) -> Result<RuntimeMeasurement> {
         let Some(ref eventlog) = self.eventlog else {
             return Ok(RuntimeMeasurement::NotEnabled);
         };
 
-        if !self.primary_attester.supports_runtime_measurement() {
+        if !self.attesters.primary.supports_runtime_measurement() {
             return Ok(RuntimeMeasurement::NotSupported);
         }
 
-        let (pcr, log_entry) = {
+        let log_entries = {
             let config = self.config.read().await;
 
-            let pcr = register_index.unwrap_or_else(|| {
-                let pcr = config.eventlog_config.init_pcr;
-                debug!("No PCR index provided, use default {pcr}");
-                pcr
-            });
+            let mut log_entries = Vec::with_capacity(events.len());
+            for event in &events {
+                event.validate()?;
 
-            let log_entry = Event::new(domain, operation, content)?;
+                let pcr = event.register_index.unwrap_or_else(|| {
+                    let pcr = config.eventlog_config.init_pcr;
+                    debug!("No PCR index provided, use default {pcr}");
+                    pcr
+                });
+
+                let log_entry = Event::new(&event.domain, &event.operation, &event.content)?;
+                log_entries.push((log_entry, pcr));
+            }
 
-            (pcr, log_entry)
+            log_entries
         };
 
-        eventlog.lock().await.extend_entry(log_entry, pcr).await?;
+        eventlog.lock().await.extend_entries(log_entries).await?;
 
         Ok(RuntimeMeasurement::Ok)
     }
@@ -271,12 +554,291 @@ impl AttestationAPIs for AttestationAgent {
     /// Perform the initdata binding. If current platform does not support initdata
     /// binding, return `InitdataResult::Unsupported`.
     async fn bind_init_data(&self, init_data: &[u8]) -> Result<InitDataResult> {
-        self.primary_attester.bind_init_data(init_data).await
+        self.attesters.primary.bind_init_data(init_data).await
+    }
+
+    /// Derive a sealing key for the purpose with the primary attester. An
+    /// empty purpose is rejected, as every caller must use its own key.
+    async fn derive_sealing_key(&self, purpose: &str) -> Result<Vec<u8>> {
+        if purpose.is_empty() {
+            bail!("the purpose of the sealing key must not be empty");
+        }
+
+        self.attesters.primary.derive_sealing_key(purpose).await
     }
 
     /// Get the tee type of current platform. If no platform is detected,
     /// `Sample` will be returned.
     fn get_tee_type(&self) -> Tee {
-        self.primary_tee
+        self.attesters.primary_tee
+    }
+
+    /// Get the eventlog of current platform. An empty vector is returned if
+    /// neither CCEL nor AAEL exists.
+    async fn get_eventlog(&self) -> Result<Vec<u8>> {
+        eventlog::replay::read_raw_eventlog(&self.aael_path().await).await
+    }
+
+    /// Replay the eventlog with the CCEL hash algorithm of the primary
+    /// attester and compare the result with its runtime measurement registers.
+    async fn verify_eventlog(&self) -> Result<EventlogVerification> {
+        // Hold the eventlog to avoid racing with `extend_runtime_measurement`
+        let _eventlog = match &self.eventlog {
+            Some(eventlog) => Some(eventlog.lock().await),
+            None => None,
+        };
+
+        let raw = eventlog::replay::read_raw_eventlog(&self.aael_path().await).await?;
+        eventlog::replay::verify_eventlog(&self.attesters.primary, &raw).await
+    }
+
+    /// Get the initdata given to AA at launch.
+    async fn get_initdata(&self) -> Result<Option<BoundInitdata>> {
+        Ok(self.initdata.clone())
+    }
+
+    /// Swap the configuration atomically. Cached tokens are dropped if the
+    /// token configurations change, as they may come from another service.
+    ///
+    /// The configuration given by the `aa.toml` section of the bound
+    /// initdata is measured, so it cannot be updated, e.g. to redirect the
+    /// KBS or the AS after the initdata is bound.
+    async fn update_config(&self, config: Config) -> Result<()> {
+        if self.config_from_initdata() {
+            bail!("AA config is provided by the bound initdata and cannot be updated");
+        }
+
+        let mut current = self.config.write().await;
+        self.check_eventlog_config(&current.eventlog_config, &config.eventlog_config)?;
+        if current.attesters != config.attesters {
+            bail!("attesters cannot be changed at runtime");
+        }
+
+        debug!("Update config: {config:#?}");
+        if current.token_configs != config.token_configs {
+            self.token_cache.set_refresh_ahead(Duration::from_secs(
+                config.token_configs.cache.refresh_ahead_secs,
+            ));
+            self.token_cache.clear();
+        }
+
+        *current = config;
+        Ok(())
+    }
+
+    /// The probes may block, e.g. on the TPM, so they run on a blocking
+    /// thread.
+    async fn get_platform_report(&self) -> Result<PlatformReport> {
+        tokio::task::spawn_blocking(platform_report)
+            .await
+            .context("probe platforms")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_toml(eventlog_dir: &Path, refresh_ahead_secs: u64) -> String {
+        format!(
+            r#"
+[eventlog_config]
+init_pcr = 17
+enable_eventlog = false
+eventlog_dir = "{}"
+
+[attesters]
+primary = "sample"
+allow = []
+
+[token_configs.cache]
+refresh_ahead_secs = {refresh_ahead_secs}
+"#,
+            eventlog_dir.display()
+        )
+    }
+
+    fn new_aa(dir: &Path) -> AttestationAgent {
+        let config_path = dir.join("aa.toml");
+        std::fs::write(&config_path, config_toml(dir, 30)).unwrap();
+        AttestationAgent::new(Some(config_path.to_str().unwrap())).unwrap()
+    }
+
+    fn tees(attesters: &Attesters) -> Vec<Tee> {
+        attesters.additional.iter().map(|(tee, _)| *tee).collect()
+    }
+
+    #[test]
+    fn test_create_attesters() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::from_toml_str(&config_toml(dir.path(), 30)).unwrap();
+        config.attesters.allow = Some(vec![Tee::SampleDevice, Tee::Sample, Tee::SampleDevice]);
+        let (attesters, metrics) = AttestationAgent::create_attesters(&config).unwrap();
+        assert_eq!(attesters.primary_tee, Tee::Sample);
+        assert_eq!(tees(&attesters), vec![Tee::SampleDevice]);
+        assert_eq!(metrics.len(), 2);
+
+        config.attesters.deny = vec![Tee::SampleDevice];
+        let (attesters, _) = AttestationAgent::create_attesters(&config).unwrap();
+        assert!(tees(&attesters).is_empty());
+
+        config.attesters.deny = vec![Tee::Sample];
+        assert!(AttestationAgent::create_attesters(&config).is_err());
+    }
+
+    #[cfg(not(feature = "tdx-attester"))]
+    #[test]
+    fn test_attester_not_compiled_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::from_toml_str(&config_toml(dir.path(), 30)).unwrap();
+        config.attesters.primary = Some(Tee::Tdx);
+        assert!(AttestationAgent::create_attesters(&config).is_err());
+
+        config.attesters.primary = Some(Tee::Sample);
+        config.attesters.allow = Some(vec![Tee::Tdx]);
+        assert!(AttestationAgent::create_attesters(&config).is_err());
+    }
+
+    struct DummyTokenGetter;
+
+    #[async_trait]
+    impl TokenGetter for DummyTokenGetter {
+        async fn get_token(&self, _initdata: Option<&str>) -> Result<token::TokenReply> {
+            Ok(token::TokenReply {
+                content: b"dummy".to_vec(),
+                exp: None,
+                nbf: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_getter_attesters() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut aa = new_aa(dir.path());
+        let given = Arc::new(std::sync::Mutex::new(None));
+        let recorder = given.clone();
+        aa.register_token_getter("dummy", move |_, attesters| {
+            *recorder.lock().unwrap() = Some(attesters.clone());
+            Ok(Arc::new(DummyTokenGetter))
+        })
+        .unwrap();
+
+        aa.get_token("dummy").await.unwrap();
+        let given = given.lock().unwrap().take().unwrap();
+        assert_eq!(given.primary_tee, Tee::Sample);
+        assert!(Arc::ptr_eq(&given.primary, &aa.attesters.primary));
+    }
+
+    #[tokio::test]
+    async fn test_update_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let aa = new_aa(dir.path());
+
+        let mut config = aa.config.read().await.clone();
+        config.token_configs.cache.refresh_ahead_secs = 10;
+        aa.update_config(config.clone()).await.unwrap();
+        assert_eq!(*aa.config.read().await, config);
+
+        let mut attesters_changed = config.clone();
+        attesters_changed.attesters.primary = Some(Tee::Tdx);
+        assert!(aa.update_config(attesters_changed).await.is_err());
+
+        let mut eventlog_enabled = config.clone();
+        eventlog_enabled.eventlog_config.enable_eventlog = true;
+        assert!(aa.update_config(eventlog_enabled).await.is_err());
+        assert_eq!(*aa.config.read().await, config);
+    }
+
+    #[tokio::test]
+    async fn test_update_config_from_initdata() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut aa = new_aa(dir.path());
+        let initdata = format!(
+            "version = \"0.1.0\"\nalgorithm = \"sha384\"\n\n[data]\n\"aa.toml\" = '''{}'''\n",
+            config_toml(dir.path(), 20)
+        );
+        aa.set_initdata_toml(initdata).unwrap();
+
+        let mut config = aa.config.read().await.clone();
+        assert_eq!(config.token_configs.cache.refresh_ahead_secs, 20);
+        config.token_configs.cache.refresh_ahead_secs = 10;
+        assert!(aa.update_config(config).await.is_err());
+        assert_eq!(
+            aa.config
+                .read()
+                .await
+                .token_configs
+                .cache
+                .refresh_ahead_secs,
+            20
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_with_initdata_without_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut aa = new_aa(dir.path());
+        let initdata = "version = \"0.1.0\"\nalgorithm = \"sha384\"\n\n[data]\n";
+        aa.set_initdata_toml(initdata.into()).unwrap();
+
+        let mut config = aa.config.read().await.clone();
+        config.token_configs.cache.refresh_ahead_secs = 10;
+        aa.update_config(config).await.unwrap();
+    }
+
+    async fn refresh_ahead_secs(aa: &AttestationAgent) -> u64 {
+        aa.config
+            .read()
+            .await
+            .token_configs
+            .cache
+            .refresh_ahead_secs
+    }
+
+    async fn wait_for_refresh_ahead_secs(aa: &AttestationAgent, expected: u64) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while refresh_ahead_secs(aa).await != expected {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("config not reloaded");
+    }
+
+    #[tokio::test]
+    async fn test_watch_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("aa.toml");
+        let aa = Arc::new(new_aa(dir.path()));
+        let watcher = {
+            let aa = aa.clone();
+            let config_path = config_path.clone();
+            tokio::spawn(async move {
+                aa.watch_config(&config_path, Duration::from_millis(10))
+                    .await
+            })
+        };
+
+        // Let the watcher read the initial file
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        std::fs::write(&config_path, config_toml(dir.path(), 10)).unwrap();
+        wait_for_refresh_ahead_secs(&aa, 10).await;
+
+        // Invalid files and rejected updates are skipped
+        std::fs::write(&config_path, "invalid").unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(refresh_ahead_secs(&aa).await, 10);
+
+        let enable_eventlog =
+            config_toml(dir.path(), 5).replace("enable_eventlog = false", "enable_eventlog = true");
+        std::fs::write(&config_path, enable_eventlog).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(refresh_ahead_secs(&aa).await, 10);
+
+        std::fs::write(&config_path, config_toml(dir.path(), 5)).unwrap();
+        wait_for_refresh_ahead_secs(&aa, 5).await;
+
+        watcher.abort();
     }
 }
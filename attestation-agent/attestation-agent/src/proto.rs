@@ -0,0 +1,373 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Conversions from and to the messages of the gRPC and ttRPC APIs of AA,
+//! shared by the `grpc-aa` and `ttrpc-aa` servers.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use kbs_types::Tee;
+
+use crate::EvidenceMetrics;
+
+/// The name of the TEE in the messages, e.g. `tdx`.
+fn tee_name(tee: Tee) -> Result<String> {
+    Ok(serde_json::to_string(&tee)?.trim_matches('"').to_string())
+}
+
+/// The metrics of each attester, ordered by the name of its TEE.
+fn named_evidence_metrics(
+    metrics: HashMap<Tee, EvidenceMetrics>,
+) -> Result<Vec<(String, EvidenceMetrics)>> {
+    let mut named = metrics
+        .into_iter()
+        .map(|(tee, metrics)| Ok((tee_name(tee)?, metrics)))
+        .collect::<Result<Vec<_>>>()?;
+    named.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(named)
+}
+
+#[cfg(feature = "grpc")]
+pub mod grpc {
+    use std::collections::HashMap;
+
+    use anyhow::{anyhow, Result};
+    use kbs_types::Tee;
+    use protos::grpc::aa::attestation_agent::{
+        runtime_measurement_event::Event, CheckedPath, EvidenceMetrics, GetEvidenceMetricsResponse,
+        GetPlatformReportResponse, PlatformDiagnosis, RuntimeMeasurementEvent,
+    };
+
+    use super::{named_evidence_metrics, tee_name};
+    use crate::eventlog::coco::{CocoEvent, GetResource, PullImage, SecureMount, UnsealSecret};
+
+    impl TryFrom<RuntimeMeasurementEvent> for crate::RuntimeMeasurementEvent {
+        type Error = anyhow::Error;
+
+        fn try_from(event: RuntimeMeasurementEvent) -> Result<Self> {
+            let register_index = event.register_index;
+            let event = match event.event.ok_or(anyhow!("no event given"))? {
+                Event::Generic(event) => {
+                    return Ok(Self::new(
+                        &event.domain,
+                        &event.operation,
+                        &event.content,
+                        register_index,
+                    ))
+                }
+                Event::PullImage(event) => CocoEvent::PullImage(PullImage {
+                    image: event.image,
+                    digest: event.digest,
+                }),
+                Event::SecureMount(event) => CocoEvent::SecureMount(SecureMount {
+                    volume_type: event.volume_type,
+                    mount_point: event.mount_point,
+                }),
+                Event::UnsealSecret(event) => CocoEvent::UnsealSecret(UnsealSecret {
+                    key_id: event.key_id,
+                }),
+                Event::GetResource(event) => CocoEvent::GetResource(GetResource {
+                    resource_uri: event.resource_uri,
+                }),
+            };
+
+            Self::coco(event, register_index)
+        }
+    }
+
+    fn platform_diagnosis(diagnosis: crate::PlatformDiagnosis) -> Result<PlatformDiagnosis> {
+        Ok(PlatformDiagnosis {
+            tee: tee_name(diagnosis.tee)?,
+            probed: diagnosis.probed,
+            detected: diagnosis.detected,
+            checked: diagnosis
+                .checked
+                .into_iter()
+                .map(|checked| CheckedPath {
+                    path: checked.path,
+                    exists: checked.exists,
+                })
+                .collect(),
+            failure: diagnosis.failure,
+        })
+    }
+
+    /// The response of `GetPlatformReport`, with the primary attester AA
+    /// uses.
+    pub fn platform_report_response(
+        report: crate::PlatformReport,
+        primary_tee: Tee,
+    ) -> Result<GetPlatformReportResponse> {
+        Ok(GetPlatformReportResponse {
+            tee: tee_name(report.tee)?,
+            sample_fallback: report.sample_fallback,
+            platforms: report
+                .platforms
+                .into_iter()
+                .map(platform_diagnosis)
+                .collect::<Result<_>>()?,
+            devices: report
+                .devices
+                .into_iter()
+                .map(platform_diagnosis)
+                .collect::<Result<_>>()?,
+            not_compiled_in: report
+                .not_compiled_in
+                .into_iter()
+                .map(tee_name)
+                .collect::<Result<_>>()?,
+            primary_tee: tee_name(primary_tee)?,
+        })
+    }
+
+    /// The response of `GetEvidenceMetrics`, ordered by TEE.
+    pub fn evidence_metrics_response(
+        metrics: HashMap<Tee, crate::EvidenceMetrics>,
+    ) -> Result<GetEvidenceMetricsResponse> {
+        let attesters = named_evidence_metrics(metrics)?
+            .into_iter()
+            .map(|(tee, metrics)| EvidenceMetrics {
+                tee,
+                generations: metrics.generations,
+                failures: metrics.failures,
+                cache_hits: metrics.cache_hits,
+                total_latency_ms: metrics.total_latency_ms,
+                max_latency_ms: metrics.max_latency_ms,
+            })
+            .collect();
+        Ok(GetEvidenceMetricsResponse { attesters })
+    }
+}
+
+#[cfg(feature = "ttrpc")]
+pub mod ttrpc {
+    use std::collections::HashMap;
+
+    use anyhow::{anyhow, bail, Result};
+    use kbs_types::Tee;
+    use protos::ttrpc::aa::attestation_agent::{
+        runtime_measurement_event::Event, CheckedPath, EvidenceMetrics, GetEvidenceMetricsResponse,
+        GetPlatformReportResponse, PlatformDiagnosis, RuntimeMeasurementEvent,
+    };
+
+    use super::{named_evidence_metrics, tee_name};
+    use crate::eventlog::coco::{CocoEvent, GetResource, PullImage, SecureMount, UnsealSecret};
+
+    impl TryFrom<RuntimeMeasurementEvent> for crate::RuntimeMeasurementEvent {
+        type Error = anyhow::Error;
+
+        fn try_from(event: RuntimeMeasurementEvent) -> Result<Self> {
+            let register_index = event.RegisterIndex;
+            let event = match event.Event.ok_or(anyhow!("no event given"))? {
+                Event::Generic(event) => {
+                    return Ok(Self::new(
+                        &event.Domain,
+                        &event.Operation,
+                        &event.Content,
+                        register_index,
+                    ))
+                }
+                Event::PullImage(event) => CocoEvent::PullImage(PullImage {
+                    image: event.Image,
+                    digest: event.Digest,
+                }),
+                Event::SecureMount(event) => CocoEvent::SecureMount(SecureMount {
+                    volume_type: event.VolumeType,
+                    mount_point: event.MountPoint,
+                }),
+                Event::UnsealSecret(event) => CocoEvent::UnsealSecret(UnsealSecret {
+                    key_id: event.KeyId,
+                }),
+                Event::GetResource(event) => CocoEvent::GetResource(GetResource {
+                    resource_uri: event.ResourceUri,
+                }),
+                _ => bail!("unknown event"),
+            };
+
+            Self::coco(event, register_index)
+        }
+    }
+
+    fn platform_diagnosis(diagnosis: crate::PlatformDiagnosis) -> Result<PlatformDiagnosis> {
+        let mut reply = PlatformDiagnosis::new();
+        reply.Tee = tee_name(diagnosis.tee)?;
+        reply.Probed = diagnosis.probed;
+        reply.Detected = diagnosis.detected;
+        reply.Checked = diagnosis
+            .checked
+            .into_iter()
+            .map(|checked| {
+                let mut path = CheckedPath::new();
+                path.Path = checked.path;
+                path.Exists = checked.exists;
+                path
+            })
+            .collect();
+        reply.Failure = diagnosis.failure;
+        Ok(reply)
+    }
+
+    /// The response of `GetPlatformReport`, with the primary attester AA
+    /// uses.
+    pub fn platform_report_response(
+        report: crate::PlatformReport,
+        primary_tee: Tee,
+    ) -> Result<GetPlatformReportResponse> {
+        let mut reply = GetPlatformReportResponse::new();
+        reply.Tee = tee_name(report.tee)?;
+        reply.SampleFallback = report.sample_fallback;
+        reply.Platforms = report
+            .platforms
+            .into_iter()
+            .map(platform_diagnosis)
+            .collect::<Result<_>>()?;
+        reply.Devices = report
+            .devices
+            .into_iter()
+            .map(platform_diagnosis)
+            .collect::<Result<_>>()?;
+        reply.NotCompiledIn = report
+            .not_compiled_in
+            .into_iter()
+            .map(tee_name)
+            .collect::<Result<_>>()?;
+        reply.PrimaryTee = tee_name(primary_tee)?;
+        Ok(reply)
+    }
+
+    /// The response of `GetEvidenceMetrics`, ordered by TEE.
+    pub fn evidence_metrics_response(
+        metrics: HashMap<Tee, crate::EvidenceMetrics>,
+    ) -> Result<GetEvidenceMetricsResponse> {
+        let mut reply = GetEvidenceMetricsResponse::new();
+        reply.Attesters = named_evidence_metrics(metrics)?
+            .into_iter()
+            .map(|(tee, metrics)| {
+                let mut entry = EvidenceMetrics::new();
+                entry.Tee = tee;
+                entry.Generations = metrics.generations;
+                entry.Failures = metrics.failures;
+                entry.CacheHits = metrics.cache_hits;
+                entry.TotalLatencyMs = metrics.total_latency_ms;
+                entry.MaxLatencyMs = metrics.max_latency_ms;
+                entry
+            })
+            .collect();
+        Ok(reply)
+    }
+}
+
+#[cfg(all(test, feature = "grpc"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use attester::diagnostics::CheckedPath;
+    use kbs_types::Tee;
+    use protos::grpc::aa::attestation_agent::{
+        runtime_measurement_event::Event, GenericEvent, PullImageEvent, RuntimeMeasurementEvent,
+    };
+
+    use super::grpc::{evidence_metrics_response, platform_report_response};
+    use crate::{
+        eventlog::coco::COCO_EVENT_DOMAIN, EvidenceMetrics, PlatformDiagnosis, PlatformReport,
+    };
+
+    #[test]
+    fn test_runtime_measurement_event() {
+        let event = RuntimeMeasurementEvent {
+            register_index: Some(17),
+            event: Some(Event::Generic(GenericEvent {
+                domain: "domain".into(),
+                operation: "operation".into(),
+                content: "content".into(),
+            })),
+        };
+        let event = crate::RuntimeMeasurementEvent::try_from(event).unwrap();
+        assert_eq!(
+            event,
+            crate::RuntimeMeasurementEvent::new("domain", "operation", "content", Some(17))
+        );
+
+        let event = RuntimeMeasurementEvent {
+            register_index: None,
+            event: Some(Event::PullImage(PullImageEvent {
+                image: "docker.io/library/busybox:latest".into(),
+                digest: format!("sha256:{}", "a".repeat(64)),
+            })),
+        };
+        let event = crate::RuntimeMeasurementEvent::try_from(event).unwrap();
+        assert_eq!(event.domain, COCO_EVENT_DOMAIN);
+        assert_eq!(event.operation, "PullImage");
+
+        let event = RuntimeMeasurementEvent {
+            register_index: None,
+            event: Some(Event::PullImage(PullImageEvent {
+                image: "docker.io/library/busybox:latest".into(),
+                digest: "sha256:invalid".into(),
+            })),
+        };
+        assert!(crate::RuntimeMeasurementEvent::try_from(event).is_err());
+
+        let event = RuntimeMeasurementEvent {
+            register_index: None,
+            event: None,
+        };
+        assert!(crate::RuntimeMeasurementEvent::try_from(event).is_err());
+    }
+
+    #[test]
+    fn test_platform_report_response() {
+        let report = PlatformReport {
+            tee: Tee::Sample,
+            sample_fallback: true,
+            platforms: vec![PlatformDiagnosis {
+                tee: Tee::Tdx,
+                probed: true,
+                detected: false,
+                checked: vec![CheckedPath {
+                    path: "/dev/tdx_guest".into(),
+                    exists: false,
+                }],
+                failure: Some("No TDX device".into()),
+            }],
+            devices: Vec::new(),
+            not_compiled_in: vec![Tee::Snp, Tee::Cca],
+        };
+
+        let response = platform_report_response(report, Tee::Sample).unwrap();
+        assert_eq!(response.tee, "sample");
+        assert_eq!(response.primary_tee, "sample");
+        assert!(response.sample_fallback);
+        assert_eq!(response.platforms[0].tee, "tdx");
+        assert_eq!(response.platforms[0].checked[0].path, "/dev/tdx_guest");
+        assert_eq!(
+            response.platforms[0].failure.as_deref(),
+            Some("No TDX device")
+        );
+        assert_eq!(response.not_compiled_in, ["snp", "cca"]);
+    }
+
+    #[test]
+    fn test_evidence_metrics_response() {
+        let metrics = HashMap::from([
+            (
+                Tee::Tdx,
+                EvidenceMetrics {
+                    generations: 2,
+                    cache_hits: 1,
+                    ..Default::default()
+                },
+            ),
+            (Tee::Nvidia, EvidenceMetrics::default()),
+        ]);
+
+        let response = evidence_metrics_response(metrics).unwrap();
+        let tees: Vec<_> = response.attesters.iter().map(|m| m.tee.as_str()).collect();
+        assert_eq!(tees, ["nvidia", "tdx"]);
+        assert_eq!(response.attesters[1].generations, 2);
+        assert_eq!(response.attesters[1].cache_hits, 1);
+    }
+}
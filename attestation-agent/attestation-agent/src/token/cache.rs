@@ -0,0 +1,503 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Per token type cache of attestation tokens.
+//!
+//! A token is returned from the cache as long as it is valid due to its
+//! `exp` and `nbf` claims. Once a token is cached, a background task is
+//! spawned to fetch a new one `refresh_ahead` before the cached one expires,
+//! so callers of `get_token` hardly ever wait for a full attestation. The
+//! margin is capped to a quarter of the token lifetime and the tokens are
+//! fetched at most every `MIN_REFRESH_INTERVAL`, so that short-lived tokens
+//! do not make the task attest in a loop.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex as StdMutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use log::{debug, info, warn};
+use serde::Serialize;
+use tokio::{sync::Mutex, task::JoinHandle};
+
+use super::TokenReply;
+
+/// The interval to wait before retrying a failed background refresh.
+const REFRESH_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The minimum interval between fetching a token and refreshing it.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The refresh margin of a token is at most its lifetime divided by this,
+/// as in `kbs_protocol`.
+const MAX_REFRESH_MARGIN_DIVISOR: u64 = 4;
+
+/// A boxed future returned by a [`TokenFetcher`].
+pub type TokenFuture = Pin<Box<dyn Future<Output = Result<TokenReply>> + Send>>;
+
+/// Performs a full attestation to get a new token. The fetcher is kept by
+/// the background refresh task, so it must own everything it needs.
+pub type TokenFetcher = Arc<dyn Fn() -> TokenFuture + Send + Sync>;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[derive(Clone, Debug)]
+struct CachedToken {
+    reply: TokenReply,
+    fetched_at: u64,
+}
+
+impl CachedToken {
+    fn is_valid(&self, now: u64) -> bool {
+        if self.reply.nbf.is_some_and(|nbf| nbf > now) {
+            return false;
+        }
+
+        self.reply.exp.is_some_and(|exp| exp > now)
+    }
+
+    /// When to refresh the token, `refresh_ahead` seconds before it expires
+    /// but no earlier than `MIN_REFRESH_INTERVAL` after it was fetched. The
+    /// lifetime starts at `nbf`, or when the token was fetched.
+    fn refresh_at(&self, refresh_ahead: u64) -> Option<u64> {
+        let exp = self.reply.exp?;
+        let lifetime = exp.saturating_sub(self.reply.nbf.unwrap_or(self.fetched_at));
+        let margin = refresh_ahead.min(lifetime / MAX_REFRESH_MARGIN_DIVISOR);
+        let earliest = self.fetched_at + MIN_REFRESH_INTERVAL.as_secs();
+        Some(exp.saturating_sub(margin).max(earliest))
+    }
+}
+
+#[derive(Default)]
+struct Slot {
+    token: Mutex<Option<CachedToken>>,
+    refresher: StdMutex<Option<JoinHandle<()>>>,
+}
+
+impl Slot {
+    fn is_refreshing(&self) -> bool {
+        self.refresher
+            .lock()
+            .expect("poisoned lock")
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+}
+
+/// The state of a cached token, as exposed by [`TokenCache::status`].
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct TokenCacheStatus {
+    /// The token type, e.g. `kbs`.
+    pub token_type: String,
+
+    /// Whether the cached token can be returned right now.
+    pub valid: bool,
+
+    /// When the cached token was fetched (seconds since UNIX epoch).
+    pub fetched_at: u64,
+
+    /// `exp` claim of the cached token.
+    pub exp: Option<u64>,
+
+    /// `nbf` claim of the cached token.
+    pub nbf: Option<u64>,
+
+    /// Whether a background refresh task is scheduled for this token type.
+    pub refreshing: bool,
+}
+
+pub struct TokenCache {
+    /// Seconds to refresh a token before it expires.
+    refresh_ahead: AtomicU64,
+    slots: StdMutex<HashMap<String, Arc<Slot>>>,
+}
+
+impl TokenCache {
+    pub fn new(refresh_ahead: Duration) -> Self {
+        Self {
+            refresh_ahead: AtomicU64::new(refresh_ahead.as_secs()),
+            slots: StdMutex::default(),
+        }
+    }
+
+    fn slot(&self, token_type: &str) -> Arc<Slot> {
+        self.slots
+            .lock()
+            .expect("poisoned lock")
+            .entry(token_type.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Return the cached token of the given type if it is still valid.
+    /// Otherwise fetch a new one with `fetcher`, cache it and schedule a
+    /// background refresh before it expires.
+    ///
+    /// Concurrent callers for the same token type wait for a single fetch.
+    pub async fn get_token(&self, token_type: &str, fetcher: TokenFetcher) -> Result<Vec<u8>> {
+        let slot = self.slot(token_type);
+        let mut cached = slot.token.lock().await;
+        if let Some(token) = cached.as_ref() {
+            if token.is_valid(now()) {
+                debug!("Return cached {} token", token_type);
+                return Ok(token.reply.content.clone());
+            }
+        }
+
+        let reply = fetcher().await?;
+        let content = reply.content.clone();
+        if reply.exp.is_none() {
+            debug!("{} token has no `exp` claim, skip caching it", token_type);
+            *cached = None;
+            return Ok(content);
+        }
+
+        // The cache may be cleared during the fetch as the token
+        // configurations change. Keep the slots locked so that a token
+        // fetched with the old configurations is neither cached nor
+        // refreshed in the background.
+        let slots = self.slots.lock().expect("poisoned lock");
+        if !slots
+            .get(token_type)
+            .is_some_and(|current| Arc::ptr_eq(current, &slot))
+        {
+            debug!("Token cache cleared while fetching {} token", token_type);
+            return Ok(content);
+        }
+
+        *cached = Some(CachedToken {
+            reply,
+            fetched_at: now(),
+        });
+        self.schedule_refresh(token_type, slot.clone(), fetcher);
+        drop(slots);
+        Ok(content)
+    }
+
+    fn schedule_refresh(&self, token_type: &str, slot: Arc<Slot>, fetcher: TokenFetcher) {
+        if slot.is_refreshing() {
+            return;
+        }
+
+        let refresh_ahead = self.refresh_ahead.load(Ordering::Relaxed);
+        let token_type = token_type.to_string();
+        let task_slot = slot.clone();
+        let handle = tokio::spawn(async move {
+            let slot = task_slot;
+            loop {
+                let Some((exp, refresh_at)) =
+                    slot.token.lock().await.as_ref().and_then(|token| {
+                        Some((token.reply.exp?, token.refresh_at(refresh_ahead)?))
+                    })
+                else {
+                    break;
+                };
+
+                let wait = refresh_at.saturating_sub(now());
+                tokio::time::sleep(Duration::from_secs(wait)).await;
+
+                debug!("Refresh {} token in background", token_type);
+                match fetcher().await {
+                    Ok(reply) if reply.exp.is_some() => {
+                        *slot.token.lock().await = Some(CachedToken {
+                            reply,
+                            fetched_at: now(),
+                        });
+                        info!("{} token refreshed", token_type);
+                    }
+                    Ok(_) => {
+                        *slot.token.lock().await = None;
+                        break;
+                    }
+                    Err(e) => {
+                        if exp <= now() {
+                            warn!(
+                                "Failed to refresh {} token and the cached one expired: {e:?}",
+                                token_type
+                            );
+                            *slot.token.lock().await = None;
+                            break;
+                        }
+
+                        warn!(
+                            "Failed to refresh {} token, retry in {}s: {e:?}",
+                            token_type,
+                            REFRESH_RETRY_INTERVAL.as_secs()
+                        );
+                        tokio::time::sleep(REFRESH_RETRY_INTERVAL).await;
+                    }
+                }
+            }
+        });
+
+        *slot.refresher.lock().expect("poisoned lock") = Some(handle);
+    }
+
+    /// Get the state of all cached tokens.
+    pub async fn status(&self) -> Vec<TokenCacheStatus> {
+        let slots: Vec<_> = self
+            .slots
+            .lock()
+            .expect("poisoned lock")
+            .iter()
+            .map(|(token_type, slot)| (token_type.clone(), slot.clone()))
+            .collect();
+
+        let now = now();
+        let mut status = Vec::new();
+        for (token_type, slot) in slots {
+            let Some(token) = slot.token.lock().await.clone() else {
+                continue;
+            };
+
+            status.push(TokenCacheStatus {
+                token_type,
+                valid: token.is_valid(now),
+                fetched_at: token.fetched_at,
+                exp: token.reply.exp,
+                nbf: token.reply.nbf,
+                refreshing: slot.is_refreshing(),
+            });
+        }
+
+        status
+    }
+
+    /// Set how long before a token expires to refresh it. Only affects the
+    /// background refresh tasks scheduled afterwards.
+    pub fn set_refresh_ahead(&self, refresh_ahead: Duration) {
+        self.refresh_ahead
+            .store(refresh_ahead.as_secs(), Ordering::Relaxed);
+    }
+
+    /// Drop the cached token of the given type, e.g. once the relying party
+    /// rejected it, so that the next `get_token` fetches a new one.
+    pub async fn invalidate(&self, token_type: &str) {
+        let slot = self
+            .slots
+            .lock()
+            .expect("poisoned lock")
+            .get(token_type)
+            .cloned();
+        if let Some(slot) = slot {
+            debug!("Invalidate cached {} token", token_type);
+            *slot.token.lock().await = None;
+        }
+    }
+
+    /// Drop all the cached tokens and stop refreshing them, e.g. when the
+    /// token configurations change and the cached tokens are stale.
+    pub fn clear(&self) {
+        let slots = std::mem::take(&mut *self.slots.lock().expect("poisoned lock"));
+        abort_refreshers(slots.values());
+    }
+}
+
+fn abort_refreshers<'a>(slots: impl Iterator<Item = &'a Arc<Slot>>) {
+    for slot in slots {
+        if let Some(handle) = slot.refresher.lock().expect("poisoned lock").take() {
+            handle.abort();
+        }
+    }
+}
+
+impl Drop for TokenCache {
+    fn drop(&mut self) {
+        let slots = self.slots.get_mut().expect("poisoned lock");
+        abort_refreshers(slots.values());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+    use super::*;
+
+    fn jwt(claims: serde_json::Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(claims.to_string());
+        format!("{header}.{claims}.signature")
+    }
+
+    fn counting_fetcher(count: Arc<AtomicUsize>, claims: serde_json::Value) -> TokenFetcher {
+        Arc::new(move || -> TokenFuture {
+            let count = count.clone();
+            let token = jwt(claims.clone());
+            Box::pin(async move {
+                count.fetch_add(1, Ordering::SeqCst);
+                TokenReply::new(token.clone().into_bytes(), &token)
+            })
+        })
+    }
+
+    #[rstest::rstest]
+    #[case(serde_json::json!({"exp": 2000, "nbf": 1000}), 1500, true)]
+    #[case(serde_json::json!({"exp": 2000}), 2500, false)]
+    #[case(serde_json::json!({"exp": 2000, "nbf": 1000}), 500, false)]
+    #[case(serde_json::json!({}), 1500, false)]
+    fn test_cached_token_validity(
+        #[case] claims: serde_json::Value,
+        #[case] now: u64,
+        #[case] valid: bool,
+    ) {
+        let token = jwt(claims);
+        let token = CachedToken {
+            reply: TokenReply::new(vec![], &token).unwrap(),
+            fetched_at: 0,
+        };
+        assert_eq!(token.is_valid(now), valid);
+    }
+
+    #[rstest::rstest]
+    #[case(serde_json::json!({"exp": 5000, "nbf": 1000}), 1000, Some(4940))]
+    #[case(serde_json::json!({"exp": 1200, "nbf": 1000}), 1000, Some(1150))]
+    #[case(serde_json::json!({"exp": 1020}), 1000, Some(1015))]
+    #[case(serde_json::json!({"exp": 1004}), 1000, Some(1010))]
+    #[case(serde_json::json!({}), 1000, None)]
+    fn test_refresh_at(
+        #[case] claims: serde_json::Value,
+        #[case] fetched_at: u64,
+        #[case] refresh_at: Option<u64>,
+    ) {
+        let token = jwt(claims);
+        let token = CachedToken {
+            reply: TokenReply::new(vec![], &token).unwrap(),
+            fetched_at,
+        };
+        assert_eq!(token.refresh_at(60), refresh_at);
+    }
+
+    #[tokio::test]
+    async fn test_short_lived_token_is_not_refreshed_in_a_loop() {
+        let cache = TokenCache::new(Duration::from_secs(60));
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = counting_fetcher(count.clone(), serde_json::json!({"exp": now() + 2}));
+
+        cache.get_token("kbs", fetcher).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert!(cache.status().await[0].refreshing);
+    }
+
+    #[tokio::test]
+    async fn test_token_is_cached() {
+        let cache = TokenCache::new(Duration::from_secs(60));
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = counting_fetcher(
+            count.clone(),
+            serde_json::json!({"exp": now() + 3600, "nbf": now() - 1}),
+        );
+
+        let first = cache.get_token("kbs", fetcher.clone()).await.unwrap();
+        let second = cache.get_token("kbs", fetcher).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        let status = cache.status().await;
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].token_type, "kbs");
+        assert!(status[0].valid);
+        assert!(status[0].refreshing);
+    }
+
+    #[tokio::test]
+    async fn test_token_without_exp_is_not_cached() {
+        let cache = TokenCache::new(Duration::from_secs(60));
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = counting_fetcher(count.clone(), serde_json::json!({}));
+
+        cache.get_token("kbs", fetcher.clone()).await.unwrap();
+        cache.get_token("kbs", fetcher).await.unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        assert!(cache.status().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_token() {
+        let cache = TokenCache::new(Duration::from_secs(60));
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = counting_fetcher(
+            count.clone(),
+            serde_json::json!({"exp": now() + 3600, "nbf": now() - 1}),
+        );
+
+        cache.invalidate("kbs").await;
+        cache.get_token("kbs", fetcher.clone()).await.unwrap();
+        cache.invalidate("kbs").await;
+        assert!(cache.status().await.is_empty());
+
+        cache.get_token("kbs", fetcher.clone()).await.unwrap();
+        cache.get_token("kbs", fetcher).await.unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_clear_cache() {
+        let cache = TokenCache::new(Duration::from_secs(60));
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = counting_fetcher(
+            count.clone(),
+            serde_json::json!({"exp": now() + 3600, "nbf": now() - 1}),
+        );
+
+        cache.get_token("kbs", fetcher.clone()).await.unwrap();
+        cache.clear();
+        assert!(cache.status().await.is_empty());
+
+        cache.get_token("kbs", fetcher).await.unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_clear_cache_during_fetch() {
+        let cache = Arc::new(TokenCache::new(Duration::from_secs(60)));
+        let fetching = Arc::new(tokio::sync::Notify::new());
+        let cleared = Arc::new(tokio::sync::Notify::new());
+        let token = jwt(serde_json::json!({"exp": now() + 3600}));
+        let fetcher: TokenFetcher = {
+            let fetching = fetching.clone();
+            let cleared = cleared.clone();
+            Arc::new(move || -> TokenFuture {
+                let fetching = fetching.clone();
+                let cleared = cleared.clone();
+                let token = token.clone();
+                Box::pin(async move {
+                    fetching.notify_one();
+                    cleared.notified().await;
+                    TokenReply::new(token.clone().into_bytes(), &token)
+                })
+            })
+        };
+
+        let task = {
+            let cache = cache.clone();
+            tokio::spawn(async move { cache.get_token("kbs", fetcher).await })
+        };
+        fetching.notified().await;
+        cache.clear();
+        cleared.notify_one();
+
+        // The token fetched with the old configurations is still returned,
+        // but neither cached nor refreshed
+        task.await.unwrap().unwrap();
+        assert!(cache.status().await.is_empty());
+        assert!(cache.slots.lock().unwrap().is_empty());
+    }
+}
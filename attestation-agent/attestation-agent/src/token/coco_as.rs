@@ -3,38 +3,214 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use crate::config::coco_as::CoCoASConfig;
+use std::collections::HashMap;
+
+use crate::{config::coco_as::CoCoASConfig, initdata::Initdata, Attesters};
+
+use super::{TokenGetter, TokenReply};
 
 use anyhow::*;
-use attester::{detect_tee_type, BoxedAttester};
+use async_trait::async_trait;
+use attester::TeeEvidence;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
+use canon_json::CanonicalFormatter;
+use crypto::ec::{Curve, EcKeyPair, KeyWrapAlgorithm};
+use kbs_protocol::ProxyConfig;
+use kbs_types::{HashAlgorithm, RuntimeData, Tee, TeePubKey};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Hash algorithm used to bind the runtime data into the evidence.
+const RUNTIME_DATA_HASH_ALGORITHM: HashAlgorithm = HashAlgorithm::Sha384;
+
+#[derive(Serialize)]
+struct Message {
+    token: String,
+    tee_keypair: String,
+}
 
-#[derive(Default)]
 pub struct CoCoASTokenGetter {
     as_uri: String,
+    policy_ids: Vec<String>,
+    with_tee_keypair: bool,
+    proxy: ProxyConfig,
+    attesters: Attesters,
+}
+
+fn tee_string(tee: &Tee) -> Result<String> {
+    Ok(serde_json::to_string(tee)?
+        .trim_end_matches('"')
+        .trim_start_matches('"')
+        .to_string())
+}
+
+fn serialize_json_canonically<T: Serialize>(value: T) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut ser = serde_json::Serializer::with_formatter(&mut buf, CanonicalFormatter::new());
+    value.serialize(&mut ser)?;
+    Ok(buf)
 }
 
 impl CoCoASTokenGetter {
-    pub async fn get_token(&self) -> Result<Vec<u8>> {
-        let primary_tee = detect_tee_type();
-        let attester = BoxedAttester::try_from(primary_tee)?;
-        let evidence = attester.get_evidence(vec![]).await?;
-
-        let tee_string = serde_json::to_string(&primary_tee)?
-            .trim_end_matches('"')
-            .trim_start_matches('"')
-            .to_string();
-
-        let request_body = serde_json::json!({
-            "verification_requests": [{
-                "tee": tee_string,
-                "evidence": URL_SAFE_NO_PAD.encode(serde_json::to_string(&evidence)?.as_bytes()),
-            }],
-            "policy_ids": [],
+    /// Get a challenge nonce from the AS, which is bound into the evidence
+    /// to prevent it from being replayed.
+    async fn get_nonce(&self, client: &reqwest::Client, tee: &str) -> Result<String> {
+        let challenge_endpoint = format!("{}/challenge", self.as_uri);
+        let res = client
+            .post(challenge_endpoint)
+            .json(&json!({
+                "tee": tee,
+                "tee_params": "",
+            }))
+            .send()
+            .await?;
+
+        if res.status() != reqwest::StatusCode::OK {
+            bail!(
+                "Failed to get challenge, AS Response: {:?}",
+                res.text().await?
+            );
+        }
+
+        let nonce = res.text().await?;
+        Ok(nonce.trim().trim_matches('"').to_string())
+    }
+}
+
+/// The runtime data bound into the evidence, carrying the AS nonce and the
+/// public TEE key.
+fn runtime_data(nonce: String, tee_key: &EcKeyPair) -> Result<RuntimeData> {
+    Ok(RuntimeData {
+        nonce,
+        tee_pubkey: TeePubKey::EC {
+            crv: Curve::P256.as_ref().to_string(),
+            alg: KeyWrapAlgorithm::EcdhEsA256Kw.as_ref().to_string(),
+            x: URL_SAFE_NO_PAD.encode(tee_key.x()?),
+            y: URL_SAFE_NO_PAD.encode(tee_key.y()?),
+        },
+    })
+}
+
+/// The runtime data of the primary TEE and the report data it binds. The
+/// runtime data is bound in the same way as the composite evidence of the
+/// KBS protocol: the primary TEE binds the runtime data plus the evidence of
+/// the additional devices, which is empty if there is none.
+fn primary_runtime_data(
+    primary_tee: Tee,
+    runtime_data: &RuntimeData,
+    additional_evidence: &str,
+) -> Result<(Option<Value>, Vec<u8>)> {
+    match primary_tee {
+        // SE handles the report data differently. As such, it does not support
+        // multi-device attestation.
+        Tee::Se => {
+            if !additional_evidence.is_empty() {
+                bail!("Cannot attest multiple devices on s390x platform.")
+            }
+            Ok((None, runtime_data.nonce.clone().into_bytes()))
+        }
+        _ => {
+            let primary_runtime_data = json!({
+                "tee-pubkey": runtime_data.tee_pubkey,
+                "nonce": runtime_data.nonce,
+                "additional-evidence": additional_evidence,
+            });
+            let hash = RUNTIME_DATA_HASH_ALGORITHM.digest(
+                &serialize_json_canonically(&primary_runtime_data)
+                    .context("serialize runtime data failed")?,
+            );
+            Ok((Some(primary_runtime_data), hash))
+        }
+    }
+}
+
+/// The verification request of the primary TEE, carrying its runtime data
+/// and the initdata if any.
+fn primary_request(
+    primary_tee: Tee,
+    evidence: &TeeEvidence,
+    primary_runtime_data: Option<Value>,
+    initdata: Option<&str>,
+) -> Result<Value> {
+    let mut request = json!({
+        "tee": tee_string(&primary_tee)?,
+        "evidence": URL_SAFE_NO_PAD.encode(serde_json::to_string(evidence)?.as_bytes()),
+    });
+    if let Some(primary_runtime_data) = primary_runtime_data {
+        request["runtime_data"] = json!({
+            "structured": primary_runtime_data,
+        });
+        request["runtime_data_hash_algorithm"] = serde_json::to_value(RUNTIME_DATA_HASH_ALGORITHM)?;
+    }
+
+    if let Some(initdata) = initdata {
+        let (parsed, _) = Initdata::parse_and_get_digest(initdata)?;
+        request["init_data"] = json!({
+            "toml": initdata,
+        });
+        request["init_data_hash_algorithm"] = serde_json::to_value(parsed.algorithm)?;
+    }
+    Ok(request)
+}
+
+/// The verification request of an additional device, which binds the
+/// runtime data itself.
+fn device_request(tee: Tee, evidence: &TeeEvidence, runtime_data: &RuntimeData) -> Result<Value> {
+    Ok(json!({
+        "tee": tee_string(&tee)?,
+        "evidence": URL_SAFE_NO_PAD.encode(serde_json::to_string(evidence)?.as_bytes()),
+        "runtime_data": {
+            "structured": runtime_data,
+        },
+        "runtime_data_hash_algorithm": serde_json::to_value(RUNTIME_DATA_HASH_ALGORITHM)?,
+    }))
+}
+
+#[async_trait]
+impl TokenGetter for CoCoASTokenGetter {
+    async fn get_token(&self, initdata: Option<&str>) -> Result<TokenReply> {
+        let primary_tee = self.attesters.primary_tee;
+
+        let client = self.proxy.http_client()?;
+        let nonce = self.get_nonce(&client, &tee_string(&primary_tee)?).await?;
+
+        let tee_key = EcKeyPair::default();
+        let runtime_data = runtime_data(nonce, &tee_key)?;
+        let device_runtime_data = serialize_json_canonically(&runtime_data)?;
+        let device_runtime_data_hash = RUNTIME_DATA_HASH_ALGORITHM.digest(&device_runtime_data);
+
+        let mut verification_requests = Vec::new();
+        let mut additional_evidence = HashMap::new();
+        for (tee, attester) in &self.attesters.additional {
+            let tee = *tee;
+            let evidence = attester
+                .get_evidence(device_runtime_data_hash.clone())
+                .await
+                .context("get additional evidence")?;
+            verification_requests.push(device_request(tee, &evidence, &runtime_data)?);
+            additional_evidence.insert(tee, evidence);
+        }
+
+        let additional_evidence = if additional_evidence.is_empty() {
+            String::new()
+        } else {
+            serde_json::to_string(&additional_evidence)?
+        };
+        let (primary_runtime_data, report_data) =
+            primary_runtime_data(primary_tee, &runtime_data, &additional_evidence)?;
+
+        let evidence = self.attesters.primary.get_evidence(report_data).await?;
+        verification_requests.insert(
+            0,
+            primary_request(primary_tee, &evidence, primary_runtime_data, initdata)?,
+        );
+
+        let request_body = json!({
+            "verification_requests": verification_requests,
+            "policy_ids": self.policy_ids,
         });
 
-        let client = reqwest::Client::new();
         let attest_endpoint = format!("{}/attestation", self.as_uri);
         let res = client
             .post(attest_endpoint)
@@ -46,7 +222,15 @@ impl CoCoASTokenGetter {
         match res.status() {
             reqwest::StatusCode::OK => {
                 let token = res.text().await?;
-                Ok(token.as_bytes().to_vec())
+                if !self.with_tee_keypair {
+                    return TokenReply::new(token.as_bytes().to_vec(), &token);
+                }
+
+                let message = Message {
+                    token: token.clone(),
+                    tee_keypair: tee_key.to_pkcs8_pem()?.to_string(),
+                };
+                TokenReply::new(serde_json::to_vec(&message)?, &token)
             }
             _ => {
                 bail!(
@@ -59,9 +243,85 @@ impl CoCoASTokenGetter {
 }
 
 impl CoCoASTokenGetter {
-    pub fn new(config: &CoCoASConfig) -> Self {
+    pub fn new(config: &CoCoASConfig, proxy: Option<&ProxyConfig>, attesters: &Attesters) -> Self {
         Self {
             as_uri: config.url.clone(),
+            policy_ids: config.policy_ids.clone(),
+            with_tee_keypair: config.with_tee_keypair,
+            proxy: proxy.cloned().unwrap_or_default(),
+            attesters: attesters.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_runtime_data() -> RuntimeData {
+        runtime_data("nonce".into(), &EcKeyPair::default()).unwrap()
+    }
+
+    #[test]
+    fn test_primary_runtime_data() {
+        let runtime_data = test_runtime_data();
+        let (structured, report_data) =
+            primary_runtime_data(Tee::Tdx, &runtime_data, "{\"nvidia\":{}}").unwrap();
+        let structured = structured.unwrap();
+        assert_eq!(structured["nonce"], "nonce");
+        assert_eq!(structured["additional-evidence"], "{\"nvidia\":{}}");
+        assert_eq!(
+            structured["tee-pubkey"],
+            serde_json::to_value(&runtime_data.tee_pubkey).unwrap()
+        );
+        assert_eq!(
+            report_data,
+            RUNTIME_DATA_HASH_ALGORITHM.digest(&serialize_json_canonically(&structured).unwrap())
+        );
+
+        // SE binds the bare nonce and cannot attest additional devices
+        let (structured, report_data) = primary_runtime_data(Tee::Se, &runtime_data, "").unwrap();
+        assert!(structured.is_none());
+        assert_eq!(report_data, b"nonce");
+        assert!(primary_runtime_data(Tee::Se, &runtime_data, "{}").is_err());
+    }
+
+    #[test]
+    fn test_primary_request() {
+        let evidence = json!({ "quote": "abc" });
+        let request = primary_request(Tee::Sample, &evidence, None, None).unwrap();
+        assert_eq!(request["tee"], "sample");
+        assert_eq!(
+            request["evidence"],
+            URL_SAFE_NO_PAD.encode(r#"{"quote":"abc"}"#)
+        );
+        assert!(request.get("runtime_data").is_none());
+        assert!(request.get("init_data").is_none());
+
+        let initdata = r#"
+version = "0.1.0"
+algorithm = "sha384"
+[data]
+"#;
+        let request = primary_request(
+            Tee::Tdx,
+            &evidence,
+            Some(json!({ "nonce": "n" })),
+            Some(initdata),
+        )
+        .unwrap();
+        assert_eq!(request["runtime_data"]["structured"]["nonce"], "n");
+        assert_eq!(request["runtime_data_hash_algorithm"], "sha384");
+        assert_eq!(request["init_data"]["toml"], initdata);
+        assert_eq!(request["init_data_hash_algorithm"], "sha384");
+    }
+
+    #[test]
+    fn test_device_request() {
+        let runtime_data = test_runtime_data();
+        let request = device_request(Tee::Nvidia, &json!({}), &runtime_data).unwrap();
+        assert_eq!(request["tee"], "nvidia");
+        assert_eq!(request["runtime_data"]["structured"]["nonce"], "nonce");
+        assert_eq!(request["runtime_data_hash_algorithm"], "sha384");
+    }
+}
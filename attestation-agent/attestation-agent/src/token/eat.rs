@@ -0,0 +1,165 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! A generic passport mode token getter. The evidence is posted to a
+//! RATS verifier over HTTP and the Entity Attestation Token (EAT) in the
+//! response is returned.
+
+use crate::{config::eat::EatConfig, Attesters};
+
+use super::{TokenGetter, TokenReply};
+
+use anyhow::*;
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use kbs_protocol::ProxyConfig;
+
+pub struct EatTokenGetter {
+    config: EatConfig,
+    proxy: ProxyConfig,
+    attesters: Attesters,
+}
+
+impl EatTokenGetter {
+    pub fn new(config: &EatConfig, proxy: Option<&ProxyConfig>, attesters: &Attesters) -> Self {
+        Self {
+            config: config.clone(),
+            proxy: proxy.cloned().unwrap_or_default(),
+            attesters: attesters.clone(),
+        }
+    }
+}
+
+/// Replace the `${name}` placeholders of the template with the JSON-escaped
+/// values and check that the result is valid JSON.
+fn render_request(template: &str, values: &[(&str, &str)]) -> Result<String> {
+    let mut body = template.to_string();
+    for (name, value) in values {
+        let escaped = serde_json::to_string(value)?;
+        body = body.replace(&format!("${{{name}}}"), &escaped[1..escaped.len() - 1]);
+    }
+
+    serde_json::from_str::<serde_json::Value>(&body)
+        .context("request template is not valid JSON after rendering")?;
+    Ok(body)
+}
+
+/// Get the token out of the verifier response.
+fn extract_token(response: &str, token_pointer: Option<&str>) -> Result<String> {
+    let Some(pointer) = token_pointer else {
+        return Ok(response.trim().to_string());
+    };
+
+    let response: serde_json::Value =
+        serde_json::from_str(response).context("verifier response is not JSON")?;
+    let token = response
+        .pointer(pointer)
+        .and_then(|token| token.as_str())
+        .ok_or_else(|| anyhow!("no string found at {pointer} of verifier response"))?;
+    Ok(token.to_string())
+}
+
+#[async_trait]
+impl TokenGetter for EatTokenGetter {
+    async fn get_token(&self, initdata: Option<&str>) -> Result<TokenReply> {
+        let primary_tee = self.attesters.primary_tee;
+        let evidence = self.attesters.primary.get_evidence(vec![]).await?;
+
+        let tee_string = serde_json::to_string(&primary_tee)?
+            .trim_end_matches('"')
+            .trim_start_matches('"')
+            .to_string();
+        let evidence = URL_SAFE_NO_PAD.encode(serde_json::to_string(&evidence)?.as_bytes());
+
+        let request_body = render_request(
+            &self.config.request_template,
+            &[
+                ("tee", &tee_string),
+                ("evidence", &evidence),
+                ("initdata", initdata.unwrap_or_default()),
+            ],
+        )?;
+
+        let client = self.proxy.http_client()?;
+        let mut request = client
+            .post(&self.config.url)
+            .header("Content-Type", "application/json");
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+
+        let res = request.body(request_body).send().await?;
+        match res.status() {
+            reqwest::StatusCode::OK => {
+                let token =
+                    extract_token(&res.text().await?, self.config.token_pointer.as_deref())?;
+                TokenReply::new(token.as_bytes().to_vec(), &token)
+            }
+            status => {
+                bail!(
+                    "Remote Attestation Failed, verifier response {status}: {:?}",
+                    res.text().await?
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::config::eat::DEFAULT_EAT_REQUEST_TEMPLATE;
+
+    #[test]
+    fn test_render_request() {
+        let body = render_request(
+            r#"{"tee":"${tee}","evidence":"${evidence}","initdata":"${initdata}"}"#,
+            &[
+                ("tee", "tdx"),
+                ("evidence", "ZXZpZGVuY2U"),
+                ("initdata", "algorithm = \"sha384\"\nversion = \"0.1.0\""),
+            ],
+        )
+        .unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "tee": "tdx",
+                "evidence": "ZXZpZGVuY2U",
+                "initdata": "algorithm = \"sha384\"\nversion = \"0.1.0\"",
+            })
+        );
+
+        assert!(render_request(DEFAULT_EAT_REQUEST_TEMPLATE, &[]).is_ok());
+        assert!(render_request(r#"{"tee":${tee}}"#, &[("tee", "tdx")]).is_err());
+    }
+
+    #[rstest]
+    #[case("eyJ0.eyJ1.c2ln\n", None, Some("eyJ0.eyJ1.c2ln"))]
+    #[case(
+        r#"{"token":"eyJ0.eyJ1.c2ln"}"#,
+        Some("/token"),
+        Some("eyJ0.eyJ1.c2ln")
+    )]
+    #[case(
+        r#"{"eat":{"jwt":"eyJ0.eyJ1.c2ln"}}"#,
+        Some("/eat/jwt"),
+        Some("eyJ0.eyJ1.c2ln")
+    )]
+    #[case(r#"{"token":"eyJ0.eyJ1.c2ln"}"#, Some("/jwt"), None)]
+    #[case("eyJ0.eyJ1.c2ln", Some("/token"), None)]
+    fn test_extract_token(
+        #[case] response: &str,
+        #[case] pointer: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let token = extract_token(response, pointer).ok();
+        assert_eq!(token.as_deref(), expected);
+    }
+}
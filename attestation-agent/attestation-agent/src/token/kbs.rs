@@ -3,10 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use crate::config::kbs::KbsConfig;
+use crate::{config::kbs::KbsConfig, Attesters};
+
+use super::{TokenGetter, TokenReply};
 
 use anyhow::*;
-use kbs_protocol::{evidence_provider::NativeEvidenceProvider, KbsClientBuilder};
+use async_trait::async_trait;
+use kbs_protocol::{
+    evidence_provider::NativeEvidenceProvider, KbsClientBuilder, ProxyConfig, SessionStore,
+    TeeKeyAlgorithm,
+};
+use log::warn;
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -15,23 +22,67 @@ struct Message {
     tee_keypair: String,
 }
 
-#[derive(Default)]
 pub struct KbsTokenGetter {
-    kbs_host_url: String,
-    cert: Option<String>,
+    config: KbsConfig,
+    proxy: Option<ProxyConfig>,
+    attesters: Attesters,
 }
 
-impl KbsTokenGetter {
-    pub async fn get_token(&self, initdata: Option<&str>) -> Result<Vec<u8>> {
-        let evidence_provider = Box::new(NativeEvidenceProvider::new()?);
+#[async_trait]
+impl TokenGetter for KbsTokenGetter {
+    async fn get_token(&self, initdata: Option<&str>) -> Result<TokenReply> {
+        let evidence_provider = Box::new(NativeEvidenceProvider::with_attesters(
+            self.attesters.primary_tee,
+            self.attesters.primary.clone(),
+            self.attesters.additional.clone(),
+        ));
+
+        // The session is only persisted if the primary attester can derive
+        // the key sealing it.
+        let session_store = match &self.config.session_file {
+            Some(session_file) => {
+                SessionStore::with_evidence_provider(session_file, evidence_provider.as_ref())
+                    .await
+                    .inspect_err(|e| warn!("KBS session is not persisted: {e}"))
+                    .ok()
+            }
+            None => None,
+        };
 
         let mut builder =
-            KbsClientBuilder::with_evidence_provider(evidence_provider, &self.kbs_host_url);
+            KbsClientBuilder::with_evidence_provider(evidence_provider, &self.config.url);
 
-        if let Some(cert) = &self.cert {
+        if let Some(cert) = &self.config.cert {
             builder = builder.add_kbs_cert(cert);
         }
 
+        match (&self.config.client_cert, &self.config.client_key) {
+            (Some(cert), Some(key)) => builder = builder.set_client_identity(cert, key),
+            (None, None) => {}
+            _ => bail!("`client_cert` and `client_key` must be given together"),
+        }
+
+        for pin in &self.config.spki_pins {
+            builder = builder.add_kbs_spki_pin(pin);
+        }
+
+        builder = builder.set_attested_tls(self.config.attested_tls);
+
+        if let Some(algorithm) = &self.config.tee_key_algorithm {
+            let algorithm = algorithm
+                .parse::<TeeKeyAlgorithm>()
+                .context("invalid `tee_key_algorithm`")?;
+            builder = builder.set_tee_key_algorithm(algorithm);
+        }
+
+        if let Some(store) = session_store {
+            builder = builder.set_session_store(store);
+        }
+
+        if let Some(proxy) = &self.proxy {
+            builder = builder.set_proxy(proxy.clone());
+        }
+
         if let Some(initdata) = initdata {
             builder = builder.add_initdata(initdata.to_string());
         }
@@ -40,20 +91,21 @@ impl KbsTokenGetter {
 
         let (token, tee_keypair) = client.get_token().await?;
         let message = Message {
-            token: token.content,
+            token: token.content.clone(),
             tee_keypair: tee_keypair.to_pem()?.to_string(),
         };
 
         let res = serde_json::to_vec(&message)?;
-        Ok(res)
+        TokenReply::new(res, &token.content)
     }
 }
 
 impl KbsTokenGetter {
-    pub fn new(config: &KbsConfig) -> Self {
+    pub fn new(config: &KbsConfig, proxy: Option<&ProxyConfig>, attesters: &Attesters) -> Self {
         Self {
-            kbs_host_url: config.url.clone(),
-            cert: config.cert.clone(),
+            config: config.clone(),
+            proxy: proxy.cloned(),
+            attesters: attesters.clone(),
         }
     }
 }
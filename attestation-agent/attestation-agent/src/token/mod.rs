@@ -3,15 +3,32 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::Deserialize;
 use strum::EnumString;
 
+use crate::{config::TokenConfigs, Attesters};
+
+pub mod cache;
+
 #[cfg(feature = "kbs")]
 pub mod kbs;
 
 #[cfg(feature = "coco_as")]
 pub mod coco_as;
 
-#[derive(EnumString, Clone, Copy)]
+#[cfg(feature = "eat")]
+pub mod eat;
+
+/// The token types enabled by cargo features.
+#[deprecated(
+    note = "token types are registry keys now, use `TokenType::as_str` or the name of the token type directly"
+)]
+#[derive(EnumString, Clone, Copy, Debug, PartialEq)]
 pub enum TokenType {
     #[cfg(feature = "kbs")]
     #[strum(serialize = "kbs")]
@@ -20,4 +37,253 @@ pub enum TokenType {
     #[cfg(feature = "coco_as")]
     #[strum(serialize = "coco_as")]
     CoCoAS,
+
+    #[cfg(feature = "eat")]
+    #[strum(serialize = "eat")]
+    Eat,
+}
+
+#[allow(deprecated)]
+impl TokenType {
+    /// The key of the token type in the [`TokenGetterRegistry`].
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "kbs")]
+            TokenType::Kbs => "kbs",
+
+            #[cfg(feature = "coco_as")]
+            TokenType::CoCoAS => "coco_as",
+
+            #[cfg(feature = "eat")]
+            TokenType::Eat => "eat",
+        }
+    }
+}
+
+/// A backend which performs remote attestation against some verifier
+/// and returns the attestation token.
+#[async_trait]
+pub trait TokenGetter: Send + Sync {
+    /// Get a new token. `initdata` is the plaintext initdata toml if AA
+    /// is launched with one.
+    async fn get_token(&self, initdata: Option<&str>) -> Result<TokenReply>;
+}
+
+/// Creates a [`TokenGetter`] from the token configs and the attesters of
+/// AA. It is called on every `get_token` request so that config updates
+/// take effect.
+pub type TokenGetterFactory =
+    Box<dyn Fn(&TokenConfigs, &Attesters) -> Result<Arc<dyn TokenGetter>> + Send + Sync>;
+
+/// Maps token type names (as given in `GetTokenRequest`) to the
+/// [`TokenGetterFactory`]s creating their getters.
+pub struct TokenGetterRegistry {
+    factories: HashMap<String, TokenGetterFactory>,
+}
+
+impl Default for TokenGetterRegistry {
+    /// A registry with all the token types enabled by cargo features.
+    #[allow(unused_mut)]
+    fn default() -> Self {
+        let mut registry = Self::empty();
+
+        #[cfg(feature = "kbs")]
+        registry
+            .register("kbs", |configs, attesters| {
+                let config = configs
+                    .kbs
+                    .as_ref()
+                    .ok_or(anyhow!("kbs token config not configured in config file"))?;
+                Ok(Arc::new(kbs::KbsTokenGetter::new(
+                    config,
+                    configs.proxy.as_ref(),
+                    attesters,
+                )))
+            })
+            .expect("register kbs token getter");
+
+        #[cfg(feature = "coco_as")]
+        registry
+            .register("coco_as", |configs, attesters| {
+                let config = configs.coco_as.as_ref().ok_or(anyhow!(
+                    "coco_as token config not configured in config file"
+                ))?;
+                Ok(Arc::new(coco_as::CoCoASTokenGetter::new(
+                    config,
+                    configs.proxy.as_ref(),
+                    attesters,
+                )))
+            })
+            .expect("register coco_as token getter");
+
+        #[cfg(feature = "eat")]
+        registry
+            .register("eat", |configs, attesters| {
+                let config = configs
+                    .eat
+                    .as_ref()
+                    .ok_or(anyhow!("eat token config not configured in config file"))?;
+                Ok(Arc::new(eat::EatTokenGetter::new(
+                    config,
+                    configs.proxy.as_ref(),
+                    attesters,
+                )))
+            })
+            .expect("register eat token getter");
+
+        registry
+    }
+}
+
+impl TokenGetterRegistry {
+    /// A registry without any token types.
+    pub fn empty() -> Self {
+        Self {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Register a new token type. Fails if the token type is already
+    /// registered.
+    pub fn register<F>(&mut self, token_type: &str, factory: F) -> Result<()>
+    where
+        F: Fn(&TokenConfigs, &Attesters) -> Result<Arc<dyn TokenGetter>> + Send + Sync + 'static,
+    {
+        if self.factories.contains_key(token_type) {
+            bail!("token type {token_type} is already registered");
+        }
+
+        self.factories
+            .insert(token_type.to_string(), Box::new(factory));
+        Ok(())
+    }
+
+    /// All registered token types, sorted by name.
+    pub fn token_types(&self) -> Vec<&str> {
+        let mut types: Vec<_> = self.factories.keys().map(String::as_str).collect();
+        types.sort_unstable();
+        types
+    }
+
+    /// Create the [`TokenGetter`] of the given token type.
+    pub fn get_getter(
+        &self,
+        token_type: &str,
+        configs: &TokenConfigs,
+        attesters: &Attesters,
+    ) -> Result<Arc<dyn TokenGetter>> {
+        let factory = self.factories.get(token_type).ok_or_else(|| {
+            anyhow!(
+                "Unsupported token type {token_type}, supported token types: {:?}",
+                self.token_types()
+            )
+        })?;
+
+        factory(configs, attesters)
+    }
+}
+
+/// The reply of a token getter, together with the validity window of the
+/// JWT carried inside it.
+#[derive(Clone, Debug)]
+pub struct TokenReply {
+    /// The bytes handed back to the caller of `get_token`.
+    pub content: Vec<u8>,
+
+    /// `exp` claim of the JWT (seconds since UNIX epoch), if any.
+    pub exp: Option<u64>,
+
+    /// `nbf` claim of the JWT (seconds since UNIX epoch), if any.
+    pub nbf: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ValidityClaims {
+    exp: Option<u64>,
+    nbf: Option<u64>,
+}
+
+impl TokenReply {
+    /// Create a new [`TokenReply`]. The `exp` and `nbf` claims are read
+    /// from the given JWT without verifying its signature. The signature
+    /// is checked by the relying party.
+    pub fn new(content: Vec<u8>, jwt: &str) -> Result<Self> {
+        let claims_b64 = jwt
+            .split('.')
+            .nth(1)
+            .ok_or_else(|| anyhow!("illegal token format"))?;
+        let claims = URL_SAFE_NO_PAD
+            .decode(claims_b64.trim_end_matches('='))
+            .context("base64 decode token claims")?;
+        let claims: ValidityClaims =
+            serde_json::from_slice(&claims).context("parse token claims")?;
+
+        Ok(Self {
+            content,
+            exp: claims.exp,
+            nbf: claims.nbf,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use attester::BoxedAttester;
+    use kbs_types::Tee;
+
+    use super::*;
+
+    struct DummyTokenGetter;
+
+    fn sample_attesters() -> Attesters {
+        Attesters {
+            primary_tee: Tee::Sample,
+            primary: Arc::new(BoxedAttester::try_from(Tee::Sample).unwrap()),
+            additional: Vec::new(),
+        }
+    }
+
+    #[async_trait]
+    impl TokenGetter for DummyTokenGetter {
+        async fn get_token(&self, _initdata: Option<&str>) -> Result<TokenReply> {
+            Ok(TokenReply {
+                content: b"dummy".to_vec(),
+                exp: None,
+                nbf: None,
+            })
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_token_type_registry_keys() {
+        let registry = TokenGetterRegistry::default();
+        for name in registry.token_types() {
+            let token_type: TokenType = name.parse().unwrap();
+            assert_eq!(token_type.as_str(), name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_token_getter() {
+        let mut registry = TokenGetterRegistry::empty();
+        registry
+            .register("dummy", |_, _| Ok(Arc::new(DummyTokenGetter)))
+            .unwrap();
+        assert!(registry
+            .register("dummy", |_, _| Ok(Arc::new(DummyTokenGetter)))
+            .is_err());
+        assert_eq!(registry.token_types(), vec!["dummy"]);
+
+        let attesters = sample_attesters();
+        let getter = registry
+            .get_getter("dummy", &TokenConfigs::default(), &attesters)
+            .unwrap();
+        let token = getter.get_token(None).await.unwrap();
+        assert_eq!(token.content, b"dummy");
+
+        assert!(registry
+            .get_getter("unknown", &TokenConfigs::default(), &attesters)
+            .is_err());
+    }
 }
@@ -4,6 +4,7 @@
 //
 
 use super::{Attester, InitDataResult, TeeEvidence};
+use crate::diagnostics::PlatformProbe;
 use anyhow::{bail, Context, Result};
 use az_snp_vtpm::{imds, is_snp_cvm, vtpm};
 use kbs_types::HashAlgorithm;
@@ -14,11 +15,19 @@ use serde_with::{base64::Base64, hex::Hex, serde_as};
 type UrlSafeBase64 = Base64<serde_with::base64::UrlSafe>;
 
 pub fn detect_platform() -> bool {
+    probe_platform().is_detected()
+}
+
+pub fn probe_platform() -> PlatformProbe {
+    let probe = PlatformProbe::default();
     match is_snp_cvm() {
-        Ok(is_snp) => is_snp,
+        Ok(true) => probe,
+        Ok(false) => probe.fail("The Azure HCL report of the vTPM is not of an SNP CVM"),
         Err(err) => {
             debug!("Failed to retrieve Azure HCL data from vTPM: {err}");
-            false
+            probe.fail(format!(
+                "Failed to retrieve Azure HCL data from vTPM: {err}"
+            ))
         }
     }
 }
@@ -5,6 +5,7 @@
 
 use super::{Attester, InitDataResult, TeeEvidence};
 use crate::az_snp_vtpm::{utils, TpmQuote};
+use crate::diagnostics::PlatformProbe;
 use anyhow::*;
 use az_tdx_vtpm::{hcl, imds, is_tdx_cvm, vtpm};
 use kbs_types::HashAlgorithm;
@@ -16,11 +17,19 @@ use std::result::Result::Ok;
 type UrlSafeBase64 = Base64<serde_with::base64::UrlSafe>;
 
 pub fn detect_platform() -> bool {
+    probe_platform().is_detected()
+}
+
+pub fn probe_platform() -> PlatformProbe {
+    let probe = PlatformProbe::default();
     match is_tdx_cvm() {
-        Ok(tdx) => tdx,
+        Ok(true) => probe,
+        Ok(false) => probe.fail("The Azure HCL report of the vTPM is not of a TDX CVM"),
         Err(err) => {
             debug!("Couldn't perform Azure TDX platform detection: {err}");
-            false
+            probe.fail(format!(
+                "Couldn't perform Azure TDX platform detection: {err}"
+            ))
         }
     }
 }
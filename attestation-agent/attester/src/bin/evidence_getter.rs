@@ -3,7 +3,12 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use attester::{detect_attestable_devices, detect_tee_type, BoxedAttester};
+use attester::{
+    detect_attestable_devices, detect_tee_type,
+    diagnostics::platform_report,
+    verify::{verify_evidence, VERIFIABLE_TEES},
+    BoxedAttester,
+};
 use clap::Parser;
 use std::io::Read;
 use tokio::fs;
@@ -21,6 +26,47 @@ enum Cli {
     /// Read report data from the given file. If the length of input is longer than
     /// 64 bytes, the input will be truncated. If shorter, it will be padded by `\0`.
     File { path: String },
+
+    /// Verify the evidence printed by the other commands, as `<tee>:<evidence>` lines, and
+    /// print its claims. The report data is given like for `commandline`. Exits with an
+    /// error if the evidence does not match the report data or fails a check.
+    Verify {
+        data: String,
+
+        /// Read the evidence from the given file instead of stdin.
+        #[arg(long)]
+        evidence: Option<String>,
+    },
+
+    /// Print which attesters are compiled in, what was checked to detect each platform and
+    /// why it was not detected. Exits with an error if the sample attester would be used.
+    Diagnose,
+}
+
+/// Verify each line of evidence, returning whether all of them are valid.
+fn verify(evidence: &str, report_data: &[u8]) -> bool {
+    let mut valid = true;
+    for line in evidence.lines().filter(|line| !line.trim().is_empty()) {
+        let (name, evidence) = line
+            .split_once(':')
+            .expect("evidence must be `<tee>:<evidence>`");
+        let Some(&tee) = VERIFIABLE_TEES
+            .iter()
+            .find(|tee| format!("{tee:?}").eq_ignore_ascii_case(name))
+        else {
+            eprintln!("Skip {name} evidence: verification is not supported");
+            continue;
+        };
+
+        let evidence = serde_json::from_str(evidence).expect("parse evidence failed");
+        let claims = verify_evidence(tee, &evidence, report_data).expect("verify evidence failed");
+        valid &= claims.is_valid();
+        println!(
+            "{tee:?}:{}",
+            serde_json::to_string_pretty(&claims).expect("serialize claims failed")
+        );
+    }
+    valid
 }
 
 #[tokio::main(flavor = "current_thread")]
@@ -46,6 +92,37 @@ async fn main() {
             let len = content.len().min(64);
             report_data[..len].copy_from_slice(&content[..len]);
         }
+        Cli::Verify { data, evidence } => {
+            let len = data.len().min(64);
+            report_data[..len].copy_from_slice(&data.as_bytes()[..len]);
+            let evidence = match evidence {
+                Some(path) => fs::read_to_string(path)
+                    .await
+                    .expect("read evidence from file failed"),
+                None => {
+                    let mut evidence = String::new();
+                    std::io::stdin()
+                        .read_to_string(&mut evidence)
+                        .expect("read evidence failed");
+                    evidence
+                }
+            };
+            if !verify(&evidence, &report_data) {
+                std::process::exit(1);
+            }
+            return;
+        }
+        Cli::Diagnose => {
+            let report = platform_report();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).expect("serialize report failed")
+            );
+            if report.sample_fallback {
+                std::process::exit(1);
+            }
+            return;
+        }
     }
 
     let evidence = TryInto::<BoxedAttester>::try_into(detect_tee_type())
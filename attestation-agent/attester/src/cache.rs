@@ -0,0 +1,370 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Caching of evidence.
+//!
+//! A [`CachingAttester`] wraps the attester of a device. Evidence is returned
+//! from the cache for the same report data within a short TTL, so retries of
+//! an attestation do not generate the quote again. Concurrent callers with
+//! the same report data wait for a single generation, and the number of
+//! concurrent generations on the device is limited. Extending the runtime
+//! measurement or binding initdata invalidates the cached evidence.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex as StdMutex,
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use kbs_types::HashAlgorithm;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, Semaphore};
+
+use crate::{Attester, BoxedAttester, InitDataResult, TeeEvidence};
+
+const DEFAULT_EVIDENCE_CACHE_TTL_SECS: u64 = 5;
+
+const DEFAULT_MAX_CONCURRENT_GENERATIONS: usize = 1;
+
+/// Options of the evidence cache of each attester.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvidenceCacheOptions {
+    /// Whether to return cached evidence for the same report data.
+    pub enable: bool,
+
+    /// How many seconds cached evidence is returned.
+    pub ttl_secs: u64,
+
+    /// Maximum number of concurrent evidence generations on a device. No
+    /// limit if 0.
+    pub max_concurrent_generations: usize,
+}
+
+impl Default for EvidenceCacheOptions {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            ttl_secs: DEFAULT_EVIDENCE_CACHE_TTL_SECS,
+            max_concurrent_generations: DEFAULT_MAX_CONCURRENT_GENERATIONS,
+        }
+    }
+}
+
+/// Metrics of the evidence generations of an attester.
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub struct EvidenceMetrics {
+    /// Number of evidence generations, i.e. calls to the hardware.
+    pub generations: u64,
+
+    /// Number of failed evidence generations.
+    pub failures: u64,
+
+    /// Number of evidence returned from the cache.
+    pub cache_hits: u64,
+
+    /// Total latency of the evidence generations, in milliseconds.
+    pub total_latency_ms: u64,
+
+    /// Maximum latency of an evidence generation, in milliseconds.
+    pub max_latency_ms: u64,
+}
+
+/// A handle to read the metrics of a [`CachingAttester`], which stays
+/// usable once the attester is boxed.
+#[derive(Clone, Default)]
+pub struct EvidenceMetricsHandle(Arc<StdMutex<EvidenceMetrics>>);
+
+impl EvidenceMetricsHandle {
+    pub fn get(&self) -> EvidenceMetrics {
+        self.0.lock().expect("poisoned lock").clone()
+    }
+
+    fn record_generation(&self, latency: Duration, ok: bool) {
+        let latency_ms = latency.as_millis() as u64;
+        let mut metrics = self.0.lock().expect("poisoned lock");
+        metrics.generations += 1;
+        if !ok {
+            metrics.failures += 1;
+        }
+        metrics.total_latency_ms += latency_ms;
+        metrics.max_latency_ms = metrics.max_latency_ms.max(latency_ms);
+    }
+
+    fn record_cache_hit(&self) {
+        self.0.lock().expect("poisoned lock").cache_hits += 1;
+    }
+}
+
+struct CachedEvidence {
+    evidence: TeeEvidence,
+    generated_at: Instant,
+
+    /// Measurement epoch the evidence was generated in
+    epoch: u64,
+}
+
+/// Evidence of a report data. The lock is held while generating it, so
+/// concurrent callers wait for a single generation.
+type Entry = Arc<Mutex<Option<CachedEvidence>>>;
+
+/// The cache and the concurrency limit are held by each `CachingAttester`,
+/// so a device must be wrapped once and the wrapper shared by all the users
+/// of the device, as AA does with its token getters. Wrappers of the same
+/// device in other processes, e.g. the `NativeEvidenceProvider` of a
+/// standalone KBS client, are not limited by it.
+pub struct CachingAttester {
+    inner: BoxedAttester,
+    ttl: Duration,
+    generations: Option<Semaphore>,
+    entries: StdMutex<HashMap<Vec<u8>, Entry>>,
+
+    /// Bumped whenever the measurements change, invalidating the evidence
+    /// generated before
+    epoch: AtomicU64,
+    metrics: EvidenceMetricsHandle,
+}
+
+impl CachingAttester {
+    pub fn new(inner: BoxedAttester, options: &EvidenceCacheOptions) -> Self {
+        let ttl = match options.enable {
+            true => Duration::from_secs(options.ttl_secs),
+            false => Duration::ZERO,
+        };
+        let generations = match options.max_concurrent_generations {
+            0 => None,
+            permits => Some(Semaphore::new(permits)),
+        };
+
+        Self {
+            inner,
+            ttl,
+            generations,
+            entries: StdMutex::default(),
+            epoch: AtomicU64::new(0),
+            metrics: EvidenceMetricsHandle::default(),
+        }
+    }
+
+    pub fn metrics(&self) -> EvidenceMetricsHandle {
+        self.metrics.clone()
+    }
+
+    fn is_fresh(&self, cached: &CachedEvidence) -> bool {
+        cached.epoch == self.epoch.load(Ordering::SeqCst)
+            && cached.generated_at.elapsed() < self.ttl
+    }
+
+    /// The entry of the report data. Stale entries nobody is waiting for
+    /// are dropped.
+    fn entry(&self, report_data: &[u8]) -> Entry {
+        let mut entries = self.entries.lock().expect("poisoned lock");
+        entries.retain(|key, entry| {
+            key == report_data
+                || match entry.try_lock() {
+                    Ok(cached) => cached.as_ref().is_some_and(|c| self.is_fresh(c)),
+                    // Someone is generating or reading it
+                    Err(_) => true,
+                }
+        });
+        entries.entry(report_data.to_vec()).or_default().clone()
+    }
+
+    fn invalidate(&self) {
+        self.epoch.fetch_add(1, Ordering::SeqCst);
+    }
+
+    async fn generate(&self, report_data: Vec<u8>) -> Result<TeeEvidence> {
+        let _permit = match &self.generations {
+            Some(generations) => Some(generations.acquire().await?),
+            None => None,
+        };
+
+        let start = Instant::now();
+        let evidence = self.inner.get_evidence(report_data).await;
+        self.metrics
+            .record_generation(start.elapsed(), evidence.is_ok());
+        evidence
+    }
+}
+
+#[async_trait::async_trait]
+impl Attester for CachingAttester {
+    async fn get_evidence(&self, report_data: Vec<u8>) -> Result<TeeEvidence> {
+        if self.ttl.is_zero() {
+            return self.generate(report_data).await;
+        }
+
+        let entry = self.entry(&report_data);
+        let mut cached = entry.lock().await;
+        if let Some(cached) = cached.as_ref().filter(|c| self.is_fresh(c)) {
+            debug!("Return cached evidence");
+            self.metrics.record_cache_hit();
+            return Ok(cached.evidence.clone());
+        }
+
+        let epoch = self.epoch.load(Ordering::SeqCst);
+        let evidence = self.generate(report_data).await?;
+        *cached = Some(CachedEvidence {
+            evidence: evidence.clone(),
+            generated_at: Instant::now(),
+            epoch,
+        });
+        Ok(evidence)
+    }
+
+    fn supports_runtime_measurement(&self) -> bool {
+        self.inner.supports_runtime_measurement()
+    }
+
+    async fn extend_runtime_measurement(
+        &self,
+        event_digest: Vec<u8>,
+        register_index: u64,
+    ) -> Result<()> {
+        let extended = self
+            .inner
+            .extend_runtime_measurement(event_digest, register_index)
+            .await;
+        self.invalidate();
+        extended
+    }
+
+    async fn bind_init_data(&self, init_data_digest: &[u8]) -> Result<InitDataResult> {
+        let bound = self.inner.bind_init_data(init_data_digest).await;
+        self.invalidate();
+        bound
+    }
+
+    async fn get_runtime_measurement(&self, pcr_index: u64) -> Result<Vec<u8>> {
+        self.inner.get_runtime_measurement(pcr_index).await
+    }
+
+    async fn derive_sealing_key(&self, purpose: &str) -> Result<Vec<u8>> {
+        self.inner.derive_sealing_key(purpose).await
+    }
+
+    fn pcr_to_ccmr(&self, pcr_index: u64) -> u64 {
+        self.inner.pcr_to_ccmr(pcr_index)
+    }
+
+    fn ccel_hash_algorithm(&self) -> HashAlgorithm {
+        self.inner.ccel_hash_algorithm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use serde_json::json;
+
+    use super::*;
+
+    /// Counts the generations, and the maximum number of concurrent ones.
+    #[derive(Default)]
+    struct CountingAttester {
+        generations: Arc<AtomicUsize>,
+        running: AtomicUsize,
+        max_running: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl Attester for CountingAttester {
+        async fn get_evidence(&self, report_data: Vec<u8>) -> Result<TeeEvidence> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            for _ in 0..10 {
+                tokio::task::yield_now().await;
+            }
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            let generation = self.generations.fetch_add(1, Ordering::SeqCst);
+            Ok(json!({ "report_data": report_data, "generation": generation }))
+        }
+
+        async fn bind_init_data(&self, _init_data_digest: &[u8]) -> Result<InitDataResult> {
+            Ok(InitDataResult::Ok)
+        }
+    }
+
+    fn caching_attester(
+        options: &EvidenceCacheOptions,
+    ) -> (CachingAttester, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+        let inner = CountingAttester::default();
+        let generations = inner.generations.clone();
+        let max_running = inner.max_running.clone();
+        (
+            CachingAttester::new(Box::new(inner), options),
+            generations,
+            max_running,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_caching_attester() {
+        let (attester, generations, _) = caching_attester(&EvidenceCacheOptions::default());
+
+        // Concurrent callers with the same report data share a generation
+        let (a, b) = tokio::join!(
+            attester.get_evidence(vec![1; 32]),
+            attester.get_evidence(vec![1; 32])
+        );
+        assert_eq!(a.unwrap(), b.unwrap());
+        assert_eq!(generations.load(Ordering::SeqCst), 1);
+
+        attester.get_evidence(vec![2; 32]).await.unwrap();
+        assert_eq!(generations.load(Ordering::SeqCst), 2);
+
+        // Changed measurements invalidate the cached evidence
+        attester.bind_init_data(&[0; 32]).await.unwrap();
+        attester.get_evidence(vec![1; 32]).await.unwrap();
+        assert_eq!(generations.load(Ordering::SeqCst), 3);
+
+        let metrics = attester.metrics().get();
+        assert_eq!(metrics.generations, 3);
+        assert_eq!(metrics.cache_hits, 1);
+        assert_eq!(metrics.failures, 0);
+
+        // Disabled cache
+        let (attester, generations, _) = caching_attester(&EvidenceCacheOptions {
+            enable: false,
+            ..Default::default()
+        });
+        for _ in 0..2 {
+            attester.get_evidence(vec![1; 32]).await.unwrap();
+        }
+        assert_eq!(generations.load(Ordering::SeqCst), 2);
+    }
+
+    #[rstest::rstest]
+    #[case(1, 1)]
+    #[case(2, 2)]
+    #[case(0, 4)]
+    #[tokio::test]
+    async fn test_caching_attester_concurrency(
+        #[case] max_concurrent_generations: usize,
+        #[case] expected_max_running: usize,
+    ) {
+        let (attester, _, max_running) = caching_attester(&EvidenceCacheOptions {
+            max_concurrent_generations,
+            ..Default::default()
+        });
+
+        let results = tokio::join!(
+            attester.get_evidence(vec![0; 32]),
+            attester.get_evidence(vec![1; 32]),
+            attester.get_evidence(vec![2; 32]),
+            attester.get_evidence(vec![3; 32]),
+        );
+        assert!(results.0.is_ok() && results.1.is_ok() && results.2.is_ok() && results.3.is_ok());
+        assert_eq!(max_running.load(Ordering::SeqCst), expected_max_running);
+    }
+}
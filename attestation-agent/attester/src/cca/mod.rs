@@ -5,16 +5,27 @@
 
 use super::tsm_report::*;
 use super::{Attester, TeeEvidence};
+use crate::diagnostics::PlatformProbe;
 use anyhow::*;
 use serde::{Deserialize, Serialize};
 
 const CCA_CHALLENGE_SIZE: usize = 64;
 
 pub fn detect_platform() -> bool {
-    #[cfg(target_arch = "aarch64")]
-    return TsmReportPath::new(TsmReportProvider::Cca).is_ok();
-    #[cfg(not(target_arch = "aarch64"))]
-    return false;
+    probe_platform().is_detected()
+}
+
+/// Probe the TSM reports of the CCA provider.
+pub fn probe_platform() -> PlatformProbe {
+    let mut probe = PlatformProbe::default();
+    if !cfg!(target_arch = "aarch64") {
+        return probe.fail("CCA is only supported on aarch64");
+    }
+    probe.check_path(TSM_REPORT_PATH);
+    if let Err(e) = TsmReportPath::new(TsmReportProvider::Cca) {
+        return probe.fail(format!("No CCA TSM reports: {e}"));
+    }
+    probe
 }
 
 #[derive(Debug, Default)]
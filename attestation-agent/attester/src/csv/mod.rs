@@ -12,6 +12,7 @@
 
 const CSV_INCLUDE_CERT_CHAIN_ENV: &str = "CSV_INCLUDE_CERT_CHAIN_IN_ATTESTATION_REPORT";
 
+use crate::diagnostics::PlatformProbe;
 use crate::utils::read_eventlog;
 
 use super::{Attester, TeeEvidence};
@@ -26,9 +27,20 @@ use hyper_tls::HttpsConnector;
 use kbs_types::HashAlgorithm;
 use log::debug;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::PathBuf;
+
+const CSV_GUEST_DEVICE: &str = "/dev/csv-guest";
+
 pub fn detect_platform() -> bool {
-    Path::new("/dev/csv-guest").exists()
+    probe_platform().is_detected()
+}
+
+pub fn probe_platform() -> PlatformProbe {
+    let mut probe = PlatformProbe::default();
+    if !probe.check_path(CSV_GUEST_DEVICE) {
+        return probe.fail("The CSV guest device is missing");
+    }
+    probe
 }
 
 #[derive(Serialize, Deserialize)]
@@ -62,7 +74,17 @@ struct CsvEvidence {
 }
 
 #[derive(Debug, Default)]
-pub struct CsvAttester {}
+pub struct CsvAttester {
+    aael_path: Option<PathBuf>,
+}
+
+impl CsvAttester {
+    /// Create a CSV attester putting the AAEL at `aael_path` into the
+    /// evidence, see [`crate::utils::read_eventlog`].
+    pub fn new(aael_path: Option<PathBuf>) -> Self {
+        Self { aael_path }
+    }
+}
 
 async fn download_hskcek_from_kds(sn: &[u8]) -> Result<Vec<u8>> {
     let mut kds_url = String::from("https://cert.hygon.cn/hsk_cek?snumber=");
@@ -121,7 +143,7 @@ impl Attester for CsvAttester {
         };
 
         let cert_chain = CertificateChain { hsk_cek, pek };
-        let cc_eventlog = read_eventlog().await?;
+        let cc_eventlog = read_eventlog(self.aael_path.as_deref()).await?;
         let evidence = CsvEvidence {
             attestation_report,
             cert_chain,
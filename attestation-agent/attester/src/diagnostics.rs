@@ -0,0 +1,350 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Explain the platform detection of [`crate::detect_tee_type`] and
+//! [`crate::detect_attestable_devices`]: which attesters are compiled in,
+//! what each probe checked and why it did not detect its platform.
+
+use std::path::Path;
+
+use kbs_types::Tee;
+use serde::Serialize;
+
+/// A path checked by a probe, e.g. a device node, a sysfs or a configfs
+/// entry.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CheckedPath {
+    pub path: String,
+    pub exists: bool,
+}
+
+/// The outcome of probing a platform.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct PlatformProbe {
+    /// The paths checked, in order.
+    pub checked: Vec<CheckedPath>,
+
+    /// Why the platform was not detected. `None` if it was.
+    pub failure: Option<String>,
+}
+
+impl PlatformProbe {
+    /// Check whether the path exists, recording it.
+    pub fn check_path(&mut self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        let exists = path.exists();
+        self.checked.push(CheckedPath {
+            path: path.display().to_string(),
+            exists,
+        });
+        exists
+    }
+
+    /// Mark the platform as not detected.
+    pub fn fail(mut self, failure: impl Into<String>) -> Self {
+        self.failure = Some(failure.into());
+        self
+    }
+
+    pub fn is_detected(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// The diagnosis of a compiled in attester.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PlatformDiagnosis {
+    pub tee: Tee,
+
+    /// Whether the platform was probed. Primary platforms after the
+    /// detected one are not probed.
+    pub probed: bool,
+    pub detected: bool,
+    pub checked: Vec<CheckedPath>,
+    pub failure: Option<String>,
+}
+
+impl PlatformDiagnosis {
+    fn probed(tee: Tee, probe: PlatformProbe) -> Self {
+        Self {
+            tee,
+            probed: true,
+            detected: probe.is_detected(),
+            checked: probe.checked,
+            failure: probe.failure,
+        }
+    }
+
+    fn not_probed(tee: Tee) -> Self {
+        Self {
+            tee,
+            probed: false,
+            detected: false,
+            checked: Vec::new(),
+            failure: None,
+        }
+    }
+}
+
+/// Why the attesters of the guest were chosen.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PlatformReport {
+    /// The detected primary platform.
+    pub tee: Tee,
+
+    /// Whether no primary platform was detected, so the sample attester is
+    /// used.
+    pub sample_fallback: bool,
+
+    /// The primary platforms, in the order they are probed.
+    pub platforms: Vec<PlatformDiagnosis>,
+
+    /// The additional devices, in the order they are probed.
+    pub devices: Vec<PlatformDiagnosis>,
+
+    /// The attesters whose feature is not enabled.
+    pub not_compiled_in: Vec<Tee>,
+}
+
+/// Probes the platform of an attester.
+pub type Probe = fn() -> PlatformProbe;
+
+/// The compiled in primary platforms, in the order they are probed.
+#[allow(unused_mut, clippy::vec_init_then_push)]
+fn primary_platforms() -> Vec<(Tee, Probe)> {
+    let mut platforms: Vec<(Tee, Probe)> = Vec::new();
+
+    #[cfg(feature = "tdx-attester")]
+    platforms.push((Tee::Tdx, crate::tdx::probe_platform));
+
+    #[cfg(feature = "sgx-attester")]
+    platforms.push((Tee::Sgx, crate::sgx_dcap::probe_platform));
+
+    #[cfg(feature = "az-tdx-vtpm-attester")]
+    platforms.push((Tee::AzTdxVtpm, crate::az_tdx_vtpm::probe_platform));
+
+    #[cfg(feature = "az-snp-vtpm-attester")]
+    platforms.push((Tee::AzSnpVtpm, crate::az_snp_vtpm::probe_platform));
+
+    #[cfg(feature = "snp-attester")]
+    platforms.push((Tee::Snp, crate::snp::probe_platform));
+
+    #[cfg(feature = "csv-attester")]
+    platforms.push((Tee::Csv, crate::csv::probe_platform));
+
+    #[cfg(feature = "cca-attester")]
+    platforms.push((Tee::Cca, crate::cca::probe_platform));
+
+    #[cfg(feature = "se-attester")]
+    platforms.push((Tee::Se, crate::se::probe_platform));
+
+    // The TPM is probed last, as it is also present in TEEs
+    #[cfg(feature = "tpm-attester")]
+    platforms.push((Tee::Tpm, crate::tpm::probe_platform));
+
+    platforms
+}
+
+/// The compiled in additional devices, in the order they are probed. The
+/// TPM is only probed as a device if it is not the primary platform.
+#[allow(clippy::vec_init_then_push)]
+fn device_platforms() -> Vec<(Tee, Probe)> {
+    let mut devices: Vec<(Tee, Probe)> = Vec::new();
+
+    #[cfg(feature = "nvidia-attester")]
+    devices.push((Tee::Nvidia, crate::nvidia::probe_platform));
+
+    devices.push((Tee::SampleDevice, crate::sample_device::probe_platform));
+
+    #[cfg(feature = "hygon-dcu-attester")]
+    devices.push((Tee::HygonDcu, crate::hygon_dcu::probe_platform));
+
+    #[cfg(feature = "tpm-attester")]
+    devices.push((Tee::Tpm, crate::tpm::probe_platform));
+
+    devices
+}
+
+/// Probe the primary platforms until one is detected, returning it or
+/// [`Tee::Sample`] if none is.
+pub(crate) fn diagnose_platforms() -> (Tee, Vec<PlatformDiagnosis>) {
+    diagnose_platforms_with(primary_platforms())
+}
+
+fn diagnose_platforms_with(platforms: Vec<(Tee, Probe)>) -> (Tee, Vec<PlatformDiagnosis>) {
+    let mut detected = None;
+    let diagnoses = platforms
+        .into_iter()
+        .map(|(tee, probe)| {
+            if detected.is_some() {
+                return PlatformDiagnosis::not_probed(tee);
+            }
+            let diagnosis = PlatformDiagnosis::probed(tee, probe());
+            if diagnosis.detected {
+                detected = Some(tee);
+            }
+            diagnosis
+        })
+        .collect();
+
+    (detected.unwrap_or(Tee::Sample), diagnoses)
+}
+
+/// Probe the additional devices of the guest with the given primary
+/// platform.
+pub(crate) fn diagnose_devices(primary: Tee) -> Vec<PlatformDiagnosis> {
+    diagnose_devices_with(device_platforms(), primary)
+}
+
+fn diagnose_devices_with(devices: Vec<(Tee, Probe)>, primary: Tee) -> Vec<PlatformDiagnosis> {
+    devices
+        .into_iter()
+        .map(|(tee, probe)| match tee == primary {
+            true => PlatformDiagnosis::not_probed(tee),
+            false => PlatformDiagnosis::probed(tee, probe()),
+        })
+        .collect()
+}
+
+/// The attesters whose feature is not enabled.
+fn not_compiled_in() -> Vec<Tee> {
+    [
+        (Tee::Tdx, cfg!(feature = "tdx-attester")),
+        (Tee::Sgx, cfg!(feature = "sgx-attester")),
+        (Tee::AzTdxVtpm, cfg!(feature = "az-tdx-vtpm-attester")),
+        (Tee::AzSnpVtpm, cfg!(feature = "az-snp-vtpm-attester")),
+        (Tee::Snp, cfg!(feature = "snp-attester")),
+        (Tee::Csv, cfg!(feature = "csv-attester")),
+        (Tee::Cca, cfg!(feature = "cca-attester")),
+        (Tee::Se, cfg!(feature = "se-attester")),
+        (Tee::Tpm, cfg!(feature = "tpm-attester")),
+        (Tee::Nvidia, cfg!(feature = "nvidia-attester")),
+        (Tee::HygonDcu, cfg!(feature = "hygon-dcu-attester")),
+    ]
+    .into_iter()
+    .filter(|(_, compiled_in)| !compiled_in)
+    .map(|(tee, _)| tee)
+    .collect()
+}
+
+/// Probe all the compiled in attesters the way [`crate::detect_tee_type`]
+/// and [`crate::detect_attestable_devices`] do, reporting the outcome of
+/// each probe.
+pub fn platform_report() -> PlatformReport {
+    platform_report_with(primary_platforms(), device_platforms())
+}
+
+/// Report the outcome of the given probes of the primary platforms and of
+/// the additional devices, in the order they are given, instead of the
+/// compiled in ones.
+pub fn platform_report_with(
+    platforms: Vec<(Tee, Probe)>,
+    devices: Vec<(Tee, Probe)>,
+) -> PlatformReport {
+    let (tee, platforms) = diagnose_platforms_with(platforms);
+    PlatformReport {
+        tee,
+        sample_fallback: tee == Tee::Sample,
+        platforms,
+        devices: diagnose_devices_with(devices, tee),
+        not_compiled_in: not_compiled_in(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut probe = PlatformProbe::default();
+        assert!(probe.check_path(dir.path()));
+        assert!(!probe.check_path(dir.path().join("tdx_guest")));
+        assert!(probe.is_detected());
+
+        let probe = probe.fail("No TDX device");
+        assert!(!probe.is_detected());
+        assert_eq!(
+            probe.checked[1],
+            CheckedPath {
+                path: dir.path().join("tdx_guest").display().to_string(),
+                exists: false,
+            }
+        );
+    }
+
+    fn detected() -> PlatformProbe {
+        let mut probe = PlatformProbe::default();
+        probe.checked.push(CheckedPath {
+            path: "/dev/detected".into(),
+            exists: true,
+        });
+        probe
+    }
+
+    fn missing() -> PlatformProbe {
+        let mut probe = PlatformProbe::default();
+        probe.checked.push(CheckedPath {
+            path: "/dev/missing".into(),
+            exists: false,
+        });
+        probe.fail("No device")
+    }
+
+    fn must_not_probe() -> PlatformProbe {
+        panic!("probed after the detected platform");
+    }
+
+    #[test]
+    fn test_platform_report() {
+        // Only the platforms up to the detected one are probed
+        let report = platform_report_with(
+            vec![
+                (Tee::Tdx, missing as Probe),
+                (Tee::Snp, detected as Probe),
+                (Tee::Tpm, must_not_probe as Probe),
+            ],
+            vec![
+                (Tee::Nvidia, missing as Probe),
+                (Tee::SampleDevice, detected as Probe),
+            ],
+        );
+        assert_eq!(report.tee, Tee::Snp);
+        assert!(!report.sample_fallback);
+        let probed: Vec<_> = report.platforms.iter().map(|p| p.probed).collect();
+        assert_eq!(probed, [true, true, false]);
+        assert_eq!(report.platforms[0].failure.as_deref(), Some("No device"));
+        assert_eq!(report.platforms[0].checked[0].path, "/dev/missing");
+        assert!(report.platforms[1].detected);
+        assert!(!report.devices[0].detected && report.devices[1].detected);
+
+        // The primary platform is not probed again as a device
+        let report = platform_report_with(
+            vec![(Tee::Tdx, missing as Probe), (Tee::Tpm, detected as Probe)],
+            vec![
+                (Tee::Tpm, must_not_probe as Probe),
+                (Tee::SampleDevice, detected as Probe),
+            ],
+        );
+        assert_eq!(report.tee, Tee::Tpm);
+        assert!(!report.devices[0].probed);
+        assert!(report.devices[1].probed);
+
+        // The sample attester is used if no platform is detected
+        let report = platform_report_with(vec![(Tee::Tdx, missing as Probe)], Vec::new());
+        assert_eq!(report.tee, Tee::Sample);
+        assert!(report.sample_fallback);
+        assert!(report.platforms[0].probed);
+    }
+
+    #[test]
+    fn test_compiled_in_platforms() {
+        let not_compiled_in = not_compiled_in();
+        for (tee, _) in primary_platforms().into_iter().chain(device_platforms()) {
+            assert!(!not_compiled_in.contains(&tee));
+        }
+    }
+}
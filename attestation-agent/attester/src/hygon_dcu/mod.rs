@@ -4,22 +4,30 @@
 //
 
 use super::{Attester, TeeEvidence};
+use crate::diagnostics::PlatformProbe;
 use anyhow::{Context, Result};
 use csv_rs::api::dcu::{AttestationReport, DcuDevice};
 use log::warn;
 use serde::{Deserialize, Serialize};
-use std::{cmp::min, fs, path::Path};
+use std::{cmp::min, fs};
 
+const DCU_DEVICE: &str = "/dev/mkfd";
 const DCU_NODES_DIR: &str = "/sys/devices/virtual/kfd/kfd/topology/nodes";
 
 pub fn detect_platform() -> bool {
-    if !Path::new("/dev/mkfd").exists() {
-        return false;
+    probe_platform().is_detected()
+}
+
+pub fn probe_platform() -> PlatformProbe {
+    let mut probe = PlatformProbe::default();
+    if !probe.check_path(DCU_DEVICE) {
+        return probe.fail("The DCU device is missing");
     }
 
+    probe.check_path(DCU_NODES_DIR);
     let std::result::Result::Ok(entries) = fs::read_dir(DCU_NODES_DIR) else {
         warn!("Cannot read DCU nodes directory: {DCU_NODES_DIR}");
-        return false;
+        return probe.fail(format!("Cannot read DCU nodes directory: {DCU_NODES_DIR}"));
     };
 
     for entry in entries {
@@ -32,11 +40,11 @@ pub fn detect_platform() -> bool {
         let file_name_str = file_name.to_string_lossy();
 
         if file_name_str != "." && file_name_str != ".." {
-            return true;
+            return probe;
         }
     }
 
-    false
+    probe.fail("No DCU nodes found")
 }
 
 #[derive(Serialize, Deserialize)]
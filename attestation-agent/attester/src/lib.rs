@@ -5,7 +5,11 @@
 
 use anyhow::*;
 use kbs_types::{HashAlgorithm, Tee};
+use serde::Deserialize;
+use std::path::PathBuf;
 
+pub mod cache;
+pub mod diagnostics;
 pub mod sample;
 pub mod sample_device;
 pub mod utils;
@@ -46,44 +50,138 @@ pub mod tpm;
 #[cfg(feature = "nvidia-attester")]
 pub mod nvidia;
 
+#[cfg(feature = "verify")]
+pub mod verify;
+
 pub type BoxedAttester = Box<dyn Attester + Send + Sync>;
 
+/// A PCR bank of the TPM.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TpmPcrBank {
+    Sha1,
+    Sha256,
+    Sha384,
+}
+
+/// Options of the TPM attester.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TpmOptions {
+    /// Path to the TPM device, e.g. `/dev/tpmrm0`. Detected if not given.
+    pub device: Option<String>,
+
+    /// Indexes of the PCRs to quote in the banks after the first one. All
+    /// the 24 PCRs if not given. The first bank always quotes all the 24
+    /// PCRs, as their values are the index-aligned `pcrs` of the evidence.
+    pub pcrs: Option<Vec<u8>>,
+
+    /// PCR banks to quote and extend, e.g. `["sha256", "sha384"]`.
+    /// `sha256` if not given.
+    pub pcr_banks: Option<Vec<TpmPcrBank>>,
+
+    /// Index of the PCR the initdata digest is extended to. PCR 8 if not
+    /// given.
+    pub init_data_pcr: Option<u64>,
+
+    /// Create an AK under the EK and persist it at the AK handle if no key
+    /// is persisted there. Otherwise a missing AK is an error. The platform
+    /// detection never creates one.
+    pub provision_ak: bool,
+}
+
+/// How the TDX attester gets the quote.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TdxQuoteProvider {
+    /// Linux TSM reports in configfs.
+    Tsm,
+
+    /// The legacy `/dev/tdx_guest` ioctl.
+    Ioctl,
+}
+
+/// Options of the TDX attester.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TdxOptions {
+    /// If not given, TSM reports are used if available, otherwise the ioctl.
+    pub quote_provider: Option<TdxQuoteProvider>,
+}
+
+/// Options of the NVIDIA attester.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct NvidiaOptions {
+    /// Indexes of the devices to collect evidence from. All the devices are
+    /// used if not given.
+    pub device_indexes: Option<Vec<u32>>,
+}
+
+/// Per-attester options. An attester which is not compiled in ignores its
+/// options.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AttesterOptions {
+    pub tpm: TpmOptions,
+    pub tdx: TdxOptions,
+    pub nvidia: NvidiaOptions,
+
+    /// Path of the AAEL the TDX and CSV attesters put into the evidence. If
+    /// not given, `AAEL_PATH` or [`utils::DEFAULT_AAEL_PATH`] is used. This
+    /// is set by AA from its eventlog configuration.
+    #[serde(skip)]
+    pub aael_path: Option<PathBuf>,
+}
+
 impl TryFrom<Tee> for BoxedAttester {
     type Error = anyhow::Error;
 
     fn try_from(value: Tee) -> Result<Self> {
-        let attester: Box<dyn Attester + Send + Sync> = match value {
-            Tee::Sample => Box::<sample::SampleAttester>::default(),
-            Tee::SampleDevice => Box::<sample_device::SampleDeviceAttester>::default(),
-            #[cfg(feature = "tdx-attester")]
-            Tee::Tdx => Box::<tdx::TdxAttester>::default(),
-            #[cfg(feature = "sgx-attester")]
-            Tee::Sgx => Box::<sgx_dcap::SgxDcapAttester>::default(),
-            #[cfg(feature = "az-snp-vtpm-attester")]
-            Tee::AzSnpVtpm => Box::<az_snp_vtpm::AzSnpVtpmAttester>::default(),
-            #[cfg(feature = "az-tdx-vtpm-attester")]
-            Tee::AzTdxVtpm => Box::<az_tdx_vtpm::AzTdxVtpmAttester>::default(),
-            #[cfg(feature = "cca-attester")]
-            Tee::Cca => Box::<cca::CcaAttester>::default(),
-            #[cfg(feature = "snp-attester")]
-            Tee::Snp => Box::<snp::SnpAttester>::default(),
-            #[cfg(feature = "csv-attester")]
-            Tee::Csv => Box::<csv::CsvAttester>::default(),
-            #[cfg(feature = "hygon-dcu-attester")]
-            Tee::HygonDcu => Box::<hygon_dcu::DcuAttester>::default(),
-            #[cfg(feature = "se-attester")]
-            Tee::Se => Box::<se::SeAttester>::default(),
-            #[cfg(feature = "tpm-attester")]
-            Tee::Tpm => Box::new(tpm::TpmAttester::new()?),
-            #[cfg(feature = "nvidia-attester")]
-            Tee::Nvidia => Box::<nvidia::NvAttester>::default(),
-            _ => bail!("TEE is not supported!"),
-        };
-
-        Ok(attester)
+        new_attester(value, &AttesterOptions::default())
     }
 }
 
+/// Create the attester of the given TEE with the given options. An error is
+/// returned if the attester is not compiled in.
+#[allow(unused_variables)]
+pub fn new_attester(tee: Tee, options: &AttesterOptions) -> Result<BoxedAttester> {
+    let attester: BoxedAttester = match tee {
+        Tee::Sample => Box::<sample::SampleAttester>::default(),
+        Tee::SampleDevice => Box::<sample_device::SampleDeviceAttester>::default(),
+        #[cfg(feature = "tdx-attester")]
+        Tee::Tdx => Box::new(
+            tdx::TdxAttester::new(options.tdx.quote_provider)
+                .with_aael_path(options.aael_path.clone()),
+        ),
+        #[cfg(feature = "sgx-attester")]
+        Tee::Sgx => Box::<sgx_dcap::SgxDcapAttester>::default(),
+        #[cfg(feature = "az-snp-vtpm-attester")]
+        Tee::AzSnpVtpm => Box::<az_snp_vtpm::AzSnpVtpmAttester>::default(),
+        #[cfg(feature = "az-tdx-vtpm-attester")]
+        Tee::AzTdxVtpm => Box::<az_tdx_vtpm::AzTdxVtpmAttester>::default(),
+        #[cfg(feature = "cca-attester")]
+        Tee::Cca => Box::<cca::CcaAttester>::default(),
+        #[cfg(feature = "snp-attester")]
+        Tee::Snp => Box::<snp::SnpAttester>::default(),
+        #[cfg(feature = "csv-attester")]
+        Tee::Csv => Box::new(csv::CsvAttester::new(options.aael_path.clone())),
+        #[cfg(feature = "hygon-dcu-attester")]
+        Tee::HygonDcu => Box::<hygon_dcu::DcuAttester>::default(),
+        #[cfg(feature = "se-attester")]
+        Tee::Se => Box::<se::SeAttester>::default(),
+        #[cfg(feature = "tpm-attester")]
+        Tee::Tpm => Box::new(tpm::TpmAttester::with_options(&options.tpm)?),
+        #[cfg(feature = "nvidia-attester")]
+        Tee::Nvidia => Box::new(nvidia::NvAttester::new(
+            options.nvidia.device_indexes.clone(),
+        )),
+        _ => bail!("{tee:?} attester is not compiled in, enable its feature of the attester crate"),
+    };
+
+    Ok(attester)
+}
+
 pub enum InitDataResult {
     Ok,
     Unsupported,
@@ -119,6 +217,15 @@ pub trait Attester {
         Ok(InitDataResult::Unsupported)
     }
 
+    /// Derive a 32 bytes key from a secret of the TEE hardware, bound to the
+    /// measurement of the guest. The same key is returned for the same
+    /// `purpose` on every boot of the same guest on the same platform, and
+    /// it is never exposed outside of the TEE, so it can be used to seal
+    /// data to the guest.
+    async fn derive_sealing_key(&self, _purpose: &str) -> Result<Vec<u8>> {
+        bail!("The Attester does not support deriving sealing keys")
+    }
+
     /// This function is used to get the runtime measurement registry value of
     /// the given PCR register index. Different platforms have different mapping
     /// relationship between PCR and platform RTMR.
@@ -147,91 +254,38 @@ pub trait Attester {
 
 // Detect which TEE platform the KBC running environment is.
 pub fn detect_tee_type() -> Tee {
-    #[cfg(feature = "tdx-attester")]
-    if tdx::detect_platform() {
-        return Tee::Tdx;
-    }
-
-    #[cfg(feature = "sgx-attester")]
-    if sgx_dcap::detect_platform() {
-        return Tee::Sgx;
-    }
-
-    #[cfg(feature = "az-tdx-vtpm-attester")]
-    if az_tdx_vtpm::detect_platform() {
-        return Tee::AzTdxVtpm;
-    }
-
-    #[cfg(feature = "az-snp-vtpm-attester")]
-    if az_snp_vtpm::detect_platform() {
-        return Tee::AzSnpVtpm;
-    }
-
-    #[cfg(feature = "snp-attester")]
-    if snp::detect_platform() {
-        return Tee::Snp;
-    }
-
-    #[cfg(feature = "csv-attester")]
-    if csv::detect_platform() {
-        return Tee::Csv;
-    }
-
-    #[cfg(feature = "cca-attester")]
-    if cca::detect_platform() {
-        return Tee::Cca;
-    }
-
-    #[cfg(feature = "se-attester")]
-    if se::detect_platform() {
-        return Tee::Se;
-    }
-
-    #[cfg(feature = "tpm-attester")]
-    if tpm::detect_platform() {
-        return Tee::Tpm;
-    }
-
-    log::warn!(
-        "No TEE platform detected. Sample Attester will be used.
+    let (tee, _) = diagnostics::diagnose_platforms();
+    if tee == Tee::Sample {
+        log::warn!(
+            "No TEE platform detected. Sample Attester will be used.
          If you are expecting to collect evidence from inside a confidential guest,
          either your guest is not configured correctly, or your attestation client
          was not built with support for the platform.
 
          Verify that your guest is a confidential guest and that your client
          (such as kbs-client or attestation-agent) was built with the feature
-         corresponding to your platform.
+         corresponding to your platform. `evidence_getter diagnose` reports
+         what was checked for each platform and why it was not detected.
 
          Attestation will continue using the fallback sample attester."
-    );
-    Tee::Sample
+        );
+    }
+    tee
 }
 
 /// Get any additional TEEs that might be connected to the guest,
 /// such as a confidential device.
 pub fn detect_attestable_devices() -> Vec<Tee> {
-    let mut additional_devices = vec![];
-
-    #[cfg(feature = "nvidia-attester")]
-    if nvidia::detect_platform() {
-        additional_devices.push(Tee::Nvidia);
-    }
-
-    if sample_device::detect_platform() {
-        additional_devices.push(Tee::SampleDevice);
-    }
-
-    #[cfg(feature = "hygon-dcu-attester")]
-    if hygon_dcu::detect_platform() {
-        additional_devices.push(Tee::HygonDcu);
-    }
-
-    // This relies on the TPM check being last in detect_tee_type()
-    // so it only adds the TPM as an additional device if it is not the primary one.
-    #[cfg(feature = "tpm-attester")]
-    if detect_tee_type() != Tee::Tpm && tpm::detect_platform() {
+    let devices = diagnostics::diagnose_devices(detect_tee_type());
+    let additional_devices: Vec<Tee> = devices
+        .into_iter()
+        .filter(|device| device.detected)
+        .map(|device| device.tee)
+        .collect();
+
+    // The TPM is only probed as an additional device if it is not the primary one.
+    if additional_devices.contains(&Tee::Tpm) {
         log::warn!("The TPM device was detected as an additional device, but please note that it is not bound to the TEE, so there may be security risks.");
-        additional_devices.push(Tee::Tpm);
     }
 
     additional_devices
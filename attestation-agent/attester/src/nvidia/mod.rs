@@ -4,6 +4,7 @@
 //
 
 use super::{Attester, TeeEvidence};
+use crate::diagnostics::PlatformProbe;
 use anyhow::{bail, Context, Result};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use nvml_wrapper::{enums::device::DeviceArchitecture, Nvml};
@@ -12,15 +13,28 @@ use serde::Serialize;
 const NVIDIA_NONCE_SIZE: usize = 32;
 
 pub fn detect_platform() -> bool {
-    // Return true iff one GPU is found and it has CC mode set.
-    match Nvml::init() {
-        Ok(nvml) => {
-            nvml.device_count().is_ok_and(|count| count == 1)
-                && nvml
-                    .device_by_index(0)
-                    .is_ok_and(|device| device.is_cc_enabled().unwrap_or_default())
-        }
-        Err(_) => false,
+    probe_platform().is_detected()
+}
+
+pub fn probe_platform() -> PlatformProbe {
+    // Detected iff one GPU is found and it has CC mode set.
+    let probe = PlatformProbe::default();
+    let nvml = match Nvml::init() {
+        Ok(nvml) => nvml,
+        Err(e) => return probe.fail(format!("Failed to initialize NVML: {e}")),
+    };
+    match nvml.device_count() {
+        Ok(1) => {}
+        Ok(count) => return probe.fail(format!("{count} GPUs found, expected one")),
+        Err(e) => return probe.fail(format!("Failed to count the GPUs: {e}")),
+    }
+    match nvml
+        .device_by_index(0)
+        .and_then(|device| device.is_cc_enabled())
+    {
+        Ok(true) => probe,
+        Ok(false) => probe.fail("CC mode is not enabled on the GPU"),
+        Err(e) => probe.fail(format!("Failed to get the CC mode of the GPU: {e}")),
     }
 }
 
@@ -41,7 +55,16 @@ struct NvDeviceEvidence {
 }
 
 #[derive(Debug, Default)]
-pub struct NvAttester {}
+pub struct NvAttester {
+    /// Indexes of the devices to collect evidence from, all if `None`.
+    device_indexes: Option<Vec<u32>>,
+}
+
+impl NvAttester {
+    pub fn new(device_indexes: Option<Vec<u32>>) -> Self {
+        Self { device_indexes }
+    }
+}
 
 #[async_trait::async_trait]
 impl Attester for NvAttester {
@@ -49,7 +72,10 @@ impl Attester for NvAttester {
     /// report_data bytes. report_data shorter than 32 bytes is zero padded.
     async fn get_evidence(&self, mut report_data: Vec<u8>) -> Result<TeeEvidence> {
         let nvml = Nvml::init()?;
-        let devices = nvml.device_count()?;
+        let devices = match &self.device_indexes {
+            Some(indexes) => indexes.clone(),
+            None => (0..nvml.device_count()?).collect(),
+        };
 
         let mut device_evidence_list = vec![];
 
@@ -59,7 +85,7 @@ impl Attester for NvAttester {
 
         let nonce: [u8; NVIDIA_NONCE_SIZE] = report_data[0..NVIDIA_NONCE_SIZE].try_into()?;
 
-        for index in 0..devices {
+        for index in devices {
             let device = nvml.device_by_index(index)?;
 
             let report = device
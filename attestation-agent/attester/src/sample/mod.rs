@@ -4,9 +4,32 @@
 //
 
 use super::{Attester, TeeEvidence};
+use crate::utils::pad;
 use anyhow::*;
 use base64::Engine;
+use kbs_types::HashAlgorithm;
+use nix::fcntl::{Flock, FlockArg};
 use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    ffi::OsString,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// If set, the emulated runtime measurement registers are kept in this file,
+/// so they are shared by all processes using the sample attester. Accesses
+/// are serialized with `flock(2)` on the file with a `.lock` suffix, and the
+/// file is replaced atomically on every extension.
+pub const SAMPLE_STATE_PATH_ENV: &str = "SAMPLE_ATTESTER_STATE_PATH";
+
+/// Number of emulated runtime measurement registers, like TDX RTMR0-3.
+const RTMR_NUM: usize = 4;
+
+/// Size of an emulated runtime measurement register (SHA-384).
+const RTMR_SIZE: usize = 48;
 
 // Sample attester is always supported
 pub fn detect_platform() -> bool {
@@ -18,10 +41,134 @@ pub fn detect_platform() -> bool {
 struct SampleQuote {
     svn: String,
     report_data: String,
+
+    /// Hex encoded values of the emulated runtime measurement registers.
+    #[serde(default)]
+    rtmrs: Vec<String>,
+}
+
+/// Content of the state file.
+#[derive(Serialize, Deserialize, Debug)]
+struct SampleState {
+    rtmrs: Vec<String>,
+}
+
+/// The sample attester emulates the runtime measurement registers in software.
+/// The registers are mapped from PCRs and extended in the same way as TDX RTMRs.
+#[derive(Debug)]
+pub struct SampleAttester {
+    rtmrs: Mutex<Vec<Vec<u8>>>,
+    state_file: Option<PathBuf>,
+}
+
+impl Default for SampleAttester {
+    fn default() -> Self {
+        Self {
+            rtmrs: Mutex::new(vec![vec![0; RTMR_SIZE]; RTMR_NUM]),
+            state_file: env::var(SAMPLE_STATE_PATH_ENV).ok().map(PathBuf::from),
+        }
+    }
+}
+
+impl SampleAttester {
+    /// Create a sample attester keeping the emulated registers in the given
+    /// state file. The registers start with zeros if the file does not exist.
+    pub fn with_state_file(state_file: impl Into<PathBuf>) -> Self {
+        Self {
+            state_file: Some(state_file.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Lock the state file against other processes, if one is configured.
+    /// The lock is taken on a separate file, as the state file is replaced
+    /// on every save. It is released when dropped.
+    fn lock(&self, arg: FlockArg) -> Result<Option<Flock<File>>> {
+        let Some(state_file) = &self.state_file else {
+            return Ok(None);
+        };
+
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(suffixed(state_file, ".lock"))
+            .context("open sample attester state lock file")?;
+        let lock = Flock::lock(file, arg)
+            .map_err(|(_, errno)| anyhow!("lock sample attester state file: {errno}"))?;
+        Ok(Some(lock))
+    }
+
+    /// Read the register values from the state file. `None` is returned if
+    /// no state file is configured or it has not been created yet, in which
+    /// case the in-memory registers are used.
+    fn load(&self) -> Result<Option<Vec<Vec<u8>>>> {
+        let Some(state_file) = &self.state_file else {
+            return Ok(None);
+        };
+
+        if !state_file.exists() {
+            return Ok(None);
+        }
+
+        let state = std::fs::read(state_file).context("read sample attester state file")?;
+        let state: SampleState =
+            serde_json::from_slice(&state).context("parse sample attester state file")?;
+        if state.rtmrs.len() != RTMR_NUM {
+            bail!("invalid sample attester state: expect {RTMR_NUM} registers");
+        }
+
+        let mut loaded = Vec::with_capacity(RTMR_NUM);
+        for rtmr in state.rtmrs {
+            let rtmr = hex::decode(rtmr).context("decode sample attester register")?;
+            if rtmr.len() != RTMR_SIZE {
+                bail!("invalid sample attester state: register must be {RTMR_SIZE} bytes");
+            }
+            loaded.push(rtmr);
+        }
+
+        Ok(Some(loaded))
+    }
+
+    /// Write the register values to a temporary file and rename it over the
+    /// state file, so that readers never see a partially written state. The
+    /// caller must hold the exclusive lock.
+    fn save(&self, rtmrs: &[Vec<u8>]) -> Result<()> {
+        let Some(state_file) = &self.state_file else {
+            return Ok(());
+        };
+
+        let state = SampleState {
+            rtmrs: rtmrs.iter().map(hex::encode).collect(),
+        };
+        let tmp_file = suffixed(state_file, ".tmp");
+        let mut file = File::create(&tmp_file).context("create sample attester state file")?;
+        file.write_all(&serde_json::to_vec(&state)?)
+            .context("write sample attester state file")?;
+        file.sync_all()
+            .context("flush sample attester state file")?;
+        std::fs::rename(&tmp_file, state_file).context("replace sample attester state file")
+    }
+
+    fn rtmrs(&self) -> Result<Vec<Vec<u8>>> {
+        let mut rtmrs = self
+            .rtmrs
+            .lock()
+            .map_err(|_| anyhow!("sample attester registers poisoned"))?;
+        let _lock = self.lock(FlockArg::LockShared)?;
+        if let Some(loaded) = self.load()? {
+            *rtmrs = loaded;
+        }
+        Ok(rtmrs.clone())
+    }
 }
 
-#[derive(Debug, Default)]
-pub struct SampleAttester {}
+/// `path` with `suffix` appended to its file name.
+fn suffixed(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(suffix);
+    path.into()
+}
 
 #[async_trait::async_trait]
 impl Attester for SampleAttester {
@@ -29,12 +176,150 @@ impl Attester for SampleAttester {
         let evidence = SampleQuote {
             svn: "1".to_string(),
             report_data: base64::engine::general_purpose::STANDARD.encode(report_data),
+            rtmrs: self.rtmrs()?.iter().map(hex::encode).collect(),
         };
 
         serde_json::to_value(&evidence).context("Serialize sample evidence failed")
     }
 
     fn supports_runtime_measurement(&self) -> bool {
-        false
+        true
+    }
+
+    async fn extend_runtime_measurement(
+        &self,
+        event_digest: Vec<u8>,
+        register_index: u64,
+    ) -> Result<()> {
+        let rtmr_index = (self.pcr_to_ccmr(register_index) - 1) as usize;
+        let extend_data: [u8; RTMR_SIZE] = pad(&event_digest);
+
+        log::debug!(
+            "Sample Attester: extend RTMR{rtmr_index}: {}",
+            hex::encode(extend_data)
+        );
+
+        let mut rtmrs = self
+            .rtmrs
+            .lock()
+            .map_err(|_| anyhow!("sample attester registers poisoned"))?;
+        let _lock = self.lock(FlockArg::LockExclusive)?;
+        if let Some(loaded) = self.load()? {
+            *rtmrs = loaded;
+        }
+
+        let rtmr = &mut rtmrs[rtmr_index];
+        rtmr.extend_from_slice(&extend_data);
+        *rtmr = self.ccel_hash_algorithm().digest(rtmr);
+
+        self.save(&rtmrs)
+    }
+
+    async fn get_runtime_measurement(&self, pcr_index: u64) -> Result<Vec<u8>> {
+        let rtmr_index = (self.pcr_to_ccmr(pcr_index) - 1) as usize;
+        Ok(self.rtmrs()?.swap_remove(rtmr_index))
+    }
+
+    fn pcr_to_ccmr(&self, pcr_index: u64) -> u64 {
+        // Follow the mapping of TDX, see `TdxAttester::pcr_to_ccmr`
+        match pcr_index {
+            1 | 7 => 1,
+            2..=6 => 2,
+            8..=15 => 3,
+            _ => 4,
+        }
+    }
+
+    fn ccel_hash_algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Sha384
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sample_runtime_measurement() {
+        let attester = SampleAttester::default();
+        assert_eq!(
+            attester.get_runtime_measurement(17).await.unwrap(),
+            vec![0; RTMR_SIZE]
+        );
+
+        attester
+            .extend_runtime_measurement(vec![1; RTMR_SIZE], 17)
+            .await
+            .unwrap();
+
+        let mut expected = vec![0; RTMR_SIZE];
+        expected.extend_from_slice(&[1; RTMR_SIZE]);
+        let expected = HashAlgorithm::Sha384.digest(&expected);
+        assert_eq!(
+            attester.get_runtime_measurement(17).await.unwrap(),
+            expected
+        );
+        assert_eq!(
+            attester.get_runtime_measurement(8).await.unwrap(),
+            vec![0; RTMR_SIZE]
+        );
+
+        let evidence = attester.get_evidence(vec![0; 64]).await.unwrap();
+        let quote: SampleQuote = serde_json::from_value(evidence).unwrap();
+        assert_eq!(quote.rtmrs.len(), RTMR_NUM);
+        assert_eq!(quote.rtmrs[3], hex::encode(expected));
+    }
+
+    #[tokio::test]
+    async fn test_sample_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("state.json");
+
+        let attester = SampleAttester::with_state_file(&state_file);
+        attester
+            .extend_runtime_measurement(vec![1; RTMR_SIZE], 8)
+            .await
+            .unwrap();
+        let rtmr = attester.get_runtime_measurement(8).await.unwrap();
+
+        // Another instance shares the registers through the state file
+        let another = SampleAttester::with_state_file(&state_file);
+        assert_eq!(another.get_runtime_measurement(8).await.unwrap(), rtmr);
+
+        std::fs::write(&state_file, r#"{"rtmrs": ["00"]}"#).unwrap();
+        assert!(another.get_runtime_measurement(8).await.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_sample_state_file_concurrent_extend() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("state.json");
+
+        // Each instance stands for a process sharing the state file
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let state_file = state_file.clone();
+                tokio::spawn(async move {
+                    let attester = SampleAttester::with_state_file(state_file);
+                    for _ in 0..25 {
+                        attester
+                            .extend_runtime_measurement(vec![1; RTMR_SIZE], 8)
+                            .await
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let mut expected = vec![0; RTMR_SIZE];
+        for _ in 0..100 {
+            expected.extend_from_slice(&[1; RTMR_SIZE]);
+            expected = HashAlgorithm::Sha384.digest(&expected);
+        }
+        let attester = SampleAttester::with_state_file(&state_file);
+        assert_eq!(attester.get_runtime_measurement(8).await.unwrap(), expected);
     }
 }
@@ -4,15 +4,26 @@
 //
 
 use super::{Attester, TeeEvidence};
+use crate::diagnostics::PlatformProbe;
 use anyhow::*;
 use base64::Engine;
 use serde::{Deserialize, Serialize};
 use std::env;
 
+const ENABLE_SAMPLE_DEVICE_ENV: &str = "ENABLE_SAMPLE_DEVICE";
+
 // The sample device attester can be enabled
 // vi an environment variable.
 pub fn detect_platform() -> bool {
-    env::var("ENABLE_SAMPLE_DEVICE").is_ok()
+    probe_platform().is_detected()
+}
+
+pub fn probe_platform() -> PlatformProbe {
+    let probe = PlatformProbe::default();
+    if env::var(ENABLE_SAMPLE_DEVICE_ENV).is_err() {
+        return probe.fail(format!("{ENABLE_SAMPLE_DEVICE_ENV} is not set"));
+    }
+    probe
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -4,6 +4,7 @@
 //
 
 use super::{Attester, TeeEvidence};
+use crate::diagnostics::PlatformProbe;
 use anyhow::*;
 use log::debug;
 use pv::{
@@ -17,8 +18,20 @@ use std::fs;
 
 const DIGEST_FILE: &str = "/run/peerpod/initdata.digest";
 
+/// The sysfs entry read by `misc::pv_guest_bit_set()`.
+const PV_GUEST_PATH: &str = "/sys/firmware/uv/prot_virt_guest";
+
 pub fn detect_platform() -> bool {
-    misc::pv_guest_bit_set()
+    probe_platform().is_detected()
+}
+
+pub fn probe_platform() -> PlatformProbe {
+    let mut probe = PlatformProbe::default();
+    probe.check_path(PV_GUEST_PATH);
+    if !misc::pv_guest_bit_set() {
+        return probe.fail("The protected virtualization guest bit is not set");
+    }
+    probe
 }
 
 #[repr(C)]
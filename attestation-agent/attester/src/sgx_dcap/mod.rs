@@ -4,12 +4,14 @@
 //
 
 use super::{Attester, TeeEvidence};
+use crate::diagnostics::PlatformProbe;
 use anyhow::{bail, Context, Result};
 use base64::Engine;
 use occlum_dcap::{sgx_report_data_t, DcapQuote};
 use serde::{Deserialize, Serialize};
 
 const OCCLUM_ENV: &str = "OCCLUM";
+const GRAMINE_ATTESTATION_TYPE: &str = "/dev/attestation/attestation_type";
 
 enum SgxLibOsType {
     Invalid,
@@ -22,17 +24,25 @@ fn get_libos_type() -> SgxLibOsType {
         return SgxLibOsType::Occlum;
     }
 
-    match std::fs::read_to_string("/dev/attestation/attestation_type") {
+    match std::fs::read_to_string(GRAMINE_ATTESTATION_TYPE) {
         Ok(d) if d == "dcap" => SgxLibOsType::Gramine,
         _ => SgxLibOsType::Invalid,
     }
 }
 
 pub fn detect_platform() -> bool {
+    probe_platform().is_detected()
+}
+
+pub fn probe_platform() -> PlatformProbe {
+    let mut probe = PlatformProbe::default();
+    probe.check_path(GRAMINE_ATTESTATION_TYPE);
     match get_libos_type() {
-        SgxLibOsType::Invalid => false,
-        SgxLibOsType::Occlum => true,
-        SgxLibOsType::Gramine => true,
+        SgxLibOsType::Invalid => probe.fail(format!(
+            "Neither in Occlum ({OCCLUM_ENV} is not set) nor in Gramine with DCAP attestation"
+        )),
+        SgxLibOsType::Occlum => probe,
+        SgxLibOsType::Gramine => probe,
     }
 }
 
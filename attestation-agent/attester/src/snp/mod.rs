@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
+use crate::diagnostics::PlatformProbe;
 use crate::utils::pad;
 use crate::InitDataResult;
 
@@ -10,14 +11,25 @@ use super::{Attester, TeeEvidence};
 use anyhow::*;
 use serde::{Deserialize, Serialize};
 use sev::firmware::guest::AttestationReport;
-use sev::firmware::guest::Firmware;
+use sev::firmware::guest::{DerivedKey, Firmware, GuestFieldSelect};
 use sev::firmware::host::CertTableEntry;
-use std::path::Path;
+use sha2::{Digest, Sha256};
 
 mod hostdata;
 
+const SEV_GUEST_DEVICE: &str = "/sys/devices/platform/sev-guest";
+
 pub fn detect_platform() -> bool {
-    Path::new("/sys/devices/platform/sev-guest").exists()
+    probe_platform().is_detected()
+}
+
+pub fn probe_platform() -> PlatformProbe {
+    let mut probe = PlatformProbe::default();
+    if !probe.check_path(SEV_GUEST_DEVICE) {
+        return probe
+            .fail("The SEV guest platform device is missing, is the sev-guest driver loaded?");
+    }
+    probe
 }
 
 #[derive(Serialize, Deserialize)]
@@ -63,4 +75,25 @@ impl Attester for SnpAttester {
 
         Ok(InitDataResult::Ok)
     }
+
+    async fn derive_sealing_key(&self, purpose: &str) -> Result<Vec<u8>> {
+        // Mix the guest policy and the launch measurement into the key
+        // derived from the VCEK, so that another guest cannot derive it.
+        let mut field_select = GuestFieldSelect::default();
+        field_select.set_guest_policy(1);
+        field_select.set_measurement(1);
+        let request = DerivedKey::new(false, field_select, 0, 0, 0);
+
+        let mut firmware = Firmware::open()?;
+        let key = firmware
+            .get_derived_key(None, request)
+            .context("Failed to get derived key")?;
+
+        // Bind the key to its purpose, so that keys of different purposes
+        // are independent.
+        let mut hasher = Sha256::new();
+        hasher.update(key);
+        hasher.update(purpose.as_bytes());
+        Ok(hasher.finalize().to_vec())
+    }
 }
@@ -5,8 +5,9 @@
 
 use super::tsm_report::*;
 use super::{Attester, TeeEvidence};
+use crate::diagnostics::PlatformProbe;
 use crate::utils::{pad, read_eventlog};
-use crate::InitDataResult;
+use crate::{InitDataResult, TdxQuoteProvider};
 use anyhow::*;
 use base64::Engine;
 use iocuddle::{Group, Ioctl, WriteRead};
@@ -14,7 +15,7 @@ use kbs_types::HashAlgorithm;
 use report::TdReport;
 use scroll::Pread;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 mod report;
 mod rtmr;
@@ -25,7 +26,22 @@ const TDX_RTMR_PATH: &str = "/sys/devices/virtual/misc/tdx_guest/measurements";
 const TDX_GUEST_IOCTL: &str = "/dev/tdx_guest";
 
 pub fn detect_platform() -> bool {
-    TsmReportPath::new(TsmReportProvider::Tdx).is_ok() || Path::new(TDX_GUEST_IOCTL).exists()
+    probe_platform().is_detected()
+}
+
+/// Probe the TSM reports of the TDX provider, then the legacy ioctl device.
+pub fn probe_platform() -> PlatformProbe {
+    let mut probe = PlatformProbe::default();
+    probe.check_path(TSM_REPORT_PATH);
+    let Err(tsm) = TsmReportPath::new(TsmReportProvider::Tdx) else {
+        return probe;
+    };
+    if probe.check_path(TDX_GUEST_IOCTL) {
+        return probe;
+    }
+    probe.fail(format!(
+        "No TDX TSM reports ({tsm}) and no {TDX_GUEST_IOCTL} device"
+    ))
 }
 
 #[allow(unused_variables)]
@@ -77,7 +93,10 @@ struct TdxEvidence {
 }
 
 #[derive(Debug, Default)]
-pub struct TdxAttester {}
+pub struct TdxAttester {
+    quote_provider: Option<TdxQuoteProvider>,
+    aael_path: Option<PathBuf>,
+}
 
 #[repr(C)]
 struct TdxReportReq {
@@ -99,6 +118,42 @@ const TDX: Group = Group::new(b'T');
 const TDX_CMD_GET_REPORT0: Ioctl<WriteRead, &TdxReportReq> = unsafe { TDX.write_read(0x1) };
 
 impl TdxAttester {
+    /// Create a TDX attester getting quotes with the given provider. If not
+    /// given, TSM reports are preferred with a fallback to the ioctl.
+    pub fn new(quote_provider: Option<TdxQuoteProvider>) -> Self {
+        Self {
+            quote_provider,
+            aael_path: None,
+        }
+    }
+
+    /// Put the AAEL at `aael_path` into the evidence, see
+    /// [`crate::utils::read_eventlog`].
+    pub fn with_aael_path(mut self, aael_path: Option<PathBuf>) -> Self {
+        self.aael_path = aael_path;
+        self
+    }
+
+    fn get_quote(&self, report_data: &[u8]) -> Result<Vec<u8>> {
+        match self.quote_provider {
+            Some(TdxQuoteProvider::Tsm) => TsmReportPath::new(TsmReportProvider::Tdx)?
+                .attestation_report(TsmReportData::Tdx(report_data.to_vec()))
+                .context("TDX Attester: quote generation using TSM reports failed"),
+            Some(TdxQuoteProvider::Ioctl) => get_quote_ioctl(report_data)
+                .context("TDX Attester: quote generation using ioctl() failed"),
+            None => TsmReportPath::new(TsmReportProvider::Tdx).map_or_else(
+                |notsm| {
+                    get_quote_ioctl(report_data)
+                        .context(format!("TDX Attester: quote generation using ioctl() fallback failed after a TSM report error ({notsm})"))
+                },
+                |tsm| {
+                    tsm.attestation_report(TsmReportData::Tdx(report_data.to_vec()))
+                        .context("TDX Attester: quote generation using TSM reports failed")
+                },
+            ),
+        }
+    }
+
     fn get_report() -> Result<TdReport> {
         let mut report = TdxReportReq::default();
         let mut fd =
@@ -126,21 +181,12 @@ impl Attester for TdxAttester {
 
         report_data.resize(TDX_REPORT_DATA_SIZE, 0);
 
-        let quote_bytes = TsmReportPath::new(TsmReportProvider::Tdx).map_or_else(
-            |notsm| {
-                get_quote_ioctl(&report_data)
-                    .context(format!("TDX Attester: quote generation using ioctl() fallback failed after a TSM report error ({notsm})"))
-            },
-            |tsm| {
-                tsm.attestation_report(TsmReportData::Tdx(report_data.clone()))
-                    .context("TDX Attester: quote generation using TSM reports failed")
-            },
-        )?;
+        let quote_bytes = self.get_quote(&report_data)?;
 
         let engine = base64::engine::general_purpose::STANDARD;
         let quote = engine.encode(quote_bytes);
 
-        let cc_eventlog = read_eventlog().await?;
+        let cc_eventlog = read_eventlog(self.aael_path.as_deref()).await?;
 
         let evidence = TdxEvidence { cc_eventlog, quote };
 
@@ -3,27 +3,38 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use super::{Attester, InitDataResult, TeeEvidence};
+use super::{Attester, InitDataResult, TeeEvidence, TpmOptions, TpmPcrBank};
+use crate::diagnostics::PlatformProbe;
 pub mod utils;
 
 use self::utils::{
-    detect_tpm_device, extend_pcr, get_ak_handle, get_quote, read_ak_public_key, read_all_pcrs,
-    TpmQuote,
+    activate_credential, ak_exists, detect_tpm_device, ensure_ak, extend_pcr, get_ak_handle,
+    get_quote, pcr_slots, read_ak_credential, read_ak_public_key, read_ek_cert, read_pcrs,
+    tpm_device_candidates, AkCredential, TpmQuote,
 };
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use base64::Engine;
 use log::info;
 use serde::{Deserialize, Serialize};
+use tss_esapi::interface_types::algorithm::HashingAlgorithm;
+use tss_esapi::structures::pcr_slot::PcrSlot;
 
 const PCR_SLOT_8: u64 = 8;
 const TPM_REPORT_DATA_SIZE: usize = 32;
-const TPM_HASH_ALGORITHM: &str = "SHA256";
+const TPM_PCR_NUM: u8 = 24;
 
 /// Evidence structure for the TPM Attester.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Evidence {
     pub tpm_quote: TpmQuote,
     pub ak_public: String,
+
+    /// Base64 encoded EK certificate (DER), if provisioned in the TPM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ek_cert: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ak_credential: Option<AkCredential>,
 }
 
 /// TpmAttester struct holds the path to the detected TPM device.
@@ -31,6 +42,11 @@ pub struct Evidence {
 pub struct TpmAttester {
     tpm_device: String,
     ak_handle_raw: u32,
+    pcr_banks: Vec<HashingAlgorithm>,
+    pcr_slots: Vec<PcrSlot>,
+    init_data_pcr: u64,
+    ek_cert: Option<Vec<u8>>,
+    ak_credential: AkCredential,
 }
 
 impl TpmAttester {
@@ -40,22 +56,122 @@ impl TpmAttester {
     /// And also retrieve the AK handle.
     /// It returns an error if no suitable TPM device or AK handle is found.
     pub fn new() -> Result<Self> {
-        let tpm_device = detect_tpm_device().ok_or_else(|| anyhow!("No TPM device found"))?;
+        Self::with_options(&TpmOptions::default())
+    }
+
+    /// Creates a new TpmAttester using the given TPM device instead of
+    /// detecting one. It returns an error if the device does not exist or
+    /// no AK handle is found.
+    pub fn with_device(tpm_device: &str) -> Result<Self> {
+        Self::with_options(&TpmOptions {
+            device: Some(tpm_device.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Creates a new TpmAttester with the given options. If no AK is
+    /// persisted at the AK handle, one is created under the EK and
+    /// persisted there when `provision_ak` is set, otherwise an error is
+    /// returned.
+    pub fn with_options(options: &TpmOptions) -> Result<Self> {
+        let tpm_device = match &options.device {
+            Some(device) if !std::path::Path::new(device).exists() => {
+                return Err(anyhow!("TPM device {device} not found"));
+            }
+            Some(device) => device.clone(),
+            None => detect_tpm_device().ok_or_else(|| anyhow!("No TPM device found"))?,
+        };
+
+        let pcrs = options
+            .pcrs
+            .clone()
+            .unwrap_or_else(|| (0..TPM_PCR_NUM).collect());
+        let pcr_slots = pcr_slots(&pcrs)?;
+        let pcr_banks = options
+            .pcr_banks
+            .clone()
+            .unwrap_or_else(|| vec![TpmPcrBank::Sha256]);
+        if pcr_banks.is_empty() {
+            return Err(anyhow!("No PCR bank to quote"));
+        }
+        let pcr_banks = pcr_banks.into_iter().map(hashing_algorithm).collect();
+        let init_data_pcr = options.init_data_pcr.unwrap_or(PCR_SLOT_8);
+        if init_data_pcr >= TPM_PCR_NUM as u64 {
+            return Err(anyhow!("Initdata PCR {init_data_pcr} out of bounds"));
+        }
+
         let ak_handle_raw = get_ak_handle().ok_or_else(|| anyhow!("Failed to get AK handle"))?;
+        if options.provision_ak {
+            ensure_ak(&tpm_device, ak_handle_raw)?;
+        } else if !ak_exists(&tpm_device, ak_handle_raw)? {
+            bail!("No AK at handle {ak_handle_raw:#X}, provision one or set `provision_ak`");
+        }
+        let ek_cert = read_ek_cert(&tpm_device)?;
+        let ak_credential = read_ak_credential(&tpm_device, ak_handle_raw)?;
         info!(
-            "[TPM Attester] Initialized using TPM device: {} and AK handle: {}",
-            &tpm_device, &ak_handle_raw,
+            "[TPM Attester] Initialized using TPM device: {} and AK handle: {:#X}",
+            &tpm_device, ak_handle_raw,
         );
         Ok(Self {
             tpm_device,
             ak_handle_raw,
+            pcr_banks,
+            pcr_slots,
+            init_data_pcr,
+            ek_cert,
+            ak_credential,
         })
     }
+
+    /// Activate the credential made by a verifier for the AK of the
+    /// evidence with TPM2_MakeCredential, returning the secret it protects.
+    /// Only the TPM holding both the EK and the AK can activate it.
+    pub fn activate_credential(
+        &self,
+        credential_blob: Vec<u8>,
+        secret: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        activate_credential(
+            &self.tpm_device,
+            self.ak_handle_raw,
+            credential_blob,
+            secret,
+        )
+    }
+}
+
+fn hashing_algorithm(bank: TpmPcrBank) -> HashingAlgorithm {
+    match bank {
+        TpmPcrBank::Sha1 => HashingAlgorithm::Sha1,
+        TpmPcrBank::Sha256 => HashingAlgorithm::Sha256,
+        TpmPcrBank::Sha384 => HashingAlgorithm::Sha384,
+    }
 }
 
-/// Detects if the platform is supported by checking if a TpmAttester can be created.
+/// Detects if the platform is supported by checking for a TPM device with
+/// an AK persisted at the AK handle.
 pub fn detect_platform() -> bool {
-    TpmAttester::new().is_ok()
+    probe_platform().is_detected()
+}
+
+/// Probe the TPM device and the AK handle. The probe is read-only: unlike
+/// [`TpmAttester::with_options`], it never creates an EK or an AK.
+pub fn probe_platform() -> PlatformProbe {
+    let mut probe = PlatformProbe::default();
+    let device = tpm_device_candidates()
+        .into_iter()
+        .find(|device| probe.check_path(device));
+    let Some(device) = device else {
+        return probe.fail("No TPM device found");
+    };
+    let Some(ak_handle_raw) = get_ak_handle() else {
+        return probe.fail("Failed to get AK handle");
+    };
+    match ak_exists(&device, ak_handle_raw) {
+        Ok(true) => probe,
+        Ok(false) => probe.fail(format!("No AK at handle {ak_handle_raw:#X} of {device}")),
+        Err(e) => probe.fail(format!("{e:#}")),
+    }
 }
 
 #[async_trait::async_trait]
@@ -70,12 +186,16 @@ impl Attester for TpmAttester {
             &self.tpm_device,
             self.ak_handle_raw,
             &report_data,
-            TPM_HASH_ALGORITHM,
+            &self.pcr_banks,
+            &self.pcr_slots,
         )?;
 
+        let engine = base64::engine::general_purpose::STANDARD;
         let evidence = Evidence {
             tpm_quote,
-            ak_public: base64::engine::general_purpose::STANDARD.encode(ak_public_bytes),
+            ak_public: engine.encode(ak_public_bytes),
+            ek_cert: self.ek_cert.as_ref().map(|ek_cert| engine.encode(ek_cert)),
+            ak_credential: Some(self.ak_credential.clone()),
         };
         Ok(serde_json::to_value(&evidence)?)
     }
@@ -91,28 +211,147 @@ impl Attester for TpmAttester {
         register_index: u64,
     ) -> Result<()> {
         // Use the stored tpm_device path.
-        extend_pcr(&self.tpm_device, event_digest, register_index)
-            .map_err(|e| anyhow!("Failed to extend PCR: {e}"))?;
+        extend_pcr(
+            &self.tpm_device,
+            &self.pcr_banks,
+            event_digest,
+            register_index,
+        )
+        .map_err(|e| anyhow!("Failed to extend PCR: {e}"))?;
         Ok(())
     }
 
-    /// Bind init data for the TPM attester (extends PCR 8).
+    /// Bind init data for the TPM attester (extends the initdata PCR, by
+    /// default PCR 8).
     async fn bind_init_data(&self, init_data_digest: &[u8]) -> Result<InitDataResult> {
         // Use the stored tpm_device path.
-        extend_pcr(&self.tpm_device, init_data_digest.to_vec(), PCR_SLOT_8)
-            .map_err(|e| anyhow!("Failed to extend PCR for init data: {e}"))?;
+        extend_pcr(
+            &self.tpm_device,
+            &self.pcr_banks,
+            init_data_digest.to_vec(),
+            self.init_data_pcr,
+        )
+        .map_err(|e| anyhow!("Failed to extend PCR for init data: {e}"))?;
         Ok(InitDataResult::Ok)
     }
 
-    /// Get runtime measurement (PCR value) for the given index.
+    /// Get runtime measurement (PCR value of the first quoted bank) for the
+    /// given index.
     async fn get_runtime_measurement(&self, index: u64) -> Result<Vec<u8>> {
+        let index = u8::try_from(index).map_err(|_| anyhow!("Register index out of bounds"))?;
         // Use the stored tpm_device path.
-        let pcrs = read_all_pcrs(&self.tpm_device, TPM_HASH_ALGORITHM)?;
-        let idx = index as usize;
+        let pcrs = read_pcrs(&self.tpm_device, self.pcr_banks[0], &pcr_slots(&[index])?)?;
         let target_pcr = pcrs
-            .get(idx)
+            .get(&index)
             .ok_or_else(|| anyhow!("Register index out of bounds"))?;
         let pcr_value = hex::decode(target_pcr)?;
         Ok(pcr_value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tss_esapi::abstraction::{ek, AsymmetricAlgorithmSelection};
+    use tss_esapi::interface_types::key_bits::RsaKeyBits;
+    use tss_esapi::structures::{Digest, Name};
+
+    // Run against swtpm, see the README, with AA_TPM_DEVICE set to its
+    // device. The AK is created on the first run.
+    #[ignore]
+    #[tokio::test]
+    async fn test_tpm_get_evidence() {
+        let attester = TpmAttester::with_options(&TpmOptions {
+            pcrs: Some(vec![0, 8, 9]),
+            pcr_banks: Some(vec![TpmPcrBank::Sha256, TpmPcrBank::Sha1]),
+            init_data_pcr: Some(9),
+            provision_ak: true,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(detect_platform());
+
+        let before = attester.get_evidence(vec![0; 32]).await.unwrap();
+        let before: Evidence = serde_json::from_value(before).unwrap();
+        attester.bind_init_data(&[1; 32]).await.unwrap();
+
+        // The initdata digest is extended to every bank
+        let evidence = attester.get_evidence(vec![0; 32]).await.unwrap();
+        let evidence: Evidence = serde_json::from_value(evidence).unwrap();
+        for bank in ["sha1", "sha256"] {
+            assert_ne!(
+                evidence.tpm_quote.pcr_banks[bank][&9],
+                before.tpm_quote.pcr_banks[bank][&9]
+            );
+        }
+
+        // The first bank quotes all the PCRs, which are the index-aligned
+        // `pcrs`, and the other banks the configured ones
+        assert_eq!(evidence.tpm_quote.pcrs.len(), TPM_PCR_NUM as usize);
+        assert_eq!(
+            evidence.tpm_quote.pcrs[9],
+            evidence.tpm_quote.pcr_banks["sha256"][&9]
+        );
+        assert_eq!(
+            attester.get_runtime_measurement(9).await.unwrap(),
+            hex::decode(&evidence.tpm_quote.pcrs[9]).unwrap()
+        );
+        assert_eq!(
+            evidence.tpm_quote.pcr_banks.keys().collect::<Vec<_>>(),
+            ["sha1", "sha256"]
+        );
+        assert_eq!(
+            evidence.tpm_quote.pcr_banks["sha256"]
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            (0..TPM_PCR_NUM).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            evidence.tpm_quote.pcr_banks["sha1"]
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            [0, 8, 9]
+        );
+        assert!(evidence.ek_cert.is_some());
+        assert!(evidence.ak_credential.is_some());
+    }
+
+    #[ignore]
+    #[tokio::test]
+    async fn test_tpm_activate_credential() {
+        let attester = TpmAttester::with_options(&TpmOptions {
+            provision_ak: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let ak_name = base64::engine::general_purpose::STANDARD
+            .decode(&attester.ak_credential.ak_name)
+            .unwrap();
+
+        // Make the credential as a verifier would, with the EK public key
+        let mut context = utils::create_ctx_without_session(&attester.tpm_device).unwrap();
+        let ek_handle = ek::create_ek_object_2(
+            &mut context,
+            AsymmetricAlgorithmSelection::Rsa(RsaKeyBits::Rsa2048),
+            None,
+        )
+        .unwrap();
+        let credential = vec![7; 32];
+        let (credential_blob, secret) = context
+            .make_credential(
+                ek_handle,
+                Digest::try_from(credential.clone()).unwrap(),
+                Name::try_from(ak_name).unwrap(),
+            )
+            .unwrap();
+        context.flush_context(ek_handle.into()).unwrap();
+        drop(context);
+
+        let activated = attester
+            .activate_credential(credential_blob.value().to_vec(), secret.value().to_vec())
+            .unwrap();
+        assert_eq!(activated, credential);
+    }
+}
@@ -7,22 +7,31 @@ use anyhow::{anyhow, bail, Context, Result};
 use base64::Engine;
 use num_traits::cast::FromPrimitive;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::str::FromStr;
 use tss_esapi::attributes::SessionAttributesBuilder;
 use tss_esapi::constants::SessionType;
-use tss_esapi::handles::{PcrHandle, TpmHandle};
-use tss_esapi::interface_types::algorithm::HashingAlgorithm;
+use tss_esapi::handles::{
+    KeyHandle, NvIndexTpmHandle, ObjectHandle, PcrHandle, PersistentTpmHandle, SessionHandle,
+    TpmHandle,
+};
+use tss_esapi::interface_types::algorithm::{HashingAlgorithm, SignatureSchemeAlgorithm};
+use tss_esapi::interface_types::dynamic_handles::Persistent;
+use tss_esapi::interface_types::key_bits::RsaKeyBits;
+use tss_esapi::interface_types::resource_handles::{AuthHandle, NvAuth, Provision};
+use tss_esapi::interface_types::session_handles::{AuthSession, PolicySession};
 use tss_esapi::structures::digest_values::DigestValues;
 use tss_esapi::structures::{
-    pcr_selection_list::PcrSelectionListBuilder, pcr_slot::PcrSlot, AttestInfo, PcrSelectionList,
-    Signature, SignatureScheme as TpmSignatureScheme, SymmetricDefinition,
+    pcr_selection_list::PcrSelectionListBuilder, pcr_slot::PcrSlot, AttestInfo, EncryptedSecret,
+    IdObject, PcrSelectionList, Signature, SignatureScheme as TpmSignatureScheme,
+    SymmetricDefinition,
 };
 use tss_esapi::tcti_ldr::TctiNameConf;
 use tss_esapi::traits::Marshall;
 use tss_esapi::Context as TssContext;
 use tss_esapi::{
-    abstraction::{pcr, public::DecodedKey},
+    abstraction::{ak, ek, nv, pcr, public::DecodedKey, AsymmetricAlgorithmSelection},
     structures::HashScheme,
 };
 
@@ -30,10 +39,40 @@ use tss_esapi::{
 pub struct TpmQuote {
     pub signature: String,
     pub message: String,
+
+    /// Values of all the 24 PCRs of the first quoted bank, which always
+    /// quotes them all, so that the values are indexed by PCR.
     pub pcrs: Vec<String>,
+
+    /// Values of the quoted PCRs of each quoted bank, e.g. `sha256`, by
+    /// PCR index.
+    #[serde(default)]
+    pub pcr_banks: BTreeMap<String, BTreeMap<u8, String>>,
+}
+
+/// Material for a verifier to check that the AK is resident in the same
+/// TPM as the EK, with TPM2_MakeCredential and TPM2_ActivateCredential.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct AkCredential {
+    /// Base64 encoded TPMT_PUBLIC of the EK
+    pub ek_public: String,
+
+    /// Base64 encoded TPMT_PUBLIC of the AK
+    pub ak_public: String,
+
+    /// Base64 encoded name of the AK
+    pub ak_name: String,
 }
 
 const TPM_DEFAULT_AK_HANDLE: u32 = 0x81010002;
+
+/// NV index of the RSA 2048 EK certificate, as in the TCG EK Credential
+/// Profile.
+const TPM_RSA_EK_CERT_NV_INDEX: u32 = 0x01c00002;
+
+/// The EK and the AK created by the attester are RSA 2048 keys.
+const TPM_KEY_ALGORITHM: AsymmetricAlgorithmSelection =
+    AsymmetricAlgorithmSelection::Rsa(RsaKeyBits::Rsa2048);
 /// Environment variable to set a specific TPM device
 const AA_TPM_DEVICE_ENV: &str = "AA_TPM_DEVICE";
 /// Environment variable to set a non-default TPM AK handle
@@ -111,64 +150,140 @@ pub fn create_ctx_with_session(tpm_device: &str) -> Result<TssContext> {
     Ok(ctx)
 }
 
-pub fn create_pcr_selection_list(algorithm: &str) -> Result<PcrSelectionList> {
-    match algorithm {
-        "SHA256" => PcrSelectionListBuilder::new()
-            .with_selection(HashingAlgorithm::Sha256, &TPM_QUOTE_PCR_SLOTS)
-            .build()
-            .context("PCR selection list build failed"),
-        _ => bail!("Unsupported PCR Hash Algorithm"),
+/// The PCR slots of the given PCR indexes.
+pub fn pcr_slots(indexes: &[u8]) -> Result<Vec<PcrSlot>> {
+    indexes
+        .iter()
+        .map(|&index| {
+            TPM_QUOTE_PCR_SLOTS
+                .get(index as usize)
+                .copied()
+                .ok_or_else(|| anyhow!("PCR index {index} out of bounds"))
+        })
+        .collect()
+}
+
+/// The index of the PCR slot.
+pub fn pcr_index(slot: PcrSlot) -> u8 {
+    u32::from(slot).trailing_zeros() as u8
+}
+
+/// The name of the PCR bank, e.g. `sha256`.
+pub fn pcr_bank_name(bank: HashingAlgorithm) -> Result<&'static str> {
+    match bank {
+        HashingAlgorithm::Sha1 => Ok("sha1"),
+        HashingAlgorithm::Sha256 => Ok("sha256"),
+        HashingAlgorithm::Sha384 => Ok("sha384"),
+        _ => bail!("Unsupported PCR bank {bank:?}"),
+    }
+}
+
+/// The size of the PCRs of the bank, in bytes.
+fn pcr_bank_digest_size(bank: HashingAlgorithm) -> usize {
+    match bank {
+        HashingAlgorithm::Sha1 => 20,
+        HashingAlgorithm::Sha384 => 48,
+        _ => 32,
     }
 }
 
-/// Extend a PCR with the given digest at the given index.
-pub fn extend_pcr(tpm_device: &str, digest: Vec<u8>, index: u64) -> Result<()> {
+pub fn create_pcr_selection_list(
+    selection: &[(HashingAlgorithm, &[PcrSlot])],
+) -> Result<PcrSelectionList> {
+    selection
+        .iter()
+        .fold(PcrSelectionListBuilder::new(), |builder, &(bank, slots)| {
+            builder.with_selection(bank, slots)
+        })
+        .build()
+        .context("PCR selection list build failed")
+}
+
+/// The PCRs quoted in each bank: all the PCRs of the first bank, whose
+/// values are the index-aligned `pcrs` of the quote, and the given PCRs of
+/// the other banks.
+fn quote_selection<'a>(
+    banks: &[HashingAlgorithm],
+    slots: &'a [PcrSlot],
+) -> Vec<(HashingAlgorithm, &'a [PcrSlot])> {
+    banks
+        .iter()
+        .enumerate()
+        .map(|(i, &bank)| match i {
+            0 => (bank, &TPM_QUOTE_PCR_SLOTS[..]),
+            _ => (bank, slots),
+        })
+        .collect()
+}
+
+/// The digest extended to each bank: the event digest truncated or padded
+/// to the size of the PCRs of the bank.
+fn bank_digests(banks: &[HashingAlgorithm], digest: &[u8]) -> Vec<(HashingAlgorithm, Vec<u8>)> {
+    banks
+        .iter()
+        .map(|&bank| {
+            let mut bank_digest = digest.to_vec();
+            bank_digest.resize(pcr_bank_digest_size(bank), 0);
+            (bank, bank_digest)
+        })
+        .collect()
+}
+
+/// Extend a PCR with the given digest at the given index, in every bank.
+pub fn extend_pcr(
+    tpm_device: &str,
+    banks: &[HashingAlgorithm],
+    digest: Vec<u8>,
+    index: u64,
+) -> Result<()> {
     let mut ctx = create_ctx_with_session(tpm_device)?;
 
     if index >= TPM_QUOTE_PCR_SLOTS.len() as u64 {
         bail!("PCR index out of bounds");
     }
-    if digest.len() != 32 {
-        bail!("Event digest length is not 32 bytes");
-    }
 
     let pcr_handle = PcrHandle::from_u64(index).ok_or_else(|| anyhow!("Invalid pcr index"))?;
     let mut digest_values = DigestValues::new();
-    digest_values.set(
-        HashingAlgorithm::Sha256,
-        digest
-            .try_into()
-            .map_err(|_| anyhow!("Failed to convert digest"))?,
-    );
+    for (bank, bank_digest) in bank_digests(banks, &digest) {
+        digest_values.set(
+            bank,
+            bank_digest
+                .try_into()
+                .map_err(|_| anyhow!("Failed to convert digest"))?,
+        );
+    }
     ctx.pcr_extend(pcr_handle, digest_values)?;
     Ok(())
 }
 
-/// Read all PCRs for the given algorithm.
-pub fn read_all_pcrs(tpm_device: &str, algorithm: &str) -> Result<Vec<String>> {
+/// Read the PCRs of the bank, by PCR index.
+pub fn read_pcrs(
+    tpm_device: &str,
+    bank: HashingAlgorithm,
+    slots: &[PcrSlot],
+) -> Result<BTreeMap<u8, String>> {
     let mut context = create_ctx_without_session(tpm_device)?;
 
-    let selection_list = create_pcr_selection_list(algorithm)?;
+    let selection_list = create_pcr_selection_list(&[(bank, slots)])?;
     let pcr_data = pcr::read_all(&mut context, selection_list)?;
-    let hashing_algorithm = match algorithm {
-        "SHA256" => HashingAlgorithm::Sha256,
-        _ => bail!("read_all_pcrs: Unsupported PCR algorithm of AA"),
-    };
     let pcr_bank = pcr_data
-        .pcr_bank(hashing_algorithm)
+        .pcr_bank(bank)
         .ok_or(anyhow!("PCR bank not found"))?;
-    pcr_bank
+    Ok(pcr_bank
         .into_iter()
-        .map(|(_, digest)| Ok(hex::encode(digest.value())))
-        .collect()
+        .map(|(slot, digest)| (pcr_index(*slot), hex::encode(digest.value())))
+        .collect())
 }
 
-/// Function to generate a quote using a persistent AK handle
+/// Function to generate a quote of the PCRs of the banks using a
+/// persistent AK handle. The first bank quotes all the PCRs, the others
+/// the given ones.
 pub fn get_quote(
     tpm_device: &str,
     ak_handle_raw: u32,
     report_data: &[u8],
-    pcr_algorithm: &str,
+    banks: &[HashingAlgorithm],
+    slots: &[PcrSlot],
 ) -> Result<TpmQuote> {
     let mut context = create_ctx_with_session(tpm_device)?;
 
@@ -176,7 +291,8 @@ pub fn get_quote(
     let tpm_handle: TpmHandle = ak_handle_raw.try_into()?;
     let ak_handle = context.tr_from_tpm_public(tpm_handle)?;
 
-    let selection_list = create_pcr_selection_list(pcr_algorithm)?;
+    let selection = quote_selection(banks, slots);
+    let selection_list = create_pcr_selection_list(&selection)?;
 
     let (attest, signature) = context
         .quote(
@@ -202,10 +318,23 @@ pub fn get_quote(
     let engine = base64::engine::general_purpose::STANDARD;
     drop(context);
 
+    let mut pcr_banks = BTreeMap::new();
+    for &(bank, bank_slots) in &selection {
+        pcr_banks.insert(
+            pcr_bank_name(bank)?.to_string(),
+            read_pcrs(tpm_device, bank, bank_slots)?,
+        );
+    }
+    let pcrs = match banks.first() {
+        Some(&bank) => pcr_banks[pcr_bank_name(bank)?].values().cloned().collect(),
+        None => Vec::new(),
+    };
+
     Ok(TpmQuote {
         signature: engine.encode(signature.marshall()?),
         message: engine.encode(attest.marshall()?),
-        pcrs: read_all_pcrs(tpm_device, pcr_algorithm)?,
+        pcrs,
+        pcr_banks,
     })
 }
 
@@ -238,6 +367,183 @@ pub fn read_ak_public_key(tpm_device: &str, ak_handle_raw: u32) -> Result<Vec<u8
     Ok(ak_pk_bytes)
 }
 
+/// Whether a key is persisted at the AK handle. This only reads the TPM.
+pub fn ak_exists(tpm_device: &str, ak_handle_raw: u32) -> Result<bool> {
+    let mut context = create_ctx_without_session(tpm_device)?;
+    let tpm_handle: TpmHandle = ak_handle_raw.try_into()?;
+    Ok(context.tr_from_tpm_public(tpm_handle).is_ok())
+}
+
+/// Create the AK under the EK and persist it at the handle, unless a key is
+/// already persisted there.
+pub fn ensure_ak(tpm_device: &str, ak_handle_raw: u32) -> Result<()> {
+    if ak_exists(tpm_device, ak_handle_raw)? {
+        return Ok(());
+    }
+
+    let mut context = create_ctx_without_session(tpm_device)?;
+
+    log::info!("No AK at handle {ak_handle_raw:#X}, creating one under the EK");
+    let ek_handle = ek::create_ek_object_2(&mut context, TPM_KEY_ALGORITHM, None)
+        .context("Failed to create EK")?;
+    let persisted = persist_ak(&mut context, ek_handle, ak_handle_raw);
+    context.flush_context(ek_handle.into())?;
+    persisted
+}
+
+fn persist_ak(context: &mut TssContext, ek_handle: KeyHandle, ak_handle_raw: u32) -> Result<()> {
+    let ak = ak::create_ak_2(
+        context,
+        ek_handle,
+        HashingAlgorithm::Sha256,
+        TPM_KEY_ALGORITHM,
+        SignatureSchemeAlgorithm::RsaSsa,
+        None,
+        None,
+    )
+    .context("Failed to create AK")?;
+    let ak_handle = ak::load_ak(context, ek_handle, None, ak.out_private, ak.out_public)
+        .context("Failed to load AK")?;
+
+    let persistent = PersistentTpmHandle::new(ak_handle_raw)?;
+    let persisted = context.execute_with_nullauth_session(|ctx| {
+        ctx.evict_control(
+            Provision::Owner,
+            ObjectHandle::from(ak_handle),
+            Persistent::Persistent(persistent),
+        )
+    });
+    context.flush_context(ak_handle.into())?;
+    persisted.context(format!("Failed to persist AK at handle {ak_handle_raw:#X}"))?;
+    Ok(())
+}
+
+/// Read the RSA EK certificate (DER) provisioned in the TPM NV, if any.
+pub fn read_ek_cert(tpm_device: &str) -> Result<Option<Vec<u8>>> {
+    let mut context = create_ctx_without_session(tpm_device)?;
+    let nv_index = NvIndexTpmHandle::new(TPM_RSA_EK_CERT_NV_INDEX)?;
+    if context
+        .tr_from_tpm_public(TpmHandle::NvIndex(nv_index))
+        .is_err()
+    {
+        log::info!("No EK certificate at NV index {TPM_RSA_EK_CERT_NV_INDEX:#X}");
+        return Ok(None);
+    }
+
+    let ek_cert = context
+        .execute_with_nullauth_session(|ctx| nv::read_full(ctx, NvAuth::Owner, nv_index))
+        .context("Failed to read EK certificate")?;
+    Ok(Some(ek_cert))
+}
+
+/// Read the material to check that the AK is resident in the TPM of the
+/// EK.
+pub fn read_ak_credential(tpm_device: &str, ak_handle_raw: u32) -> Result<AkCredential> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let mut context = create_ctx_without_session(tpm_device)?;
+    let tpm_handle: TpmHandle = ak_handle_raw.try_into()?;
+    let ak_handle = context.tr_from_tpm_public(tpm_handle)?;
+    let (ak_public, ak_name, _) = context
+        .read_public(ak_handle.into())
+        .context("Failed to read AK public key from handle")?;
+
+    let ek_handle = ek::create_ek_object_2(&mut context, TPM_KEY_ALGORITHM, None)
+        .context("Failed to create EK")?;
+    let ek_public = context.read_public(ek_handle);
+    context.flush_context(ek_handle.into())?;
+    let (ek_public, _, _) = ek_public.context("Failed to read EK public key")?;
+
+    Ok(AkCredential {
+        ek_public: engine.encode(ek_public.marshall()?),
+        ak_public: engine.encode(ak_public.marshall()?),
+        ak_name: engine.encode(ak_name.value()),
+    })
+}
+
+/// Activate the credential made by a verifier for the AK with
+/// TPM2_MakeCredential, returning the secret it protects.
+pub fn activate_credential(
+    tpm_device: &str,
+    ak_handle_raw: u32,
+    credential_blob: Vec<u8>,
+    secret: Vec<u8>,
+) -> Result<Vec<u8>> {
+    let credential_blob = IdObject::try_from(credential_blob)?;
+    let secret = EncryptedSecret::try_from(secret)?;
+
+    let mut context = create_ctx_without_session(tpm_device)?;
+    let tpm_handle: TpmHandle = ak_handle_raw.try_into()?;
+    let ak_handle = context.tr_from_tpm_public(tpm_handle)?;
+    let ek_handle = ek::create_ek_object_2(&mut context, TPM_KEY_ALGORITHM, None)
+        .context("Failed to create EK")?;
+
+    let activated = activate_credential_with_ek(
+        &mut context,
+        ak_handle.into(),
+        ek_handle,
+        credential_blob,
+        secret,
+    );
+    context.flush_context(ek_handle.into())?;
+    activated
+}
+
+fn activate_credential_with_ek(
+    context: &mut TssContext,
+    ak_handle: KeyHandle,
+    ek_handle: KeyHandle,
+    credential_blob: IdObject,
+    secret: EncryptedSecret,
+) -> Result<Vec<u8>> {
+    // The EK is only usable with a policy session satisfying the
+    // endorsement hierarchy secret.
+    let session = context
+        .start_auth_session(
+            None,
+            None,
+            None,
+            SessionType::Policy,
+            SymmetricDefinition::AES_128_CFB,
+            HashingAlgorithm::Sha256,
+        )?
+        .ok_or(anyhow!("Failed to start policy session"))?;
+    let (session_attributes, session_attributes_mask) = SessionAttributesBuilder::new()
+        .with_decrypt(true)
+        .with_encrypt(true)
+        .build();
+    context.tr_sess_set_attributes(session, session_attributes, session_attributes_mask)?;
+    let policy_session = PolicySession::try_from(session)?;
+    context.execute_with_nullauth_session(|ctx| {
+        ctx.policy_secret(
+            policy_session,
+            AuthHandle::Endorsement,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+        )
+    })?;
+
+    let activated = context
+        .execute_with_sessions((Some(AuthSession::Password), Some(session), None), |ctx| {
+            ctx.activate_credential(ak_handle, ek_handle, credential_blob, secret)
+        });
+    context.flush_context(SessionHandle::from(session).into())?;
+    let digest = activated.context("Failed to activate credential")?;
+    Ok(digest.value().to_vec())
+}
+
+/// The TPM devices probed if `AA_TPM_DEVICE` is not set.
+const TPM_DEVICES: [&str; 3] = ["/dev/tpm0", "/dev/tpm1", "/dev/tpm2"];
+
+/// The TPM devices [`detect_tpm_device`] looks for, in order.
+pub fn tpm_device_candidates() -> Vec<String> {
+    match env::var(AA_TPM_DEVICE_ENV) {
+        Ok(dev) => vec![dev],
+        Err(_) => TPM_DEVICES.iter().map(|dev| dev.to_string()).collect(),
+    }
+}
+
 /// Detect the TPM device to use.
 /// Priority: 1. AA_TPM_DEVICE env var, 2. /dev/tpm[0..2]
 pub fn detect_tpm_device() -> Option<String> {
@@ -264,7 +570,7 @@ pub fn detect_tpm_device() -> Option<String> {
     }
 
     // Check predefined TPM device paths
-    for &dev in &["/dev/tpm0", "/dev/tpm1", "/dev/tpm2"] {
+    for &dev in &TPM_DEVICES {
         if std::path::Path::new(dev).exists() {
             log::info!("TPM device detected: {}", dev);
             return Some(dev.to_string());
@@ -304,3 +610,53 @@ pub fn get_ak_handle() -> Option<u32> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pcr_slots() {
+        let slots = pcr_slots(&[0, 8, 23]).unwrap();
+        assert_eq!(slots, [PcrSlot::Slot0, PcrSlot::Slot8, PcrSlot::Slot23]);
+        assert_eq!(
+            slots.into_iter().map(pcr_index).collect::<Vec<_>>(),
+            [0, 8, 23]
+        );
+        assert!(pcr_slots(&[24]).is_err());
+    }
+
+    #[test]
+    fn test_quote_selection() {
+        let slots = pcr_slots(&[0, 8]).unwrap();
+        let selection =
+            quote_selection(&[HashingAlgorithm::Sha256, HashingAlgorithm::Sha1], &slots);
+        assert_eq!(selection.len(), 2);
+        assert_eq!(selection[0].0, HashingAlgorithm::Sha256);
+        assert_eq!(selection[0].1, TPM_QUOTE_PCR_SLOTS);
+        assert_eq!(selection[1], (HashingAlgorithm::Sha1, &slots[..]));
+    }
+
+    #[test]
+    fn test_bank_digests() {
+        let digests = bank_digests(
+            &[
+                HashingAlgorithm::Sha256,
+                HashingAlgorithm::Sha1,
+                HashingAlgorithm::Sha384,
+            ],
+            &[1; 32],
+        );
+        assert_eq!(
+            digests,
+            [
+                (HashingAlgorithm::Sha256, vec![1; 32]),
+                (HashingAlgorithm::Sha1, vec![1; 20]),
+                (
+                    HashingAlgorithm::Sha384,
+                    [vec![1; 32], vec![0; 16]].concat()
+                ),
+            ]
+        );
+    }
+}
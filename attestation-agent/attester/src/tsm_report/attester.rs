@@ -0,0 +1,151 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use std::sync::Arc;
+
+use anyhow::*;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+use super::{TsmConfigfs, TsmReportPath, TsmReportProvider, TsmReportRequest, TsmReportRoot};
+use crate::{Attester, TeeEvidence};
+
+/// Size of the inblob, the report data of all the providers.
+const TSM_INBLOB_SIZE: usize = 64;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct TsmEvidence {
+    /// Provider of the report, e.g. `tdx_guest`
+    provider: String,
+
+    /// Base64 encoded report
+    outblob: String,
+
+    /// Base64 encoded supplemental data, e.g. the certificate chain of the
+    /// report signing key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auxblob: Option<String>,
+
+    /// Base64 encoded manifest of the service provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    manifestblob: Option<String>,
+}
+
+/// A generic attester getting the evidence from the TSM_REPORT attestation
+/// ABI of the kernel, for whatever provider the TEE has. It is library-only:
+/// [`crate::new_attester`] never selects it, as its evidence has no `Tee`
+/// type a verifier knows.
+pub struct TsmAttester {
+    configfs: Arc<dyn TsmConfigfs>,
+    provider: TsmReportProvider,
+    privlevel: Option<u32>,
+    service_provider: Option<String>,
+}
+
+impl TsmAttester {
+    /// An attester detecting the provider of the TSM reports on
+    /// `/sys/kernel/config/tsm/report`.
+    pub fn new() -> Result<Self> {
+        Self::with_configfs(Arc::new(TsmReportRoot::default()))
+    }
+
+    /// An attester detecting the provider of the TSM reports on the
+    /// configfs, e.g. a [`TsmReportRoot`] mounted elsewhere.
+    pub fn with_configfs(configfs: Arc<dyn TsmConfigfs>) -> Result<Self> {
+        let provider = TsmReportPath::open(configfs.clone(), None)
+            .context("TSM Attester: provider detection failed")?
+            .provider();
+
+        Ok(Self {
+            configfs,
+            provider,
+            privlevel: None,
+            service_provider: None,
+        })
+    }
+
+    /// Set the privilege level of the reports, if the provider offers it.
+    pub fn set_privlevel(mut self, privlevel: u32) -> Self {
+        self.privlevel = Some(privlevel);
+        self
+    }
+
+    /// Set the service provider generating the reports, e.g. `svsm`, if
+    /// the provider offers it.
+    pub fn set_service_provider(mut self, service_provider: impl Into<String>) -> Self {
+        self.service_provider = Some(service_provider.into());
+        self
+    }
+
+    pub fn provider(&self) -> TsmReportProvider {
+        self.provider
+    }
+}
+
+#[async_trait::async_trait]
+impl Attester for TsmAttester {
+    async fn get_evidence(&self, mut report_data: Vec<u8>) -> Result<TeeEvidence> {
+        if report_data.len() > TSM_INBLOB_SIZE {
+            bail!("TSM Attester: Report data size must be {TSM_INBLOB_SIZE} bytes or less.");
+        }
+        report_data.resize(TSM_INBLOB_SIZE, 0);
+
+        let request = TsmReportRequest {
+            inblob: report_data,
+            privlevel: self.privlevel,
+            service_provider: self.service_provider.clone(),
+        };
+        let report = TsmReportPath::open(self.configfs.clone(), Some(self.provider))?
+            .generate(&request)
+            .context("TSM Attester: report generation failed")?;
+
+        let evidence = TsmEvidence {
+            provider: self.provider.name().to_string(),
+            outblob: STANDARD.encode(report.outblob),
+            auxblob: report.auxblob.map(|blob| STANDARD.encode(blob)),
+            manifestblob: report.manifestblob.map(|blob| STANDARD.encode(blob)),
+        };
+        let ev = serde_json::to_value(&evidence).context("Serialization of TSM evidence failed")?;
+        Ok(ev)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tsm_report::fake::FakeConfigfs;
+
+    #[tokio::test]
+    async fn test_tsm_attester_get_evidence() {
+        let configfs = Arc::new(
+            FakeConfigfs::new(TsmReportProvider::Sev).with_attributes(&["privlevel", "auxblob"]),
+        );
+        let attester = TsmAttester::with_configfs(configfs.clone())
+            .unwrap()
+            .set_privlevel(2)
+            .set_service_provider("svsm");
+        assert_eq!(attester.provider(), TsmReportProvider::Sev);
+
+        let evidence = attester.get_evidence(vec![1; 32]).await.unwrap();
+        let evidence: TsmEvidence = serde_json::from_value(evidence).unwrap();
+        let mut inblob = vec![1; 32];
+        inblob.resize(TSM_INBLOB_SIZE, 0);
+        assert_eq!(evidence.provider, "sev_guest");
+        assert_eq!(
+            STANDARD.decode(evidence.outblob).unwrap(),
+            FakeConfigfs::outblob(&inblob, Some("2\n"))
+        );
+        assert_eq!(
+            STANDARD.decode(evidence.auxblob.unwrap()).unwrap(),
+            FakeConfigfs::AUXBLOB
+        );
+        assert!(evidence.manifestblob.is_none());
+        assert_eq!(configfs.reports(), 0);
+
+        assert!(attester.get_evidence(vec![1; 65]).await.is_err());
+        configfs.race_next_report();
+        assert!(attester.get_evidence(vec![1; 32]).await.is_err());
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! A simulated configfs tree of TSM reports, behaving like the kernel: a
+//! report directory is populated with the attributes of the provider, each
+//! attribute write bumps the generation, and the outblob is generated from
+//! the inblob and the privlevel when read.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use tempfile::TempDir;
+
+use super::{TsmConfigfs, TsmReportProvider};
+
+pub(crate) struct FakeConfigfs {
+    root: TempDir,
+    provider: TsmReportProvider,
+
+    /// Optional attributes the provider offers
+    attributes: Vec<&'static str>,
+
+    next_report: AtomicU32,
+
+    /// Whether someone else writes the next report directory after its
+    /// inblob is written
+    race: AtomicBool,
+}
+
+impl FakeConfigfs {
+    pub(crate) const AUXBLOB: &'static [u8] = b"fake certificate chain";
+    pub(crate) const MANIFESTBLOB: &'static [u8] = b"fake manifest";
+
+    pub(crate) fn new(provider: TsmReportProvider) -> Self {
+        Self {
+            root: tempfile::tempdir().expect("create fake configfs"),
+            provider,
+            attributes: Vec::new(),
+            next_report: AtomicU32::new(0),
+            race: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn with_attributes(mut self, attributes: &[&'static str]) -> Self {
+        self.attributes = attributes.to_vec();
+        self
+    }
+
+    pub(crate) fn race_next_report(&self) {
+        self.race.store(true, Ordering::SeqCst);
+    }
+
+    /// Number of report directories.
+    pub(crate) fn reports(&self) -> usize {
+        std::fs::read_dir(self.root.path())
+            .expect("read fake configfs")
+            .count()
+    }
+
+    /// The outblob generated for the inblob and privlevel.
+    pub(crate) fn outblob(inblob: &[u8], privlevel: Option<&str>) -> Vec<u8> {
+        [b"report:", privlevel.unwrap_or_default().as_bytes(), inblob].concat()
+    }
+
+    fn bump_generation(report_path: &Path) -> std::io::Result<()> {
+        let path = report_path.join("generation");
+        let generation: u32 = std::fs::read_to_string(&path)?
+            .trim()
+            .parse()
+            .expect("generation is a number");
+        std::fs::write(path, format!("{}\n", generation + 1))
+    }
+}
+
+impl TsmConfigfs for FakeConfigfs {
+    fn create_report(&self) -> std::io::Result<PathBuf> {
+        let n = self.next_report.fetch_add(1, Ordering::SeqCst);
+        let path = self.root.path().join(format!("report{n}"));
+        std::fs::create_dir(&path)?;
+        std::fs::write(path.join("provider"), format!("{}\n", self.provider.name()))?;
+        std::fs::write(path.join("generation"), "0\n")?;
+        for attribute in &self.attributes {
+            std::fs::write(path.join(attribute), "")?;
+        }
+        Ok(path)
+    }
+
+    fn remove_report(&self, report_path: &Path) -> std::io::Result<()> {
+        std::fs::remove_dir_all(report_path)
+    }
+
+    fn read(&self, report_path: &Path, attribute: &str) -> std::io::Result<Vec<u8>> {
+        match attribute {
+            "outblob" => {
+                let inblob = std::fs::read(report_path.join("inblob"))?;
+                let privlevel = std::fs::read_to_string(report_path.join("privlevel")).ok();
+                Ok(Self::outblob(&inblob, privlevel.as_deref()))
+            }
+            "auxblob" if self.has_attribute(report_path, attribute) => Ok(Self::AUXBLOB.to_vec()),
+            "manifestblob" if self.has_attribute(report_path, attribute) => {
+                Ok(Self::MANIFESTBLOB.to_vec())
+            }
+            _ => std::fs::read(report_path.join(attribute)),
+        }
+    }
+
+    fn write(&self, report_path: &Path, attribute: &str, value: &[u8]) -> std::io::Result<()> {
+        std::fs::write(report_path.join(attribute), value)?;
+        Self::bump_generation(report_path)?;
+        if attribute == "inblob" && self.race.swap(false, Ordering::SeqCst) {
+            Self::bump_generation(report_path)?;
+        }
+        Ok(())
+    }
+}
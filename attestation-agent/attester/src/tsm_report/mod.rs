@@ -3,13 +3,22 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
+use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 use strum::EnumString;
 use tempfile::tempdir_in;
 use thiserror::Error;
 
-const TSM_REPORT_PATH: &str = "/sys/kernel/config/tsm/report";
+mod attester;
+#[cfg(test)]
+mod fake;
+
+pub use attester::TsmAttester;
+
+pub(crate) const TSM_REPORT_PATH: &str = "/sys/kernel/config/tsm/report";
 
 #[derive(Error, Debug)]
 pub enum TsmReportError {
@@ -25,13 +34,13 @@ pub enum TsmReportError {
     MissingProvider(TsmReportProvider, TsmReportProvider),
     #[error("Failed to open TSM Report path: unknown provider ({0})")]
     UnknownProvider(#[from] strum::ParseError),
-    #[error("Failed to generate TSM Report: inblob write conflict (generation={0}, expected 1)")]
-    InblobConflict(u32),
+    #[error("Failed to generate TSM Report: inblob write conflict (generation={0}, expected {1})")]
+    InblobConflict(u32, u32),
     #[error("Failed to generate TSM Report: missing inblob (len=0)")]
     InblobLen,
 }
 
-#[derive(PartialEq, Debug, EnumString)]
+#[derive(Clone, Copy, PartialEq, Debug, EnumString)]
 pub enum TsmReportProvider {
     #[strum(serialize = "arm_cca_guest\n")]
     Cca,
@@ -41,134 +50,329 @@ pub enum TsmReportProvider {
     Sev,
 }
 
+impl TsmReportProvider {
+    /// The name of the provider, as in the `provider` attribute.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cca => "arm_cca_guest",
+            Self::Tdx => "tdx_guest",
+            Self::Sev => "sev_guest",
+        }
+    }
+}
+
 pub enum TsmReportData {
     Cca(Vec<u8>),
     Tdx(Vec<u8>),
     Sev(u8, Vec<u8>),
 }
 
+/// A request for a TSM report. The optional attributes are only written if
+/// the provider offers them.
+#[derive(Clone, Debug, Default)]
+pub struct TsmReportRequest {
+    pub inblob: Vec<u8>,
+
+    /// Privilege level of the report, at least the `privlevel_floor` of the
+    /// provider.
+    pub privlevel: Option<u32>,
+
+    /// Service provider generating the report, e.g. `svsm` for the SEV
+    /// Secure VM Service Module.
+    pub service_provider: Option<String>,
+}
+
+/// A TSM report, with the blobs the provider returned along the outblob.
+#[derive(Clone, Debug, Default)]
+pub struct TsmReport {
+    pub outblob: Vec<u8>,
+
+    /// Supplemental data, e.g. the certificate chain of the report signing
+    /// key.
+    pub auxblob: Option<Vec<u8>>,
+
+    /// Manifest of the service provider, if one generated the report.
+    pub manifestblob: Option<Vec<u8>>,
+}
+
+/// The configfs tree of the TSM_REPORT attestation ABI. The reports are
+/// accessed through this trait, so that the attesters can be tested against
+/// a simulated configfs tree.
+pub trait TsmConfigfs: Send + Sync {
+    /// Create a report directory, returning its path.
+    fn create_report(&self) -> std::io::Result<PathBuf>;
+
+    fn remove_report(&self, report_path: &Path) -> std::io::Result<()>;
+
+    fn read(&self, report_path: &Path, attribute: &str) -> std::io::Result<Vec<u8>>;
+
+    fn write(&self, report_path: &Path, attribute: &str, value: &[u8]) -> std::io::Result<()>;
+
+    /// Whether the provider offers the attribute. The kernel hides the
+    /// attributes a provider does not support.
+    fn has_attribute(&self, report_path: &Path, attribute: &str) -> bool {
+        report_path.join(attribute).exists()
+    }
+}
+
+/// The TSM reports on the ConfigFS mounted at `root`, by default
+/// `/sys/kernel/config/tsm/report`.
+#[derive(Clone, Debug)]
+pub struct TsmReportRoot {
+    root: PathBuf,
+}
+
+impl Default for TsmReportRoot {
+    fn default() -> Self {
+        Self::new(TSM_REPORT_PATH)
+    }
+}
+
+impl TsmReportRoot {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl TsmConfigfs for TsmReportRoot {
+    fn create_report(&self) -> std::io::Result<PathBuf> {
+        // Remove the Drop set by tempdir_in() since it errors on ConfigFS
+        // and leaks the created path. TsmReportPath implements its own Drop
+        // that removes the path (rmdir way) when it goes out of scope.
+        Ok(tempdir_in(&self.root)?.keep())
+    }
+
+    fn remove_report(&self, report_path: &Path) -> std::io::Result<()> {
+        std::fs::remove_dir(report_path)
+    }
+
+    fn read(&self, report_path: &Path, attribute: &str) -> std::io::Result<Vec<u8>> {
+        std::fs::read(report_path.join(attribute))
+    }
+
+    fn write(&self, report_path: &Path, attribute: &str, value: &[u8]) -> std::io::Result<()> {
+        std::fs::write(report_path.join(attribute), value)
+    }
+}
+
 /// TsmReportPath instance represents a unique path on ConfigFS
 /// provided by the TSM_REPORT attestation ABI. Currently, each
 /// instance is a one-shot attestation request and the path is
 /// automatically removed when the instance goes out of scope.
 pub struct TsmReportPath {
+    configfs: Arc<dyn TsmConfigfs>,
     path: PathBuf,
+    provider: TsmReportProvider,
+
+    /// Number of attribute writes, each one bumping the generation
+    writes: AtomicU32,
 }
 
 impl Drop for TsmReportPath {
     fn drop(&mut self) {
-        let _ = std::fs::remove_dir(self.path.as_path())
+        let _ = self
+            .configfs
+            .remove_report(self.path.as_path())
             .map_err(|e| log::error!("Failed to remove TSM Report directory: {e}"));
     }
 }
 
 impl TsmReportPath {
     pub fn new(wanted: TsmReportProvider) -> Result<Self, TsmReportError> {
-        if !Path::new(TSM_REPORT_PATH).exists() {
-            return Err(TsmReportError::NoTsmReports);
-        }
-
-        let p = tempdir_in(TSM_REPORT_PATH).map_err(TsmReportError::Open)?;
+        Self::open(Arc::new(TsmReportRoot::default()), Some(wanted))
+    }
 
-        // Remove the Drop set by tempdir_in() since it errors on ConfigFS
-        // and leaks the created path. We implement our own Drop that removes the
-        // path (rmdir way) when TsmReportPath instance goes out of scope.
-        let path = p.keep();
+    /// Create a report directory on the configfs. If a provider is wanted,
+    /// it is checked to be the provider of the TEE, otherwise the provider
+    /// is detected.
+    pub fn open(
+        configfs: Arc<dyn TsmConfigfs>,
+        wanted: Option<TsmReportProvider>,
+    ) -> Result<Self, TsmReportError> {
+        let path = configfs.create_report().map_err(|e| match e.kind() {
+            ErrorKind::NotFound => TsmReportError::NoTsmReports,
+            _ => TsmReportError::Open(e),
+        })?;
 
-        check_tsm_report_provider(path.as_path(), wanted).inspect_err(|_| {
-            let _ = std::fs::remove_dir(path.as_path());
+        let provider = match wanted {
+            Some(wanted) => check_tsm_report_provider(configfs.as_ref(), &path, wanted),
+            None => read_tsm_report_provider(configfs.as_ref(), &path),
+        };
+        let provider = provider.inspect_err(|_| {
+            let _ = configfs.remove_report(&path);
         })?;
 
-        Ok(Self { path })
+        Ok(Self {
+            configfs,
+            path,
+            provider,
+            writes: AtomicU32::new(0),
+        })
+    }
+
+    pub fn provider(&self) -> TsmReportProvider {
+        self.provider
     }
+
     pub fn attestation_report(
         &self,
         provider_data: TsmReportData,
     ) -> Result<Vec<u8>, TsmReportError> {
-        let report_path = self.path.as_path();
-
-        let report_data = match provider_data {
-            TsmReportData::Cca(inblob) => inblob,
-            TsmReportData::Tdx(inblob) => inblob,
-            TsmReportData::Sev(privlevel, inblob) => {
-                // TODO: untested
-                std::fs::write(report_path.join("privlevel"), vec![privlevel])
-                    .map_err(|e| TsmReportError::Access("privlevel", e))?;
-                inblob
-            }
+        let request = match provider_data {
+            TsmReportData::Cca(inblob) | TsmReportData::Tdx(inblob) => TsmReportRequest {
+                inblob,
+                ..Default::default()
+            },
+            TsmReportData::Sev(privlevel, inblob) => TsmReportRequest {
+                inblob,
+                privlevel: Some(privlevel.into()),
+                ..Default::default()
+            },
         };
 
-        if report_data.is_empty() {
+        Ok(self.generate(&request)?.outblob)
+    }
+
+    /// Generate a report, reading along the outblob the auxblob and the
+    /// manifestblob if the provider offers them.
+    pub fn generate(&self, request: &TsmReportRequest) -> Result<TsmReport, TsmReportError> {
+        if request.inblob.is_empty() {
             return Err(TsmReportError::InblobLen);
         }
 
-        std::fs::write(report_path.join("inblob"), report_data)
-            .map_err(|e| TsmReportError::Access("inblob", e))?;
+        if let Some(privlevel) = request.privlevel {
+            self.write_offered("privlevel", format!("{privlevel}\n").as_bytes())?;
+        }
+        if let Some(service_provider) = &request.service_provider {
+            self.write_offered("service_provider", service_provider.as_bytes())?;
+        }
+        self.write("inblob", &request.inblob)?;
 
-        let q = std::fs::read(report_path.join("outblob"))
-            .map_err(|e| TsmReportError::Access("outblob", e))?;
+        let outblob = self.read("outblob")?;
+        let auxblob = self.read_offered("auxblob")?;
+        let manifestblob = self.read_offered("manifestblob")?;
 
-        check_inblob_write_race(report_path)?;
+        check_write_race(
+            self.configfs.as_ref(),
+            &self.path,
+            self.writes.load(Ordering::SeqCst),
+        )?;
 
-        Ok(q)
+        Ok(TsmReport {
+            outblob,
+            auxblob,
+            manifestblob,
+        })
     }
-    pub fn supplemental_data(&self) -> Result<Vec<u8>, TsmReportError> {
-        let report_path = self.path.as_path();
 
-        let aux = std::fs::read(report_path.join("auxblob"))
-            .map_err(|e| TsmReportError::Access("auxblob", e))?;
+    pub fn supplemental_data(&self) -> Result<Vec<u8>, TsmReportError> {
+        let aux = self.read("auxblob")?;
 
-        check_inblob_write_race(report_path)?;
+        check_write_race(
+            self.configfs.as_ref(),
+            &self.path,
+            self.writes.load(Ordering::SeqCst),
+        )?;
 
         Ok(aux)
     }
+
+    fn read(&self, attribute: &'static str) -> Result<Vec<u8>, TsmReportError> {
+        self.configfs
+            .read(&self.path, attribute)
+            .map_err(|e| TsmReportError::Access(attribute, e))
+    }
+
+    /// Read the attribute if the provider offers it and it is not empty.
+    fn read_offered(&self, attribute: &'static str) -> Result<Option<Vec<u8>>, TsmReportError> {
+        if !self.configfs.has_attribute(&self.path, attribute) {
+            return Ok(None);
+        }
+
+        let blob = self.read(attribute)?;
+        Ok((!blob.is_empty()).then_some(blob))
+    }
+
+    fn write(&self, attribute: &'static str, value: &[u8]) -> Result<(), TsmReportError> {
+        self.configfs
+            .write(&self.path, attribute, value)
+            .map_err(|e| TsmReportError::Access(attribute, e))?;
+        self.writes.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Write the attribute if the provider offers it.
+    fn write_offered(&self, attribute: &'static str, value: &[u8]) -> Result<(), TsmReportError> {
+        if !self.configfs.has_attribute(&self.path, attribute) {
+            log::warn!(
+                "TSM Report provider {} does not offer attribute {attribute}, ignored",
+                self.provider.name()
+            );
+            return Ok(());
+        }
+
+        self.write(attribute, value)
+    }
 }
 
-/// check_inblob_write_race checks that the returned outblob/auxblob
+/// check_write_race checks that the returned outblob/auxblob
 /// matches the quote generation request originally triggered when
-/// inblob was written by the TsmReportPath instance. It prevents
-/// the race condition that someone else could use the same temporary
-/// directory to generate a quote.
-fn check_inblob_write_race(report_path: &Path) -> Result<(), TsmReportError> {
-    let g = std::fs::read_to_string(report_path.join("generation"))
+/// the attributes were written by the TsmReportPath instance. Each write
+/// bumps the generation, so a generation beyond the number of writes
+/// means that someone else used the same temporary directory to
+/// generate a quote.
+fn check_write_race(
+    configfs: &dyn TsmConfigfs,
+    report_path: &Path,
+    writes: u32,
+) -> Result<(), TsmReportError> {
+    let g = configfs
+        .read(report_path, "generation")
         .map_err(|e| TsmReportError::Access("generation", e))?;
 
-    let generation = g
+    let generation = String::from_utf8_lossy(&g)
         .trim_matches('\n')
-        .to_string()
         .parse::<u32>()
         .map_err(TsmReportError::Parse)?;
 
-    if generation > 1 {
-        return Err(TsmReportError::InblobConflict(generation));
+    if generation > writes {
+        return Err(TsmReportError::InblobConflict(generation, writes));
     }
 
     Ok(())
 }
 
+fn read_tsm_report_provider(
+    configfs: &dyn TsmConfigfs,
+    report_path: &Path,
+) -> Result<TsmReportProvider, TsmReportError> {
+    let report_provider = configfs
+        .read(report_path, "provider")
+        .map_err(|e| TsmReportError::Access("provider", e))?;
+
+    Ok(TsmReportProvider::from_str(&String::from_utf8_lossy(
+        &report_provider,
+    ))?)
+}
+
 /// check_tsm_report_provider checks that the TEE is
 /// the requested TsmReportProvider.
 fn check_tsm_report_provider(
+    configfs: &dyn TsmConfigfs,
     report_path: &Path,
     wanted: TsmReportProvider,
-) -> Result<(), TsmReportError> {
-    let report_provider = std::fs::read_to_string(report_path.join("provider"))
-        .map_err(|e| TsmReportError::Access("provider", e))?;
-
-    match TsmReportProvider::from_str(&report_provider) {
-        Ok(provider) => {
-            if provider == wanted {
-                Ok(())
-            } else {
-                Err(TsmReportError::MissingProvider(wanted, provider))
-            }
-        }
-        Err(e) => Err(TsmReportError::UnknownProvider(e)),
+) -> Result<TsmReportProvider, TsmReportError> {
+    let provider = read_tsm_report_provider(configfs, report_path)?;
+    if provider != wanted {
+        return Err(TsmReportError::MissingProvider(wanted, provider));
     }
+
+    Ok(provider)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::fake::FakeConfigfs;
     use super::*;
     use rstest::*;
 
@@ -181,19 +385,102 @@ mod tests {
     #[case("generation", "parseerror\n", true)]
     fn test_tsm_report(#[case] file: &str, #[case] file_data: &str, #[case] expect_error: bool) {
         let tsm_dir = tempfile::tempdir().unwrap();
+        let configfs = TsmReportRoot::new(tsm_dir.path());
 
         std::fs::write(tsm_dir.path().join(file), file_data).unwrap();
 
         match file {
             "provider" => assert_eq!(
                 expect_error,
-                check_tsm_report_provider(tsm_dir.path(), TsmReportProvider::Tdx).is_err()
+                check_tsm_report_provider(&configfs, tsm_dir.path(), TsmReportProvider::Tdx)
+                    .is_err()
             ),
             "generation" => assert_eq!(
                 expect_error,
-                check_inblob_write_race(tsm_dir.path()).is_err(),
+                check_write_race(&configfs, tsm_dir.path(), 1).is_err(),
             ),
             _ => unimplemented!(),
         }
     }
+
+    #[rstest]
+    #[case(TsmReportProvider::Tdx, Some(TsmReportProvider::Tdx), false)]
+    #[case(TsmReportProvider::Sev, None, false)]
+    #[case(TsmReportProvider::Cca, Some(TsmReportProvider::Tdx), true)]
+    fn test_tsm_report_path_open(
+        #[case] provider: TsmReportProvider,
+        #[case] wanted: Option<TsmReportProvider>,
+        #[case] expect_error: bool,
+    ) {
+        let configfs = Arc::new(FakeConfigfs::new(provider));
+        let report = TsmReportPath::open(configfs.clone(), wanted);
+        assert_eq!(expect_error, report.is_err());
+        if let Ok(report) = report {
+            assert_eq!(report.provider(), provider);
+            assert_eq!(configfs.reports(), 1);
+            drop(report);
+        }
+        assert_eq!(configfs.reports(), 0);
+
+        let missing = TsmReportRoot::new("/nonexistent/tsm/report");
+        assert!(matches!(
+            TsmReportPath::open(Arc::new(missing), wanted),
+            Err(TsmReportError::NoTsmReports)
+        ));
+    }
+
+    #[test]
+    fn test_tsm_report_generate() {
+        let configfs = Arc::new(FakeConfigfs::new(TsmReportProvider::Tdx));
+        let report = TsmReportPath::open(configfs.clone(), None).unwrap();
+        let request = TsmReportRequest {
+            inblob: vec![1; 64],
+            privlevel: Some(2),
+            service_provider: Some("svsm".into()),
+        };
+
+        // Attributes not offered are ignored
+        let tsm_report = report.generate(&request).unwrap();
+        assert_eq!(
+            tsm_report.outblob,
+            FakeConfigfs::outblob(&request.inblob, None)
+        );
+        assert!(tsm_report.auxblob.is_none());
+        assert!(tsm_report.manifestblob.is_none());
+        assert!(matches!(
+            report.generate(&TsmReportRequest::default()),
+            Err(TsmReportError::InblobLen)
+        ));
+
+        // Each write bumps the generation
+        let configfs = Arc::new(FakeConfigfs::new(TsmReportProvider::Sev).with_attributes(&[
+            "privlevel",
+            "service_provider",
+            "auxblob",
+            "manifestblob",
+        ]));
+        let report = TsmReportPath::open(configfs.clone(), None).unwrap();
+        let tsm_report = report.generate(&request).unwrap();
+        assert_eq!(
+            tsm_report.outblob,
+            FakeConfigfs::outblob(&request.inblob, Some("2\n"))
+        );
+        assert_eq!(tsm_report.auxblob.unwrap(), FakeConfigfs::AUXBLOB);
+        assert_eq!(tsm_report.manifestblob.unwrap(), FakeConfigfs::MANIFESTBLOB);
+        assert_eq!(report.supplemental_data().unwrap(), FakeConfigfs::AUXBLOB);
+
+        let report = TsmReportPath::open(configfs.clone(), None).unwrap();
+        let outblob = report
+            .attestation_report(TsmReportData::Sev(1, vec![2; 64]))
+            .unwrap();
+        assert_eq!(outblob, FakeConfigfs::outblob(&[2; 64], Some("1\n")));
+
+        // Someone else wrote to the report directory
+        configfs.race_next_report();
+        let report = TsmReportPath::open(configfs, None).unwrap();
+        assert!(matches!(
+            report.generate(&request),
+            Err(TsmReportError::InblobConflict(4, 3))
+        ));
+    }
 }
@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use std::{env, path::Path};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{bail, Result};
 use base64::{engine::general_purpose::STANDARD, Engine};
@@ -139,8 +142,20 @@ fn trim_ccel(mut ccel: Vec<u8>) -> Result<Vec<u8>> {
     }
 }
 
-pub async fn read_eventlog() -> Result<Option<String>> {
-    let aael_path = env::var("AAEL_PATH").unwrap_or(DEFAULT_AAEL_PATH.to_string());
+/// Read the CCEL followed by the AAEL at `aael_path`. If not given, the AAEL
+/// at `AAEL_PATH`, or [`DEFAULT_AAEL_PATH`] if the environment variable is not
+/// set, is read.
+pub async fn read_eventlog(aael_path: Option<&Path>) -> Result<Option<String>> {
+    let aael_path = match aael_path {
+        Some(path) => path.to_path_buf(),
+        None => PathBuf::from(env::var("AAEL_PATH").unwrap_or(DEFAULT_AAEL_PATH.to_string())),
+    };
+    read_eventlog_from(&aael_path).await
+}
+
+/// Read the CCEL followed by the AAEL at `aael_path`. The result is base64
+/// encoded, and `None` is returned if neither of them exists.
+pub async fn read_eventlog_from(aael_path: &Path) -> Result<Option<String>> {
     let mut eventlog = Vec::new();
     if Path::new(CCEL_PATH).exists() {
         let mut file = File::open(CCEL_PATH).await?;
@@ -148,7 +163,7 @@ pub async fn read_eventlog() -> Result<Option<String>> {
         eventlog = trim_ccel(eventlog)?;
     }
 
-    if Path::new(&aael_path).exists() {
+    if aael_path.exists() {
         let mut file = File::open(aael_path).await?;
         if eventlog.is_empty() {
             eventlog.extend_from_slice(&EL_HEADER);
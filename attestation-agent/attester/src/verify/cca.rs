@@ -0,0 +1,374 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! CCA attestation tokens: a CBOR collection of the platform token, signed
+//! by the CPAK, and the realm token, signed by the RAK. The platform token
+//! binds the RAK with its hash as challenge.
+
+use anyhow::{bail, Context, Result};
+use ciborium::Value as Cbor;
+use p384::ecdsa::Signature;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use super::{verify_p384, CheckStatus, ParsedEvidence};
+
+const CCA_TOKEN_COLLECTION_TAG: u64 = 399;
+const CCA_PLATFORM_TOKEN: i128 = 44234;
+const CCA_REALM_TOKEN: i128 = 44241;
+
+const COSE_SIGN1_TAG: u64 = 18;
+const COSE_HEADER_ALG: i128 = 1;
+const COSE_ALG_ES384: i128 = -35;
+const COSE_KEY_KTY: i128 = 1;
+const COSE_KEY_KTY_EC2: i128 = 2;
+const COSE_KEY_CRV: i128 = -1;
+const COSE_KEY_CRV_P384: i128 = 2;
+const COSE_KEY_X: i128 = -2;
+const COSE_KEY_Y: i128 = -3;
+
+const CHALLENGE: i128 = 10;
+const REALM_PUBLIC_KEY: i128 = 44237;
+const REALM_PUBLIC_KEY_HASH_ALGO: i128 = 44240;
+
+fn realm_claim_name(key: i128) -> Option<&'static str> {
+    match key {
+        10 => Some("challenge"),
+        265 => Some("profile"),
+        44235 => Some("personalization_value"),
+        44236 => Some("hash_algo_id"),
+        44237 => Some("public_key"),
+        44238 => Some("initial_measurement"),
+        44239 => Some("extensible_measurements"),
+        44240 => Some("public_key_hash_algo_id"),
+        _ => None,
+    }
+}
+
+fn platform_claim_name(key: i128) -> Option<&'static str> {
+    match key {
+        10 => Some("challenge"),
+        256 => Some("instance_id"),
+        265 => Some("profile"),
+        2395 => Some("lifecycle"),
+        2396 => Some("implementation_id"),
+        2399 => Some("sw_components"),
+        2400 => Some("verification_service"),
+        2401 => Some("config"),
+        2402 => Some("hash_algo_id"),
+        _ => None,
+    }
+}
+
+/// Decode a single CBOR item filling the bytes.
+fn decode(bytes: &[u8]) -> Result<Cbor> {
+    let mut rest = bytes;
+    let item = ciborium::from_reader(&mut rest)?;
+    if !rest.is_empty() {
+        bail!("{} trailing bytes after CBOR item", rest.len());
+    }
+    Ok(item)
+}
+
+fn encode(item: &Cbor) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(item, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// The item, without the tag if it is tagged with it.
+fn untag(item: Cbor, tag: u64) -> Cbor {
+    match item {
+        Cbor::Tag(t, item) if t == tag => *item,
+        item => item,
+    }
+}
+
+fn as_int(item: &Cbor) -> Option<i128> {
+    item.as_integer().map(i128::from)
+}
+
+fn as_bytes(item: &Cbor) -> Option<&[u8]> {
+    item.as_bytes().map(Vec::as_slice)
+}
+
+/// The value of the integer key, if the item is a map.
+fn get(item: &Cbor, key: i128) -> Option<&Cbor> {
+    item.as_map()?
+        .iter()
+        .find(|(k, _)| as_int(k) == Some(key))
+        .map(|(_, v)| v)
+}
+
+/// Convert to JSON, with hex encoded byte strings. Integer map keys are
+/// named with `key_name`, or are their number.
+fn to_json(item: &Cbor, key_name: &dyn Fn(i128) -> Option<&'static str>) -> Value {
+    match item {
+        Cbor::Integer(n) => u64::try_from(*n)
+            .map(Value::from)
+            .or_else(|_| i64::try_from(*n).map(Value::from))
+            .unwrap_or(Value::Null),
+        Cbor::Bytes(bytes) => hex::encode(bytes).into(),
+        Cbor::Float(f) => (*f).into(),
+        Cbor::Text(text) => text.clone().into(),
+        Cbor::Bool(b) => (*b).into(),
+        Cbor::Tag(_, item) => to_json(item, key_name),
+        Cbor::Array(items) => items.iter().map(|item| to_json(item, key_name)).collect(),
+        Cbor::Map(entries) => {
+            let mut map = Map::new();
+            for (key, value) in entries {
+                let key = match (key.as_text(), as_int(key)) {
+                    (Some(text), _) => text.to_string(),
+                    (None, Some(n)) => key_name(n).map_or_else(|| n.to_string(), String::from),
+                    (None, None) => format!("{key:?}"),
+                };
+                map.insert(key, to_json(value, key_name));
+            }
+            Value::Object(map)
+        }
+        _ => Value::Null,
+    }
+}
+
+#[derive(Deserialize)]
+struct CcaEvidence {
+    token: Vec<u8>,
+}
+
+/// A COSE_Sign1 message.
+struct Sign1 {
+    protected: Vec<u8>,
+    payload: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+impl Sign1 {
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let Cbor::Array(items) = untag(decode(bytes)?, COSE_SIGN1_TAG) else {
+            bail!("COSE_Sign1 is not an array");
+        };
+        match <[Cbor; 4]>::try_from(items) {
+            Ok(
+                [Cbor::Bytes(protected), Cbor::Map(_), Cbor::Bytes(payload), Cbor::Bytes(signature)],
+            ) => Ok(Self {
+                protected,
+                payload,
+                signature,
+            }),
+            _ => bail!("Malformed COSE_Sign1"),
+        }
+    }
+
+    fn algorithm(&self) -> Result<i128> {
+        get(&decode(&self.protected)?, COSE_HEADER_ALG)
+            .and_then(as_int)
+            .context("No algorithm in the protected header")
+    }
+
+    /// The `Sig_structure` the signature is over.
+    fn to_be_signed(&self) -> Result<Vec<u8>> {
+        encode(&Cbor::Array(vec![
+            Cbor::Text("Signature1".into()),
+            Cbor::Bytes(self.protected.clone()),
+            Cbor::Bytes(Vec::new()),
+            Cbor::Bytes(self.payload.clone()),
+        ]))
+    }
+}
+
+/// The SEC1 point of the RAK, a raw point or a COSE_Key.
+fn realm_public_key(key: &[u8]) -> Result<Vec<u8>> {
+    if key.first() == Some(&0x04) {
+        return Ok(key.to_vec());
+    }
+
+    let key = decode(key).context("Parse RAK failed")?;
+    if get(&key, COSE_KEY_KTY).and_then(as_int) != Some(COSE_KEY_KTY_EC2)
+        || get(&key, COSE_KEY_CRV).and_then(as_int) != Some(COSE_KEY_CRV_P384)
+    {
+        bail!("RAK is not a P-384 key");
+    }
+    let x = get(&key, COSE_KEY_X).and_then(as_bytes);
+    let y = get(&key, COSE_KEY_Y).and_then(as_bytes);
+    match (x, y) {
+        (Some(x), Some(y)) => Ok([&[0x04], x, y].concat()),
+        _ => bail!("RAK without coordinates"),
+    }
+}
+
+fn check_realm_token(
+    realm: &Sign1,
+    realm_claims: &Cbor,
+    platform_claims: &Cbor,
+) -> Result<CheckStatus> {
+    let algorithm = realm.algorithm()?;
+    if algorithm != COSE_ALG_ES384 {
+        return Ok(CheckStatus::Invalid(format!(
+            "Unsupported realm token algorithm {algorithm}"
+        )));
+    }
+
+    let rak = get(realm_claims, REALM_PUBLIC_KEY)
+        .and_then(as_bytes)
+        .context("No RAK in the realm token")?;
+    let signature = Signature::from_slice(&realm.signature)
+        .map_err(anyhow::Error::from)
+        .and_then(|signature| {
+            verify_p384(&realm_public_key(rak)?, &realm.to_be_signed()?, &signature)
+        });
+    if let Err(e) = signature {
+        return Ok(CheckStatus::Invalid(format!(
+            "Realm token signature: {e:#}"
+        )));
+    }
+
+    let rak_hash = match get(realm_claims, REALM_PUBLIC_KEY_HASH_ALGO).and_then(Cbor::as_text) {
+        Some("sha-256") => Sha256::digest(rak).to_vec(),
+        Some("sha-384") => Sha384::digest(rak).to_vec(),
+        Some("sha-512") => Sha512::digest(rak).to_vec(),
+        algorithm => bail!("Unsupported RAK hash algorithm {algorithm:?}"),
+    };
+    if get(platform_claims, CHALLENGE).and_then(as_bytes) != Some(&rak_hash[..]) {
+        return Ok(CheckStatus::Invalid(
+            "The RAK is not bound to the platform token".into(),
+        ));
+    }
+
+    Ok(CheckStatus::Verified("RAK of the evidence".into()))
+}
+
+pub(super) fn parse(evidence: &Value) -> Result<ParsedEvidence> {
+    let evidence: CcaEvidence = serde_json::from_value(evidence.clone())?;
+    let collection = untag(
+        decode(&evidence.token).context("Parse CCA token failed")?,
+        CCA_TOKEN_COLLECTION_TAG,
+    );
+
+    let token = |key: i128, name: &str| {
+        get(&collection, key)
+            .and_then(as_bytes)
+            .with_context(|| format!("No {name} token in the collection"))
+            .and_then(Sign1::decode)
+    };
+    let platform = token(CCA_PLATFORM_TOKEN, "platform")?;
+    let realm = token(CCA_REALM_TOKEN, "realm")?;
+    let platform_claims = decode(&platform.payload).context("Parse platform claims failed")?;
+    let realm_claims = decode(&realm.payload).context("Parse realm claims failed")?;
+
+    let challenge = get(&realm_claims, CHALLENGE)
+        .and_then(as_bytes)
+        .context("No challenge in the realm token")?;
+
+    Ok(ParsedEvidence {
+        report_data: challenge.to_vec(),
+        signature: check_realm_token(&realm, &realm_claims, &platform_claims)?,
+        cert_chain: CheckStatus::NotChecked(
+            "The platform token is signed by the CPAK, which is not part of the evidence".into(),
+        ),
+        claims: json!({
+            "realm": to_json(&realm_claims, &realm_claim_name),
+            "platform": to_json(&platform_claims, &platform_claim_name),
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use p384::ecdsa::{signature::Signer, SigningKey};
+
+    use super::*;
+
+    fn int(n: i128) -> Cbor {
+        Cbor::Integer(n.try_into().unwrap())
+    }
+
+    fn sign1(payload: &Cbor, key: &SigningKey) -> Vec<u8> {
+        let mut message = Sign1 {
+            protected: encode(&Cbor::Map(vec![(
+                int(COSE_HEADER_ALG),
+                int(COSE_ALG_ES384),
+            )]))
+            .unwrap(),
+            payload: encode(payload).unwrap(),
+            signature: Vec::new(),
+        };
+        let signature: Signature = key.sign(&message.to_be_signed().unwrap());
+        message.signature = signature.to_bytes().to_vec();
+
+        encode(&Cbor::Tag(
+            COSE_SIGN1_TAG,
+            Box::new(Cbor::Array(vec![
+                Cbor::Bytes(message.protected),
+                Cbor::Map(Vec::new()),
+                Cbor::Bytes(message.payload),
+                Cbor::Bytes(message.signature),
+            ])),
+        ))
+        .unwrap()
+    }
+
+    fn token(challenge: &[u8], platform_challenge: Option<&[u8]>) -> Vec<u8> {
+        let rak = SigningKey::from_slice(&[1; 48]).unwrap();
+        let rak_public = rak
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec();
+        let cpak = SigningKey::from_slice(&[2; 48]).unwrap();
+
+        let realm_claims = Cbor::Map(vec![
+            (int(CHALLENGE), Cbor::Bytes(challenge.to_vec())),
+            (int(44238), Cbor::Bytes(vec![3; 48])),
+            (int(REALM_PUBLIC_KEY), Cbor::Bytes(rak_public.clone())),
+            (
+                int(REALM_PUBLIC_KEY_HASH_ALGO),
+                Cbor::Text("sha-256".into()),
+            ),
+        ]);
+        let rak_hash = Sha256::digest(&rak_public).to_vec();
+        let platform_claims = Cbor::Map(vec![(
+            int(CHALLENGE),
+            Cbor::Bytes(platform_challenge.map_or(rak_hash, <[u8]>::to_vec)),
+        )]);
+
+        encode(&Cbor::Tag(
+            CCA_TOKEN_COLLECTION_TAG,
+            Box::new(Cbor::Map(vec![
+                (
+                    int(CCA_PLATFORM_TOKEN),
+                    Cbor::Bytes(sign1(&platform_claims, &cpak)),
+                ),
+                (
+                    int(CCA_REALM_TOKEN),
+                    Cbor::Bytes(sign1(&realm_claims, &rak)),
+                ),
+            ])),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_cca_token() {
+        let evidence = json!({ "token": token(&[4; 64], None) });
+        let parsed = parse(&evidence).unwrap();
+        assert_eq!(parsed.report_data, [4; 64]);
+        assert!(matches!(parsed.signature, CheckStatus::Verified(_)));
+        assert_eq!(
+            parsed.claims["realm"]["initial_measurement"],
+            hex::encode([3; 48])
+        );
+
+        let evidence = json!({ "token": token(&[4; 64], Some(&[0; 32])) });
+        assert!(parse(&evidence).unwrap().signature.is_invalid());
+
+        let evidence = json!({ "token": [1, 2, 3] });
+        assert!(parse(&evidence).is_err());
+
+        let mut truncated = token(&[4; 64], None);
+        truncated.pop();
+        assert!(parse(&json!({ "token": truncated })).is_err());
+    }
+}
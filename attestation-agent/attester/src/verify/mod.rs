@@ -0,0 +1,243 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Local verification of evidence.
+//!
+//! [`verify_evidence`] parses the evidence of a platform into normalised
+//! claims, checks that it carries the expected report data, and checks its
+//! signature and certificate chain offline with the keys and certificates in
+//! the evidence. It helps debugging attesters: the roots of the certificate
+//! chains are not pinned and no collateral, e.g. TCB info or CRLs, is
+//! fetched, so it does not replace a verifier.
+
+use anyhow::{bail, Context, Result};
+use kbs_types::Tee;
+use rsa::{pkcs8::DecodePublicKey, RsaPublicKey};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha384;
+use x509_cert::{
+    der::{asn1::ObjectIdentifier, Encode},
+    Certificate,
+};
+
+use crate::TeeEvidence;
+
+mod cca;
+mod sample;
+mod snp;
+mod tdx;
+mod tpm;
+
+/// The TEEs whose evidence can be verified.
+pub const VERIFIABLE_TEES: &[Tee] = &[Tee::Sample, Tee::Tdx, Tee::Snp, Tee::Tpm, Tee::Cca];
+
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const ECDSA_WITH_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+const RSASSA_PSS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.10");
+
+/// Result of a check of the evidence.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// Passed, with what the evidence was checked against
+    Verified(String),
+
+    /// Not checked, with the reason
+    NotChecked(String),
+
+    /// Failed, with the reason
+    Invalid(String),
+}
+
+impl CheckStatus {
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, CheckStatus::Invalid(_))
+    }
+}
+
+/// Normalised claims of evidence.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EvidenceClaims {
+    pub tee: Tee,
+
+    /// Hex encoded report data of the evidence
+    pub report_data: String,
+
+    /// Whether the report data of the evidence is the expected one, padded
+    /// with zeros to the report data size of the platform.
+    pub report_data_matches: bool,
+
+    /// Signature of the evidence
+    pub signature: CheckStatus,
+
+    /// Certificate chain of the key signing the evidence
+    pub cert_chain: CheckStatus,
+
+    /// Claims of the platform, e.g. the measurements
+    pub claims: Value,
+}
+
+impl EvidenceClaims {
+    /// Whether the report data matches and none of the checks failed.
+    pub fn is_valid(&self) -> bool {
+        self.report_data_matches && !self.signature.is_invalid() && !self.cert_chain.is_invalid()
+    }
+}
+
+/// Evidence parsed by the module of a platform.
+struct ParsedEvidence {
+    report_data: Vec<u8>,
+    signature: CheckStatus,
+    cert_chain: CheckStatus,
+    claims: Value,
+}
+
+/// Parse the evidence of the TEE, as returned by its attester, and check it
+/// against the report data the evidence was requested with.
+///
+/// An error is returned if the evidence cannot be parsed. Failed checks are
+/// reported in the claims, see [`EvidenceClaims::is_valid`].
+pub fn verify_evidence(
+    tee: Tee,
+    evidence: &TeeEvidence,
+    report_data: &[u8],
+) -> Result<EvidenceClaims> {
+    let parsed = match tee {
+        Tee::Sample => sample::parse(evidence),
+        Tee::Tdx => tdx::parse(evidence),
+        Tee::Snp => snp::parse(evidence),
+        Tee::Tpm => tpm::parse(evidence),
+        Tee::Cca => cca::parse(evidence),
+        tee => bail!("Verification of {tee:?} evidence is not supported"),
+    }
+    .with_context(|| format!("Parse {tee:?} evidence failed"))?;
+
+    // Attesters pad the report data with zeros
+    let mut expected = report_data.to_vec();
+    if expected.len() < parsed.report_data.len() {
+        expected.resize(parsed.report_data.len(), 0);
+    }
+
+    Ok(EvidenceClaims {
+        tee,
+        report_data: hex::encode(&parsed.report_data),
+        report_data_matches: expected == parsed.report_data,
+        signature: parsed.signature,
+        cert_chain: parsed.cert_chain,
+        claims: parsed.claims,
+    })
+}
+
+/// The `len` bytes of `bytes` at `offset`.
+fn field(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| bytes.get(offset..end))
+        .with_context(|| format!("Evidence too short for {len} bytes at offset {offset}"))
+}
+
+fn verify_p256(key_sec1: &[u8], message: &[u8], signature: &p256::ecdsa::Signature) -> Result<()> {
+    use p256::ecdsa::{signature::Verifier, VerifyingKey};
+
+    VerifyingKey::from_sec1_bytes(key_sec1)?.verify(message, signature)?;
+    Ok(())
+}
+
+fn verify_p384(key_sec1: &[u8], message: &[u8], signature: &p384::ecdsa::Signature) -> Result<()> {
+    use p384::ecdsa::{signature::Verifier, VerifyingKey};
+
+    VerifyingKey::from_sec1_bytes(key_sec1)?.verify(message, signature)?;
+    Ok(())
+}
+
+/// The raw bytes of the subject public key of the certificate, e.g. the
+/// SEC1 point of an EC key.
+fn subject_public_key(cert: &Certificate) -> Result<&[u8]> {
+    cert.tbs_certificate
+        .subject_public_key_info
+        .subject_public_key
+        .as_bytes()
+        .context("Malformed subject public key")
+}
+
+fn verify_issued_by(cert: &Certificate, issuer: &Certificate) -> Result<()> {
+    if cert.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+        bail!("Issued by {}", cert.tbs_certificate.issuer);
+    }
+
+    let message = cert.tbs_certificate.to_der()?;
+    let signature = cert
+        .signature
+        .as_bytes()
+        .context("Malformed certificate signature")?;
+    let algorithm = cert.signature_algorithm.oid;
+    if algorithm == ECDSA_WITH_SHA256 {
+        let signature = p256::ecdsa::Signature::from_der(signature)?;
+        verify_p256(subject_public_key(issuer)?, &message, &signature)
+    } else if algorithm == ECDSA_WITH_SHA384 {
+        let signature = p384::ecdsa::Signature::from_der(signature)?;
+        verify_p384(subject_public_key(issuer)?, &message, &signature)
+    } else if algorithm == RSASSA_PSS {
+        use rsa::signature::Verifier;
+
+        // Only used by the AMD SEV certificates, which are signed with SHA-384
+        let key = RsaPublicKey::from_public_key_der(
+            &issuer.tbs_certificate.subject_public_key_info.to_der()?,
+        )?;
+        let signature = rsa::pss::Signature::try_from(signature)?;
+        rsa::pss::VerifyingKey::<Sha384>::new(key).verify(&message, &signature)?;
+        Ok(())
+    } else {
+        bail!("Unsupported signature algorithm {algorithm}");
+    }
+}
+
+/// Check that each certificate of the chain, starting with the leaf, is
+/// issued by the next one, and the last one is self-signed.
+fn verify_cert_chain(chain: &[Certificate]) -> CheckStatus {
+    let Some(root) = chain.last() else {
+        return CheckStatus::NotChecked("No certificate chain in the evidence".into());
+    };
+
+    let issuers = chain.iter().skip(1).chain(std::iter::once(root));
+    for (cert, issuer) in chain.iter().zip(issuers) {
+        if let Err(e) = verify_issued_by(cert, issuer) {
+            return CheckStatus::Invalid(format!(
+                "Certificate {}: {e:#}",
+                cert.tbs_certificate.subject
+            ));
+        }
+    }
+
+    CheckStatus::Verified(format!("Root {}", root.tbs_certificate.subject))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sample::SampleAttester, Attester};
+
+    #[tokio::test]
+    async fn test_verify_sample_evidence() {
+        let evidence = SampleAttester::default()
+            .get_evidence(vec![1; 32])
+            .await
+            .unwrap();
+
+        let claims = verify_evidence(Tee::Sample, &evidence, &[1; 32]).unwrap();
+        assert!(claims.report_data_matches);
+        assert!(claims.is_valid());
+        assert_eq!(claims.report_data, hex::encode([1; 32]));
+        assert_eq!(claims.claims["svn"], "1");
+
+        let claims = verify_evidence(Tee::Sample, &evidence, &[2; 32]).unwrap();
+        assert!(!claims.report_data_matches);
+        assert!(!claims.is_valid());
+
+        assert!(verify_evidence(Tee::Sample, &serde_json::json!({}), &[1; 32]).is_err());
+        assert!(verify_evidence(Tee::Se, &evidence, &[1; 32]).is_err());
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::{CheckStatus, ParsedEvidence};
+
+#[derive(Deserialize)]
+struct SampleQuote {
+    svn: String,
+    report_data: String,
+    #[serde(default)]
+    rtmrs: Vec<String>,
+}
+
+pub(super) fn parse(evidence: &Value) -> Result<ParsedEvidence> {
+    let quote: SampleQuote = serde_json::from_value(evidence.clone())?;
+    let report_data = STANDARD
+        .decode(quote.report_data)
+        .context("Base64 decode report data failed")?;
+
+    Ok(ParsedEvidence {
+        report_data,
+        signature: CheckStatus::NotChecked("Sample evidence is not signed".into()),
+        cert_chain: CheckStatus::NotChecked("Sample evidence has no certificates".into()),
+        claims: json!({
+            "svn": quote.svn,
+            "rtmrs": quote.rtmrs,
+        }),
+    })
+}
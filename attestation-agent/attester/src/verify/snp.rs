@@ -0,0 +1,245 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! SNP attestation reports, as serialized by the `sev` crate, with the
+//! VCEK (or VLEK) certificate chain from the extended report.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use sev::firmware::guest::AttestationReport;
+use x509_cert::{der::Decode, Certificate};
+
+use super::{
+    field, subject_public_key, verify_cert_chain, verify_p384, CheckStatus, ParsedEvidence,
+};
+
+/// Size of the part of the raw report signed by the VCEK, followed by the
+/// signature.
+const SIGNED_REPORT_SIZE: usize = 0x2a0;
+
+/// Size of each little-endian component of the signature, zero-extended
+/// from the 48 bytes of a P-384 scalar.
+const SIGNATURE_COMPONENT_SIZE: usize = 72;
+
+const P384_SCALAR_SIZE: usize = 48;
+
+/// `sig_algo` of a report signed with ECDSA P-384 and SHA-384.
+const SIG_ALGO_ECDSA_P384_SHA384: u64 = 1;
+
+/// Byte array fields of the report, hex encoded in the claims.
+const BYTES_FIELDS: &[&str] = &[
+    "family_id",
+    "image_id",
+    "report_data",
+    "measurement",
+    "host_data",
+    "id_key_digest",
+    "author_key_digest",
+    "report_id",
+    "report_id_ma",
+    "chip_id",
+];
+
+/// Other fields of the report, copied to the claims.
+const OTHER_FIELDS: &[&str] = &[
+    "version",
+    "guest_svn",
+    "policy",
+    "vmpl",
+    "sig_algo",
+    "plat_info",
+    "current_tcb",
+    "reported_tcb",
+    "committed_tcb",
+    "launch_tcb",
+];
+
+/// Certificate types of the chain, starting with the leaf.
+const CHAIN_CERT_TYPES: &[&[&str]] = &[&["VCEK", "VLEK"], &["ASK", "ASVK"], &["ARK"]];
+
+#[derive(Deserialize)]
+struct CertTableEntry {
+    cert_type: Value,
+    data: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct SnpEvidence {
+    attestation_report: Map<String, Value>,
+    #[serde(default)]
+    cert_chain: Option<Vec<CertTableEntry>>,
+}
+
+/// Bytes serialized as an array of numbers or a hex string.
+fn bytes(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .map(|v| v.as_u64().and_then(|b| u8::try_from(b).ok()))
+            .collect(),
+        Value::String(s) => hex::decode(s).ok(),
+        _ => None,
+    }
+}
+
+/// Serialize the report back into the raw bytes signed by the firmware.
+fn raw_report(report: &Map<String, Value>) -> Result<Vec<u8>> {
+    let report: AttestationReport = serde_json::from_value(Value::Object(report.clone()))
+        .context("Parse attestation report failed")?;
+    let mut raw = Vec::new();
+    report
+        .write_bytes(&mut raw)
+        .context("Serialize attestation report failed")?;
+    Ok(raw)
+}
+
+/// Check the signature of the raw report against the SEC1 public key of
+/// the VCEK.
+fn verify_report_signature(raw: &[u8], vcek: &[u8]) -> Result<()> {
+    let mut signature = Vec::with_capacity(2 * P384_SCALAR_SIZE);
+    for offset in [
+        SIGNED_REPORT_SIZE,
+        SIGNED_REPORT_SIZE + SIGNATURE_COMPONENT_SIZE,
+    ] {
+        let mut component = field(raw, offset, P384_SCALAR_SIZE)?.to_vec();
+        component.reverse();
+        signature.extend(component);
+    }
+    let signature = p384::ecdsa::Signature::from_slice(&signature)?;
+
+    verify_p384(vcek, field(raw, 0, SIGNED_REPORT_SIZE)?, &signature)
+}
+
+fn check_signature(report: &Map<String, Value>, vcek: &Certificate) -> CheckStatus {
+    let checked = (|| {
+        let sig_algo = report.get("sig_algo").and_then(Value::as_u64);
+        if sig_algo != Some(SIG_ALGO_ECDSA_P384_SHA384) {
+            bail!("Unsupported signature algorithm {sig_algo:?}");
+        }
+        verify_report_signature(&raw_report(report)?, subject_public_key(vcek)?)
+    })();
+
+    match checked {
+        Ok(()) => CheckStatus::Verified(format!("VCEK {}", vcek.tbs_certificate.subject)),
+        Err(e) => CheckStatus::Invalid(format!("Report signature: {e:#}")),
+    }
+}
+
+fn cert_chain(entries: &[CertTableEntry]) -> Result<Vec<Certificate>> {
+    let mut chain = Vec::new();
+    for types in CHAIN_CERT_TYPES {
+        let entry = entries.iter().find(|entry| {
+            entry
+                .cert_type
+                .as_str()
+                .is_some_and(|t| types.iter().any(|ty| ty.eq_ignore_ascii_case(t)))
+        });
+        let Some(entry) = entry else {
+            break;
+        };
+        let cert = Certificate::from_der(&entry.data)
+            .with_context(|| format!("Parse {} certificate failed", types[0]))?;
+        chain.push(cert);
+    }
+    Ok(chain)
+}
+
+pub(super) fn parse(evidence: &Value) -> Result<ParsedEvidence> {
+    let evidence: SnpEvidence = serde_json::from_value(evidence.clone())?;
+    let report = &evidence.attestation_report;
+
+    let mut claims = Map::new();
+    for &name in BYTES_FIELDS {
+        if let Some(value) = report.get(name).and_then(bytes) {
+            claims.insert(name.into(), hex::encode(value).into());
+        }
+    }
+    for &name in OTHER_FIELDS {
+        if let Some(value) = report.get(name) {
+            claims.insert(name.into(), value.clone());
+        }
+    }
+
+    let report_data = report
+        .get("report_data")
+        .and_then(bytes)
+        .context("No report data in the attestation report")?;
+
+    let chain = cert_chain(evidence.cert_chain.as_deref().unwrap_or_default())?;
+
+    // The VCEK is fetched from the AMD KDS when it is not in the extended
+    // report, which is not done here.
+    let signature = match chain.first() {
+        Some(vcek) => check_signature(report, vcek),
+        None => CheckStatus::NotChecked("No VCEK certificate in the evidence".into()),
+    };
+
+    Ok(ParsedEvidence {
+        report_data,
+        signature,
+        cert_chain: verify_cert_chain(&chain),
+        claims: Value::Object(claims),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_snp_evidence() {
+        let report_data = vec![2u8; 64];
+        let measurement = vec![3u8; 48];
+        let evidence = json!({
+            "attestation_report": {
+                "version": 3,
+                "guest_svn": 1,
+                "report_data": report_data,
+                "measurement": measurement,
+                "host_data": hex::encode([4; 32]),
+            },
+            "cert_chain": null,
+        });
+
+        let parsed = parse(&evidence).unwrap();
+        assert_eq!(parsed.report_data, [2; 64]);
+        assert_eq!(parsed.claims["measurement"], hex::encode([3; 48]));
+        assert_eq!(parsed.claims["host_data"], hex::encode([4; 32]));
+        assert_eq!(parsed.claims["guest_svn"], 1);
+        assert!(matches!(parsed.cert_chain, CheckStatus::NotChecked(_)));
+
+        let evidence = json!({ "attestation_report": { "version": 3 } });
+        assert!(parse(&evidence).is_err());
+    }
+
+    #[test]
+    fn test_verify_report_signature() {
+        use p384::ecdsa::{signature::Signer, Signature, SigningKey};
+
+        let key = SigningKey::from_slice(&[1; P384_SCALAR_SIZE]).unwrap();
+        let vcek = key.verifying_key().to_encoded_point(false);
+
+        let mut raw = vec![0; 0x4a0];
+        raw[0x90..0xd0].copy_from_slice(&[2; 64]);
+        let signature: Signature = key.sign(&raw[..SIGNED_REPORT_SIZE]);
+        let (r, s) = signature.split_bytes();
+        for (offset, component) in [
+            (SIGNED_REPORT_SIZE, r),
+            (SIGNED_REPORT_SIZE + SIGNATURE_COMPONENT_SIZE, s),
+        ] {
+            let mut component = component.to_vec();
+            component.reverse();
+            raw[offset..offset + P384_SCALAR_SIZE].copy_from_slice(&component);
+        }
+        verify_report_signature(&raw, vcek.as_bytes()).unwrap();
+
+        // A tampered report data fails the signature
+        raw[0x90] ^= 1;
+        assert!(verify_report_signature(&raw, vcek.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! TDX quotes of version 4 and 5, signed by an ECDSA P-256 attestation key
+//! of the Quoting Enclave (QE). The QE report binding the key is signed by
+//! the PCK, whose certificate chain is the certification data of the quote.
+
+use anyhow::{bail, Context, Error, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use p256::ecdsa::Signature;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use x509_cert::Certificate;
+
+use super::{
+    field, subject_public_key, verify_cert_chain, verify_p256, CheckStatus, ParsedEvidence,
+};
+
+const QUOTE_HEADER_SIZE: usize = 48;
+
+/// Size of the TD 1.0 report body. TD 1.5 ones append `tee_tcb_svn2` and
+/// `mr_servicetd`.
+const TD10_REPORT_BODY_SIZE: usize = 584;
+
+const TDX_TEE_TYPE: u32 = 0x81;
+const ECDSA_P256_KEY_TYPE: u16 = 2;
+
+const QE_REPORT_SIZE: usize = 384;
+const QE_REPORT_DATA_OFFSET: usize = 320;
+const QE_REPORT_CERTIFICATION_DATA: u16 = 6;
+const PCK_CERT_CHAIN_CERTIFICATION_DATA: u16 = 5;
+
+/// Fields of the TD report body: name, offset and size.
+const REPORT_BODY_FIELDS: &[(&str, usize, usize)] = &[
+    ("tee_tcb_svn", 0, 16),
+    ("mr_seam", 16, 48),
+    ("mr_signer_seam", 64, 48),
+    ("seam_attributes", 112, 8),
+    ("td_attributes", 120, 8),
+    ("xfam", 128, 8),
+    ("mr_td", 136, 48),
+    ("mr_config_id", 184, 48),
+    ("mr_owner", 232, 48),
+    ("mr_owner_config", 280, 48),
+    ("rtmr_0", 328, 48),
+    ("rtmr_1", 376, 48),
+    ("rtmr_2", 424, 48),
+    ("rtmr_3", 472, 48),
+    ("report_data", 520, 64),
+    ("tee_tcb_svn2", 584, 16),
+    ("mr_servicetd", 600, 48),
+];
+
+#[derive(Deserialize)]
+struct TdxEvidence {
+    cc_eventlog: Option<String>,
+    quote: String,
+}
+
+fn le_u16(bytes: &[u8], offset: usize) -> Result<u16> {
+    Ok(u16::from_le_bytes(field(bytes, offset, 2)?.try_into()?))
+}
+
+fn le_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(field(bytes, offset, 4)?.try_into()?))
+}
+
+pub(super) fn parse(evidence: &Value) -> Result<ParsedEvidence> {
+    let evidence: TdxEvidence = serde_json::from_value(evidence.clone())?;
+    let quote = STANDARD
+        .decode(evidence.quote)
+        .context("Base64 decode quote failed")?;
+
+    let version = le_u16(&quote, 0)?;
+    let key_type = le_u16(&quote, 2)?;
+    let tee_type = le_u32(&quote, 4)?;
+    if tee_type != TDX_TEE_TYPE {
+        bail!("Not a TDX quote, TEE type {tee_type:#x}");
+    }
+    if key_type != ECDSA_P256_KEY_TYPE {
+        bail!("Unsupported attestation key type {key_type}");
+    }
+
+    let (body_offset, body_size) = match version {
+        4 => (QUOTE_HEADER_SIZE, TD10_REPORT_BODY_SIZE),
+        // The body is prefixed with its type and size
+        5 => (
+            QUOTE_HEADER_SIZE + 6,
+            le_u32(&quote, QUOTE_HEADER_SIZE + 2)? as usize,
+        ),
+        version => bail!("Unsupported quote version {version}"),
+    };
+    let body = field(&quote, body_offset, body_size)?;
+    if body_size < TD10_REPORT_BODY_SIZE {
+        bail!("Report body too short: {body_size} bytes");
+    }
+
+    let signed_size = body_offset + body_size;
+    let signature_data_size = le_u32(&quote, signed_size)? as usize;
+    let signature_data = field(&quote, signed_size + 4, signature_data_size)?;
+    let (signature, cert_chain) = check_signature_data(&quote[..signed_size], signature_data)?;
+
+    let mut claims = Map::new();
+    claims.insert("version".into(), version.into());
+    for &(name, offset, size) in REPORT_BODY_FIELDS {
+        if let Ok(value) = field(body, offset, size) {
+            claims.insert(name.into(), hex::encode(value).into());
+        }
+    }
+    claims.insert("cc_eventlog".into(), evidence.cc_eventlog.is_some().into());
+
+    Ok(ParsedEvidence {
+        report_data: field(body, 520, 64)?.to_vec(),
+        signature,
+        cert_chain,
+        claims: Value::Object(claims),
+    })
+}
+
+/// Check the signature of the quote and the QE report, returning the status
+/// of the signature and the PCK certificate chain.
+fn check_signature_data(signed: &[u8], data: &[u8]) -> Result<(CheckStatus, CheckStatus)> {
+    let signature = field(data, 0, 64)?;
+    let attestation_key = field(data, 64, 64)?;
+    let certification_type = le_u16(data, 128)?;
+    let certification_size = le_u32(data, 130)? as usize;
+    let certification = field(data, 134, certification_size)?;
+    if certification_type != QE_REPORT_CERTIFICATION_DATA {
+        bail!("Unsupported certification data type {certification_type}");
+    }
+
+    let qe_report = field(certification, 0, QE_REPORT_SIZE)?;
+    let qe_report_signature = field(certification, QE_REPORT_SIZE, 64)?;
+    let qe_auth_size = le_u16(certification, QE_REPORT_SIZE + 64)? as usize;
+    let qe_auth = field(certification, QE_REPORT_SIZE + 66, qe_auth_size)?;
+    let pck_offset = QE_REPORT_SIZE + 66 + qe_auth_size;
+    let pck_type = le_u16(certification, pck_offset)?;
+    let pck_size = le_u32(certification, pck_offset + 2)? as usize;
+    let pck_data = field(certification, pck_offset + 6, pck_size)?;
+
+    let pck_chain = match pck_type {
+        PCK_CERT_CHAIN_CERTIFICATION_DATA => {
+            // The PEM chain may be NUL terminated
+            let pem_len = pck_data.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+            Certificate::load_pem_chain(&pck_data[..pem_len])
+                .context("Parse PCK certificate chain failed")?
+        }
+        _ => Vec::new(),
+    };
+
+    let key_sec1 = [&[0x04], attestation_key].concat();
+    let quote_signature = Signature::from_slice(signature)
+        .map_err(Error::from)
+        .and_then(|signature| verify_p256(&key_sec1, signed, &signature));
+    if let Err(e) = quote_signature {
+        let signature = CheckStatus::Invalid(format!("Quote signature: {e:#}"));
+        return Ok((signature, verify_cert_chain(&pck_chain)));
+    }
+
+    // The QE binds the attestation key with the hash in its report data
+    let key_hash = Sha256::digest([attestation_key, qe_auth].concat());
+    if field(qe_report, QE_REPORT_DATA_OFFSET, 32)? != key_hash.as_slice() {
+        let signature =
+            CheckStatus::Invalid("The attestation key is not bound to the QE report".into());
+        return Ok((signature, verify_cert_chain(&pck_chain)));
+    }
+
+    let Some(pck) = pck_chain.first() else {
+        let signature = CheckStatus::NotChecked(
+            "The quote is signed by the attestation key, but there is no PCK certificate to check the QE report".into(),
+        );
+        return Ok((signature, verify_cert_chain(&pck_chain)));
+    };
+
+    let qe_report_signature = Signature::from_slice(qe_report_signature)
+        .map_err(Error::from)
+        .and_then(|signature| verify_p256(subject_public_key(pck)?, qe_report, &signature));
+    let signature = match qe_report_signature {
+        Ok(()) => CheckStatus::Verified(format!("PCK {}", pck.tbs_certificate.subject)),
+        Err(e) => CheckStatus::Invalid(format!("QE report signature: {e:#}")),
+    };
+
+    Ok((signature, verify_cert_chain(&pck_chain)))
+}
+
+#[cfg(test)]
+mod tests {
+    use p256::ecdsa::{signature::Signer, SigningKey};
+
+    use super::*;
+
+    /// A TDX quote v4 with the report data, signed by an attestation key
+    /// bound to the QE report, without PCK certificates.
+    fn quote(report_data: &[u8; 64]) -> Vec<u8> {
+        let mut quote = vec![0; QUOTE_HEADER_SIZE + TD10_REPORT_BODY_SIZE];
+        quote[0..2].copy_from_slice(&4u16.to_le_bytes());
+        quote[2..4].copy_from_slice(&ECDSA_P256_KEY_TYPE.to_le_bytes());
+        quote[4..8].copy_from_slice(&TDX_TEE_TYPE.to_le_bytes());
+        quote[QUOTE_HEADER_SIZE + 136..QUOTE_HEADER_SIZE + 184].copy_from_slice(&[3; 48]);
+        quote[QUOTE_HEADER_SIZE + 520..].copy_from_slice(report_data);
+
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let signature: Signature = key.sign(&quote);
+        let attestation_key = key.verifying_key().to_encoded_point(false).as_bytes()[1..].to_vec();
+
+        let mut qe_report = vec![0; QE_REPORT_SIZE];
+        qe_report[QE_REPORT_DATA_OFFSET..QE_REPORT_DATA_OFFSET + 32]
+            .copy_from_slice(&Sha256::digest(&attestation_key));
+        let mut certification = qe_report;
+        certification.extend_from_slice(&[0; 64]);
+        certification.extend_from_slice(&0u16.to_le_bytes());
+        certification.extend_from_slice(&PCK_CERT_CHAIN_CERTIFICATION_DATA.to_le_bytes());
+        certification.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut signature_data = signature.to_bytes().to_vec();
+        signature_data.extend_from_slice(&attestation_key);
+        signature_data.extend_from_slice(&QE_REPORT_CERTIFICATION_DATA.to_le_bytes());
+        signature_data.extend_from_slice(&(certification.len() as u32).to_le_bytes());
+        signature_data.extend_from_slice(&certification);
+
+        quote.extend_from_slice(&(signature_data.len() as u32).to_le_bytes());
+        quote.extend_from_slice(&signature_data);
+        quote
+    }
+
+    fn evidence(quote: &[u8]) -> Value {
+        json!({ "cc_eventlog": null, "quote": STANDARD.encode(quote) })
+    }
+
+    #[test]
+    fn test_parse_tdx_quote() {
+        let quote = quote(&[2; 64]);
+        let parsed = parse(&evidence(&quote)).unwrap();
+        assert_eq!(parsed.report_data, [2; 64]);
+        assert_eq!(parsed.claims["mr_td"], hex::encode([3; 48]));
+        assert_eq!(parsed.claims["cc_eventlog"], false);
+        assert!(matches!(parsed.signature, CheckStatus::NotChecked(_)));
+        assert!(matches!(parsed.cert_chain, CheckStatus::NotChecked(_)));
+
+        let mut tampered = quote.clone();
+        tampered[QUOTE_HEADER_SIZE + 136] ^= 1;
+        let parsed = parse(&evidence(&tampered)).unwrap();
+        assert!(parsed.signature.is_invalid());
+
+        assert!(parse(&evidence(&quote[..QUOTE_HEADER_SIZE + 100])).is_err());
+    }
+}
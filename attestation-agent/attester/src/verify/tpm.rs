@@ -0,0 +1,329 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! TPM quotes: a marshalled `TPMS_ATTEST` signed by the AK with RSASSA and
+//! SHA-256, and the values of the quoted PCRs.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rsa::{pkcs1v15, pkcs8::DecodePublicKey, signature::Verifier, RsaPublicKey};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use x509_cert::{der::Decode, Certificate};
+
+use super::{CheckStatus, ParsedEvidence};
+
+const TPM_GENERATED_VALUE: u32 = 0xff54_4347;
+const TPM_ST_ATTEST_QUOTE: u16 = 0x8018;
+const TPM_ALG_RSASSA: u16 = 0x0014;
+const TPM_ALG_SHA1: u16 = 0x0004;
+const TPM_ALG_SHA256: u16 = 0x000b;
+const TPM_ALG_SHA384: u16 = 0x000c;
+
+#[derive(Deserialize)]
+struct TpmQuote {
+    signature: String,
+    message: String,
+    pcrs: Vec<String>,
+    #[serde(default)]
+    pcr_banks: BTreeMap<String, BTreeMap<u8, String>>,
+}
+
+#[derive(Deserialize)]
+struct TpmEvidence {
+    tpm_quote: TpmQuote,
+    ak_public: String,
+    #[serde(default)]
+    ek_cert: Option<String>,
+}
+
+/// Reader of big-endian TPM structures.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            bail!("TPM structure too short");
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into()?))
+    }
+
+    /// A `TPM2B` structure, prefixed with its size.
+    fn sized(&mut self) -> Result<&'a [u8]> {
+        let size = self.u16()? as usize;
+        self.take(size)
+    }
+}
+
+/// The fields of a `TPMS_ATTEST` of a quote.
+#[derive(Debug, PartialEq)]
+struct Attest {
+    qualified_signer: Vec<u8>,
+    extra_data: Vec<u8>,
+    clock: u64,
+    reset_count: u32,
+    restart_count: u32,
+    firmware_version: u64,
+
+    /// Quoted PCR indexes of each bank
+    pcr_selection: Vec<(u16, Vec<u8>)>,
+    pcr_digest: Vec<u8>,
+}
+
+impl Attest {
+    fn parse(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader { bytes };
+        if reader.u32()? != TPM_GENERATED_VALUE {
+            bail!("Not generated by a TPM");
+        }
+        if reader.u16()? != TPM_ST_ATTEST_QUOTE {
+            bail!("Not a quote");
+        }
+
+        let qualified_signer = reader.sized()?.to_vec();
+        let extra_data = reader.sized()?.to_vec();
+        let clock = reader.u64()?;
+        let reset_count = reader.u32()?;
+        let restart_count = reader.u32()?;
+        let _safe = reader.u8()?;
+        let firmware_version = reader.u64()?;
+
+        let mut pcr_selection = Vec::new();
+        for _ in 0..reader.u32()? {
+            let bank = reader.u16()?;
+            let size = reader.u8()? as usize;
+            let bitmap = reader.take(size)?;
+            let indexes = (0..size * 8)
+                .filter(|i| bitmap[i / 8] & (1 << (i % 8)) != 0)
+                .map(|i| i as u8)
+                .collect();
+            pcr_selection.push((bank, indexes));
+        }
+        let pcr_digest = reader.sized()?.to_vec();
+
+        Ok(Self {
+            qualified_signer,
+            extra_data,
+            clock,
+            reset_count,
+            restart_count,
+            firmware_version,
+            pcr_selection,
+            pcr_digest,
+        })
+    }
+}
+
+fn bank_name(bank: u16) -> Result<&'static str> {
+    match bank {
+        TPM_ALG_SHA1 => Ok("sha1"),
+        TPM_ALG_SHA256 => Ok("sha256"),
+        TPM_ALG_SHA384 => Ok("sha384"),
+        bank => bail!("Unsupported PCR bank {bank:#06x}"),
+    }
+}
+
+/// The quoted PCR values of each bank. Evidence without `pcr_banks` only
+/// has the values of the first bank.
+fn quoted_pcrs(
+    quote: &TpmQuote,
+    attest: &Attest,
+) -> Result<BTreeMap<String, BTreeMap<u8, String>>> {
+    if !quote.pcr_banks.is_empty() {
+        return Ok(quote.pcr_banks.clone());
+    }
+
+    let Some((bank, indexes)) = attest.pcr_selection.first() else {
+        return Ok(BTreeMap::new());
+    };
+    if indexes.len() != quote.pcrs.len() {
+        bail!(
+            "{} PCR values for {} quoted PCRs",
+            quote.pcrs.len(),
+            indexes.len()
+        );
+    }
+    let values = indexes.iter().copied().zip(quote.pcrs.iter().cloned());
+    Ok(BTreeMap::from([(
+        bank_name(*bank)?.to_string(),
+        values.collect(),
+    )]))
+}
+
+/// The digest of the quoted PCR values, in the order of the selection.
+fn pcr_digest(attest: &Attest, pcrs: &BTreeMap<String, BTreeMap<u8, String>>) -> Result<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    for (bank, indexes) in &attest.pcr_selection {
+        let name = bank_name(*bank)?;
+        for index in indexes {
+            let value = pcrs
+                .get(name)
+                .and_then(|values| values.get(index))
+                .with_context(|| format!("No value of PCR {index} of bank {name}"))?;
+            hasher.update(hex::decode(value)?);
+        }
+    }
+    Ok(hasher.finalize().to_vec())
+}
+
+fn check_signature(
+    message: &[u8],
+    signature: &[u8],
+    ak_public: &[u8],
+    attest: &Attest,
+    pcrs: &BTreeMap<String, BTreeMap<u8, String>>,
+) -> Result<CheckStatus> {
+    let mut reader = Reader { bytes: signature };
+    let algorithm = reader.u16()?;
+    let hash = reader.u16()?;
+    if algorithm != TPM_ALG_RSASSA || hash != TPM_ALG_SHA256 {
+        return Ok(CheckStatus::Invalid(format!(
+            "Unsupported signature scheme {algorithm:#06x} with hash {hash:#06x}"
+        )));
+    }
+    let signature = pkcs1v15::Signature::try_from(reader.sized()?)?;
+
+    let ak = RsaPublicKey::from_public_key_der(ak_public).context("Parse AK failed")?;
+    if let Err(e) = pkcs1v15::VerifyingKey::<Sha256>::new(ak).verify(message, &signature) {
+        return Ok(CheckStatus::Invalid(format!("Quote signature: {e}")));
+    }
+
+    // Only the digest of the PCR values is signed
+    if pcr_digest(attest, pcrs)? != attest.pcr_digest {
+        return Ok(CheckStatus::Invalid(
+            "The PCR values do not match the quoted digest".into(),
+        ));
+    }
+
+    Ok(CheckStatus::Verified("AK of the evidence".into()))
+}
+
+pub(super) fn parse(evidence: &Value) -> Result<ParsedEvidence> {
+    let evidence: TpmEvidence = serde_json::from_value(evidence.clone())?;
+    let message = STANDARD
+        .decode(&evidence.tpm_quote.message)
+        .context("Base64 decode quote message failed")?;
+    let signature = STANDARD
+        .decode(&evidence.tpm_quote.signature)
+        .context("Base64 decode quote signature failed")?;
+    let ak_public = STANDARD
+        .decode(&evidence.ak_public)
+        .context("Base64 decode AK failed")?;
+
+    let attest = Attest::parse(&message).context("Parse quote message failed")?;
+    let pcrs = quoted_pcrs(&evidence.tpm_quote, &attest)?;
+    let signature = check_signature(&message, &signature, &ak_public, &attest, &pcrs)?;
+
+    let (cert_chain, ek_cert) = match &evidence.ek_cert {
+        Some(ek_cert) => {
+            let der = STANDARD
+                .decode(ek_cert)
+                .context("Base64 decode EK certificate failed")?;
+            let cert = Certificate::from_der(&der).context("Parse EK certificate failed")?;
+            let tbs = &cert.tbs_certificate;
+            let status = CheckStatus::NotChecked(format!(
+                "The EK certificate is issued by {}, whose certificate is not part of the evidence",
+                tbs.issuer
+            ));
+            let claims = json!({
+                "subject": tbs.subject.to_string(),
+                "issuer": tbs.issuer.to_string(),
+            });
+            (status, claims)
+        }
+        None => (
+            CheckStatus::NotChecked("No EK certificate in the evidence".into()),
+            Value::Null,
+        ),
+    };
+
+    Ok(ParsedEvidence {
+        report_data: attest.extra_data.clone(),
+        signature,
+        cert_chain,
+        claims: json!({
+            "pcrs": pcrs,
+            "qualified_signer": hex::encode(&attest.qualified_signer),
+            "clock": attest.clock,
+            "reset_count": attest.reset_count,
+            "restart_count": attest.restart_count,
+            "firmware_version": format!("{:#018x}", attest.firmware_version),
+            "ek_cert": ek_cert,
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attest(pcr_digest: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&TPM_GENERATED_VALUE.to_be_bytes());
+        bytes.extend_from_slice(&TPM_ST_ATTEST_QUOTE.to_be_bytes());
+        bytes.extend_from_slice(&[0, 2, 0xaa, 0xbb]);
+        bytes.extend_from_slice(&64u16.to_be_bytes());
+        bytes.extend_from_slice(&[1; 64]);
+        bytes.extend_from_slice(&5u64.to_be_bytes());
+        bytes.extend_from_slice(&6u32.to_be_bytes());
+        bytes.extend_from_slice(&7u32.to_be_bytes());
+        bytes.push(1);
+        bytes.extend_from_slice(&8u64.to_be_bytes());
+        // PCRs 0 and 9 of the SHA-256 bank
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.extend_from_slice(&TPM_ALG_SHA256.to_be_bytes());
+        bytes.extend_from_slice(&[3, 0b0000_0001, 0b0000_0010, 0]);
+        bytes.extend_from_slice(&(pcr_digest.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(pcr_digest);
+        bytes
+    }
+
+    #[test]
+    fn test_parse_attest() {
+        let pcrs = vec![hex::encode([2; 32]), hex::encode([3; 32])];
+        let digest = Sha256::digest([[2; 32], [3; 32]].concat());
+        let attest = Attest::parse(&attest(&digest)).unwrap();
+        assert_eq!(attest.qualified_signer, [0xaa, 0xbb]);
+        assert_eq!(attest.extra_data, [1; 64]);
+        assert_eq!(attest.clock, 5);
+        assert_eq!(attest.firmware_version, 8);
+        assert_eq!(attest.pcr_selection, [(TPM_ALG_SHA256, vec![0, 9])]);
+
+        let quote = TpmQuote {
+            signature: String::new(),
+            message: String::new(),
+            pcrs,
+            pcr_banks: BTreeMap::new(),
+        };
+        let pcrs = quoted_pcrs(&quote, &attest).unwrap();
+        assert_eq!(pcrs["sha256"][&9], hex::encode([3; 32]));
+        assert_eq!(pcr_digest(&attest, &pcrs).unwrap(), attest.pcr_digest);
+
+        assert!(Attest::parse(&attest(&digest)[..20]).is_err());
+    }
+}
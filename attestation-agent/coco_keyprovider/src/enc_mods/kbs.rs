@@ -13,6 +13,7 @@ const KBS_URL_PATH_PREFIX: &str = "kbs/v0/resource";
 /// Register the given key with kid into the kbs. This request will be authorized with a
 /// JWT token, which will be signed by the private_key.
 pub(crate) async fn register_kek(
+    client: &reqwest::Client,
     private_key: &Ed25519KeyPair,
     kbs_addr: &Url,
     key: Vec<u8>,
@@ -23,7 +24,6 @@ pub(crate) async fn register_kek(
     let token = private_key.sign(claims)?;
     debug!("sign claims.");
 
-    let client = reqwest::Client::new();
     let mut resource_url = kbs_addr.clone();
 
     let path = format!("{KBS_URL_PATH_PREFIX}/{kid}");
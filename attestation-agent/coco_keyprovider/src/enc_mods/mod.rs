@@ -186,7 +186,7 @@ fn normalize_path(keyid: &str) -> Result<(String, String)> {
 /// | keypath   | path to the KEK, e.g. `/home/key`    | Specify the KEK to encrypted the image in local filesystem                                       |
 /// | algorithm | `A256GCM` or `A256CTR`               | Encryption algorithm, included in the `wrap_type` field of AnnotationPacket. By default `A256GCM`|
 pub async fn enc_optsdata_gen_anno(
-    kbs_parameter: (&Option<Url>, &Option<Ed25519KeyPair>),
+    kbs_parameter: (&Option<Url>, &Option<Ed25519KeyPair>, &reqwest::Client),
     optsdata: &[u8],
     params: Vec<String>,
 ) -> Result<String> {
@@ -201,10 +201,10 @@ pub async fn enc_optsdata_gen_anno(
     let encrypt_optsdata = crypto::encrypt(optsdata, &key, &iv, &algorithm)
         .map_err(|e| anyhow!("Encrypt failed: {:?}", e))?;
 
-    if let (Some(addr), Some(private_key)) = kbs_parameter {
+    if let (Some(addr), Some(private_key), http_client) = kbs_parameter {
         if !input_params.sample {
             // We do not register KEK for sample kbc
-            register_kek(private_key, addr, key, &k_path)
+            register_kek(http_client, private_key, addr, key, &k_path)
                 .await
                 .context("register KEK failed")?;
             info!("register KEK succeeded.");
@@ -6,6 +6,7 @@
 use anyhow::*;
 use clap::Parser;
 use daemonize::Daemonize;
+use kbs_protocol::ProxyConfig;
 use log::*;
 use std::{fs::File, net::SocketAddr, path::PathBuf};
 use tokio::fs;
@@ -33,6 +34,19 @@ struct Cli {
     #[arg(long)]
     kbs: Option<String>,
 
+    /// Proxy for the requests to `https://` KBS addresses.
+    #[arg(long)]
+    https_proxy: Option<String>,
+
+    /// Proxy for the requests to `http://` KBS addresses.
+    #[arg(long)]
+    http_proxy: Option<String>,
+
+    /// Comma separated list of hosts, domains, IPs or CIDRs that bypass the
+    /// proxies, e.g. `localhost,.example.com,10.0.0.0/8`.
+    #[arg(long)]
+    no_proxy: Option<String>,
+
     /// Whether this process is launched in daemon mode. If it is set to
     /// true, the stdio and stderr will be redirected to
     /// `/run/confidential-containers/coco_keyprovider.out` and
@@ -78,7 +92,14 @@ async fn main() -> Result<()> {
         daemonize.start().context("daemonize failed")?;
     }
 
-    grpc::start_service(cli.socket, cli.auth_private_key, cli.kbs).await?;
+    let http_client = ProxyConfig {
+        https_proxy: cli.https_proxy,
+        http_proxy: cli.http_proxy,
+        no_proxy: cli.no_proxy,
+    }
+    .http_client()?;
+
+    grpc::start_service(cli.socket, cli.auth_private_key, cli.kbs, http_client).await?;
 
     Ok(())
 }
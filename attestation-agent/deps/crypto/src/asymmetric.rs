@@ -53,9 +53,25 @@ pub mod ec {
         EcdhEsA256Kw,
     }
 
-    #[derive(EnumString, AsRefStr)]
+    /// Definitions of the supported elliptic curves. Refer to
+    /// <https://datatracker.ietf.org/doc/html/rfc7518#section-6.2.1.1> for
+    /// more information.
+    #[derive(EnumString, AsRefStr, Clone, Copy, Debug, PartialEq, Eq)]
     pub enum Curve {
         #[strum(serialize = "P-256")]
         P256,
+
+        #[strum(serialize = "P-384")]
+        P384,
+    }
+
+    impl Curve {
+        /// Length in bytes of the coordinates of a point on the curve.
+        pub fn coordinate_len(&self) -> usize {
+            match self {
+                Curve::P256 => 32,
+                Curve::P384 => 48,
+            }
+        }
     }
 }
@@ -23,6 +23,7 @@ use crate::{
 #[derive(Clone, Debug)]
 pub enum EcKeyPair {
     P256(P256EcKeyPair),
+    P384(P384EcKeyPair),
 }
 
 impl Default for EcKeyPair {
@@ -32,28 +33,37 @@ impl Default for EcKeyPair {
 }
 
 impl EcKeyPair {
+    /// Generate a new key pair on the given curve.
+    pub fn new(curve: Curve) -> Result<Self> {
+        let keypair = match curve {
+            Curve::P256 => Self::P256(P256EcKeyPair::new()?),
+            Curve::P384 => Self::P384(P384EcKeyPair::new()?),
+        };
+        Ok(keypair)
+    }
+
     fn private_key(&self) -> &PKey<Private> {
         match self {
             Self::P256(p256) => p256.private_key(),
+            Self::P384(p384) => p384.private_key(),
         }
     }
 
     pub fn curve(&self) -> Curve {
         match self {
             Self::P256(_) => Curve::P256,
+            Self::P384(_) => Curve::P384,
         }
     }
 
     pub fn x(&self) -> Result<Vec<u8>> {
-        match self {
-            Self::P256(p256) => p256.x(),
-        }
+        let (x, _) = affine_coordinates(self.private_key(), self.curve())?;
+        Ok(x)
     }
 
     pub fn y(&self) -> Result<Vec<u8>> {
-        match self {
-            Self::P256(p256) => p256.y(),
-        }
+        let (_, y) = affine_coordinates(self.private_key(), self.curve())?;
+        Ok(y)
     }
 
     pub fn to_pkcs8_pem(&self) -> Result<Zeroizing<String>> {
@@ -71,6 +81,7 @@ impl EcKeyPair {
             .ok_or(anyhow!("failed to get curve name"))?;
         match curve_nid {
             Nid::X9_62_PRIME256V1 => Ok(Self::P256(P256EcKeyPair { private_key })),
+            Nid::SECP384R1 => Ok(Self::P384(P384EcKeyPair { private_key })),
             _ => bail!("unsupported EC curve with NID {curve_nid:?}"),
         }
     }
@@ -82,25 +93,9 @@ impl EcKeyPair {
         epk_y: Vec<u8>,
         wrapping_algorithm: KeyWrapAlgorithm,
     ) -> Result<Vec<u8>> {
-        let group = match self.curve() {
-            Curve::P256 => EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?,
-        };
-        let point = group.generator();
-        let mut point = point.to_owned(&group)?;
-
-        let epk_x = BigNum::from_slice(&epk_x)?;
-        let epk_y = BigNum::from_slice(&epk_y)?;
-
-        let mut ctx = BigNumContext::new()?;
-        point.set_affine_coordinates_gfp(&group, &epk_x, &epk_y, &mut ctx)?;
-
-        let epk = EcKey::from_public_key(&group, &point)?;
-        let epk = PKey::from_ec_key(epk)?;
         match wrapping_algorithm {
             KeyWrapAlgorithm::EcdhEsA256Kw => {
-                let mut deriver = Deriver::new(self.private_key())?;
-                deriver.set_peer(&epk)?;
-                let z = deriver.derive_to_vec()?;
+                let z = self.diffie_hellman(&epk_x, &epk_y)?;
                 let shared_key = concat_kdf(
                     KeyWrapAlgorithm::EcdhEsA256Kw.as_ref(),
                     AES_GCM_256_KEY_BITS as usize / 8,
@@ -115,6 +110,89 @@ impl EcKeyPair {
             }
         }
     }
+
+    /// Wrap the key to the peer public key of the given coordinates, with
+    /// this key pair as the ephemeral key, as the KBS does.
+    pub fn wrap_key(
+        &self,
+        key: &[u8],
+        peer_x: &[u8],
+        peer_y: &[u8],
+        wrapping_algorithm: KeyWrapAlgorithm,
+    ) -> Result<Vec<u8>> {
+        match wrapping_algorithm {
+            KeyWrapAlgorithm::EcdhEsA256Kw => {
+                let z = self.diffie_hellman(peer_x, peer_y)?;
+                let shared_key = concat_kdf(
+                    KeyWrapAlgorithm::EcdhEsA256Kw.as_ref(),
+                    AES_GCM_256_KEY_BITS as usize / 8,
+                    &z,
+                )?;
+                let mut wrapped_key = vec![0; key.len() + 8];
+                let wrapping_key = AesKey::new_encrypt(&shared_key)
+                    .map_err(|e| anyhow!("failed to create AES wrapping key: {e:?}"))?;
+                aes::wrap_key(&wrapping_key, None, &mut wrapped_key, key)
+                    .map_err(|e| anyhow!("failed to wrap key: {e:?}"))?;
+                Ok(wrapped_key)
+            }
+        }
+    }
+
+    /// The ECDH shared secret with the peer public key of the given
+    /// coordinates.
+    fn diffie_hellman(&self, epk_x: &[u8], epk_y: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let coordinate_len = self.curve().coordinate_len();
+        if epk_x.len() != coordinate_len {
+            bail!("invalid bytes length of coordinates X");
+        }
+        if epk_y.len() != coordinate_len {
+            bail!("invalid bytes length of coordinates Y");
+        }
+
+        let group = EcGroup::from_curve_name(curve_nid(self.curve()))?;
+        let point = group.generator();
+        let mut point = point.to_owned(&group)?;
+
+        let epk_x = BigNum::from_slice(epk_x)?;
+        let epk_y = BigNum::from_slice(epk_y)?;
+
+        let mut ctx = BigNumContext::new()?;
+        point.set_affine_coordinates_gfp(&group, &epk_x, &epk_y, &mut ctx)?;
+
+        let epk = EcKey::from_public_key(&group, &point)?;
+        let epk = PKey::from_ec_key(epk)?;
+
+        let mut deriver = Deriver::new(self.private_key())?;
+        deriver.set_peer(&epk)?;
+        Ok(Zeroizing::new(deriver.derive_to_vec()?))
+    }
+}
+
+fn curve_nid(curve: Curve) -> Nid {
+    match curve {
+        Curve::P256 => Nid::X9_62_PRIME256V1,
+        Curve::P384 => Nid::SECP384R1,
+    }
+}
+
+fn generate_private_key(curve: Curve) -> Result<PKey<Private>> {
+    let ec_group = EcGroup::from_curve_name(curve_nid(curve))?;
+    let ec_key = EcKey::generate(&ec_group)?;
+    let private_key = PKey::from_ec_key(ec_key)?;
+    Ok(private_key)
+}
+
+/// The affine coordinates of the public key, left padded with zeros to the
+/// coordinate length of the curve.
+fn affine_coordinates(private_key: &PKey<Private>, curve: Curve) -> Result<(Vec<u8>, Vec<u8>)> {
+    let private_key = private_key.ec_key().context("must be a ec key")?;
+    let public_key = private_key.public_key();
+    let mut x = BigNum::new()?;
+    let mut y = BigNum::new()?;
+    let mut ctx = BigNumContext::new()?;
+    public_key.affine_coordinates_gfp(private_key.group(), &mut x, &mut y, &mut ctx)?;
+    let len = curve.coordinate_len() as i32;
+    Ok((x.to_vec_padded(len)?, y.to_vec_padded(len)?))
 }
 
 fn concat_kdf(alg: &str, target_length: usize, z: &[u8]) -> Result<Vec<u8>> {
@@ -162,33 +240,100 @@ impl P256EcKeyPair {
     }
 
     pub fn new() -> Result<Self> {
-        let ec_group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
-        let ec_key = EcKey::generate(&ec_group)?;
-        let private_key = PKey::from_ec_key(ec_key)?;
+        let private_key = generate_private_key(Curve::P256)?;
         Ok(Self { private_key })
     }
 
     pub fn x(&self) -> Result<Vec<u8>> {
-        let private_key = self.private_key.ec_key().context("must be a ec key")?;
-        let public_key = private_key.public_key();
-        let mut x = BigNum::new()?;
-        let mut _y = BigNum::new()?;
-        let mut ctx = BigNumContext::new()?;
-        public_key.affine_coordinates_gfp(private_key.group(), &mut x, &mut _y, &mut ctx)?;
-        let mut x = x.to_vec();
-        x.resize(32, b'0');
+        let (x, _) = affine_coordinates(&self.private_key, Curve::P256)?;
         Ok(x)
     }
 
     pub fn y(&self) -> Result<Vec<u8>> {
-        let private_key = self.private_key.ec_key().context("must be a ec key")?;
-        let public_key = private_key.public_key();
-        let mut _x = BigNum::new()?;
-        let mut y = BigNum::new()?;
-        let mut ctx = BigNumContext::new()?;
-        public_key.affine_coordinates_gfp(private_key.group(), &mut _x, &mut y, &mut ctx)?;
-        let mut y = y.to_vec();
-        y.resize(32, b'0');
+        let (_, y) = affine_coordinates(&self.private_key, Curve::P256)?;
         Ok(y)
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct P384EcKeyPair {
+    private_key: PKey<Private>,
+}
+
+impl Default for P384EcKeyPair {
+    fn default() -> Self {
+        Self::new().expect("Create P384 key pair failed")
+    }
+}
+
+impl P384EcKeyPair {
+    fn private_key(&self) -> &PKey<Private> {
+        &self.private_key
+    }
+
+    pub fn new() -> Result<Self> {
+        let private_key = generate_private_key(Curve::P384)?;
+        Ok(Self { private_key })
+    }
+
+    pub fn x(&self) -> Result<Vec<u8>> {
+        let (x, _) = affine_coordinates(&self.private_key, Curve::P384)?;
+        Ok(x)
+    }
+
+    pub fn y(&self) -> Result<Vec<u8>> {
+        let (_, y) = affine_coordinates(&self.private_key, Curve::P384)?;
+        Ok(y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::EcKeyPair;
+    use crate::ec::{Curve, KeyWrapAlgorithm};
+
+    #[rstest]
+    #[case(Curve::P256)]
+    #[case(Curve::P384)]
+    fn test_unwrap_key(#[case] curve: Curve) {
+        let keypair = EcKeyPair::new(curve).unwrap();
+        assert_eq!(keypair.curve(), curve);
+        assert_eq!(keypair.x().unwrap().len(), curve.coordinate_len());
+
+        // Wrap the CEK as the KBS does, with an ephemeral key on the curve
+        let ephemeral = EcKeyPair::new(curve).unwrap();
+        let cek = [7_u8; 32];
+        let wrapped_cek = ephemeral
+            .wrap_key(
+                &cek,
+                &keypair.x().unwrap(),
+                &keypair.y().unwrap(),
+                KeyWrapAlgorithm::EcdhEsA256Kw,
+            )
+            .unwrap();
+
+        let unwrapped_cek = keypair
+            .unwrap_key(
+                wrapped_cek,
+                ephemeral.x().unwrap(),
+                ephemeral.y().unwrap(),
+                KeyWrapAlgorithm::EcdhEsA256Kw,
+            )
+            .unwrap();
+        assert_eq!(unwrapped_cek, cek);
+    }
+
+    #[rstest]
+    #[case(Curve::P256)]
+    #[case(Curve::P384)]
+    fn test_pkcs8_pem(#[case] curve: Curve) {
+        let keypair = EcKeyPair::new(curve).unwrap();
+        let pem = keypair.to_pkcs8_pem().unwrap();
+        let restored = EcKeyPair::from_pkcs8_pem(&pem).unwrap();
+        assert_eq!(restored.curve(), curve);
+        assert_eq!(restored.x().unwrap(), keypair.x().unwrap());
+        assert_eq!(restored.y().unwrap(), keypair.y().unwrap());
+    }
+}
@@ -5,6 +5,7 @@
 
 use anyhow::*;
 use openssl::{
+    bn::BigNum,
     pkey::Private,
     rsa::{Padding, Rsa},
 };
@@ -70,3 +71,35 @@ impl RSAKeyPair {
         Ok(Self { private_key })
     }
 }
+
+/// Encrypt the plaintext to the RSA public key of the given modulus and
+/// public exponent, both big endian. As in [`RSAKeyPair::decrypt`], OAEP
+/// uses SHA-1.
+pub fn encrypt(mode: PaddingMode, n: &[u8], e: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let public_key = Rsa::from_public_components(BigNum::from_slice(n)?, BigNum::from_slice(e)?)?;
+    let padding = match mode {
+        PaddingMode::OAEP => Padding::PKCS1_OAEP,
+        #[allow(deprecated)]
+        PaddingMode::PKCS1v15 => Padding::PKCS1,
+    };
+    let mut ciphertext = vec![0; public_key.size() as usize];
+    let len = public_key
+        .public_encrypt(plaintext, &mut ciphertext, padding)
+        .map_err(|e| anyhow!("RSA key encrypt failed: {:?}", e))?;
+    ciphertext.truncate(len);
+    Ok(ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encrypt, RSAKeyPair};
+    use crate::rsa::PaddingMode;
+
+    #[test]
+    fn test_encrypt() {
+        let keypair = RSAKeyPair::new().unwrap();
+        let ciphertext = encrypt(PaddingMode::OAEP, &keypair.n(), &keypair.e(), b"cek").unwrap();
+        let plaintext = keypair.decrypt(PaddingMode::OAEP, ciphertext).unwrap();
+        assert_eq!(plaintext, b"cek");
+    }
+}
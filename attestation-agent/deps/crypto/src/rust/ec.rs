@@ -10,18 +10,17 @@ use crate::{
 
 use aes_gcm::aead::generic_array::GenericArray;
 use aes_kw::{Kek, KekAes256};
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use p256::{
-    ecdh::diffie_hellman,
     elliptic_curve::sec1::FromEncodedPoint,
     pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding},
-    EncodedPoint, PublicKey as P256PublicKey, SecretKey,
 };
 use zeroize::Zeroizing;
 
 #[derive(Clone, Debug)]
 pub enum EcKeyPair {
     P256(P256EcKeyPair),
+    P384(P384EcKeyPair),
 }
 
 impl Default for EcKeyPair {
@@ -31,32 +30,41 @@ impl Default for EcKeyPair {
 }
 
 impl EcKeyPair {
-    pub fn curve(&self) -> Curve {
-        match self {
-            Self::P256(_) => Curve::P256,
-        }
+    /// Generate a new key pair on the given curve.
+    pub fn new(curve: Curve) -> Result<Self> {
+        let keypair = match curve {
+            Curve::P256 => Self::P256(P256EcKeyPair::default()),
+            Curve::P384 => Self::P384(P384EcKeyPair::default()),
+        };
+        Ok(keypair)
     }
 
-    pub fn secret_key(&self) -> &SecretKey {
+    pub fn curve(&self) -> Curve {
         match self {
-            Self::P256(p256) => p256.secret_key(),
+            Self::P256(_) => Curve::P256,
+            Self::P384(_) => Curve::P384,
         }
     }
 
     pub fn x(&self) -> Result<Vec<u8>> {
         match self {
             Self::P256(p256) => p256.x(),
+            Self::P384(p384) => p384.x(),
         }
     }
 
     pub fn y(&self) -> Result<Vec<u8>> {
         match self {
             Self::P256(p256) => p256.y(),
+            Self::P384(p384) => p384.y(),
         }
     }
 
     pub fn to_pkcs8_pem(&self) -> Result<Zeroizing<String>> {
-        let pem = self.secret_key().to_pkcs8_pem(LineEnding::default())?;
+        let pem = match self {
+            Self::P256(p256) => p256.secret_key().to_pkcs8_pem(LineEnding::default())?,
+            Self::P384(p384) => p384.secret_key().to_pkcs8_pem(LineEnding::default())?,
+        };
         Ok(pem)
     }
 
@@ -65,6 +73,10 @@ impl EcKeyPair {
             return Ok(Self::P256(p256));
         };
 
+        if let Ok(p384) = P384EcKeyPair::from_pkcs8_pem(pem) {
+            return Ok(Self::P384(p384));
+        };
+
         Err(anyhow!("invalid key type"))
     }
 
@@ -77,45 +89,8 @@ impl EcKeyPair {
     ) -> Result<Vec<u8>> {
         match wrapping_algorithm {
             KeyWrapAlgorithm::EcdhEsA256Kw => {
-                let secret_key = self.secret_key();
-                let epk_x: [u8; 32] = epk_x
-                    .try_into()
-                    .map_err(|_| anyhow!("invalid bytes length of coordinates X"))?;
-                let epk_y: [u8; 32] = epk_y
-                    .try_into()
-                    .map_err(|_| anyhow!("invalid bytes length of coordinates Y"))?;
-                let epk_point = EncodedPoint::from_affine_coordinates(
-                    &GenericArray::from(epk_x),
-                    &GenericArray::from(epk_y),
-                    false,
-                );
-                let public_key =
-                    Into::<Option<_>>::into(P256PublicKey::from_encoded_point(&epk_point));
-                let public_key: P256PublicKey = public_key.ok_or(anyhow!("invalid public key"))?;
-
-                let z = diffie_hellman(secret_key.to_nonzero_scalar(), public_key.as_affine())
-                    .raw_secret_bytes()
-                    .to_vec();
-
-                let mut key_derivation_materials = Vec::new();
-                let algorithm_str = KeyWrapAlgorithm::EcdhEsA256Kw.as_ref();
-                key_derivation_materials
-                    .extend_from_slice(&(algorithm_str.len() as u32).to_be_bytes());
-                key_derivation_materials.extend_from_slice(algorithm_str.as_bytes());
-                key_derivation_materials.extend_from_slice(&(0_u32).to_be_bytes());
-                key_derivation_materials.extend_from_slice(&(0_u32).to_be_bytes());
-                key_derivation_materials.extend_from_slice(&AES_GCM_256_KEY_BITS.to_be_bytes());
-                let mut unwrapping_key = vec![0; 32];
-                concat_kdf::derive_key_into::<rsa::sha2::Sha256>(
-                    &z,
-                    &key_derivation_materials,
-                    &mut unwrapping_key,
-                )
-                .map_err(|e| anyhow!("failed to do concat KDF: {e:?}"))?;
-                let unwrapping_key: [u8; 32] = unwrapping_key
-                    .try_into()
-                    .map_err(|_| anyhow!("invalid bytes length of AES wrapping key"))?;
-                let unwrapping_key: KekAes256 = Kek::new(&GenericArray::from(unwrapping_key));
+                let z = self.diffie_hellman(&epk_x, &epk_y)?;
+                let unwrapping_key = ecdh_es_a256kw_key(&z)?;
                 let mut decrypted_key = vec![0; encrypted_key.len() - 8];
                 unwrapping_key
                     .unwrap(&encrypted_key, &mut decrypted_key)
@@ -125,18 +100,81 @@ impl EcKeyPair {
             }
         }
     }
+
+    /// Wrap the key to the peer public key of the given coordinates, with
+    /// this key pair as the ephemeral key, as the KBS does.
+    pub fn wrap_key(
+        &self,
+        key: &[u8],
+        peer_x: &[u8],
+        peer_y: &[u8],
+        wrapping_algorithm: KeyWrapAlgorithm,
+    ) -> Result<Vec<u8>> {
+        match wrapping_algorithm {
+            KeyWrapAlgorithm::EcdhEsA256Kw => {
+                let z = self.diffie_hellman(peer_x, peer_y)?;
+                let wrapping_key = ecdh_es_a256kw_key(&z)?;
+                let mut wrapped_key = vec![0; key.len() + 8];
+                wrapping_key
+                    .wrap(key, &mut wrapped_key)
+                    .map_err(|e| anyhow!("failed to wrap key: {e:?}"))?;
+
+                Ok(wrapped_key)
+            }
+        }
+    }
+
+    /// The ECDH shared secret with the peer public key of the given
+    /// coordinates.
+    fn diffie_hellman(&self, epk_x: &[u8], epk_y: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let coordinate_len = self.curve().coordinate_len();
+        if epk_x.len() != coordinate_len {
+            bail!("invalid bytes length of coordinates X");
+        }
+        if epk_y.len() != coordinate_len {
+            bail!("invalid bytes length of coordinates Y");
+        }
+
+        match self {
+            Self::P256(p256) => p256.diffie_hellman(epk_x, epk_y),
+            Self::P384(p384) => p384.diffie_hellman(epk_x, epk_y),
+        }
+    }
+}
+
+/// Derive the AES key wrapping key of `ECDH-ES+A256KW` from the ECDH shared
+/// secret with concat KDF.
+fn ecdh_es_a256kw_key(z: &[u8]) -> Result<KekAes256> {
+    let mut key_derivation_materials = Vec::new();
+    let algorithm_str = KeyWrapAlgorithm::EcdhEsA256Kw.as_ref();
+    key_derivation_materials.extend_from_slice(&(algorithm_str.len() as u32).to_be_bytes());
+    key_derivation_materials.extend_from_slice(algorithm_str.as_bytes());
+    key_derivation_materials.extend_from_slice(&(0_u32).to_be_bytes());
+    key_derivation_materials.extend_from_slice(&(0_u32).to_be_bytes());
+    key_derivation_materials.extend_from_slice(&AES_GCM_256_KEY_BITS.to_be_bytes());
+    let mut wrapping_key = vec![0; 32];
+    concat_kdf::derive_key_into::<rsa::sha2::Sha256>(
+        z,
+        &key_derivation_materials,
+        &mut wrapping_key,
+    )
+    .map_err(|e| anyhow!("failed to do concat KDF: {e:?}"))?;
+    let wrapping_key: [u8; 32] = wrapping_key
+        .try_into()
+        .map_err(|_| anyhow!("invalid bytes length of AES wrapping key"))?;
+    Ok(Kek::new(&GenericArray::from(wrapping_key)))
 }
 
 #[derive(Clone, Debug)]
 pub struct P256EcKeyPair {
-    secret_key: SecretKey,
-    public_key: P256PublicKey,
+    secret_key: p256::SecretKey,
+    public_key: p256::PublicKey,
 }
 
 impl Default for P256EcKeyPair {
     fn default() -> Self {
         let mut rng = rand_08::thread_rng();
-        let secret_key = SecretKey::random(&mut rng);
+        let secret_key = p256::SecretKey::random(&mut rng);
         let public_key = secret_key.public_key();
         Self {
             secret_key,
@@ -146,12 +184,12 @@ impl Default for P256EcKeyPair {
 }
 
 impl P256EcKeyPair {
-    pub fn secret_key(&self) -> &SecretKey {
+    pub fn secret_key(&self) -> &p256::SecretKey {
         &self.secret_key
     }
 
     pub fn from_pkcs8_pem(pem: &str) -> Result<Self> {
-        let secret_key = SecretKey::from_pkcs8_pem(pem)?;
+        let secret_key = p256::SecretKey::from_pkcs8_pem(pem)?;
         let public_key = secret_key.public_key();
         Ok(Self {
             secret_key,
@@ -160,7 +198,7 @@ impl P256EcKeyPair {
     }
 
     pub fn x(&self) -> Result<Vec<u8>> {
-        let x = EncodedPoint::from(self.public_key)
+        let x = p256::EncodedPoint::from(self.public_key)
             .x()
             .ok_or(anyhow!("invalid public key: without coordinate X"))?
             .to_vec();
@@ -168,10 +206,142 @@ impl P256EcKeyPair {
     }
 
     pub fn y(&self) -> Result<Vec<u8>> {
-        let y = EncodedPoint::from(self.public_key)
+        let y = p256::EncodedPoint::from(self.public_key)
             .y()
             .ok_or(anyhow!("invalid public key: without coordinate Y"))?
             .to_vec();
         Ok(y)
     }
+
+    fn diffie_hellman(&self, epk_x: &[u8], epk_y: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let epk_point = p256::EncodedPoint::from_affine_coordinates(
+            p256::FieldBytes::from_slice(epk_x),
+            p256::FieldBytes::from_slice(epk_y),
+            false,
+        );
+        let public_key = Into::<Option<_>>::into(p256::PublicKey::from_encoded_point(&epk_point));
+        let public_key: p256::PublicKey = public_key.ok_or(anyhow!("invalid public key"))?;
+
+        let z =
+            p256::ecdh::diffie_hellman(self.secret_key.to_nonzero_scalar(), public_key.as_affine())
+                .raw_secret_bytes()
+                .to_vec();
+        Ok(Zeroizing::new(z))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct P384EcKeyPair {
+    secret_key: p384::SecretKey,
+    public_key: p384::PublicKey,
+}
+
+impl Default for P384EcKeyPair {
+    fn default() -> Self {
+        let mut rng = rand_08::thread_rng();
+        let secret_key = p384::SecretKey::random(&mut rng);
+        let public_key = secret_key.public_key();
+        Self {
+            secret_key,
+            public_key,
+        }
+    }
+}
+
+impl P384EcKeyPair {
+    pub fn secret_key(&self) -> &p384::SecretKey {
+        &self.secret_key
+    }
+
+    pub fn from_pkcs8_pem(pem: &str) -> Result<Self> {
+        let secret_key = p384::SecretKey::from_pkcs8_pem(pem)?;
+        let public_key = secret_key.public_key();
+        Ok(Self {
+            secret_key,
+            public_key,
+        })
+    }
+
+    pub fn x(&self) -> Result<Vec<u8>> {
+        let x = p384::EncodedPoint::from(self.public_key)
+            .x()
+            .ok_or(anyhow!("invalid public key: without coordinate X"))?
+            .to_vec();
+        Ok(x)
+    }
+
+    pub fn y(&self) -> Result<Vec<u8>> {
+        let y = p384::EncodedPoint::from(self.public_key)
+            .y()
+            .ok_or(anyhow!("invalid public key: without coordinate Y"))?
+            .to_vec();
+        Ok(y)
+    }
+
+    fn diffie_hellman(&self, epk_x: &[u8], epk_y: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let epk_point = p384::EncodedPoint::from_affine_coordinates(
+            p384::FieldBytes::from_slice(epk_x),
+            p384::FieldBytes::from_slice(epk_y),
+            false,
+        );
+        let public_key = Into::<Option<_>>::into(p384::PublicKey::from_encoded_point(&epk_point));
+        let public_key: p384::PublicKey = public_key.ok_or(anyhow!("invalid public key"))?;
+
+        let z =
+            p384::ecdh::diffie_hellman(self.secret_key.to_nonzero_scalar(), public_key.as_affine())
+                .raw_secret_bytes()
+                .to_vec();
+        Ok(Zeroizing::new(z))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::EcKeyPair;
+    use crate::ec::{Curve, KeyWrapAlgorithm};
+
+    #[rstest]
+    #[case(Curve::P256)]
+    #[case(Curve::P384)]
+    fn test_unwrap_key(#[case] curve: Curve) {
+        let keypair = EcKeyPair::new(curve).unwrap();
+        assert_eq!(keypair.curve(), curve);
+        assert_eq!(keypair.x().unwrap().len(), curve.coordinate_len());
+
+        // Wrap the CEK as the KBS does, with an ephemeral key on the curve
+        let ephemeral = EcKeyPair::new(curve).unwrap();
+        let cek = [7_u8; 32];
+        let wrapped_cek = ephemeral
+            .wrap_key(
+                &cek,
+                &keypair.x().unwrap(),
+                &keypair.y().unwrap(),
+                KeyWrapAlgorithm::EcdhEsA256Kw,
+            )
+            .unwrap();
+
+        let unwrapped_cek = keypair
+            .unwrap_key(
+                wrapped_cek,
+                ephemeral.x().unwrap(),
+                ephemeral.y().unwrap(),
+                KeyWrapAlgorithm::EcdhEsA256Kw,
+            )
+            .unwrap();
+        assert_eq!(unwrapped_cek, cek);
+    }
+
+    #[rstest]
+    #[case(Curve::P256)]
+    #[case(Curve::P384)]
+    fn test_pkcs8_pem(#[case] curve: Curve) {
+        let keypair = EcKeyPair::new(curve).unwrap();
+        let pem = keypair.to_pkcs8_pem().unwrap();
+        let restored = EcKeyPair::from_pkcs8_pem(&pem).unwrap();
+        assert_eq!(restored.curve(), curve);
+        assert_eq!(restored.x().unwrap(), keypair.x().unwrap());
+        assert_eq!(restored.y().unwrap(), keypair.y().unwrap());
+    }
 }
@@ -10,7 +10,7 @@ use rsa::{
     pkcs1::{DecodeRsaPrivateKey, EncodeRsaPrivateKey},
     pkcs8::LineEnding,
     traits::PublicKeyParts,
-    Oaep, Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey,
+    BigUint, Oaep, Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey,
 };
 use zeroize::Zeroizing;
 
@@ -72,3 +72,33 @@ impl RSAKeyPair {
         })
     }
 }
+
+/// Encrypt the plaintext to the RSA public key of the given modulus and
+/// public exponent, both big endian.
+pub fn encrypt(mode: PaddingMode, n: &[u8], e: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let public_key = RsaPublicKey::new(BigUint::from_bytes_be(n), BigUint::from_bytes_be(e))?;
+    let mut rng = rsa::rand_core::OsRng;
+    match mode {
+        PaddingMode::OAEP => public_key
+            .encrypt(&mut rng, Oaep::new::<sha2::Sha256>(), plaintext)
+            .map_err(|e| anyhow!("RSA key encrypt OAEP failed: {:?}", e)),
+        #[allow(deprecated)]
+        PaddingMode::PKCS1v15 => public_key
+            .encrypt(&mut rng, Pkcs1v15Encrypt, plaintext)
+            .map_err(|e| anyhow!("RSA key pkcs1v15 encrypt failed: {:?}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encrypt, RSAKeyPair};
+    use crate::rsa::PaddingMode;
+
+    #[test]
+    fn test_encrypt() {
+        let keypair = RSAKeyPair::new().unwrap();
+        let ciphertext = encrypt(PaddingMode::OAEP, &keypair.n(), &keypair.e(), b"cek").unwrap();
+        let plaintext = keypair.decrypt(PaddingMode::OAEP, ciphertext).unwrap();
+        assert_eq!(plaintext, b"cek");
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! JWE encryption of the resources to the TEE public key, as the KBS does.
+//! The content is encrypted with `A256GCM` under a random CEK, which is
+//! wrapped with `RSA-OAEP-256` or `ECDH-ES+A256KW`.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use crypto::{
+    ec::{Curve, EcKeyPair, KeyWrapAlgorithm, EC_KTY},
+    rsa::PaddingMode,
+    WrapType,
+};
+use kbs_types::{ProtectedHeader, TeePubKey};
+use serde_json::{json, Value};
+use zeroize::Zeroizing;
+
+/// Encrypt the plaintext to the TEE public key. Returns the JSON body of
+/// the KBS response.
+pub(crate) fn encrypt(tee_pubkey: &TeePubKey, plaintext: &[u8]) -> Result<Value> {
+    let cek = Zeroizing::new(rand::random::<[u8; 32]>().to_vec());
+    let enc = WrapType::Aes256Gcm.as_ref();
+    let (header, encrypted_key) = match tee_pubkey {
+        TeePubKey::RSA { alg, k_mod, k_exp } => {
+            if alg != PaddingMode::OAEP.as_ref() {
+                bail!("unsupported RSA algorithm `{alg}`");
+            }
+
+            let n = URL_SAFE_NO_PAD.decode(k_mod)?;
+            let e = URL_SAFE_NO_PAD.decode(k_exp)?;
+            let encrypted_key = crypto::rsa::encrypt(PaddingMode::OAEP, &n, &e, &cek)?;
+            (json!({ "alg": alg, "enc": enc }), encrypted_key)
+        }
+        TeePubKey::EC { crv, alg, x, y } => {
+            let curve = Curve::from_str(crv).map_err(|_| anyhow!("unsupported curve `{crv}`"))?;
+            let algorithm = KeyWrapAlgorithm::from_str(alg)
+                .map_err(|_| anyhow!("unsupported EC algorithm `{alg}`"))?;
+
+            let x = URL_SAFE_NO_PAD.decode(x)?;
+            let y = URL_SAFE_NO_PAD.decode(y)?;
+            let ephemeral = EcKeyPair::new(curve)?;
+            let encrypted_key = ephemeral.wrap_key(&cek, &x, &y, algorithm)?;
+            let header = json!({
+                "alg": alg,
+                "enc": enc,
+                "epk": {
+                    "kty": EC_KTY,
+                    "crv": crv,
+                    "x": URL_SAFE_NO_PAD.encode(ephemeral.x()?),
+                    "y": URL_SAFE_NO_PAD.encode(ephemeral.y()?),
+                },
+            });
+            (header, encrypted_key)
+        }
+    };
+
+    let protected: ProtectedHeader = serde_json::from_value(header)?;
+    let aad = protected.generate_aad()?;
+    let iv = rand::random::<[u8; 12]>();
+    let cipher = crypto::encrypt_aead(
+        cek,
+        plaintext.to_vec(),
+        iv.to_vec(),
+        aad,
+        WrapType::Aes256Gcm,
+    )?;
+
+    Ok(json!({
+        "protected": URL_SAFE_NO_PAD.encode(serde_json::to_string(&protected)?),
+        "encrypted_key": URL_SAFE_NO_PAD.encode(encrypted_key),
+        "iv": URL_SAFE_NO_PAD.encode(iv),
+        "ciphertext": URL_SAFE_NO_PAD.encode(cipher.ciphertext),
+        "tag": URL_SAFE_NO_PAD.encode(cipher.tag),
+    }))
+}
@@ -0,0 +1,609 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! # Mock KBS
+//!
+//! A minimal in-process KBS to test the KBS clients hermetically. It serves
+//! the `auth`, `attest` and `resource` endpoints of the
+//! [KBS attestation protocol](https://github.com/confidential-containers/trustee/blob/main/kbs/docs/kbs_attestation_protocol.md):
+//! - Any evidence is accepted. A successful attestation returns a JWT signed
+//!   with an Ed25519 key of the mock, certifying the TEE public key.
+//! - Resources are JWE encrypted to the TEE public key of the attested
+//!   session, or of the token given as bearer.
+//! - Resources can be set and deleted, authenticated with a JWT signed by the
+//!   admin key if one is set, otherwise with the attested session or token.
+//! - A single byte range of a resource can be requested with a `Range`
+//!   header. The range is applied to the content before encryption, and
+//!   answered with `206 Partial Content` and a `Content-Range` header.
+//! - The `challenge` and `attestation` endpoints of the RESTful CoCo
+//!   attestation service are served as well. A token certifying the
+//!   `tee-pubkey` of the structured runtime data of the first verification
+//!   request is returned for the nonces the mock issued.
+//! - Error responses can be injected for each endpoint.
+//!
+//! ```no_run
+//! use mock_kbs::{Endpoint, MockKbs};
+//!
+//! async fn test() {
+//!     let kbs = MockKbs::start().await.unwrap();
+//!     kbs.add_resource("default/key/1", b"secret");
+//!     kbs.inject_error(Endpoint::Resource, 500);
+//!
+//!     // point the client to `kbs.url()`
+//! }
+//! ```
+
+mod jwe;
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    body::{Bytes, Incoming},
+    header,
+    server::conn::http1,
+    service::service_fn,
+    Method, Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use jwt_simple::prelude::{
+    Claims, Duration, Ed25519KeyPair, Ed25519PublicKey, EdDSAKeyPairLike, EdDSAPublicKeyLike,
+};
+use kbs_types::{Attestation, Challenge, TeePubKey};
+use log::debug;
+use serde_json::{json, Value};
+use tokio::{net::TcpListener, sync::oneshot};
+
+/// Name of the cookie carrying the session id.
+pub const KBS_SESSION_ID: &str = "kbs-session-id";
+
+/// Claim of the token carrying the TEE public key.
+pub const TEE_PUBKEY_CLAIM: &str = "tee-pubkey";
+
+const KBS_PREFIX: &str = "/kbs/v0";
+
+/// Default validity of the issued tokens.
+const DEFAULT_TOKEN_VALIDITY_MINUTES: u64 = 5;
+
+type Body = Full<Bytes>;
+
+/// The endpoints of the mock KBS.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Auth,
+    Attest,
+    Resource,
+    SetResource,
+    DeleteResource,
+    AsChallenge,
+    AsAttestation,
+}
+
+#[derive(Default)]
+struct Session {
+    nonce: String,
+    tee_pubkey: Option<TeePubKey>,
+}
+
+struct State {
+    resources: HashMap<String, Vec<u8>>,
+    sessions: HashMap<String, Session>,
+    as_nonces: HashSet<String>,
+    injected_errors: HashMap<Endpoint, VecDeque<u16>>,
+    requests: HashMap<Endpoint, usize>,
+    token_key: Ed25519KeyPair,
+    token_validity: Duration,
+    trusted_token_keys: Vec<Ed25519PublicKey>,
+    admin_key: Option<Ed25519PublicKey>,
+}
+
+/// A mock KBS listening on a random local port. It stops when dropped.
+pub struct MockKbs {
+    state: Arc<Mutex<State>>,
+    addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockKbs {
+    /// Start the mock KBS on a random local port. Must be called inside a
+    /// tokio runtime.
+    pub async fn start() -> Result<Self> {
+        let state = Arc::new(Mutex::new(State {
+            resources: HashMap::new(),
+            sessions: HashMap::new(),
+            as_nonces: HashSet::new(),
+            injected_errors: HashMap::new(),
+            requests: HashMap::new(),
+            token_key: Ed25519KeyPair::generate(),
+            token_validity: Duration::from_mins(DEFAULT_TOKEN_VALIDITY_MINUTES),
+            trusted_token_keys: Vec::new(),
+            admin_key: None,
+        }));
+
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .context("bind mock KBS")?;
+        let addr = listener.local_addr().context("get mock KBS address")?;
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        tokio::spawn(serve(listener, state.clone(), shutdown_rx));
+        debug!("mock KBS listening on {addr}");
+
+        Ok(Self {
+            state,
+            addr,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// The URL of the mock KBS, e.g. `http://127.0.0.1:34567`. It is also
+    /// the URL of the mock attestation service.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Add a resource of path `<repository>/<type>/<tag>`.
+    pub fn add_resource(&self, path: &str, content: &[u8]) {
+        self.state()
+            .resources
+            .insert(path.trim_start_matches('/').to_string(), content.to_vec());
+    }
+
+    /// The content of the resource of path `<repository>/<type>/<tag>`, if
+    /// any.
+    pub fn resource(&self, path: &str) -> Option<Vec<u8>> {
+        self.state()
+            .resources
+            .get(path.trim_start_matches('/'))
+            .cloned()
+    }
+
+    /// Require the resource management requests to be authenticated with a
+    /// JWT signed by the admin key, given by its public key (PEM format).
+    pub fn set_admin_public_key(&self, public_key_pem: &str) -> Result<()> {
+        let public_key = Ed25519PublicKey::from_pem(public_key_pem)
+            .map_err(|e| anyhow!("parse admin public key: {e}"))?;
+        self.state().admin_key = Some(public_key);
+        Ok(())
+    }
+
+    /// Answer the next request to the endpoint with the HTTP status. Errors
+    /// injected several times are returned in order.
+    pub fn inject_error(&self, endpoint: Endpoint, status: u16) {
+        self.state()
+            .injected_errors
+            .entry(endpoint)
+            .or_default()
+            .push_back(status);
+    }
+
+    /// Number of requests received by the endpoint, including the ones
+    /// answered with an injected error.
+    pub fn requests(&self, endpoint: Endpoint) -> usize {
+        self.state().requests.get(&endpoint).copied().unwrap_or(0)
+    }
+
+    /// Set the validity of the tokens issued from now on.
+    pub fn set_token_validity(&self, validity: Duration) {
+        self.state().token_validity = validity;
+    }
+
+    /// Forget all the attested sessions, as a restarted KBS would.
+    pub fn clear_sessions(&self) {
+        self.state().sessions.clear();
+    }
+
+    /// Issue a token certifying the TEE public key, as after a successful
+    /// attestation. Useful to test the passport mode.
+    pub fn issue_token(&self, tee_pubkey: &TeePubKey) -> Result<String> {
+        self.state().issue_token(tee_pubkey)
+    }
+
+    /// The public key (PEM format) verifying the issued tokens.
+    pub fn token_public_key_pem(&self) -> String {
+        self.state().token_key.public_key().to_pem()
+    }
+
+    /// Also accept the tokens verified by the public key (PEM format), e.g.
+    /// issued by another mock KBS acting as the attestation service of the
+    /// passport mode.
+    pub fn trust_token_issuer(&self, public_key_pem: &str) -> Result<()> {
+        let public_key = Ed25519PublicKey::from_pem(public_key_pem)
+            .map_err(|e| anyhow!("parse token public key: {e}"))?;
+        self.state().trusted_token_keys.push(public_key);
+        Ok(())
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("mock KBS state poisoned")
+    }
+}
+
+impl Drop for MockKbs {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+impl State {
+    fn issue_token(&self, tee_pubkey: &TeePubKey) -> Result<String> {
+        let claims = Claims::with_custom_claims(
+            json!({ TEE_PUBKEY_CLAIM: tee_pubkey }),
+            self.token_validity,
+        );
+        self.token_key
+            .sign(claims)
+            .map_err(|e| anyhow!("sign token: {e}"))
+    }
+
+    /// The TEE public key certified by the bearer token, if valid.
+    fn token_tee_pubkey(&self, token: &str) -> Option<TeePubKey> {
+        let claims = std::iter::once(self.token_key.public_key())
+            .chain(self.trusted_token_keys.iter().cloned())
+            .find_map(|key| key.verify_token::<Value>(token, None).ok())?;
+        serde_json::from_value(claims.custom.get(TEE_PUBKEY_CLAIM)?.clone()).ok()
+    }
+
+    /// The TEE public key of the attested session, or certified by the
+    /// bearer token.
+    fn tee_pubkey(&self, session_id: Option<&str>, bearer: Option<&str>) -> Option<TeePubKey> {
+        match bearer {
+            Some(token) => self.token_tee_pubkey(token),
+            None => session_id
+                .and_then(|id| self.sessions.get(id))
+                .and_then(|session| session.tee_pubkey.clone()),
+        }
+    }
+
+    /// Whether a resource management request is authenticated, with the
+    /// admin JWT if the admin key is set, otherwise as a resource request.
+    fn is_admin(&self, session_id: Option<&str>, bearer: Option<&str>) -> bool {
+        match &self.admin_key {
+            Some(admin_key) => {
+                bearer.is_some_and(|token| admin_key.verify_token::<Value>(token, None).is_ok())
+            }
+            None => self.tee_pubkey(session_id, bearer).is_some(),
+        }
+    }
+}
+
+/// Serve the connections accepted by the listener until shut down.
+async fn serve(
+    listener: TcpListener,
+    state: Arc<Mutex<State>>,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    debug!("mock KBS accept failed: {e}");
+                    continue;
+                }
+            },
+            _ = &mut shutdown => break,
+        };
+
+        let state = state.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |req| {
+                let state = state.clone();
+                async move { Ok::<_, Infallible>(handle(state, req).await) }
+            });
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!("mock KBS connection failed: {e}");
+            }
+        });
+    }
+}
+
+async fn handle(state: Arc<Mutex<State>>, req: Request<Incoming>) -> Response<Body> {
+    let path = req.uri().path().to_string();
+    let endpoint = match (req.method(), path.strip_prefix(KBS_PREFIX)) {
+        (&Method::POST, None) if path == "/challenge" => Endpoint::AsChallenge,
+        (&Method::POST, None) if path == "/attestation" => Endpoint::AsAttestation,
+        (&Method::POST, Some("/auth")) => Endpoint::Auth,
+        (&Method::POST, Some("/attest")) => Endpoint::Attest,
+        (&Method::GET, Some(p)) if p.starts_with("/resource/") => Endpoint::Resource,
+        (&Method::POST, Some(p)) if p.starts_with("/resource/") => Endpoint::SetResource,
+        (&Method::DELETE, Some(p)) if p.starts_with("/resource/") => Endpoint::DeleteResource,
+        _ => return error_response(StatusCode::NOT_FOUND, "NotFound", "unknown endpoint"),
+    };
+
+    let injected = {
+        let mut state = state.lock().expect("mock KBS state poisoned");
+        *state.requests.entry(endpoint).or_default() += 1;
+        state
+            .injected_errors
+            .get_mut(&endpoint)
+            .and_then(VecDeque::pop_front)
+    };
+    if let Some(status) = injected {
+        let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        return error_response(status, "InjectedError", "error injected by the test");
+    }
+
+    let session_id = session_id(&req);
+    let range = req
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let bearer = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(String::from);
+    let body = match req.into_body().collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, "InvalidRequest", &e.to_string()),
+    };
+
+    let mut state = state.lock().expect("mock KBS state poisoned");
+    let resource_path = path.trim_start_matches(&format!("{KBS_PREFIX}/resource/"));
+    match endpoint {
+        Endpoint::Auth => auth(&mut state),
+        Endpoint::Attest => attest(&mut state, session_id, &body),
+        Endpoint::AsChallenge => as_challenge(&mut state),
+        Endpoint::AsAttestation => as_attestation(&mut state, &body),
+        Endpoint::Resource => resource(
+            &state,
+            session_id.as_deref(),
+            bearer.as_deref(),
+            resource_path,
+            range.as_deref(),
+        ),
+        _ if !state.is_admin(session_id.as_deref(), bearer.as_deref()) => error_response(
+            StatusCode::UNAUTHORIZED,
+            "AdminAuthError",
+            "resource management request not authenticated",
+        ),
+        Endpoint::SetResource => {
+            state
+                .resources
+                .insert(resource_path.to_string(), body.to_vec());
+            Response::new(Body::default())
+        }
+        Endpoint::DeleteResource => match state.resources.remove(resource_path) {
+            Some(_) => Response::new(Body::default()),
+            None => error_response(
+                StatusCode::NOT_FOUND,
+                "ResourceNotFound",
+                &format!("resource {resource_path} not found"),
+            ),
+        },
+    }
+}
+
+fn auth(state: &mut State) -> Response<Body> {
+    let session_id = format!("{:032x}", rand::random::<u128>());
+    let nonce = STANDARD.encode(rand::random::<[u8; 32]>());
+    let challenge = Challenge {
+        nonce: nonce.clone(),
+        extra_params: json!({}),
+    };
+    state.sessions.insert(
+        session_id.clone(),
+        Session {
+            nonce,
+            tee_pubkey: None,
+        },
+    );
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(
+            header::SET_COOKIE,
+            format!("{KBS_SESSION_ID}={session_id}; Path={KBS_PREFIX}"),
+        )
+        .body(Body::from(json!(challenge).to_string()))
+        .expect("build response")
+}
+
+fn attest(state: &mut State, session_id: Option<String>, body: &[u8]) -> Response<Body> {
+    let Some(session) = session_id.and_then(|id| state.sessions.get_mut(&id)) else {
+        return error_response(StatusCode::UNAUTHORIZED, "InvalidSession", "no session");
+    };
+
+    let attestation = match serde_json::from_slice::<Attestation>(body) {
+        Ok(attestation) => attestation,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, "InvalidRequest", &e.to_string()),
+    };
+    if attestation.runtime_data.nonce != session.nonce {
+        return error_response(StatusCode::UNAUTHORIZED, "InvalidNonce", "nonce mismatch");
+    }
+
+    let tee_pubkey = attestation.runtime_data.tee_pubkey;
+    session.tee_pubkey = Some(tee_pubkey.clone());
+    match state.issue_token(&tee_pubkey) {
+        Ok(token) => json_response(json!({ "token": token })),
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "TokenIssueFailed",
+            &e.to_string(),
+        ),
+    }
+}
+
+fn as_challenge(state: &mut State) -> Response<Body> {
+    let nonce = STANDARD.encode(rand::random::<[u8; 32]>());
+    state.as_nonces.insert(nonce.clone());
+    Response::new(Body::from(nonce))
+}
+
+fn as_attestation(state: &mut State, body: &[u8]) -> Response<Body> {
+    let request = match serde_json::from_slice::<Value>(body) {
+        Ok(request) => request,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, "InvalidRequest", &e.to_string()),
+    };
+    let runtime_data = &request["verification_requests"][0]["runtime_data"]["structured"];
+    let nonce = runtime_data["nonce"].as_str().unwrap_or_default();
+    if !state.as_nonces.remove(nonce) {
+        return error_response(StatusCode::UNAUTHORIZED, "InvalidNonce", "unknown nonce");
+    }
+
+    let tee_pubkey = match serde_json::from_value::<TeePubKey>(runtime_data["tee-pubkey"].clone()) {
+        Ok(tee_pubkey) => tee_pubkey,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, "InvalidRequest", &e.to_string()),
+    };
+    match state.issue_token(&tee_pubkey) {
+        Ok(token) => Response::new(Body::from(token)),
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "TokenIssueFailed",
+            &e.to_string(),
+        ),
+    }
+}
+
+fn resource(
+    state: &State,
+    session_id: Option<&str>,
+    bearer: Option<&str>,
+    resource_path: &str,
+    range: Option<&str>,
+) -> Response<Body> {
+    let Some(tee_pubkey) = state.tee_pubkey(session_id, bearer) else {
+        return error_response(
+            StatusCode::UNAUTHORIZED,
+            "TokenNotFound",
+            "no attested session nor valid token",
+        );
+    };
+
+    let Some(content) = state.resources.get(resource_path) else {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            "ResourceNotFound",
+            &format!("resource {resource_path} not found"),
+        );
+    };
+
+    let total = content.len();
+    let (status, content_range, content) = match range {
+        Some(range) => match byte_range(range, total) {
+            Some(range) => (
+                StatusCode::PARTIAL_CONTENT,
+                Some(format!("bytes {}-{}/{total}", range.start, range.end - 1)),
+                &content[range],
+            ),
+            None => {
+                let mut response = error_response(
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    "InvalidRange",
+                    &format!("range `{range}` not satisfiable"),
+                );
+                response.headers_mut().insert(
+                    header::CONTENT_RANGE,
+                    header::HeaderValue::from_str(&format!("bytes */{total}"))
+                        .expect("valid Content-Range"),
+                );
+                return response;
+            }
+        },
+        None => (StatusCode::OK, None, &content[..]),
+    };
+
+    match jwe::encrypt(&tee_pubkey, content) {
+        Ok(response) => {
+            let mut response = json_response(response);
+            *response.status_mut() = status;
+            if let Some(content_range) = content_range {
+                response.headers_mut().insert(
+                    header::CONTENT_RANGE,
+                    header::HeaderValue::from_str(&content_range).expect("valid Content-Range"),
+                );
+            }
+            response
+        }
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "JweEncryptionFailed",
+            &format!("{e:#}"),
+        ),
+    }
+}
+
+/// The byte range of a `Range` header (`bytes=<first>-<last>`,
+/// `bytes=<first>-` or `bytes=-<suffix length>`) in a content of the length,
+/// if satisfiable. Multiple ranges are not supported.
+fn byte_range(range: &str, len: usize) -> Option<std::ops::Range<usize>> {
+    let (first, last) = range.strip_prefix("bytes=")?.split_once('-')?;
+    let (first, last) = match (first.trim(), last.trim()) {
+        ("", suffix) => {
+            let suffix: usize = suffix.parse().ok()?;
+            (len.checked_sub(suffix.min(len))?, len.checked_sub(1)?)
+        }
+        (first, "") => (first.parse().ok()?, len.checked_sub(1)?),
+        (first, last) => (
+            first.parse().ok()?,
+            last.parse::<usize>().ok()?.min(len.checked_sub(1)?),
+        ),
+    };
+    (first <= last && first < len).then_some(first..last + 1)
+}
+
+fn session_id(req: &Request<Incoming>) -> Option<String> {
+    req.headers()
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split("; "))
+        .find_map(|cookie| cookie.strip_prefix(&format!("{KBS_SESSION_ID}=")))
+        .map(String::from)
+}
+
+fn json_response(body: Value) -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("build response")
+}
+
+/// An error response carrying an `ErrorInformation`, as the KBS returns.
+fn error_response(status: StatusCode, error_type: &str, detail: &str) -> Response<Body> {
+    let body = json!({ "type": error_type, "detail": detail });
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("build response")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::byte_range;
+
+    #[rstest]
+    #[case("bytes=0-3", Some(0..4))]
+    #[case("bytes=2-", Some(2..10))]
+    #[case("bytes=-4", Some(6..10))]
+    #[case("bytes=5-100", Some(5..10))]
+    #[case("bytes=-100", Some(0..10))]
+    #[case("bytes=10-", None)]
+    #[case("bytes=4-3", None)]
+    #[case("bytes=0-1,4-5", None)]
+    #[case("items=0-3", None)]
+    fn test_byte_range(#[case] range: &str, #[case] expected: Option<std::ops::Range<usize>>) {
+        assert_eq!(byte_range(range, 10), expected);
+    }
+}
@@ -3,11 +3,243 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use crate::Result;
+use crate::{Error, Result};
 use async_trait::async_trait;
 pub use resource_uri::ResourceUri;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// Size of the plaintext ranges requested when streaming a resource.
+pub const RESOURCE_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+/// A plaintext range of a resource returned by the KBS.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResourceChunk {
+    /// Offset of `data` in the resource.
+    pub offset: u64,
+
+    pub data: Vec<u8>,
+
+    /// Size of the whole resource. `None` if the KBS does not support
+    /// ranges and returned the whole resource in `data`.
+    pub total: Option<u64>,
+}
 
 #[async_trait]
 pub trait KbsClientCapabilities {
     async fn get_resource(&mut self, resource_uri: ResourceUri) -> Result<Vec<u8>>;
+
+    /// Get the plaintext range `[offset, offset + len)` of the resource with
+    /// a `Range` header. Each range is encrypted separately by the KBS, so
+    /// only one range needs to be held in memory.
+    ///
+    /// Ranges are not part of the KBS protocol, so a KBS may ignore the
+    /// header and return the whole resource. Clients not implementing this
+    /// get the whole resource as well.
+    async fn get_resource_range(
+        &mut self,
+        resource_uri: ResourceUri,
+        _offset: u64,
+        _len: u64,
+    ) -> Result<ResourceChunk>
+    where
+        Self: Send,
+    {
+        let data = self.get_resource(resource_uri).await?;
+        Ok(ResourceChunk {
+            offset: 0,
+            data,
+            total: None,
+        })
+    }
+
+    /// Stream the resource into the writer, one range of
+    /// [`RESOURCE_CHUNK_SIZE`] at a time, each range being written before
+    /// the next one is requested. Returns the size of the resource.
+    async fn get_resource_to_writer(
+        &mut self,
+        resource_uri: ResourceUri,
+        writer: &mut (dyn AsyncWrite + Unpin + Send),
+    ) -> Result<u64>
+    where
+        Self: Send,
+    {
+        let mut offset = 0;
+        loop {
+            let chunk = self
+                .get_resource_range(resource_uri.clone(), offset, RESOURCE_CHUNK_SIZE)
+                .await?;
+
+            match chunk.total {
+                Some(_) if chunk.offset != offset => {
+                    return Err(Error::InvalidResourceRange(format!(
+                        "expected range from {offset}, got range from {}",
+                        chunk.offset
+                    )))
+                }
+                None if offset != 0 => {
+                    return Err(Error::InvalidResourceRange(format!(
+                        "expected range from {offset}, got the whole resource"
+                    )))
+                }
+                _ => {}
+            }
+
+            writer
+                .write_all(&chunk.data)
+                .await
+                .map_err(|e| Error::WriteResourceFailed(e.to_string()))?;
+            offset += chunk.data.len() as u64;
+
+            let Some(total) = chunk.total else {
+                break;
+            };
+            if offset >= total {
+                break;
+            }
+
+            if chunk.data.is_empty() {
+                return Err(Error::InvalidResourceRange(format!(
+                    "empty range at {offset} of {total} bytes"
+                )));
+            }
+        }
+
+        writer
+            .flush()
+            .await
+            .map_err(|e| Error::WriteResourceFailed(e.to_string()))?;
+        Ok(offset)
+    }
+
+    /// Store the resource into the KBS, overwriting any existing one. Not
+    /// supported unless implemented by the client.
+    async fn set_resource(&mut self, _resource_uri: ResourceUri, _content: Vec<u8>) -> Result<()>
+    where
+        Self: Send,
+    {
+        Err(Error::Unsupported("set_resource".into()))
+    }
+
+    /// Delete the resource from the KBS. Not supported unless implemented
+    /// by the client.
+    async fn delete_resource(&mut self, _resource_uri: ResourceUri) -> Result<()>
+    where
+        Self: Send,
+    {
+        Err(Error::Unsupported("delete_resource".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+    };
+
+    use async_trait::async_trait;
+    use tokio::io::AsyncWrite;
+
+    use super::{KbsClientCapabilities, ResourceChunk, ResourceUri, RESOURCE_CHUNK_SIZE};
+    use crate::Result;
+
+    const RESOURCE_SIZE: u64 = 2 * RESOURCE_CHUNK_SIZE + 1;
+
+    /// The byte of the resource at the offset.
+    fn resource_byte(offset: u64) -> u8 {
+        (offset % 251) as u8
+    }
+
+    /// A KBS client serving a resource of [`RESOURCE_SIZE`] bytes by range,
+    /// which checks that the previous ranges were written out before the
+    /// next one is requested.
+    struct RangeClient {
+        written: Arc<AtomicU64>,
+        requests: u64,
+    }
+
+    #[async_trait]
+    impl KbsClientCapabilities for RangeClient {
+        async fn get_resource(&mut self, _resource_uri: ResourceUri) -> Result<Vec<u8>> {
+            unreachable!("the whole resource must not be requested")
+        }
+
+        async fn get_resource_range(
+            &mut self,
+            _resource_uri: ResourceUri,
+            offset: u64,
+            len: u64,
+        ) -> Result<ResourceChunk> {
+            assert!(len <= RESOURCE_CHUNK_SIZE);
+            assert_eq!(self.written.load(Ordering::SeqCst), offset);
+            self.requests += 1;
+
+            let end = (offset + len).min(RESOURCE_SIZE);
+            Ok(ResourceChunk {
+                offset,
+                data: (offset..end).map(resource_byte).collect(),
+                total: Some(RESOURCE_SIZE),
+            })
+        }
+    }
+
+    /// A writer checking the resource bytes rather than keeping them.
+    struct CheckingWriter {
+        written: Arc<AtomicU64>,
+        max_write: usize,
+    }
+
+    impl AsyncWrite for CheckingWriter {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let offset = self.written.load(Ordering::SeqCst);
+            for (i, byte) in buf.iter().enumerate() {
+                assert_eq!(*byte, resource_byte(offset + i as u64));
+            }
+            self.written.fetch_add(buf.len() as u64, Ordering::SeqCst);
+            self.max_write = self.max_write.max(buf.len());
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_resource_to_writer_streams_ranges() {
+        let written = Arc::new(AtomicU64::new(0));
+        let mut client = RangeClient {
+            written: written.clone(),
+            requests: 0,
+        };
+        let mut writer = CheckingWriter {
+            written: written.clone(),
+            max_write: 0,
+        };
+
+        let size = client
+            .get_resource_to_writer(
+                "kbs:///default/model/large".try_into().unwrap(),
+                &mut writer,
+            )
+            .await
+            .expect("stream resource");
+        assert_eq!(size, RESOURCE_SIZE);
+        assert_eq!(written.load(Ordering::SeqCst), RESOURCE_SIZE);
+        assert_eq!(client.requests, 3);
+        assert!(writer.max_write as u64 <= RESOURCE_CHUNK_SIZE);
+    }
 }
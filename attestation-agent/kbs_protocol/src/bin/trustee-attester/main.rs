@@ -3,20 +3,23 @@
 // Licensed under the Apache License, Version 2.0, see LICENSE for details.
 // SPDX-License-Identifier: Apache-2.0
 
-//! Attest and fetch confidential resources from Trustee
+//! Attest and fetch, store or delete confidential resources in Trustee
 
 use anyhow::Result;
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use log::debug;
 use std::fs;
 use std::path::PathBuf;
 
 use kbs_protocol::evidence_provider::NativeEvidenceProvider;
+use kbs_protocol::token_provider::{CoCoAsTokenProvider, KbsTokenProvider, TokenProvider};
 use kbs_protocol::KbsClientBuilder;
 use kbs_protocol::KbsClientCapabilities;
 use kbs_protocol::ResourceUri;
+use kbs_protocol::SessionStore;
+use kbs_protocol::TeeKeyAlgorithm;
 
 #[derive(Parser)]
 struct Cli {
@@ -28,10 +31,76 @@ struct Cli {
     #[clap(long, value_parser)]
     cert_file: Option<PathBuf>,
 
+    /// Client certificate file path (PEM format) for mutual TLS with Trustee
+    #[clap(long, value_parser, requires = "client_key_file")]
+    client_cert_file: Option<PathBuf>,
+
+    /// Client private key file path (PKCS#8 PEM format) for mutual TLS with
+    /// Trustee
+    #[clap(long, value_parser, requires = "client_cert_file")]
+    client_key_file: Option<PathBuf>,
+
+    /// SHA-256 hash of the SubjectPublicKeyInfo of the Trustee https
+    /// certificate, in `sha256/<base64>` format. Can be given multiple times
+    #[clap(long)]
+    spki_pin: Vec<String>,
+
+    /// Pin the Trustee https certificate by the `kbs_spki_pins` entry of the
+    /// initdata rather than by `--spki-pin`
+    #[clap(long)]
+    attested_tls: bool,
+
+    /// Trustee admin private key file path (PEM format). If given, the
+    /// resource management requests are authenticated with it rather than
+    /// with the attested session
+    #[clap(long, value_parser)]
+    admin_key_file: Option<PathBuf>,
+
+    /// Algorithm of the TEE key the secrets are wrapped to, one of `rsa`,
+    /// `ec-p256` or `ec-p384`
+    #[clap(long, default_value_t = TeeKeyAlgorithm::default())]
+    tee_key_algorithm: TeeKeyAlgorithm,
+
+    /// Persist the attested session into the file, sealed with a key
+    /// derived from the TEE, and resume it in later runs while its token is
+    /// valid. Fails on a platform which cannot derive sealing keys
+    #[clap(long, value_parser)]
+    session_file: Option<PathBuf>,
+
+    /// Passport mode: attest to the attestation service at this URL, e.g.
+    /// the issuer KBS, and present the token it issues to Trustee at `--url`
+    /// rather than attesting to it. The token is refreshed before it
+    /// expires
+    #[clap(long, value_parser)]
+    as_url: Option<String>,
+
+    /// API of the attestation service of `--as-url`
+    #[clap(long, value_enum, default_value_t = AsType::Kbs, requires = "as_url")]
+    as_type: AsType,
+
+    /// Attestation service https certificate file path (PEM format)
+    #[clap(long, value_parser, requires = "as_url")]
+    as_cert_file: Option<PathBuf>,
+
+    /// Id of a policy the CoCo attestation service evaluates. Can be given
+    /// multiple times
+    #[clap(long, requires = "as_url")]
+    as_policy_id: Vec<String>,
+
     #[clap(subcommand)]
     command: Commands,
 }
 
+/// The API of the attestation service issuing the tokens in passport mode.
+#[derive(Clone, Copy, ValueEnum)]
+enum AsType {
+    /// The KBS attestation protocol, e.g. an issuer KBS
+    Kbs,
+
+    /// The RESTful API of a CoCo attestation service
+    CocoAs,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Get confidential resource
@@ -45,54 +114,226 @@ enum Commands {
         /// Initdata string
         #[clap(long)]
         initdata: Option<String>,
+
+        /// Stream the resource into the file rather than printing it base64
+        /// encoded
+        #[clap(long, value_parser)]
+        output: Option<PathBuf>,
     },
-}
 
-#[tokio::main(flavor = "current_thread")]
-async fn main() -> Result<()> {
-    env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
+    /// Store a confidential resource, overwriting any existing one
+    #[clap(arg_required_else_help = true)]
+    SetResource {
+        /// KBS Resource path of format <repository>/<type>/<tag>
+        #[clap(long, value_parser)]
+        path: String,
 
-    let cli = Cli::parse();
+        /// Path of the file holding the resource content
+        #[clap(long, value_parser)]
+        resource_file: PathBuf,
 
-    let url = cli.url;
-    let cert_file = cli.cert_file;
+        /// Initdata string
+        #[clap(long)]
+        initdata: Option<String>,
+    },
 
-    debug!("url {url}");
-    debug!("cert_file {cert_file:?}");
+    /// Delete a confidential resource
+    #[clap(arg_required_else_help = true)]
+    DeleteResource {
+        /// KBS Resource path of format <repository>/<type>/<tag>
+        #[clap(long, value_parser)]
+        path: String,
 
-    // Native evidence provider
-    let evidence_provider = Box::new(NativeEvidenceProvider::new()?);
+        /// Initdata string
+        #[clap(long)]
+        initdata: Option<String>,
+    },
+}
 
-    // a kbs_protocol client with evidence_provider
-    let mut client_builder = KbsClientBuilder::with_evidence_provider(evidence_provider, &url);
+/// Parse a resource path, which should start with '/' but not with '//'.
+fn parse_resource_uri(path: String) -> Result<ResourceUri> {
+    let resource_path = match path.starts_with('/') {
+        false => format!("/{path}"),
+        true => path,
+    };
 
+    ResourceUri::new("", &resource_path)
+}
+
+impl Commands {
+    fn initdata(&self) -> Option<&String> {
+        match self {
+            Commands::GetResource { initdata, .. }
+            | Commands::SetResource { initdata, .. }
+            | Commands::DeleteResource { initdata, .. } => initdata.as_ref(),
+        }
+    }
+}
+
+/// Apply the global options to the client of Trustee.
+fn configure<T>(
+    mut client_builder: KbsClientBuilder<T>,
+    cli: &Cli,
+    session_store: Option<SessionStore>,
+) -> Result<KbsClientBuilder<T>> {
     // if a certificate is given, use it
-    if let Some(cf) = cert_file {
+    if let Some(cf) = &cli.cert_file {
         debug!("Reading certificate from cert_file {}", cf.display());
         let cert = fs::read_to_string(cf)?;
         client_builder = client_builder.add_kbs_cert(&cert)
     }
 
-    match cli.command {
-        Commands::GetResource { path, initdata } => {
-            // resource_path should start with '/' but not with '//'
-            let resource_path = match path.starts_with('/') {
-                false => format!("/{path}"),
-                true => path,
-            };
+    if let (Some(cf), Some(kf)) = (&cli.client_cert_file, &cli.client_key_file) {
+        debug!(
+            "Reading client identity from {} and {}",
+            cf.display(),
+            kf.display()
+        );
+        let cert = fs::read_to_string(cf)?;
+        let key = fs::read_to_string(kf)?;
+        client_builder = client_builder.set_client_identity(&cert, &key);
+    }
 
-            if let Some(init) = initdata {
-                client_builder = client_builder.add_initdata(init);
-            }
-            let mut client = client_builder.build()?;
+    for pin in &cli.spki_pin {
+        client_builder = client_builder.add_kbs_spki_pin(pin);
+    }
+    client_builder = client_builder.set_attested_tls(cli.attested_tls);
+    client_builder = client_builder.set_tee_key_algorithm(cli.tee_key_algorithm);
 
-            let resource = ResourceUri::new("", &resource_path)?;
-            let (_token, _key) = client.get_token().await?; // attest first
-            let resource_bytes = client.get_resource(resource).await?;
+    if let Some(store) = session_store {
+        client_builder = client_builder.set_session_store(store);
+    }
 
-            println!("{}", STANDARD.encode(resource_bytes));
+    if let Some(kf) = &cli.admin_key_file {
+        debug!("Reading admin key from admin_key_file {}", kf.display());
+        let admin_key = fs::read_to_string(kf)?;
+        client_builder = client_builder.set_admin_key(&admin_key);
+    }
+
+    if let Some(init) = cli.command.initdata() {
+        client_builder = client_builder.add_initdata(init.clone());
+    }
+
+    Ok(client_builder)
+}
+
+async fn run(client: &mut (impl KbsClientCapabilities + Send), command: Commands) -> Result<()> {
+    match command {
+        Commands::GetResource { path, output, .. } => {
+            let resource = parse_resource_uri(path)?;
+            match output {
+                Some(output) => {
+                    let mut file = tokio::fs::File::create(&output).await?;
+                    let size = client.get_resource_to_writer(resource, &mut file).await?;
+                    debug!("Wrote {size} bytes to {}", output.display());
+                }
+                None => {
+                    let resource_bytes = client.get_resource(resource).await?;
+                    println!("{}", STANDARD.encode(resource_bytes));
+                }
+            }
+        }
+        Commands::SetResource {
+            path,
+            resource_file,
+            ..
+        } => {
+            let resource = parse_resource_uri(path)?;
+            let content = fs::read(resource_file)?;
+            client.set_resource(resource, content).await?;
+        }
+        Commands::DeleteResource { path, .. } => {
+            let resource = parse_resource_uri(path)?;
+            client.delete_resource(resource).await?;
         }
     };
 
     Ok(())
 }
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
+
+    let cli = Cli::parse();
+
+    debug!("url {}", cli.url);
+    debug!("cert_file {:?}", cli.cert_file);
+
+    // Native evidence provider
+    let evidence_provider = Box::new(NativeEvidenceProvider::new()?);
+
+    let session_store = match &cli.session_file {
+        Some(sf) => {
+            debug!("Deriving the key sealing session_file {}", sf.display());
+            Some(SessionStore::with_evidence_provider(sf, evidence_provider.as_ref()).await?)
+        }
+        None => None,
+    };
+
+    match &cli.as_url {
+        Some(as_url) => {
+            debug!("passport mode with attestation service {as_url}");
+
+            let as_cert = match &cli.as_cert_file {
+                Some(cf) => {
+                    debug!("Reading certificate from as_cert_file {}", cf.display());
+                    Some(fs::read_to_string(cf)?)
+                }
+                None => None,
+            };
+            let initdata = cli.command.initdata().cloned();
+
+            let token_provider: Box<dyn TokenProvider> = match cli.as_type {
+                AsType::Kbs => {
+                    // a kbs_protocol client of the attestation service, whose
+                    // tokens are presented to Trustee
+                    let mut as_client_builder =
+                        KbsClientBuilder::with_evidence_provider(evidence_provider, as_url)
+                            .set_tee_key_algorithm(cli.tee_key_algorithm);
+                    if let Some(cert) = &as_cert {
+                        as_client_builder = as_client_builder.add_kbs_cert(cert);
+                    }
+                    if let Some(init) = initdata {
+                        as_client_builder = as_client_builder.add_initdata(init);
+                    }
+                    Box::new(KbsTokenProvider::new(as_client_builder.build()?))
+                }
+                AsType::CocoAs => {
+                    let mut http_client_builder = reqwest::Client::builder();
+                    if let Some(cert) = &as_cert {
+                        http_client_builder = http_client_builder
+                            .add_root_certificate(reqwest::Certificate::from_pem(cert.as_bytes())?);
+                    }
+                    let mut provider = CoCoAsTokenProvider::new(
+                        evidence_provider,
+                        as_url,
+                        http_client_builder.build()?,
+                    )
+                    .set_policy_ids(cli.as_policy_id.clone())
+                    .set_tee_key_algorithm(cli.tee_key_algorithm);
+                    if let Some(init) = initdata {
+                        provider = provider.add_initdata(init);
+                    }
+                    Box::new(provider)
+                }
+            };
+
+            let client_builder = KbsClientBuilder::with_token_provider(token_provider, &cli.url);
+            let mut client = configure(client_builder, &cli, session_store)?.build()?;
+            run(&mut client, cli.command).await
+        }
+        None => {
+            // a kbs_protocol client with evidence_provider
+            let client_builder =
+                KbsClientBuilder::with_evidence_provider(evidence_provider, &cli.url);
+            let mut client = configure(client_builder, &cli, session_store)?.build()?;
+
+            let admin = cli.admin_key_file.is_some();
+            if !admin || matches!(cli.command, Commands::GetResource { .. }) {
+                let (_token, _key) = client.get_token().await?; // attest first
+            }
+            run(&mut client, cli.command).await
+        }
+    }
+}
@@ -4,28 +4,50 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use anyhow::*;
+use jwt_simple::prelude::Ed25519KeyPair;
+use log::warn;
+use reqwest::cookie::Jar;
 
 use crate::{
-    client::ClientTee,
+    client::{session_context, session_cookie_url, ClientTee},
     evidence_provider::EvidenceProvider,
-    keypair::TeeKeyPair,
+    keypair::{TeeKeyAlgorithm, TeeKeyPair},
+    proxy::ProxyConfig,
+    retry::RetryPolicy,
+    session::SessionStore,
+    tls::{spki_pins_from_initdata, SpkiPin, INITDATA_KBS_SPKI_PINS},
     token_provider::{Token, TokenProvider},
 };
 
+#[cfg(feature = "rust-crypto")]
+use crate::tls::pinned_tls_config;
+
 use super::client::KbsClient;
 
 const KBS_REQ_TIMEOUT_SEC: u64 = 60;
 
+pub(crate) const TOKEN_REFRESH_MARGIN_SEC: u64 = 60;
+
 pub struct KbsClientBuilder<T> {
     provider: T,
     kbs_certs: Vec<String>,
+    client_identity: Option<(String, String)>,
+    spki_pins: Vec<String>,
+    attested_tls: bool,
     kbs_host_url: String,
     token: Option<String>,
     tee_key: Option<String>,
+    tee_key_algorithm: TeeKeyAlgorithm,
     initdata: Option<String>,
+    admin_key: Option<String>,
+    proxy: Option<ProxyConfig>,
+    session_store: Option<SessionStore>,
+    retry_policy: RetryPolicy,
+    request_timeout: Duration,
+    token_refresh_margin: Duration,
 }
 
 impl KbsClientBuilder<Box<dyn EvidenceProvider>> {
@@ -36,10 +58,20 @@ impl KbsClientBuilder<Box<dyn EvidenceProvider>> {
         Self {
             provider: evidence_provider,
             kbs_certs: vec![],
+            client_identity: None,
+            spki_pins: vec![],
+            attested_tls: false,
             kbs_host_url: kbs_host_url.trim_end_matches('/').to_string(),
             token: None,
             tee_key: None,
+            tee_key_algorithm: TeeKeyAlgorithm::default(),
             initdata: None,
+            admin_key: None,
+            proxy: None,
+            session_store: None,
+            retry_policy: RetryPolicy::default(),
+            request_timeout: Duration::from_secs(KBS_REQ_TIMEOUT_SEC),
+            token_refresh_margin: Duration::from_secs(TOKEN_REFRESH_MARGIN_SEC),
         }
     }
 }
@@ -49,10 +81,20 @@ impl KbsClientBuilder<Box<dyn TokenProvider>> {
         Self {
             provider: token_provider,
             kbs_certs: vec![],
+            client_identity: None,
+            spki_pins: vec![],
+            attested_tls: false,
             kbs_host_url: kbs_host_url.trim_end_matches('/').to_string(),
             token: None,
             tee_key: None,
+            tee_key_algorithm: TeeKeyAlgorithm::default(),
             initdata: None,
+            admin_key: None,
+            proxy: None,
+            session_store: None,
+            retry_policy: RetryPolicy::default(),
+            request_timeout: Duration::from_secs(KBS_REQ_TIMEOUT_SEC),
+            token_refresh_margin: Duration::from_secs(TOKEN_REFRESH_MARGIN_SEC),
         }
     }
 }
@@ -63,6 +105,36 @@ impl<T> KbsClientBuilder<T> {
         self
     }
 
+    /// Set the client certificate (chain) and its PKCS#8 private key, both
+    /// in PEM format, to authenticate to the KBS with mutual TLS.
+    pub fn set_client_identity(mut self, cert_pem: &str, key_pem: &str) -> Self {
+        self.client_identity = Some((cert_pem.to_string(), key_pem.to_string()));
+        self
+    }
+
+    /// Pin the KBS certificate by the SHA-256 hash of its SubjectPublicKeyInfo,
+    /// in `sha256/<base64>` format. If any pin is given, the TLS handshake
+    /// with a KBS whose certificate matches none of them fails before any
+    /// request is sent, even if the certificate is trusted by the root
+    /// certificates.
+    ///
+    /// Pinning needs the `rust-crypto` feature; building the client fails
+    /// otherwise.
+    pub fn add_kbs_spki_pin(mut self, pin: &str) -> Self {
+        self.spki_pins.push(pin.to_string());
+        self
+    }
+
+    /// Enable the attested TLS mode. The KBS certificate is pinned by the
+    /// SPKI pins in the `kbs_spki_pins` entry of the initdata rather than by
+    /// the pins given with [`Self::add_kbs_spki_pin`]. As the initdata is
+    /// bound to the evidence, the pins cannot be altered without the KBS
+    /// noticing.
+    pub fn set_attested_tls(mut self, attested_tls: bool) -> Self {
+        self.attested_tls = attested_tls;
+        self
+    }
+
     pub fn set_token(mut self, token: &str) -> Self {
         self.token = Some(token.to_string());
         self
@@ -73,19 +145,99 @@ impl<T> KbsClientBuilder<T> {
         self
     }
 
+    /// Set the algorithm of the TEE key pair generated for the handshake.
+    /// Defaults to EC P-256. Ignored if the TEE key is given with
+    /// [`Self::set_tee_key`].
+    pub fn set_tee_key_algorithm(mut self, algorithm: TeeKeyAlgorithm) -> Self {
+        self.tee_key_algorithm = algorithm;
+        self
+    }
+
     pub fn add_initdata(mut self, initdata: String) -> Self {
         self.initdata = Some(initdata);
         self
     }
 
+    /// Set the Ed25519 admin private key (PEM format) of the KBS. If set, the
+    /// resource management requests are authenticated with the admin key
+    /// rather than the attested session.
+    pub fn set_admin_key(mut self, admin_key: &str) -> Self {
+        self.admin_key = Some(admin_key.to_string());
+        self
+    }
+
+    /// Set the proxies to reach the KBS. Without it, the proxies are taken
+    /// from the `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` environment
+    /// variables, if any.
+    pub fn set_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Persist the session with the KBS in the store. The client resumes
+    /// the persisted session, rather than performing a new RCAR handshake or
+    /// asking the token provider, while its token is valid. Ignored if the
+    /// token is given with [`Self::set_token`].
+    pub fn set_session_store(mut self, session_store: SessionStore) -> Self {
+        self.session_store = Some(session_store);
+        self
+    }
+
+    /// Set how the requests to the KBS are retried. By default, an RCAR
+    /// handshake is attempted 5 times, one second apart.
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the timeout of each request to the KBS. Defaults to 60 seconds.
+    pub fn set_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Refresh the token, with a new RCAR handshake or from the token
+    /// provider, once it expires within the margin rather than once it is
+    /// expired, so it does not expire while in use. Defaults to 60 seconds.
+    /// The margin is capped to a quarter of the lifetime of each token, so
+    /// a short-lived token is not refreshed at every request.
+    pub fn set_token_refresh_margin(mut self, margin: Duration) -> Self {
+        self.token_refresh_margin = margin;
+        self
+    }
+
+    /// The SPKI pins of the KBS certificate, read from the initdata in the
+    /// attested TLS mode.
+    fn spki_pins(&self) -> Result<Vec<SpkiPin>> {
+        if !self.attested_tls {
+            return self
+                .spki_pins
+                .iter()
+                .map(|pin| pin.parse::<SpkiPin>())
+                .collect::<std::result::Result<_, _>>()
+                .context("read KBS SPKI pin");
+        }
+
+        let initdata = self
+            .initdata
+            .as_deref()
+            .context("attested TLS requires initdata")?;
+        let pins = spki_pins_from_initdata(initdata).context("read KBS SPKI pins from initdata")?;
+        if pins.is_empty() {
+            bail!("attested TLS requires `{INITDATA_KBS_SPKI_PINS}` in initdata");
+        }
+        Ok(pins)
+    }
+
     pub fn build(self) -> Result<KbsClient<T>> {
+        let cookie_jar = Arc::new(Jar::default());
         let mut http_client_builder = reqwest::Client::builder()
-            .cookie_store(true)
+            .cookie_provider(cookie_jar.clone())
             .user_agent(format!(
                 "attestation-agent-kbs-client/{}",
                 env!("CARGO_PKG_VERSION")
             ))
-            .timeout(Duration::from_secs(KBS_REQ_TIMEOUT_SEC));
+            .timeout(self.request_timeout);
 
         for customer_root_cert in &self.kbs_certs {
             let cert = reqwest::Certificate::from_pem(customer_root_cert.as_bytes())
@@ -93,31 +245,89 @@ impl<T> KbsClientBuilder<T> {
             http_client_builder = http_client_builder.add_root_certificate(cert);
         }
 
+        if let Some((cert_pem, key_pem)) = &self.client_identity {
+            #[cfg(feature = "rust-crypto")]
+            let identity = reqwest::Identity::from_pem(format!("{key_pem}\n{cert_pem}").as_bytes());
+            #[cfg(not(feature = "rust-crypto"))]
+            let identity =
+                reqwest::Identity::from_pkcs8_pem(cert_pem.as_bytes(), key_pem.as_bytes());
+            http_client_builder =
+                http_client_builder.identity(identity.context("read client identity")?);
+        }
+
+        let spki_pins = self.spki_pins()?;
+
+        if let Some(proxy) = &self.proxy {
+            http_client_builder = proxy.apply(http_client_builder).context("set KBS proxy")?;
+        }
+
         #[cfg(feature = "rust-crypto")]
         {
             http_client_builder = http_client_builder.use_rustls_tls();
         }
 
-        let tee_key = match self.tee_key {
+        // The root certificates and the client identity given above are
+        // ignored with a preconfigured TLS, so they are set in it again.
+        if !spki_pins.is_empty() {
+            #[cfg(feature = "rust-crypto")]
+            {
+                let client_identity = self
+                    .client_identity
+                    .as_ref()
+                    .map(|(cert_pem, key_pem)| (cert_pem.as_str(), key_pem.as_str()));
+                let tls_config = pinned_tls_config(&self.kbs_certs, client_identity, spki_pins)
+                    .context("build pinned TLS config")?;
+                http_client_builder = http_client_builder.use_preconfigured_tls(tls_config);
+            }
+            #[cfg(not(feature = "rust-crypto"))]
+            bail!("pinning the KBS certificate requires the `rust-crypto` feature");
+        }
+
+        let mut tee_key = match self.tee_key {
             Some(key) => TeeKeyPair::from_pem(&key[..]).context("read tee public key")?,
-            None => TeeKeyPair::new()?,
+            None => TeeKeyPair::generate(self.tee_key_algorithm)?,
         };
 
-        let token = match self.token {
+        let mut token = match self.token {
             Some(t) => Some(Token::new(t).context("read token")?),
             None => None,
         };
 
+        if let (Some(store), None) = (&self.session_store, &token) {
+            let context = session_context(&self.kbs_host_url, self.initdata.as_deref());
+            let cookie_url = session_cookie_url(&self.kbs_host_url)?;
+            let resumed = store
+                .resume(&context, &cookie_jar, &cookie_url)
+                .unwrap_or_else(|e| {
+                    warn!("ignore the persisted KBS session: {e}");
+                    None
+                });
+            if let Some(session) = resumed {
+                token = Some(session.token);
+                tee_key = session.tee_key;
+            }
+        }
+
+        let admin_key = match self.admin_key {
+            Some(key) => Some(Ed25519KeyPair::from_pem(&key).context("read admin key")?),
+            None => None,
+        };
+
         let client = KbsClient {
             _tee: ClientTee::Uninitialized,
             tee_key,
             token,
+            token_refresh_margin: self.token_refresh_margin,
             provider: self.provider,
             http_client: http_client_builder
                 .build()
                 .context("Build KBS http client")?,
             kbs_host_url: self.kbs_host_url,
             _initdata: self.initdata,
+            admin_key,
+            retry_policy: self.retry_policy,
+            cookie_jar,
+            session_store: self.session_store,
         };
 
         Ok(client)
@@ -128,7 +338,14 @@ impl<T> KbsClientBuilder<T> {
 mod tests {
     use rstest::rstest;
 
-    use crate::{builder::KbsClientBuilder, evidence_provider::MockedEvidenceProvider};
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use jwt_simple::prelude::{Clock, Duration};
+    use serde_json::json;
+
+    use crate::{
+        builder::KbsClientBuilder, evidence_provider::MockedEvidenceProvider, SessionStore,
+        SpkiPin, TeeKeyAlgorithm, Token,
+    };
 
     #[rstest]
     #[tokio::test]
@@ -155,4 +372,97 @@ x13TMfDeczAFBgMrZXADQQBpP6ABBkzVj3mF55nWUtP5vxwq3t91wqQJ6NyC7WsT
         .build()
         .expect("build client failed");
     }
+
+    #[tokio::test]
+    async fn test_build_client_with_pins() {
+        let pin = "sha256/47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=";
+        let builder = || {
+            KbsClientBuilder::with_evidence_provider(
+                Box::<MockedEvidenceProvider>::default(),
+                "https://test.io",
+            )
+        };
+
+        let pinned = builder().add_kbs_spki_pin(pin);
+        assert_eq!(
+            pinned.spki_pins().unwrap(),
+            vec![pin.parse::<SpkiPin>().unwrap()]
+        );
+        pinned.build().unwrap();
+        assert!(builder().add_kbs_spki_pin("sha256/AAAA").build().is_err());
+
+        // Attested TLS requires the pins in the initdata
+        assert!(builder().set_attested_tls(true).build().is_err());
+        let initdata = format!(
+            r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"kbs_spki_pins" = "{pin}"
+"#
+        );
+        let attested = builder()
+            .add_kbs_spki_pin("sha256/FfdLqu4mw4OHQzXnKnrkWmLqUsTqKlHfjeYFl9RLXhQ=")
+            .add_initdata(initdata)
+            .set_attested_tls(true);
+        assert_eq!(
+            attested.spki_pins().unwrap(),
+            vec![pin.parse::<SpkiPin>().unwrap()]
+        );
+        attested.build().unwrap();
+    }
+
+    #[rstest]
+    #[case(TeeKeyAlgorithm::Rsa)]
+    #[case(TeeKeyAlgorithm::EcP256)]
+    #[case(TeeKeyAlgorithm::EcP384)]
+    #[tokio::test]
+    async fn test_build_client_with_tee_key_algorithm(#[case] algorithm: TeeKeyAlgorithm) {
+        let client = KbsClientBuilder::with_evidence_provider(
+            Box::<MockedEvidenceProvider>::default(),
+            "https://test.io",
+        )
+        .set_tee_key_algorithm(algorithm)
+        .build()
+        .unwrap();
+        assert_eq!(client.tee_key.algorithm(), algorithm);
+    }
+
+    #[tokio::test]
+    async fn test_build_client_with_session_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SessionStore::with_evidence_provider(
+            dir.path().join("session"),
+            &MockedEvidenceProvider::default(),
+        )
+        .await
+        .unwrap();
+        let builder = || {
+            KbsClientBuilder::with_evidence_provider(
+                Box::<MockedEvidenceProvider>::default(),
+                "https://test.io",
+            )
+            .set_tee_key_algorithm(TeeKeyAlgorithm::EcP384)
+            .set_session_store(store.clone())
+        };
+
+        let mut client = builder().build().unwrap();
+        assert!(client.token.is_none());
+
+        let exp = (Clock::now_since_epoch() + Duration::from_hours(1)).as_secs();
+        let claims = URL_SAFE_NO_PAD.encode(json!({ "exp": exp }).to_string());
+        client.token = Some(Token::new(format!("e30.{claims}.c2lnbmF0dXJl")).unwrap());
+        client.save_session();
+
+        let resumed = builder().build().unwrap();
+        assert_eq!(
+            resumed.token.unwrap().content,
+            client.token.unwrap().content
+        );
+        assert_eq!(
+            *resumed.tee_key.to_pem().unwrap(),
+            *client.tee_key.to_pem().unwrap()
+        );
+    }
 }
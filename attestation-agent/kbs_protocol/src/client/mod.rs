@@ -12,6 +12,13 @@
 //! - `Token Client`: s.t. `KbsClient<Box<dyn TokenProvider>>`. It is a
 //!   simpler client. It can only get resource with a valid token as its
 //!   authentication materials.
+//!
+//! Both clients can also set and delete resources. These requests are
+//! authenticated with the attested session of the client by default, or with
+//! a JWT signed by the admin key if one is given to the builder.
+//!
+//! Several clients can be grouped into a [`pool::KbsClientPool`] to get
+//! resources from several KBSes, each one served by one or more replicas.
 
 #[cfg(feature = "background_check")]
 pub mod rcar_client;
@@ -19,9 +26,21 @@ pub mod rcar_client;
 #[cfg(feature = "passport")]
 pub mod token_client;
 
-use kbs_types::Tee;
+pub mod pool;
+
+use std::{sync::Arc, time::Duration as StdDuration};
+
+use async_trait::async_trait;
+use jwt_simple::prelude::{Claims, Duration, Ed25519KeyPair, EdDSAKeyPairLike};
+use kbs_types::{Response, Tee};
+use log::warn;
+use reqwest::{cookie::Jar, Url};
+use resource_uri::ResourceUri;
 
-use crate::{keypair::TeeKeyPair, token_provider::Token};
+use crate::{
+    api::ResourceChunk, keypair::TeeKeyPair, retry::RetryPolicy, session::SessionStore,
+    token_provider::Token, Error, KbsErrorResponse, Result,
+};
 
 pub(crate) enum ClientTee {
     Uninitialized,
@@ -47,12 +66,315 @@ pub struct KbsClient<T> {
     /// token
     pub(crate) token: Option<Token>,
 
+    /// The token is refreshed when it expires within this margin
+    pub(crate) token_refresh_margin: StdDuration,
+
     /// initdata toml plaintext (if any)
     pub(crate) _initdata: Option<String>,
+
+    /// Admin private key to sign the JWT authenticating resource management
+    /// requests. If not given, the attested session is used instead.
+    pub(crate) admin_key: Option<Ed25519KeyPair>,
+
+    /// How failed requests are retried
+    pub(crate) retry_policy: RetryPolicy,
+
+    /// Cookies of the session with the KBS
+    pub(crate) cookie_jar: Arc<Jar>,
+
+    /// Store persisting the session, if any
+    pub(crate) session_store: Option<SessionStore>,
 }
 
 pub const KBS_PROTOCOL_VERSION: &str = "0.4.0";
 
-pub const KBS_GET_RESOURCE_MAX_ATTEMPT: u64 = 3;
-
 pub const KBS_PREFIX: &str = "kbs/v0";
+
+/// Validity of the admin JWT signed for each resource management request.
+const ADMIN_TOKEN_VALIDITY_MINUTES: u64 = 5;
+
+impl<T> KbsClient<T> {
+    pub(crate) fn resource_url(&self, resource_uri: &ResourceUri) -> String {
+        let remote_url = format!(
+            "{}/{KBS_PREFIX}/resource/{}/{}/{}",
+            self.kbs_host_url, resource_uri.repository, resource_uri.r#type, resource_uri.tag
+        );
+        match resource_uri.query {
+            Some(ref q) => format!("{remote_url}?{q}"),
+            None => remote_url,
+        }
+    }
+
+    /// Send a request. A request failing because the KBS certificate matches
+    /// none of the SPKI pins fails with [`Error::KbsCertificateMismatch`].
+    pub(crate) async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        request.send().await.map_err(|e| {
+            #[cfg(feature = "rust-crypto")]
+            if let Some(mismatch) = crate::tls::spki_pin_mismatch(&e) {
+                return Error::KbsCertificateMismatch(mismatch.to_string());
+            }
+            Error::HttpError(format!("request failed: {e:?}"))
+        })
+    }
+
+    /// Persist the token, the TEE key and the cookies of the session, if a
+    /// session store is given. A failure is only logged, as the session can
+    /// be established again.
+    pub(crate) fn save_session(&self) {
+        let (Some(store), Some(token)) = (&self.session_store, &self.token) else {
+            return;
+        };
+
+        let result = session_cookie_url(&self.kbs_host_url).and_then(|cookie_url| {
+            store.save(
+                &session_context(&self.kbs_host_url, self._initdata.as_deref()),
+                token,
+                &self.tee_key,
+                &self.cookie_jar,
+                &cookie_url,
+            )
+        });
+        if let Err(e) = result {
+            warn!("failed to persist the KBS session: {e}");
+        }
+    }
+
+    /// Sign a short-lived JWT with the admin key, if any.
+    pub(crate) fn admin_token(&self) -> Result<Option<String>> {
+        let Some(admin_key) = &self.admin_key else {
+            return Ok(None);
+        };
+
+        let claims = Claims::create(Duration::from_mins(ADMIN_TOKEN_VALIDITY_MINUTES));
+        let token = admin_key
+            .sign(claims)
+            .map_err(|e| Error::SignAdminTokenFailed(e.to_string()))?;
+        Ok(Some(token))
+    }
+}
+
+/// Sends requests authenticated with the attested session of a client, s.t.
+/// the session cookie of the RCAR client or the token of the token client.
+#[async_trait]
+pub(crate) trait SessionSender {
+    async fn send_with_session<F>(&mut self, build_request: F) -> Result<reqwest::Response>
+    where
+        F: Fn(&reqwest::Client) -> reqwest::RequestBuilder + Send + Sync;
+}
+
+impl<T: Send + Sync> KbsClient<T>
+where
+    Self: SessionSender,
+{
+    /// Send a resource management request. It is authenticated with the admin
+    /// key if one is given, otherwise with the attested session.
+    pub(crate) async fn send_authenticated<F>(
+        &mut self,
+        build_request: F,
+    ) -> Result<reqwest::Response>
+    where
+        F: Fn(&reqwest::Client) -> reqwest::RequestBuilder + Send + Sync,
+    {
+        let res = match self.admin_token()? {
+            Some(admin_token) => {
+                let res = self
+                    .send(build_request(&self.http_client).bearer_auth(admin_token))
+                    .await?;
+                if res.status() == reqwest::StatusCode::UNAUTHORIZED {
+                    let response = KbsErrorResponse::from_response(res).await;
+                    return Err(Error::UnAuthorized(Some(response)));
+                }
+                res
+            }
+            None => self.send_with_session(build_request).await?,
+        };
+
+        check_response(res).await
+    }
+}
+
+/// The URL the session cookies are persisted for. The KBS scopes them to
+/// the endpoints under [`KBS_PREFIX`].
+pub(crate) fn session_cookie_url(kbs_host_url: &str) -> Result<Url> {
+    Url::parse(&format!("{kbs_host_url}/{KBS_PREFIX}/session"))
+        .map_err(|e| Error::SessionStore(format!("invalid KBS URL: {e}")))
+}
+
+/// The context a persisted session is bound to, s.t. the KBS and the
+/// initdata, if any.
+pub(crate) fn session_context(kbs_host_url: &str, initdata: Option<&str>) -> Vec<u8> {
+    let mut context = kbs_host_url.as_bytes().to_vec();
+    if let Some(initdata) = initdata {
+        context.push(b'\n');
+        context.extend_from_slice(initdata.as_bytes());
+    }
+    context
+}
+
+/// Check the response of a request. 401 responses are handled by the
+/// callers as they re-authenticate in different ways.
+pub(crate) async fn check_response(res: reqwest::Response) -> Result<reqwest::Response> {
+    if res.status().is_success() {
+        return Ok(res);
+    }
+
+    let response = KbsErrorResponse::from_response(res).await;
+    Err(Error::from_kbs_response(response))
+}
+
+/// Decrypt the resource in a successful response.
+pub(crate) async fn decrypt_resource(
+    res: reqwest::Response,
+    tee_key: &TeeKeyPair,
+) -> Result<Vec<u8>> {
+    let response = res
+        .json::<Response>()
+        .await
+        .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?;
+    tee_key
+        .decrypt_response(response)
+        .map_err(|e| Error::DecryptResponseFailed(e.to_string()))
+}
+
+/// The `Range` header requesting the plaintext range `[offset, offset + len)`.
+pub(crate) fn range_header(offset: u64, len: u64) -> String {
+    format!("bytes={offset}-{}", offset + len.max(1) - 1)
+}
+
+/// Parse a `Content-Range` header, either `bytes <start>-<end>/<total>` or
+/// `bytes */<total>`. Returns the start offset, if any, and the total size.
+pub(crate) fn parse_content_range(content_range: &str) -> Result<(Option<u64>, u64)> {
+    let invalid = || Error::InvalidResourceRange(format!("Content-Range `{content_range}`"));
+    let (range, total) = content_range
+        .strip_prefix("bytes ")
+        .and_then(|range| range.split_once('/'))
+        .ok_or_else(invalid)?;
+    let total = total.parse::<u64>().map_err(|_| invalid())?;
+    if range == "*" {
+        return Ok((None, total));
+    }
+
+    let (start, end) = range.split_once('-').ok_or_else(invalid)?;
+    let start = start.parse::<u64>().map_err(|_| invalid())?;
+    let end = end.parse::<u64>().map_err(|_| invalid())?;
+    if start > end || end >= total {
+        return Err(invalid());
+    }
+
+    Ok((Some(start), total))
+}
+
+/// Decrypt the resource in a response to a range request. A response without
+/// `206 Partial Content` status is the whole resource. 401 responses are
+/// handled by the callers.
+pub(crate) async fn parse_resource_chunk(
+    res: reqwest::Response,
+    tee_key: &TeeKeyPair,
+    offset: u64,
+) -> Result<ResourceChunk> {
+    let content_range = res
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|e| Error::InvalidResourceRange(e.to_string()))
+                .and_then(parse_content_range)
+        })
+        .transpose()?;
+
+    if res.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // The offset is at the end of the resource, e.g. when the resource
+        // is empty.
+        return match content_range {
+            Some((_, total)) if offset >= total => Ok(ResourceChunk {
+                offset,
+                data: vec![],
+                total: Some(total),
+            }),
+            _ => Err(Error::InvalidResourceRange(format!(
+                "KBS cannot satisfy range from {offset}"
+            ))),
+        };
+    }
+
+    let res = check_response(res).await?;
+    let partial = res.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let data = decrypt_resource(res, tee_key).await?;
+
+    if !partial {
+        return Ok(ResourceChunk {
+            offset: 0,
+            data,
+            total: None,
+        });
+    }
+
+    let Some((Some(start), total)) = content_range else {
+        return Err(Error::InvalidResourceRange(
+            "partial content without a valid Content-Range".into(),
+        ));
+    };
+
+    Ok(ResourceChunk {
+        offset: start,
+        data,
+        total: Some(total),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use jwt_simple::prelude::{Ed25519KeyPair, EdDSAPublicKeyLike, NoCustomClaims};
+    use rstest::rstest;
+
+    use crate::{evidence_provider::MockedEvidenceProvider, KbsClientBuilder};
+
+    use super::{parse_content_range, range_header};
+
+    #[rstest]
+    #[case("bytes 0-9/100", Some((Some(0), 100)))]
+    #[case("bytes 90-99/100", Some((Some(90), 100)))]
+    #[case("bytes */100", Some((None, 100)))]
+    #[case("bytes 90-100/100", None)]
+    #[case("bytes 9-0/100", None)]
+    #[case("bytes 0-9/*", None)]
+    #[case("items 0-9/100", None)]
+    fn test_parse_content_range(
+        #[case] content_range: &str,
+        #[case] expected: Option<(Option<u64>, u64)>,
+    ) {
+        assert_eq!(parse_content_range(content_range).ok(), expected);
+    }
+
+    #[test]
+    fn test_range_header() {
+        assert_eq!(range_header(0, 4096), "bytes=0-4095");
+        assert_eq!(range_header(4096, 1), "bytes=4096-4096");
+    }
+
+    #[test]
+    fn test_resource_management_auth() {
+        let client = KbsClientBuilder::with_evidence_provider(
+            Box::<MockedEvidenceProvider>::default(),
+            "http://127.0.0.1:8080/",
+        )
+        .build()
+        .expect("build client failed");
+        assert!(client.admin_token().unwrap().is_none());
+
+        let admin_key = Ed25519KeyPair::generate();
+        let client = KbsClientBuilder::with_evidence_provider(
+            Box::<MockedEvidenceProvider>::default(),
+            "http://127.0.0.1:8080",
+        )
+        .set_admin_key(&admin_key.to_pem())
+        .build()
+        .expect("build client failed");
+        let token = client.admin_token().unwrap().expect("admin token");
+        admin_key
+            .public_key()
+            .verify_token::<NoCustomClaims>(&token, None)
+            .expect("verify admin token");
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! # KBS Client Pool
+//!
+//! A [`KbsClientPool`] gets resources from several KBSes. Each KBS is served
+//! by one or more replicas, each one with its own [`KbsClient`] and thus its
+//! own attested session.
+//!
+//! A resource URI is routed by its address, s.t. `kbs://<host>:<port>/...`,
+//! to the KBS with a replica of that address, which is tried first. The
+//! port may be omitted if it is the default one of the scheme of the
+//! replica. A resource URI without address, s.t. `kbs:///...`, or with an
+//! address of no replica, is routed to the first KBS added to the pool,
+//! starting from the replica which served the last request. If a replica fails in a way which may be transient, e.g. it is
+//! unreachable, the request fails over to the next replica of the KBS.
+
+use async_trait::async_trait;
+use log::warn;
+use resource_uri::ResourceUri;
+
+use crate::{api::ResourceChunk, Error, KbsClientCapabilities, Result};
+
+use super::KbsClient;
+
+/// A KBS served by one or more replicas.
+struct Kbs<T> {
+    replicas: Vec<KbsClient<T>>,
+
+    /// `<host>:<port>` address of each replica, as in resource URIs
+    addrs: Vec<String>,
+
+    /// Addresses of each replica in resource URIs, with and without the
+    /// port if it is the default one of the scheme
+    aliases: Vec<Vec<String>>,
+
+    /// Index of the replica which served the last request
+    active: usize,
+}
+
+impl<T> Kbs<T> {
+    /// The order the replicas are tried in, starting from `first`.
+    fn failover_order(&self, first: usize) -> Vec<usize> {
+        let len = self.replicas.len();
+        (0..len).map(|i| (first + i) % len).collect()
+    }
+}
+
+/// A pool of clients of several KBSes.
+pub struct KbsClientPool<T> {
+    kbses: Vec<Kbs<T>>,
+}
+
+impl<T> Default for KbsClientPool<T> {
+    fn default() -> Self {
+        Self { kbses: Vec::new() }
+    }
+}
+
+/// Send the request with the client of each replica of the KBS serving the
+/// address in turn, until one does not fail in a way which may be transient.
+macro_rules! failover {
+    ($pool:expr, $kbs_addr:expr, |$client:ident| $request:expr) => {{
+        let (kbs, replicas) = $pool.route($kbs_addr)?;
+        let mut last_error = None;
+        for replica in replicas {
+            let $client = &mut $pool.kbses[kbs].replicas[replica];
+            match $request.await {
+                Err(e) if e.is_retryable() => {
+                    warn!(
+                        "KBS replica {} failed: {e}, failing over",
+                        $pool.kbses[kbs].addrs[replica]
+                    );
+                    last_error = Some(e);
+                }
+                result => {
+                    if result.is_ok() {
+                        $pool.kbses[kbs].active = replica;
+                    }
+                    return result;
+                }
+            }
+        }
+
+        Err(last_error.expect("a KBS has at least one replica"))
+    }};
+}
+
+impl<T> KbsClientPool<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a KBS served by the replicas, given by their clients. The first
+    /// KBS added is the default one, serving the resource URIs without
+    /// address.
+    pub fn add_kbs(&mut self, replicas: Vec<KbsClient<T>>) -> Result<()> {
+        if replicas.is_empty() {
+            return Err(Error::KbsPool("a KBS needs at least one replica".into()));
+        }
+
+        let aliases: Vec<Vec<String>> = replicas
+            .iter()
+            .map(|replica| kbs_addrs(&replica.kbs_host_url))
+            .collect();
+        if let Some(addr) = aliases
+            .iter()
+            .flatten()
+            .find(|addr| self.find_replica(addr).is_some())
+        {
+            return Err(Error::KbsPool(format!("KBS {addr} is already in the pool")));
+        }
+
+        let addrs = aliases.iter().map(|aliases| aliases[0].clone()).collect();
+        self.kbses.push(Kbs {
+            replicas,
+            addrs,
+            aliases,
+            active: 0,
+        });
+        Ok(())
+    }
+
+    /// The indexes of the KBS and of the replica of the address, if any.
+    fn find_replica(&self, kbs_addr: &str) -> Option<(usize, usize)> {
+        self.kbses
+            .iter()
+            .enumerate()
+            .find_map(|(kbs, Kbs { aliases, .. })| {
+                let replica = aliases
+                    .iter()
+                    .position(|aliases| aliases.iter().any(|addr| addr == kbs_addr))?;
+                Some((kbs, replica))
+            })
+    }
+
+    /// The index of the KBS serving the address, and the order its replicas
+    /// are tried in. An address of no replica falls back to the first KBS.
+    fn route(&self, kbs_addr: &str) -> Result<(usize, Vec<usize>)> {
+        if !kbs_addr.is_empty() {
+            if let Some((kbs, replica)) = self.find_replica(&kbs_addr.to_ascii_lowercase()) {
+                return Ok((kbs, self.kbses[kbs].failover_order(replica)));
+            }
+        }
+
+        let kbs = self
+            .kbses
+            .first()
+            .ok_or_else(|| Error::KbsPool("no KBS in the pool".into()))?;
+        if !kbs_addr.is_empty() {
+            warn!(
+                "no KBS {kbs_addr} in the pool, using KBS {}",
+                kbs.addrs[kbs.active]
+            );
+        }
+        Ok((0, kbs.failover_order(kbs.active)))
+    }
+}
+
+/// The `<host>:<port>` addresses of the KBS URL, as in resource URIs. The
+/// address without port is also given if the port is the default one of the
+/// scheme.
+fn kbs_addrs(kbs_host_url: &str) -> Vec<String> {
+    let Ok(url) = url::Url::parse(kbs_host_url) else {
+        return vec![kbs_host_url.to_string()];
+    };
+
+    let host = url.host_str().unwrap_or_default();
+    match (url.port(), url.port_or_known_default()) {
+        (None, Some(port)) => vec![format!("{host}:{port}"), host.to_string()],
+        (_, Some(port)) => vec![format!("{host}:{port}")],
+        (_, None) => vec![host.to_string()],
+    }
+}
+
+#[async_trait]
+impl<T: Send> KbsClientCapabilities for KbsClientPool<T>
+where
+    KbsClient<T>: KbsClientCapabilities + Send,
+{
+    async fn get_resource(&mut self, resource_uri: ResourceUri) -> Result<Vec<u8>> {
+        failover!(self, &resource_uri.kbs_addr, |client| client
+            .get_resource(resource_uri.clone()))
+    }
+
+    async fn get_resource_range(
+        &mut self,
+        resource_uri: ResourceUri,
+        offset: u64,
+        len: u64,
+    ) -> Result<ResourceChunk> {
+        failover!(self, &resource_uri.kbs_addr, |client| client
+            .get_resource_range(resource_uri.clone(), offset, len))
+    }
+
+    async fn set_resource(&mut self, resource_uri: ResourceUri, content: Vec<u8>) -> Result<()> {
+        failover!(self, &resource_uri.kbs_addr, |client| client
+            .set_resource(resource_uri.clone(), content.clone()))
+    }
+
+    async fn delete_resource(&mut self, resource_uri: ResourceUri) -> Result<()> {
+        failover!(self, &resource_uri.kbs_addr, |client| client
+            .delete_resource(resource_uri.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use mock_kbs::{Endpoint, MockKbs};
+    use rstest::rstest;
+
+    use crate::{
+        evidence_provider::{EvidenceProvider, MockedEvidenceProvider},
+        Error, KbsClientBuilder, KbsClientCapabilities, KbsClientPool, RetryPolicy,
+    };
+
+    use super::{kbs_addrs, KbsClient};
+
+    const CONTENT: &[u8] = b"test content";
+
+    fn client(kbs: &MockKbs) -> KbsClient<Box<dyn EvidenceProvider>> {
+        KbsClientBuilder::with_evidence_provider(
+            Box::<MockedEvidenceProvider>::default(),
+            &kbs.url(),
+        )
+        .set_retry_policy(RetryPolicy::exponential(1, Duration::ZERO, Duration::ZERO))
+        .build()
+        .expect("client create")
+    }
+
+    #[rstest]
+    #[case("http://127.0.0.1:8080", &["127.0.0.1:8080"])]
+    #[case("https://kbs.io", &["kbs.io:443", "kbs.io"])]
+    #[case("https://kbs.io:443", &["kbs.io:443", "kbs.io"])]
+    #[case("http://KBS.io", &["kbs.io:80", "kbs.io"])]
+    #[case("https://kbs.io:8443", &["kbs.io:8443"])]
+    fn test_kbs_addrs(#[case] url: &str, #[case] addrs: &[&str]) {
+        assert_eq!(kbs_addrs(url), addrs);
+    }
+
+    #[tokio::test]
+    async fn test_kbs_client_pool() {
+        let kbs_a = [
+            MockKbs::start().await.unwrap(),
+            MockKbs::start().await.unwrap(),
+        ];
+        let kbs_b = MockKbs::start().await.unwrap();
+        for kbs in kbs_a.iter().chain([&kbs_b]) {
+            kbs.add_resource("default/key/1", kbs.url().as_bytes());
+        }
+        kbs_b.add_resource("default/key/2", CONTENT);
+
+        let mut pool = KbsClientPool::new();
+        assert!(matches!(
+            pool.get_resource("kbs:///default/key/1".try_into().unwrap())
+                .await,
+            Err(Error::KbsPool(_))
+        ));
+        pool.add_kbs(kbs_a.iter().map(client).collect()).unwrap();
+        pool.add_kbs(vec![client(&kbs_b)]).unwrap();
+        assert!(pool.add_kbs(vec![]).is_err());
+        assert!(pool.add_kbs(vec![client(&kbs_b)]).is_err());
+
+        // Routed to the default KBS or by address
+        let resource = pool
+            .get_resource("kbs:///default/key/1".try_into().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resource, kbs_a[0].url().as_bytes());
+
+        let addr = &kbs_addrs(&kbs_b.url())[0];
+        let resource = pool
+            .get_resource(
+                format!("kbs://{addr}/default/key/2")
+                    .as_str()
+                    .try_into()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resource, CONTENT);
+
+        // An unknown address falls back to the default KBS
+        let resource = pool
+            .get_resource("kbs://unknown:8080/default/key/1".try_into().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resource, kbs_a[0].url().as_bytes());
+
+        // Fail over to the next replica, which then serves the next requests
+        kbs_a[0].inject_error(Endpoint::Resource, 503);
+        for _ in 0..2 {
+            let resource = pool
+                .get_resource("kbs:///default/key/1".try_into().unwrap())
+                .await
+                .unwrap();
+            assert_eq!(resource, kbs_a[1].url().as_bytes());
+        }
+
+        // Definitive errors are not failed over
+        let e = pool
+            .get_resource("kbs:///default/key/2".try_into().unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(e, Error::ResourceNotFound(_)), "{e:?}");
+
+        // Each replica attested its own session once
+        for kbs in kbs_a.iter().chain([&kbs_b]) {
+            assert_eq!(kbs.requests(Endpoint::Attest), 1);
+        }
+    }
+}
@@ -3,39 +3,29 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use std::time::Duration;
-
 use anyhow::{bail, Context};
 use async_trait::async_trait;
 use canon_json::CanonicalFormatter;
 use kbs_types::HashAlgorithm;
-use kbs_types::{
-    Attestation, Challenge, CompositeEvidence, ErrorInformation, InitData, Request, Response,
-    RuntimeData, Tee,
-};
+use kbs_types::{Attestation, Challenge, CompositeEvidence, InitData, Request, RuntimeData, Tee};
 use log::{debug, warn};
 use resource_uri::ResourceUri;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::{
-    api::KbsClientCapabilities,
+    api::{KbsClientCapabilities, ResourceChunk},
     client::{
-        ClientTee, KbsClient, KBS_GET_RESOURCE_MAX_ATTEMPT, KBS_PREFIX, KBS_PROTOCOL_VERSION,
+        check_response, decrypt_resource, parse_resource_chunk, range_header, ClientTee, KbsClient,
+        SessionSender, KBS_PREFIX, KBS_PROTOCOL_VERSION,
     },
     evidence_provider::EvidenceProvider,
     keypair::TeeKeyPair,
+    retry::Retry,
     token_provider::Token,
-    Error, Result,
+    Error, KbsErrorResponse, Result,
 };
 
-/// When executing get token, RCAR handshake should retry if failed to
-/// make the logic robust. This constant is the max retry times.
-const RCAR_MAX_ATTEMPT: i32 = 5;
-
-/// The interval (seconds) between RCAR handshake retries.
-const RCAR_RETRY_TIMEOUT_SECOND: u64 = 1;
-
 /// JSON object added to a 'Request's extra parameters.
 const SUPPORTED_HASH_ALGORITHMS_JSON_KEY: &str = "supported-hash-algorithms";
 
@@ -83,13 +73,46 @@ fn get_hash_algorithm(extra_params: serde_json::Value) -> Result<HashAlgorithm>
     Ok(algorithm)
 }
 
-fn serialize_json_canonically<T: Serialize>(value: T) -> anyhow::Result<Vec<u8>> {
+pub(crate) fn serialize_json_canonically<T: Serialize>(value: T) -> anyhow::Result<Vec<u8>> {
     let mut buf = Vec::new();
     let mut ser = serde_json::Serializer::with_formatter(&mut buf, CanonicalFormatter::new());
     value.serialize(&mut ser)?;
     Ok(buf)
 }
 
+/// The runtime data the primary TEE binds into its evidence: the hash of
+/// the runtime data plus the evidence of the additional devices, which is
+/// also returned for the verifier. SE binds the bare nonce.
+pub(crate) fn primary_runtime_data(
+    tee: Tee,
+    runtime_data: &RuntimeData,
+    additional_evidence: &str,
+    hash_algorithm: HashAlgorithm,
+) -> anyhow::Result<(Option<serde_json::Value>, Vec<u8>)> {
+    match tee {
+        // SE handles the report data differently. As such, it does not support
+        // multi-device attestation.
+        Tee::Se => {
+            if !additional_evidence.is_empty() {
+                bail!("Cannot attest multiple devices on s390x platform.")
+            }
+            Ok((None, runtime_data.nonce.clone().into_bytes()))
+        }
+        _ => {
+            let primary_runtime_data = json!({
+                "tee-pubkey": runtime_data.tee_pubkey,
+                "nonce": runtime_data.nonce,
+                "additional-evidence": additional_evidence,
+            });
+            let hash = hash_algorithm.digest(
+                &serialize_json_canonically(&primary_runtime_data)
+                    .context("serialize runtime data failed")?,
+            );
+            Ok((Some(primary_runtime_data), hash))
+        }
+    }
+}
+
 async fn build_request(tee: Tee) -> Request {
     let extra_params = get_request_extra_params().await;
 
@@ -105,16 +128,18 @@ async fn build_request(tee: Tee) -> Request {
 
 impl KbsClient<Box<dyn EvidenceProvider>> {
     /// Get a [`TeeKeyPair`] and a [`Token`] that certifies the [`TeeKeyPair`].
-    /// If the client does not already have token or the token is invalid,
-    /// an RCAR handshake will be performed.
+    /// If the client does not already have token or the token is invalid or
+    /// expires within the refresh margin, an RCAR handshake will be performed.
     /// Otherwise, the existing token will be returned.
     pub async fn get_token(&mut self) -> Result<(Token, TeeKeyPair)> {
-        if let Some(token) = &self.token {
-            if token.check_valid().is_err() {
-                self.repeat_rcar_handshake().await?;
+        match &self.token {
+            Some(token) if !token.expires_within(self.token_refresh_margin) => {}
+            Some(token) if token.check_valid().is_ok() => {
+                if let Err(e) = self.repeat_rcar_handshake().await {
+                    warn!("refresh the token before it expires failed: {e}");
+                }
             }
-        } else {
-            self.repeat_rcar_handshake().await?;
+            _ => self.repeat_rcar_handshake().await?,
         }
 
         assert!(self.token.is_some());
@@ -124,29 +149,29 @@ impl KbsClient<Box<dyn EvidenceProvider>> {
         Ok((token, tee_key))
     }
 
-    /// Call rcar_hanshake several times and handle errors.
+    /// Get a new [`Token`] with an RCAR handshake, even if the current one
+    /// is valid, e.g. because a KBS rejected it.
+    pub async fn refresh_token(&mut self) -> Result<(Token, TeeKeyPair)> {
+        self.repeat_rcar_handshake().await?;
+        let token = self.token.clone().expect("token must have been got");
+        Ok((token, self.tee_key.clone()))
+    }
+
+    /// Call rcar_hanshake several times following the retry policy. Only
+    /// failures which may be transient are retried, e.g. the attestation
+    /// denied by the KBS policy is not.
     async fn repeat_rcar_handshake(&mut self) -> Result<()> {
-        let mut retry_count = 1;
+        let mut retry = Retry::new(self.retry_policy.clone());
         loop {
-            let res = self
-                .rcar_handshake()
-                .await
-                .map_err(|e| Error::RcarHandshake(format!("{e:#?}")));
+            let Err(e) = self.rcar_handshake().await else {
+                return Ok(());
+            };
 
-            match res {
-                Ok(_) => break,
-                Err(e) => {
-                    if retry_count >= RCAR_MAX_ATTEMPT {
-                        return Err(Error::RcarHandshake(format!("Unable to get token. RCAR handshake retried {RCAR_MAX_ATTEMPT} times. Final attempt failed with: {e:?}")));
-                    } else {
-                        warn!("RCAR handshake failed: {e:?}, retry {retry_count}...");
-                        retry_count += 1;
-                        tokio::time::sleep(Duration::from_secs(RCAR_RETRY_TIMEOUT_SECOND)).await;
-                    }
-                }
+            if !e.is_retryable() || !retry.wait().await {
+                return Err(e);
             }
+            warn!("RCAR handshake failed: {e}, retrying...");
         }
-        Ok(())
     }
 
     /// Get composite evidence for the confidential guest.
@@ -168,26 +193,8 @@ impl KbsClient<Box<dyn EvidenceProvider>> {
 
         // Calculate the runtime data for the primary attester, which includes
         // the device evidence retrieved above.
-        let primary_runtime_data = match tee {
-            // SE handles the report data differently. As such, it does not support
-            // multi-device attestation.
-            Tee::Se => {
-                if !additional_evidence.is_empty() {
-                    bail!("Cannot attest multiple devices on s390x platform.")
-                }
-                runtime_data.nonce.into_bytes()
-            }
-            _ => {
-                let primary_runtime_data = json!({
-                    "tee-pubkey": runtime_data.tee_pubkey,
-                    "nonce": runtime_data.nonce,
-                    "additional-evidence": additional_evidence,
-                });
-                let primary_runtime_data = serialize_json_canonically(&primary_runtime_data)
-                    .context("serialize runtime data failed")?;
-                hash_algorithm.digest(&primary_runtime_data)
-            }
-        };
+        let (_, primary_runtime_data) =
+            primary_runtime_data(tee, &runtime_data, &additional_evidence, hash_algorithm)?;
 
         let primary_evidence = self.provider.primary_evidence(primary_runtime_data).await?;
         let guest_evidence = CompositeEvidence {
@@ -202,7 +209,7 @@ impl KbsClient<Box<dyn EvidenceProvider>> {
     ///
     /// Note: if RCAR succeeds, the http client will record the cookie with the kbs server,
     /// which means that this client can be then used to retrieve resources.
-    async fn rcar_handshake(&mut self) -> anyhow::Result<()> {
+    async fn rcar_handshake(&mut self) -> Result<()> {
         let auth_endpoint = format!("{}/{KBS_PREFIX}/auth", self.kbs_host_url);
 
         let tee = match &self._tee {
@@ -219,40 +226,31 @@ impl KbsClient<Box<dyn EvidenceProvider>> {
         debug!("send auth request {request:?} to {auth_endpoint}");
 
         let resp = self
-            .http_client
-            .post(auth_endpoint)
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
+            .send(
+                self.http_client
+                    .post(auth_endpoint)
+                    .header("Content-Type", "application/json")
+                    .json(&request),
+            )
             .await?;
 
-        match resp.status() {
-            reqwest::StatusCode::OK => {
-                debug!("KBS request OK");
-            }
-            reqwest::StatusCode::UNAUTHORIZED => {
-                let error_info = resp.json::<ErrorInformation>().await?;
-                bail!(
-                    "KBS request unauthorized, ErrorInformation: {:?}",
-                    error_info
-                );
-            }
-            _ => {
-                bail!(
-                    "KBS Server Internal Failed, Response: {:?}",
-                    resp.text().await?
-                );
-            }
-        }
+        let resp = check_response(resp).await?;
+        debug!("KBS request OK");
 
-        let challenge = resp.json::<Challenge>().await?;
+        let challenge = resp
+            .json::<Challenge>()
+            .await
+            .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?;
         debug!("get challenge: {challenge:#?}");
 
         let extra_params = challenge.extra_params;
 
         let algorithm = get_hash_algorithm(extra_params)?;
 
-        let tee_pubkey = self.tee_key.export_pubkey()?;
+        let tee_pubkey = self
+            .tee_key
+            .export_pubkey()
+            .map_err(|e| Error::RcarHandshake(format!("export TEE public key failed: {e:#}")))?;
         let runtime_data = RuntimeData {
             nonce: challenge.nonce,
             tee_pubkey,
@@ -261,7 +259,7 @@ impl KbsClient<Box<dyn EvidenceProvider>> {
         let tee_evidence = self
             .get_composite_evidence(runtime_data.clone(), algorithm, tee)
             .await
-            .context("get composite evidence failed")?;
+            .map_err(|e| Error::GetEvidence(format!("get composite evidence failed: {e:#}")))?;
 
         let attest_endpoint = format!("{}/{KBS_PREFIX}/attest", self.kbs_host_url);
         let init_data = self._initdata.as_ref().map(|initdata| InitData {
@@ -277,105 +275,125 @@ impl KbsClient<Box<dyn EvidenceProvider>> {
 
         debug!("send attest request.");
         let attest_response = self
-            .http_client
-            .post(attest_endpoint)
-            .header("Content-Type", "application/json")
-            .json(&attest)
-            .send()
+            .send(
+                self.http_client
+                    .post(attest_endpoint)
+                    .header("Content-Type", "application/json")
+                    .json(&attest),
+            )
             .await?;
 
-        match attest_response.status() {
-            reqwest::StatusCode::OK => {
-                let resp = attest_response.json::<AttestationResponseData>().await?;
-                let token = Token::new(resp.token)?;
-                self.token = Some(token);
-            }
-            reqwest::StatusCode::UNAUTHORIZED => {
-                let error_info = attest_response.json::<ErrorInformation>().await?;
-                bail!("KBS attest unauthorized, Error Info: {:?}", error_info);
-            }
-            _ => {
-                bail!(
-                    "KBS Server Internal Failed, Response: {:?}",
-                    attest_response.text().await?
-                );
-            }
+        if attest_response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            let response = KbsErrorResponse::from_response(attest_response).await;
+            return Err(Error::AttestationDenied(response));
         }
 
+        let resp = check_response(attest_response)
+            .await?
+            .json::<AttestationResponseData>()
+            .await
+            .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?;
+        let token = Token::new(resp.token).map_err(|e| {
+            Error::KbsResponseDeserializationFailed(format!("invalid token: {e:#}"))
+        })?;
+        self.token = Some(token);
+        self.save_session();
+
         Ok(())
     }
 }
 
 #[async_trait]
-impl KbsClientCapabilities for KbsClient<Box<dyn EvidenceProvider>> {
-    async fn get_resource(&mut self, resource_uri: ResourceUri) -> Result<Vec<u8>> {
-        let mut remote_url = format!(
-            "{}/{KBS_PREFIX}/resource/{}/{}/{}",
-            self.kbs_host_url, resource_uri.repository, resource_uri.r#type, resource_uri.tag
-        );
-        if let Some(ref q) = resource_uri.query {
-            remote_url = format!("{remote_url}?{q}");
-        }
+impl SessionSender for KbsClient<Box<dyn EvidenceProvider>> {
+    /// Send a request authenticated with the attested session, which is
+    /// re-established with a new RCAR handshake if the KBS rejects it.
+    /// Requests failing for transient reasons are retried following the
+    /// retry policy.
+    async fn send_with_session<F>(&mut self, build_request: F) -> Result<reqwest::Response>
+    where
+        F: Fn(&reqwest::Client) -> reqwest::RequestBuilder + Send + Sync,
+    {
+        let mut retry = Retry::new(self.retry_policy.clone());
+        let mut auth_attempts = 0;
+        loop {
+            let res = match self.send(build_request(&self.http_client)).await {
+                Ok(res) => res,
+                Err(e) => {
+                    if !e.is_connection_error() || !retry.wait().await {
+                        return Err(e);
+                    }
+                    warn!("{e}, retrying...");
+                    continue;
+                }
+            };
 
-        for attempt in 1..=KBS_GET_RESOURCE_MAX_ATTEMPT {
-            debug!("KBS client: trying to request KBS, attempt {attempt}");
+            if res.status().is_server_error() && retry.wait().await {
+                warn!("KBS returned {}, retrying...", res.status());
+                continue;
+            }
 
-            let res = self
-                .http_client
-                .get(&remote_url)
-                .send()
-                .await
-                .map_err(|e| Error::HttpError(format!("get failed: {e:?}")))?;
-
-            match res.status() {
-                reqwest::StatusCode::OK => {
-                    let response = res
-                        .json::<Response>()
-                        .await
-                        .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?;
-                    let payload_data = self
-                        .tee_key
-                        .decrypt_response(response)
-                        .map_err(|e| Error::DecryptResponseFailed(e.to_string()))?;
-                    return Ok(payload_data);
-                }
-                reqwest::StatusCode::UNAUTHORIZED => {
-                    warn!(
-                        "Authenticating with KBS failed. Perform a new RCAR handshake: {:#?}",
-                        res.json::<ErrorInformation>()
-                            .await
-                            .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?,
-                    );
-                    self.rcar_handshake()
-                        .await
-                        .map_err(|e| Error::RcarHandshake(format!("{e:#?}")))?;
+            if res.status() != reqwest::StatusCode::UNAUTHORIZED {
+                return Ok(res);
+            }
 
-                    continue;
-                }
-                reqwest::StatusCode::NOT_FOUND => {
-                    let errorinfo = format!(
-                        "KBS resource Not Found (Error 404): {:#?}",
-                        res.json::<ErrorInformation>()
-                            .await
-                            .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?
-                    );
-
-                    return Err(Error::ResourceNotFound(errorinfo));
-                }
-                _ => {
-                    let errorinfo = format!(
-                        "KBS Server Internal Failed, Response: {:#?}",
-                        res.json::<ErrorInformation>()
-                            .await
-                            .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?
-                    );
-
-                    return Err(Error::KbsInternalError(errorinfo));
-                }
+            auth_attempts += 1;
+            let response = KbsErrorResponse::from_response(res).await;
+            if auth_attempts >= self.retry_policy.max_auth_attempts {
+                return Err(Error::UnAuthorized(Some(response)));
             }
+
+            warn!("Authenticating with KBS failed. Perform a new RCAR handshake: {response}");
+            self.repeat_rcar_handshake().await?;
         }
+    }
+}
+
+#[async_trait]
+impl KbsClientCapabilities for KbsClient<Box<dyn EvidenceProvider>> {
+    async fn get_resource(&mut self, resource_uri: ResourceUri) -> Result<Vec<u8>> {
+        let remote_url = self.resource_url(&resource_uri);
+        let res = self
+            .send_with_session(|client| client.get(&remote_url))
+            .await?;
+        let res = check_response(res).await?;
+        decrypt_resource(res, &self.tee_key).await
+    }
 
-        Err(Error::UnAuthorized)
+    async fn get_resource_range(
+        &mut self,
+        resource_uri: ResourceUri,
+        offset: u64,
+        len: u64,
+    ) -> Result<ResourceChunk> {
+        let remote_url = self.resource_url(&resource_uri);
+        let range = range_header(offset, len);
+        let res = self
+            .send_with_session(|client| {
+                client
+                    .get(&remote_url)
+                    .header(reqwest::header::RANGE, &range)
+            })
+            .await?;
+        parse_resource_chunk(res, &self.tee_key, offset).await
+    }
+
+    async fn set_resource(&mut self, resource_uri: ResourceUri, content: Vec<u8>) -> Result<()> {
+        let remote_url = self.resource_url(&resource_uri);
+        self.send_authenticated(|client| {
+            client
+                .post(&remote_url)
+                .header("Content-Type", "application/octet-stream")
+                .body(content.clone())
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_resource(&mut self, resource_uri: ResourceUri) -> Result<()> {
+        let remote_url = self.resource_url(&resource_uri);
+        self.send_authenticated(|client| client.delete(&remote_url))
+            .await?;
+        Ok(())
     }
 }
 
@@ -392,8 +410,12 @@ mod test {
     };
     use tokio::fs;
 
+    use mock_kbs::{Endpoint, MockKbs};
+
     use crate::{
-        evidence_provider::NativeEvidenceProvider, Error, KbsClientBuilder, KbsClientCapabilities,
+        evidence_provider::{MockedEvidenceProvider, NativeEvidenceProvider},
+        Error, KbsClientBuilder, KbsClientCapabilities, RetryPolicy, TeeKeyAlgorithm,
+        RESOURCE_CHUNK_SIZE,
     };
 
     use crate::client::rcar_client::{
@@ -401,6 +423,7 @@ mod test {
         DEFAULT_HASH_ALGORITHM, KBS_PROTOCOL_VERSION, SELECTED_HASH_ALGORITHM_JSON_KEY,
         SUPPORTED_HASH_ALGORITHMS_JSON_KEY,
     };
+    use jwt_simple::prelude::Ed25519KeyPair;
     use kbs_types::Tee;
 
     const CONTENT: &[u8] = b"test content";
@@ -493,6 +516,177 @@ mod test {
         println!("Get key: {key:?}");
     }
 
+    #[rstest]
+    #[case(TeeKeyAlgorithm::Rsa)]
+    #[case(TeeKeyAlgorithm::EcP256)]
+    #[case(TeeKeyAlgorithm::EcP384)]
+    #[tokio::test]
+    async fn test_client_with_mock_kbs(#[case] algorithm: TeeKeyAlgorithm) {
+        let kbs = MockKbs::start().await.expect("start mock kbs");
+        kbs.add_resource("default/key/testfile", CONTENT);
+
+        let mut client = KbsClientBuilder::with_evidence_provider(
+            Box::<MockedEvidenceProvider>::default(),
+            &kbs.url(),
+        )
+        .set_tee_key_algorithm(algorithm)
+        .build()
+        .expect("client create");
+
+        for _ in 0..2 {
+            let resource = client
+                .get_resource("kbs:///default/key/testfile".try_into().unwrap())
+                .await
+                .expect("get resource");
+            assert_eq!(resource, CONTENT);
+        }
+
+        // The attested session is reused
+        assert_eq!(kbs.requests(Endpoint::Attest), 1);
+        assert_eq!(kbs.requests(Endpoint::Resource), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_resource_with_mock_kbs() {
+        let kbs = MockKbs::start().await.expect("start mock kbs");
+        let content: Vec<u8> = (0..2 * RESOURCE_CHUNK_SIZE + 1)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        kbs.add_resource("default/model/large", &content);
+        kbs.add_resource("default/model/empty", b"");
+
+        let mut client = KbsClientBuilder::with_evidence_provider(
+            Box::<MockedEvidenceProvider>::default(),
+            &kbs.url(),
+        )
+        .build()
+        .expect("client create");
+
+        // Each range is requested and decrypted separately
+        let mut streamed = Vec::new();
+        let size = client
+            .get_resource_to_writer(
+                "kbs:///default/model/large".try_into().unwrap(),
+                &mut streamed,
+            )
+            .await
+            .expect("stream resource");
+        assert_eq!(size, content.len() as u64);
+        assert_eq!(streamed, content);
+        assert_eq!(kbs.requests(Endpoint::Resource), 3);
+
+        let mut streamed = Vec::new();
+        let size = client
+            .get_resource_to_writer(
+                "kbs:///default/model/empty".try_into().unwrap(),
+                &mut streamed,
+            )
+            .await
+            .expect("stream empty resource");
+        assert_eq!(size, 0);
+        assert!(streamed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_manage_resources_with_mock_kbs() {
+        let kbs = MockKbs::start().await.expect("start mock kbs");
+        let new_client = |admin_key: Option<&str>| {
+            let mut builder = KbsClientBuilder::with_evidence_provider(
+                Box::<MockedEvidenceProvider>::default(),
+                &kbs.url(),
+            );
+            if let Some(admin_key) = admin_key {
+                builder = builder.set_admin_key(admin_key);
+            }
+            builder.build().expect("client create")
+        };
+        let resource_uri = |path: &str| format!("kbs:///{path}").try_into().unwrap();
+
+        // Authenticated with the attested session
+        let mut client = new_client(None);
+        client
+            .set_resource(resource_uri("default/key/1"), CONTENT.to_vec())
+            .await
+            .expect("set resource");
+        assert_eq!(kbs.resource("default/key/1").as_deref(), Some(CONTENT));
+        let resource = client
+            .get_resource(resource_uri("default/key/1"))
+            .await
+            .expect("get resource");
+        assert_eq!(resource, CONTENT);
+
+        client
+            .delete_resource(resource_uri("default/key/1"))
+            .await
+            .expect("delete resource");
+        assert!(kbs.resource("default/key/1").is_none());
+        let e = client
+            .delete_resource(resource_uri("default/key/1"))
+            .await
+            .expect_err("delete missing resource");
+        assert!(matches!(e, Error::ResourceNotFound(_)), "{e:?}");
+
+        // Authenticated with the admin key once the KBS requires it
+        let admin_key = Ed25519KeyPair::generate();
+        kbs.set_admin_public_key(&admin_key.public_key().to_pem())
+            .unwrap();
+        let e = client
+            .set_resource(resource_uri("default/key/2"), CONTENT.to_vec())
+            .await
+            .expect_err("set resource without admin key");
+        assert!(matches!(e, Error::UnAuthorized(_)), "{e:?}");
+
+        let mut client = new_client(Some(&admin_key.to_pem()));
+        client
+            .set_resource(resource_uri("default/key/2"), CONTENT.to_vec())
+            .await
+            .expect("set resource with admin key");
+        assert_eq!(kbs.resource("default/key/2").as_deref(), Some(CONTENT));
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_with_mock_kbs() {
+        let kbs = MockKbs::start().await.expect("start mock kbs");
+        kbs.add_resource("default/key/testfile", CONTENT);
+
+        let mut client = KbsClientBuilder::with_evidence_provider(
+            Box::<MockedEvidenceProvider>::default(),
+            &kbs.url(),
+        )
+        .set_retry_policy(RetryPolicy::exponential(3, Duration::ZERO, Duration::ZERO))
+        .build()
+        .expect("client create");
+        let resource_uri = || "kbs:///default/key/testfile".try_into().unwrap();
+
+        // Transient errors are retried
+        kbs.inject_error(Endpoint::Resource, 500);
+        kbs.inject_error(Endpoint::Auth, 503);
+        let resource = client.get_resource(resource_uri()).await.expect("get");
+        assert_eq!(resource, CONTENT);
+        assert_eq!(kbs.requests(Endpoint::Auth), 2);
+
+        // A lost session is established again
+        kbs.clear_sessions();
+        client.get_resource(resource_uri()).await.expect("get");
+        assert_eq!(kbs.requests(Endpoint::Attest), 2);
+
+        let e = client
+            .get_resource("kbs:///default/key/missing".try_into().unwrap())
+            .await
+            .expect_err("missing resource");
+        assert!(matches!(e, Error::ResourceNotFound(_)), "{e:?}");
+
+        // A denied attestation is not retried
+        kbs.clear_sessions();
+        kbs.inject_error(Endpoint::Attest, 401);
+        let e = client
+            .get_resource(resource_uri())
+            .await
+            .expect_err("denied attestation");
+        assert!(matches!(e, Error::AttestationDenied(_)), "{e:?}");
+        assert_eq!(kbs.requests(Endpoint::Attest), 3);
+    }
+
     #[tokio::test]
     #[serial_test::serial]
     async fn test_get_request_extra_params() {
@@ -4,101 +4,154 @@
 //
 
 use async_trait::async_trait;
-use kbs_types::{ErrorInformation, Response};
-use log::{debug, warn};
+use log::warn;
 use resource_uri::ResourceUri;
 
 use crate::{
-    api::KbsClientCapabilities,
-    client::{KbsClient, KBS_GET_RESOURCE_MAX_ATTEMPT, KBS_PREFIX},
+    api::{KbsClientCapabilities, ResourceChunk},
+    client::{
+        check_response, decrypt_resource, parse_resource_chunk, range_header, KbsClient,
+        SessionSender,
+    },
+    retry::Retry,
     token_provider::TokenProvider,
-    Error, Result,
+    Error, KbsErrorResponse, Result,
 };
 
 impl KbsClient<Box<dyn TokenProvider>> {
-    async fn update_token(&mut self) -> Result<()> {
-        let (token, teekey) = self
-            .provider
-            .get_token()
-            .await
-            .map_err(|e| Error::GetTokenFailed(e.to_string()))?;
+    /// Get a token from the token provider. If `refresh` is set, the
+    /// provider is asked for a new token rather than a cached one.
+    async fn update_token(&mut self, refresh: bool) -> Result<()> {
+        let (token, teekey) = match refresh {
+            true => self.provider.refresh_token().await,
+            false => self.provider.get_token().await,
+        }
+        .map_err(|e| Error::GetTokenFailed(e.to_string()))?;
         self.token = Some(token);
         self.tee_key = teekey;
+        self.save_session();
         Ok(())
     }
-}
 
-#[async_trait]
-impl KbsClientCapabilities for KbsClient<Box<dyn TokenProvider>> {
-    async fn get_resource(&mut self, resource_uri: ResourceUri) -> Result<Vec<u8>> {
-        let mut remote_url = format!(
-            "{}/{KBS_PREFIX}/resource/{}/{}/{}",
-            self.kbs_host_url, resource_uri.repository, resource_uri.r#type, resource_uri.tag
-        );
-        if let Some(ref q) = resource_uri.query {
-            remote_url = format!("{remote_url}?{q}");
+    /// Get a token if there is none or it expires within the refresh
+    /// margin. Failing to refresh a token which is still valid is only
+    /// logged, as the token can still be used.
+    async fn ensure_token(&mut self) -> Result<()> {
+        let Some(token) = &self.token else {
+            return self.update_token(false).await;
+        };
+
+        if !token.expires_within(self.token_refresh_margin) {
+            return Ok(());
         }
 
-        for attempt in 1..=KBS_GET_RESOURCE_MAX_ATTEMPT {
-            debug!("KBS client: trying to request KBS, attempt {attempt}");
-            if self.token.is_none() {
-                self.update_token().await?;
+        let valid = token.check_valid().is_ok();
+        match self.update_token(false).await {
+            Err(e) if valid => {
+                warn!("refresh the token before it expires failed: {e}");
+                Ok(())
             }
+            result => result,
+        }
+    }
+}
 
+#[async_trait]
+impl SessionSender for KbsClient<Box<dyn TokenProvider>> {
+    /// Send a request authenticated with the token, which is renewed from
+    /// the token provider if the KBS rejects it. Requests failing for
+    /// transient reasons are retried following the retry policy.
+    async fn send_with_session<F>(&mut self, build_request: F) -> Result<reqwest::Response>
+    where
+        F: Fn(&reqwest::Client) -> reqwest::RequestBuilder + Send + Sync,
+    {
+        self.ensure_token().await?;
+
+        let mut retry = Retry::new(self.retry_policy.clone());
+        let mut auth_attempts = 0;
+        loop {
             let token = self.token.as_ref().expect("token must have been got");
 
-            let res = self
-                .http_client
-                .get(&remote_url)
-                .bearer_auth(&token.content)
-                .send()
+            let res = match self
+                .send(build_request(&self.http_client).bearer_auth(&token.content))
                 .await
-                .map_err(|e| Error::HttpError(format!("get failed: {e:?}")))?;
-
-            match res.status() {
-                reqwest::StatusCode::OK => {
-                    let response = res
-                        .json::<Response>()
-                        .await
-                        .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?;
-                    let payload_data = self
-                        .tee_key
-                        .decrypt_response(response)
-                        .map_err(|e| Error::DecryptResponseFailed(e.to_string()))?;
-                    return Ok(payload_data);
-                }
-                reqwest::StatusCode::UNAUTHORIZED => {
-                    warn!(
-                        "Authenticating with KBS failed. Get a new token from the token provider: {:#?}",
-                        res.json::<ErrorInformation>().await.map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?
-                    );
-                    self.update_token().await?;
-
+            {
+                Ok(res) => res,
+                Err(e) => {
+                    if !e.is_connection_error() || !retry.wait().await {
+                        return Err(e);
+                    }
+                    warn!("{e}, retrying...");
                     continue;
                 }
-                reqwest::StatusCode::NOT_FOUND => {
-                    let errorinfo = format!(
-                        "KBS resource Not Found (Error 404): {:#?}",
-                        res.json::<ErrorInformation>()
-                            .await
-                            .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?
-                    );
-
-                    return Err(Error::ResourceNotFound(errorinfo));
-                }
-                _ => {
-                    let errorinfo = format!(
-                        "KBS Server Internal Failed, Response: {:#?}",
-                        res.json::<ErrorInformation>()
-                            .await
-                            .map_err(|e| Error::KbsResponseDeserializationFailed(e.to_string()))?
-                    );
-
-                    return Err(Error::KbsInternalError(errorinfo));
-                }
+            };
+
+            if res.status().is_server_error() && retry.wait().await {
+                warn!("KBS returned {}, retrying...", res.status());
+                continue;
+            }
+
+            if res.status() != reqwest::StatusCode::UNAUTHORIZED {
+                return Ok(res);
+            }
+
+            auth_attempts += 1;
+            let response = KbsErrorResponse::from_response(res).await;
+            if auth_attempts >= self.retry_policy.max_auth_attempts {
+                return Err(Error::UnAuthorized(Some(response)));
             }
+
+            warn!("Authenticating with KBS failed. Get a new token from the token provider: {response}");
+            self.update_token(true).await?;
         }
+    }
+}
 
-        Err(Error::UnAuthorized)
+#[async_trait]
+impl KbsClientCapabilities for KbsClient<Box<dyn TokenProvider>> {
+    async fn get_resource(&mut self, resource_uri: ResourceUri) -> Result<Vec<u8>> {
+        let remote_url = self.resource_url(&resource_uri);
+        let res = self
+            .send_with_session(|client| client.get(&remote_url))
+            .await?;
+        let res = check_response(res).await?;
+        decrypt_resource(res, &self.tee_key).await
+    }
+
+    async fn get_resource_range(
+        &mut self,
+        resource_uri: ResourceUri,
+        offset: u64,
+        len: u64,
+    ) -> Result<ResourceChunk> {
+        let remote_url = self.resource_url(&resource_uri);
+        let range = range_header(offset, len);
+        let res = self
+            .send_with_session(|client| {
+                client
+                    .get(&remote_url)
+                    .header(reqwest::header::RANGE, &range)
+            })
+            .await?;
+        parse_resource_chunk(res, &self.tee_key, offset).await
+    }
+
+    async fn set_resource(&mut self, resource_uri: ResourceUri, content: Vec<u8>) -> Result<()> {
+        let remote_url = self.resource_url(&resource_uri);
+        self.send_authenticated(|client| {
+            client
+                .post(&remote_url)
+                .header("Content-Type", "application/octet-stream")
+                .body(content.clone())
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_resource(&mut self, resource_uri: ResourceUri) -> Result<()> {
+        let remote_url = self.resource_url(&resource_uri);
+        self.send_authenticated(|client| client.delete(&remote_url))
+            .await?;
+        Ok(())
     }
 }
@@ -3,10 +3,55 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
+use std::fmt;
+
+use kbs_types::ErrorInformation;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// An error response of the KBS.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KbsErrorResponse {
+    /// HTTP status code of the response.
+    pub status: u16,
+
+    /// `type` of the returned `ErrorInformation`, if the body is one.
+    pub error_type: Option<String>,
+
+    /// `detail` of the returned `ErrorInformation`, or the raw body.
+    pub detail: String,
+}
+
+impl KbsErrorResponse {
+    pub(crate) async fn from_response(res: reqwest::Response) -> Self {
+        let status = res.status().as_u16();
+        let body = res.text().await.unwrap_or_default();
+        match serde_json::from_str::<ErrorInformation>(&body) {
+            Ok(info) => Self {
+                status,
+                error_type: Some(info.error_type),
+                detail: info.detail,
+            },
+            Err(_) => Self {
+                status,
+                error_type: None,
+                detail: body,
+            },
+        }
+    }
+}
+
+impl fmt::Display for KbsErrorResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HTTP {}", self.status)?;
+        if let Some(error_type) = &self.error_type {
+            write!(f, " {error_type}")?;
+        }
+        write!(f, ": {}", self.detail)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Attestation Agent evidence provider error: {0}")]
@@ -15,9 +60,15 @@ pub enum Error {
     #[error("Attestation Agent token provider error: {0}")]
     AATokenProvider(String),
 
+    #[error("KBS denied the attestation: {0}")]
+    AttestationDenied(KbsErrorResponse),
+
     #[error("decrypt KBS response body failed: {0}")]
     DecryptResponseFailed(String),
 
+    #[error("derive sealing key failed: {0}")]
+    DeriveSealingKey(String),
+
     #[error("get key pair failed: {0}")]
     GenerateKeyPairFailed(String),
 
@@ -30,8 +81,14 @@ pub enum Error {
     #[error("http request failed: {0}")]
     HttpError(String),
 
+    #[error("KBS request forbidden: {0}")]
+    Forbidden(KbsErrorResponse),
+
     #[error("KBS internal error: {0}")]
-    KbsInternalError(String),
+    KbsInternalError(KbsErrorResponse),
+
+    #[error("KBS pool error: {0}")]
+    KbsPool(String),
 
     #[error("deserialize http response failed: {0}")]
     KbsResponseDeserializationFailed(String),
@@ -42,11 +99,40 @@ pub enum Error {
     #[error("RCAR handshake failed: {0}")]
     RcarHandshake(String),
 
+    #[error("invalid certificate: {0}")]
+    InvalidCertificate(String),
+
+    #[error("KBS certificate mismatch: {0}")]
+    KbsCertificateMismatch(String),
+
+    #[error("invalid proxy: {0}")]
+    InvalidProxy(String),
+
+    #[error("invalid resource range: {0}")]
+    InvalidResourceRange(String),
+
     #[error("KBS resource not found: {0}")]
-    ResourceNotFound(String),
+    ResourceNotFound(KbsErrorResponse),
+
+    #[error("KBS session store error: {0}")]
+    SessionStore(String),
+
+    #[error("sign admin token failed: {0}")]
+    SignAdminTokenFailed(String),
 
-    #[error("request unauthorized")]
-    UnAuthorized,
+    #[error("not supported by the KBS client: {0}")]
+    Unsupported(String),
+
+    /// The session or the token was rejected by the KBS, with its error
+    /// response if there is one.
+    ///
+    /// This variant had no field before the KBS error responses were typed,
+    /// so it is matched with `Error::UnAuthorized(_)` now.
+    #[error("request unauthorized{}", .0.as_ref().map(|r| format!(": {r}")).unwrap_or_default())]
+    UnAuthorized(Option<KbsErrorResponse>),
+
+    #[error("write resource failed: {0}")]
+    WriteResourceFailed(String),
 
     #[error("invalid hash algorithm: {0}")]
     InvalidHashAlgorithm(String),
@@ -54,3 +140,95 @@ pub enum Error {
     #[error("unexpected JSON data type: expected {0}, got {1}")]
     UnexpectedJSONDataType(String, String),
 }
+
+impl Error {
+    /// Map a KBS error response to an error.
+    pub(crate) fn from_kbs_response(response: KbsErrorResponse) -> Self {
+        match response.status {
+            401 => Error::UnAuthorized(Some(response)),
+            403 => Error::Forbidden(response),
+            404 => Error::ResourceNotFound(response),
+            _ => Error::KbsInternalError(response),
+        }
+    }
+
+    /// The error response of the KBS which caused the error, if any.
+    pub fn kbs_response(&self) -> Option<&KbsErrorResponse> {
+        match self {
+            Error::AttestationDenied(response)
+            | Error::Forbidden(response)
+            | Error::KbsInternalError(response)
+            | Error::ResourceNotFound(response)
+            | Error::UnAuthorized(Some(response)) => Some(response),
+            _ => None,
+        }
+    }
+
+    /// The HTTP status of the KBS error response which caused the error, if any.
+    pub fn http_status(&self) -> Option<u16> {
+        self.kbs_response().map(|response| response.status)
+    }
+
+    /// Whether the KBS could not be reached.
+    pub fn is_connection_error(&self) -> bool {
+        matches!(self, Error::HttpError(_))
+    }
+
+    /// Whether the request may succeed if retried, i.e. the KBS could not
+    /// be reached or answered with a transient error. Errors without a KBS
+    /// response, e.g. decryption, evidence or deserialization failures, are
+    /// deterministic and not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self.http_status() {
+            Some(status) => status >= 500 || status == 408 || status == 429,
+            None => self.is_connection_error(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{Error, KbsErrorResponse};
+
+    fn response(status: u16) -> KbsErrorResponse {
+        KbsErrorResponse {
+            status,
+            error_type: Some("PolicyDeny".into()),
+            detail: "denied".into(),
+        }
+    }
+
+    #[rstest]
+    #[case(Error::from_kbs_response(response(401)), Some(401), false)]
+    #[case(Error::from_kbs_response(response(403)), Some(403), false)]
+    #[case(Error::from_kbs_response(response(404)), Some(404), false)]
+    #[case(Error::from_kbs_response(response(503)), Some(503), true)]
+    #[case(Error::AttestationDenied(response(401)), Some(401), false)]
+    #[case(Error::HttpError("connection refused".into()), None, true)]
+    #[case(Error::KbsCertificateMismatch("no pin matched".into()), None, false)]
+    #[case(Error::DecryptResponseFailed("aead::Error".into()), None, false)]
+    #[case(Error::GetEvidence("no TEE".into()), None, false)]
+    #[case(Error::KbsResponseDeserializationFailed("EOF".into()), None, false)]
+    fn test_error_status(
+        #[case] error: Error,
+        #[case] status: Option<u16>,
+        #[case] retryable: bool,
+    ) {
+        assert_eq!(error.http_status(), status);
+        assert_eq!(error.is_retryable(), retryable);
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            Error::from_kbs_response(response(401)).to_string(),
+            "request unauthorized: HTTP 401 PolicyDeny: denied"
+        );
+        assert_eq!(
+            Error::UnAuthorized(None).to_string(),
+            "request unauthorized"
+        );
+    }
+}
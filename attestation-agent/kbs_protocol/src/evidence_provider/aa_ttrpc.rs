@@ -11,7 +11,10 @@ use ttrpc::context;
 
 use crate::{Error, Result};
 use protos::ttrpc::aa::{
-    attestation_agent::{GetAdditionalEvidenceRequest, GetEvidenceRequest, GetTeeTypeRequest},
+    attestation_agent::{
+        DeriveSealingKeyRequest, GetAdditionalEvidenceRequest, GetEvidenceRequest,
+        GetTeeTypeRequest,
+    },
     attestation_agent_ttrpc::AttestationAgentServiceClient,
 };
 
@@ -97,4 +100,21 @@ impl EvidenceProvider for AAEvidenceProvider {
             .map_err(|e| Error::AAEvidenceProvider(format!("failed to parse Tee type: {e}")))?;
         Ok(tee)
     }
+
+    /// Derive the sealing key with the primary attester of AA
+    async fn derive_sealing_key(&self, purpose: &str) -> Result<Vec<u8>> {
+        let req = DeriveSealingKeyRequest {
+            Purpose: purpose.to_string(),
+            ..Default::default()
+        };
+        let res = self
+            .client
+            .derive_sealing_key(
+                context::with_timeout(AA_TTRPC_TIMEOUT_SECONDS * 1000 * 1000 * 1000),
+                &req,
+            )
+            .await
+            .map_err(|e| Error::DeriveSealingKey(format!("call ttrpc failed: {e}")))?;
+        Ok(res.Key)
+    }
 }
@@ -6,6 +6,7 @@
 use async_trait::async_trait;
 use attester::TeeEvidence;
 use kbs_types::Tee;
+use sha2::{Digest, Sha256};
 
 use super::EvidenceProvider;
 
@@ -27,4 +28,9 @@ impl EvidenceProvider for MockedEvidenceProvider {
     async fn get_tee_type(&self) -> Result<Tee> {
         Ok(Tee::Sample)
     }
+
+    /// A key only depending on the purpose, not bound to any TEE.
+    async fn derive_sealing_key(&self, purpose: &str) -> Result<Vec<u8>> {
+        Ok(Sha256::digest(purpose.as_bytes()).to_vec())
+    }
 }
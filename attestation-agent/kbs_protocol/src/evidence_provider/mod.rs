@@ -16,7 +16,7 @@ pub mod aa_ttrpc;
 #[cfg(feature = "aa_ttrpc")]
 pub use aa_ttrpc::*;
 
-use crate::Result;
+use crate::{Error, Result};
 use async_trait::async_trait;
 use kbs_types::Tee;
 
@@ -30,4 +30,12 @@ pub trait EvidenceProvider: Send + Sync {
 
     /// Get the underlying Tee type
     async fn get_tee_type(&self) -> Result<Tee>;
+
+    /// Derive a 32 bytes key bound to the TEE and the guest measurement
+    /// for the purpose, to seal data persisted by the guest.
+    async fn derive_sealing_key(&self, _purpose: &str) -> Result<Vec<u8>> {
+        Err(Error::DeriveSealingKey(
+            "not supported by the evidence provider".into(),
+        ))
+    }
 }
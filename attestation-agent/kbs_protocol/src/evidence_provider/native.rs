@@ -3,10 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
-use attester::{detect_attestable_devices, detect_tee_type, BoxedAttester, TeeEvidence};
+use attester::{
+    cache::{CachingAttester, EvidenceCacheOptions},
+    detect_attestable_devices, detect_tee_type, BoxedAttester, TeeEvidence,
+};
 use kbs_types::Tee;
 
 use super::EvidenceProvider;
@@ -15,12 +18,27 @@ use crate::{Error, Result};
 
 pub struct NativeEvidenceProvider {
     primary_tee: Tee,
-    primary_attester: BoxedAttester,
-    additional_attesters: Vec<(Tee, BoxedAttester)>,
+    primary_attester: Arc<BoxedAttester>,
+    additional_attesters: Vec<(Tee, Arc<BoxedAttester>)>,
 }
 
 impl NativeEvidenceProvider {
+    /// Create the evidence provider with the attesters of the detected
+    /// platform and devices, caching their evidence with the default
+    /// options. See [`Self::with_cache_options`].
     pub fn new() -> Result<Self> {
+        Self::with_cache_options(&EvidenceCacheOptions::default())
+    }
+
+    /// Create the evidence provider with the attesters of the detected
+    /// platform and devices, each one wrapped by a [`CachingAttester`] with
+    /// the given options.
+    ///
+    /// The cache and the concurrency limit only apply to the evidence of
+    /// this provider. Other providers, or an AA running on the same guest,
+    /// generate evidence on the devices independently. To share the
+    /// attesters of AA, use [`Self::with_attesters`].
+    pub fn with_cache_options(options: &EvidenceCacheOptions) -> Result<Self> {
         let primary_tee = detect_tee_type();
         let primary_attester = BoxedAttester::try_from(primary_tee).map_err(|e| {
             Error::NativeEvidenceProvider(format!("failed to initialize primary attester: {e}"))
@@ -33,15 +51,34 @@ impl NativeEvidenceProvider {
                         "failed to initialize additional attester: {e}"
                     ))
                 })?;
-                Ok((tee, boxed_attester))
+                Ok((tee, with_cache(boxed_attester, options)))
             })
-            .collect::<Result<Vec<(Tee, BoxedAttester)>>>()?;
+            .collect::<Result<Vec<_>>>()?;
         Ok(Self {
             primary_tee,
-            primary_attester,
+            primary_attester: with_cache(primary_attester, options),
             additional_attesters,
         })
     }
+
+    /// Create the evidence provider with the given attesters, e.g. the ones
+    /// configured in AA.
+    pub fn with_attesters(
+        primary_tee: Tee,
+        primary_attester: Arc<BoxedAttester>,
+        additional_attesters: Vec<(Tee, Arc<BoxedAttester>)>,
+    ) -> Self {
+        Self {
+            primary_tee,
+            primary_attester,
+            additional_attesters,
+        }
+    }
+}
+
+fn with_cache(attester: BoxedAttester, options: &EvidenceCacheOptions) -> Arc<BoxedAttester> {
+    let attester = CachingAttester::new(attester, options);
+    Arc::new(Box::new(attester))
 }
 
 #[async_trait]
@@ -77,4 +114,11 @@ impl EvidenceProvider for NativeEvidenceProvider {
     async fn get_tee_type(&self) -> Result<Tee> {
         Ok(self.primary_tee)
     }
+
+    async fn derive_sealing_key(&self, purpose: &str) -> Result<Vec<u8>> {
+        self.primary_attester
+            .derive_sealing_key(purpose)
+            .await
+            .map_err(|e| Error::DeriveSealingKey(e.to_string()))
+    }
 }
@@ -3,6 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
+use std::{fmt, str::FromStr};
+
 use anyhow::{anyhow, bail, Context, Result};
 
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
@@ -14,6 +16,50 @@ use kbs_types::{ProtectedHeader, Response, TeePubKey};
 use log::warn;
 use zeroize::Zeroizing;
 
+/// Algorithm of the TEE key pair, to which the KBS wraps the keys encrypting
+/// the resources.
+///
+/// X25519 is not offered, as the TEE public key of the KBS protocol can
+/// only be an RSA or an EC (`kty` of `RSA` or `EC`) JWK.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TeeKeyAlgorithm {
+    /// RSA 2048 key, with the CEK wrapped by `RSA-OAEP-256`.
+    Rsa,
+
+    /// EC P-256 key, with the CEK wrapped by `ECDH-ES+A256KW`.
+    #[default]
+    EcP256,
+
+    /// EC P-384 key, with the CEK wrapped by `ECDH-ES+A256KW`.
+    EcP384,
+}
+
+impl FromStr for TeeKeyAlgorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(algorithm: &str) -> Result<Self> {
+        match algorithm {
+            "rsa" => Ok(Self::Rsa),
+            "ec-p256" => Ok(Self::EcP256),
+            "ec-p384" => Ok(Self::EcP384),
+            _ => bail!(
+                "unsupported TEE key algorithm `{algorithm}`, expected `rsa`, `ec-p256` or `ec-p384`"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for TeeKeyAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let algorithm = match self {
+            Self::Rsa => "rsa",
+            Self::EcP256 => "ec-p256",
+            Self::EcP384 => "ec-p384",
+        };
+        f.write_str(algorithm)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TeeKeyPair {
     key: TeeKey,
@@ -28,10 +74,30 @@ pub enum TeeKey {
 impl TeeKeyPair {
     /// Create a new Tee key pair. We by default to use EC key pair.
     pub fn new() -> Result<Self> {
-        let key = TeeKey::Ec(Box::default());
+        Self::generate(TeeKeyAlgorithm::default())
+    }
+
+    /// Create a new Tee key pair of the given algorithm.
+    pub fn generate(algorithm: TeeKeyAlgorithm) -> Result<Self> {
+        let key = match algorithm {
+            TeeKeyAlgorithm::Rsa => TeeKey::Rsa(Box::new(RSAKeyPair::new()?)),
+            TeeKeyAlgorithm::EcP256 => TeeKey::Ec(Box::new(EcKeyPair::new(Curve::P256)?)),
+            TeeKeyAlgorithm::EcP384 => TeeKey::Ec(Box::new(EcKeyPair::new(Curve::P384)?)),
+        };
         Ok(Self { key })
     }
 
+    /// The algorithm of the key pair.
+    pub fn algorithm(&self) -> TeeKeyAlgorithm {
+        match &self.key {
+            TeeKey::Rsa(_) => TeeKeyAlgorithm::Rsa,
+            TeeKey::Ec(key) => match key.curve() {
+                Curve::P256 => TeeKeyAlgorithm::EcP256,
+                Curve::P384 => TeeKeyAlgorithm::EcP384,
+            },
+        }
+    }
+
     /// Export TEE public key as specific structure.
     pub fn export_pubkey(&self) -> Result<TeePubKey> {
         match &self.key {
@@ -50,7 +116,7 @@ impl TeeKeyPair {
                 let y = URL_SAFE_NO_PAD.encode(key.y()?);
 
                 Ok(TeePubKey::EC {
-                    crv: Curve::P256.as_ref().to_string(),
+                    crv: key.curve().as_ref().to_string(),
                     alg: KeyWrapAlgorithm::EcdhEsA256Kw.as_ref().to_string(),
                     x,
                     y,
@@ -130,7 +196,7 @@ impl TeeKeyPair {
         }
 
         let keypair = EcKeyPair::from_pkcs8_pem(pem)
-            .context("private key is not RSA (PKCS#1) nor EC P256 (PKCS#8)")?;
+            .context("private key is not RSA (PKCS#1) nor EC P-256/P-384 (PKCS#8)")?;
         Ok(Self {
             key: TeeKey::Ec(Box::new(keypair)),
         })
@@ -161,3 +227,46 @@ impl TeeKeyPair {
         Ok(plaintext)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use kbs_types::TeePubKey;
+    use rstest::rstest;
+
+    use super::{TeeKeyAlgorithm, TeeKeyPair};
+
+    #[rstest]
+    #[case(TeeKeyAlgorithm::Rsa, None)]
+    #[case(TeeKeyAlgorithm::EcP256, Some("P-256"))]
+    #[case(TeeKeyAlgorithm::EcP384, Some("P-384"))]
+    fn test_generate_tee_key(#[case] algorithm: TeeKeyAlgorithm, #[case] curve: Option<&str>) {
+        let keypair = TeeKeyPair::generate(algorithm).unwrap();
+        assert_eq!(keypair.algorithm(), algorithm);
+        assert_eq!(
+            algorithm.to_string().parse::<TeeKeyAlgorithm>().unwrap(),
+            algorithm
+        );
+
+        let pubkey = keypair.export_pubkey().unwrap();
+        match (&pubkey, curve) {
+            (TeePubKey::RSA { alg, .. }, None) => assert_eq!(alg, "RSA-OAEP-256"),
+            (TeePubKey::EC { crv, alg, .. }, Some(curve)) => {
+                assert_eq!(crv, curve);
+                assert_eq!(alg, "ECDH-ES+A256KW");
+            }
+            _ => panic!("unexpected TEE public key {pubkey:?}"),
+        }
+
+        let restored = TeeKeyPair::from_pem(&keypair.to_pem().unwrap()).unwrap();
+        assert_eq!(restored.algorithm(), algorithm);
+        assert_eq!(
+            serde_json::to_value(restored.export_pubkey().unwrap()).unwrap(),
+            serde_json::to_value(pubkey).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_tee_key_algorithm() {
+        assert!("x25519".parse::<TeeKeyAlgorithm>().is_err());
+    }
+}
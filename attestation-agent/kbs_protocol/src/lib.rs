@@ -67,8 +67,25 @@
 //! }
 //! ```
 //!
-//! Note: everytime the token is found expired, the client will call the
-//! `token_provider` to retrieve a new token.
+//! Note: everytime the token is found expired or about to expire (see
+//! [`KbsClientBuilder::set_token_refresh_margin`]), or is rejected by the
+//! KBS, the client will call the `token_provider` to retrieve a new token.
+//! [`token_provider::KbsTokenProvider`] gets the tokens from an attestation
+//! service speaking the KBS attestation protocol, e.g. the issuer KBS, and
+//! [`token_provider::CoCoAsTokenProvider`] from the RESTful API of a CoCo
+//! attestation service.
+//!
+//! ## Retries and errors
+//!
+//! Requests failing for a transient reason, e.g. an unreachable KBS or a
+//! 5xx response, are retried following the [`RetryPolicy`] given with
+//! [`KbsClientBuilder::set_retry_policy`]. The policy replaces the fixed
+//! `client::KBS_GET_RESOURCE_MAX_ATTEMPT`, which has been removed; set
+//! [`RetryPolicy::max_attempts`] instead.
+//!
+//! The error responses of the KBS are kept in the [`Error`]s as a
+//! [`KbsErrorResponse`], so that callers can tell e.g. a policy denial from
+//! a missing resource. [`Error::UnAuthorized`] carries the response too.
 
 pub mod api;
 pub mod builder;
@@ -76,10 +93,19 @@ pub mod client;
 pub mod error;
 pub mod evidence_provider;
 pub mod keypair;
+pub mod proxy;
+pub mod retry;
+pub mod session;
+pub mod tls;
 pub mod token_provider;
 
 pub use api::*;
 pub use builder::KbsClientBuilder;
-pub use error::{Error, Result};
-pub use keypair::TeeKeyPair;
+pub use client::pool::KbsClientPool;
+pub use error::{Error, KbsErrorResponse, Result};
+pub use keypair::{TeeKeyAlgorithm, TeeKeyPair};
+pub use proxy::ProxyConfig;
+pub use retry::RetryPolicy;
+pub use session::SessionStore;
+pub use tls::SpkiPin;
 pub use token_provider::Token;
@@ -0,0 +1,84 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Proxy configuration of the HTTP requests to the KBS and, in AA and the
+//! key provider, to the other services.
+
+use serde::Deserialize;
+
+use crate::{Error, Result};
+
+/// Proxies of the HTTP requests, with the same semantics as the
+/// `image_pull_proxy` of image-rs. Without any, the proxies are taken from
+/// the `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` environment variables.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct ProxyConfig {
+    /// Proxy for the requests to `https://` URLs.
+    #[serde(default)]
+    pub https_proxy: Option<String>,
+
+    /// Proxy for the requests to `http://` URLs.
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+
+    /// Comma separated list of hosts, domains, IPs or CIDRs that bypass the
+    /// proxies, e.g. `localhost,.example.com,10.0.0.0/8`.
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Set the proxies of the given HTTP client builder.
+    pub fn apply(&self, mut builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        let no_proxy = self
+            .no_proxy
+            .as_deref()
+            .and_then(reqwest::NoProxy::from_string);
+
+        if let Some(https_proxy) = &self.https_proxy {
+            let proxy = reqwest::Proxy::https(https_proxy)
+                .map_err(|e| Error::InvalidProxy(format!("https proxy `{https_proxy}`: {e}")))?;
+            builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+        }
+
+        if let Some(http_proxy) = &self.http_proxy {
+            let proxy = reqwest::Proxy::http(http_proxy)
+                .map_err(|e| Error::InvalidProxy(format!("http proxy `{http_proxy}`: {e}")))?;
+            builder = builder.proxy(proxy.no_proxy(no_proxy));
+        }
+
+        Ok(builder)
+    }
+
+    /// Build an HTTP client with the proxies.
+    pub fn http_client(&self) -> Result<reqwest::Client> {
+        self.apply(reqwest::Client::builder())?
+            .build()
+            .map_err(|e| Error::HttpError(format!("build http client: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::ProxyConfig;
+
+    #[rstest]
+    #[case(ProxyConfig::default(), true)]
+    #[case(ProxyConfig {
+        https_proxy: Some("http://127.0.0.1:3128".into()),
+        http_proxy: Some("http://127.0.0.1:3128".into()),
+        no_proxy: Some("localhost,.example.com,10.0.0.0/8".into()),
+    }, true)]
+    #[case(ProxyConfig {
+        https_proxy: Some("http://[::1".into()),
+        ..Default::default()
+    }, false)]
+    fn test_apply_proxy(#[case] config: ProxyConfig, #[case] valid: bool) {
+        assert_eq!(config.apply(reqwest::Client::builder()).is_ok(), valid);
+        assert_eq!(config.http_client().is_ok(), valid);
+    }
+}
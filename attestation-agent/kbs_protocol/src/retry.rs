@@ -0,0 +1,204 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Retry policy of the requests to the KBS.
+
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+/// Default number of attempts of an RCAR handshake or a request failing
+/// for a transient reason.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Default number of attempts of a request rejected by the KBS because the
+/// session or the token is no longer valid.
+pub const DEFAULT_MAX_AUTH_ATTEMPTS: u32 = 3;
+
+/// Default delay before a retry.
+pub const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How the KBS client retries failed requests.
+///
+/// Requests failing for transient reasons, e.g. network errors or KBS 5xx
+/// responses, are retried after an exponentially growing delay, with random
+/// jitter to avoid many guests retrying in lockstep. Requests denied by the
+/// KBS, e.g. by its policy, are never retried.
+///
+/// The default policy retries with a fixed one second delay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+
+    /// Maximum number of attempts of a request re-authenticated after the
+    /// KBS rejected the session or the token.
+    pub max_auth_attempts: u32,
+
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+
+    /// Upper bound of the delay between two attempts.
+    pub max_backoff: Duration,
+
+    /// Factor the delay is multiplied by after each retry.
+    pub multiplier: f64,
+
+    /// Fraction of the delay randomly added or removed, within `[0, 1]`.
+    pub jitter: f64,
+
+    /// Overall deadline of all the attempts. No retry is made if it would
+    /// start after the deadline.
+    pub deadline: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            max_auth_attempts: DEFAULT_MAX_AUTH_ATTEMPTS,
+            initial_backoff: DEFAULT_BACKOFF,
+            max_backoff: DEFAULT_BACKOFF,
+            multiplier: 1.0,
+            jitter: 0.0,
+            deadline: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy doubling the delay after each retry, from `initial_backoff`
+    /// up to `max_backoff`, with 20% jitter.
+    pub fn exponential(
+        max_attempts: u32,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+    ) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff,
+            max_backoff,
+            multiplier: 2.0,
+            jitter: 0.2,
+            ..Default::default()
+        }
+    }
+
+    /// A policy never retrying.
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            max_auth_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// The delay before the given retry, starting from 1.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        let delay = delay.min(self.max_backoff.as_secs_f64());
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = match jitter > 0.0 {
+            true => rand::rng().random_range(1.0 - jitter..=1.0 + jitter),
+            false => 1.0,
+        };
+
+        Duration::try_from_secs_f64(delay * factor).unwrap_or(self.max_backoff)
+    }
+}
+
+/// The retry state of a single request following a [`RetryPolicy`].
+pub(crate) struct Retry {
+    policy: RetryPolicy,
+    start: Instant,
+    retries: u32,
+}
+
+impl Retry {
+    pub(crate) fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            start: Instant::now(),
+            retries: 0,
+        }
+    }
+
+    /// Wait before the next attempt. Returns `false` without waiting if the
+    /// attempts are exhausted or the next attempt would start after the
+    /// deadline.
+    pub(crate) async fn wait(&mut self) -> bool {
+        if self.retries + 1 >= self.policy.max_attempts {
+            return false;
+        }
+
+        let delay = self.policy.backoff(self.retries + 1);
+        if let Some(deadline) = self.policy.deadline {
+            if self.start.elapsed() + delay > deadline {
+                return false;
+            }
+        }
+
+        self.retries += 1;
+        tokio::time::sleep(delay).await;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Retry, RetryPolicy};
+
+    #[test]
+    fn test_backoff() {
+        let policy =
+            RetryPolicy::exponential(10, Duration::from_millis(100), Duration::from_secs(1))
+                .with_jitter(0.0);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+
+        let policy = policy.with_jitter(0.5);
+        for retry in 1..5 {
+            let delay = policy.backoff(retry);
+            let base = Duration::from_millis(100 * 2u64.pow(retry - 1));
+            assert!(delay >= base / 2 && delay <= base * 3 / 2);
+        }
+
+        assert_eq!(RetryPolicy::default().backoff(3), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let policy = RetryPolicy::exponential(3, Duration::ZERO, Duration::ZERO);
+        let mut retry = Retry::new(policy.clone());
+        assert!(retry.wait().await);
+        assert!(retry.wait().await);
+        assert!(!retry.wait().await);
+
+        let mut retry = Retry::new(RetryPolicy::no_retry());
+        assert!(!retry.wait().await);
+
+        let policy = RetryPolicy::exponential(3, Duration::from_secs(10), Duration::from_secs(10))
+            .with_deadline(Duration::from_secs(1));
+        let mut retry = Retry::new(policy);
+        assert!(!retry.wait().await);
+    }
+}
@@ -0,0 +1,377 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Persistence of the KBS session.
+//!
+//! The token, the TEE key pair and the session cookies of a [`crate::KbsClient`]
+//! can be persisted with a [`SessionStore`], so that a restarted process
+//! resumes the session instead of repeating the RCAR handshake while the
+//! token is valid.
+//!
+//! The session is sealed with AES-256-GCM under a key derived from the TEE
+//! by the evidence provider, see [`EvidenceProvider::derive_sealing_key`].
+//! The key is bound to the guest measurement and never leaves the TEE, so
+//! the session can only be unsealed by the same guest. The KBS URL and the
+//! initdata are bound to the sealed session as additional authenticated
+//! data, so a session sealed for another KBS or under other initdata is
+//! never resumed.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{
+    cookie::{CookieStore, Jar},
+    Url,
+};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{
+    evidence_provider::EvidenceProvider, keypair::TeeKeyPair, token_provider::Token, Error, Result,
+};
+
+/// Version of the sealed session format.
+const SESSION_VERSION: u32 = 1;
+
+/// Length of the sealing key, in bytes.
+const SEALING_KEY_LEN: usize = 32;
+
+/// Purpose of the sealing key derived from the TEE.
+const SEALING_KEY_PURPOSE: &str = "kbs-session";
+
+const AES_GCM_IV_LEN: usize = 12;
+
+/// A file persisting the KBS session, sealed with a key derived from the TEE.
+#[derive(Clone)]
+pub struct SessionStore {
+    path: PathBuf,
+    sealing_key: Zeroizing<Vec<u8>>,
+}
+
+/// The plaintext of a sealed session.
+#[derive(Serialize, Deserialize)]
+struct Session {
+    token: String,
+    tee_key: String,
+    cookies: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SealedSession {
+    version: u32,
+    iv: String,
+    ciphertext: String,
+    tag: String,
+}
+
+/// A session resumed from a [`SessionStore`].
+pub(crate) struct ResumedSession {
+    pub(crate) token: Token,
+    pub(crate) tee_key: TeeKeyPair,
+}
+
+impl SessionStore {
+    /// A store persisting the session at `path`, sealed with a key derived
+    /// by the evidence provider. Fails if the evidence provider cannot
+    /// derive sealing keys, e.g. on a platform without such a key.
+    pub async fn with_evidence_provider(
+        path: impl Into<PathBuf>,
+        evidence_provider: &dyn EvidenceProvider,
+    ) -> Result<Self> {
+        let sealing_key = Zeroizing::new(
+            evidence_provider
+                .derive_sealing_key(SEALING_KEY_PURPOSE)
+                .await?,
+        );
+        Self::new(path, &sealing_key)
+    }
+
+    /// A store persisting the session at `path`, sealed with the 32 bytes
+    /// `sealing_key`.
+    pub(crate) fn new(path: impl Into<PathBuf>, sealing_key: &[u8]) -> Result<Self> {
+        if sealing_key.len() != SEALING_KEY_LEN {
+            return Err(Error::SessionStore(format!(
+                "sealing key must be {SEALING_KEY_LEN} bytes, got {}",
+                sealing_key.len()
+            )));
+        }
+
+        Ok(Self {
+            path: path.into(),
+            sealing_key: Zeroizing::new(sealing_key.to_vec()),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Delete the persisted session, if any.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(Error::SessionStore(
+                format!("remove {}: {e}", self.path.display()),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resume the persisted session into the cookie jar. Returns `None` if
+    /// no session is persisted or its token is no longer valid.
+    pub(crate) fn resume(
+        &self,
+        context: &[u8],
+        cookie_jar: &Jar,
+        cookie_url: &Url,
+    ) -> Result<Option<ResumedSession>> {
+        let sealed = match fs::read(&self.path) {
+            Ok(sealed) => sealed,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(Error::SessionStore(format!(
+                    "read {}: {e}",
+                    self.path.display()
+                )))
+            }
+        };
+        let session = self.unseal(&sealed, context)?;
+
+        let token = Token::new(session.token)
+            .map_err(|e| Error::SessionStore(format!("invalid token: {e:#}")))?;
+        if token.check_valid().is_err() {
+            return Ok(None);
+        }
+
+        let tee_key = TeeKeyPair::from_pem(&session.tee_key)
+            .map_err(|e| Error::SessionStore(format!("invalid TEE key: {e:#}")))?;
+
+        for cookie in session.cookies.iter().flat_map(|c| c.split("; ")) {
+            cookie_jar.add_cookie_str(cookie, cookie_url);
+        }
+
+        Ok(Some(ResumedSession { token, tee_key }))
+    }
+
+    /// Persist the session, replacing the previous one atomically.
+    pub(crate) fn save(
+        &self,
+        context: &[u8],
+        token: &Token,
+        tee_key: &TeeKeyPair,
+        cookie_jar: &Jar,
+        cookie_url: &Url,
+    ) -> Result<()> {
+        let tee_key = tee_key
+            .to_pem()
+            .map_err(|e| Error::SessionStore(format!("export TEE key: {e:#}")))?;
+        let cookies = cookie_jar
+            .cookies(cookie_url)
+            .and_then(|cookies| cookies.to_str().ok().map(String::from));
+        let session = Session {
+            token: token.content.clone(),
+            tee_key: tee_key.to_string(),
+            cookies,
+        };
+        let sealed = self.seal(&session, context)?;
+
+        let tmp_path = self.path.with_extension("tmp");
+        write_private(&tmp_path, &sealed)
+            .and_then(|_| fs::rename(&tmp_path, &self.path))
+            .map_err(|e| Error::SessionStore(format!("write {}: {e}", self.path.display())))
+    }
+
+    fn seal(&self, session: &Session, context: &[u8]) -> Result<Vec<u8>> {
+        let plaintext = Zeroizing::new(
+            serde_json::to_vec(session).map_err(|e| Error::SessionStore(e.to_string()))?,
+        );
+        let iv = rand::random::<[u8; AES_GCM_IV_LEN]>().to_vec();
+        let cipher = crypto::encrypt_aead(
+            self.sealing_key.clone(),
+            plaintext.to_vec(),
+            iv.clone(),
+            context.to_vec(),
+            crypto::WrapType::Aes256Gcm,
+        )
+        .map_err(|e| Error::SessionStore(format!("seal session: {e:#}")))?;
+
+        let sealed = SealedSession {
+            version: SESSION_VERSION,
+            iv: STANDARD.encode(iv),
+            ciphertext: STANDARD.encode(cipher.ciphertext),
+            tag: STANDARD.encode(cipher.tag),
+        };
+        serde_json::to_vec(&sealed).map_err(|e| Error::SessionStore(e.to_string()))
+    }
+
+    fn unseal(&self, sealed: &[u8], context: &[u8]) -> Result<Session> {
+        let sealed: SealedSession = serde_json::from_slice(sealed)
+            .map_err(|e| Error::SessionStore(format!("parse sealed session: {e}")))?;
+        if sealed.version != SESSION_VERSION {
+            return Err(Error::SessionStore(format!(
+                "unsupported sealed session version {}",
+                sealed.version
+            )));
+        }
+
+        let decode = |field: &str| {
+            STANDARD
+                .decode(field)
+                .map_err(|e| Error::SessionStore(format!("parse sealed session: {e}")))
+        };
+        let plaintext = crypto::decrypt_aead(
+            self.sealing_key.clone(),
+            decode(&sealed.ciphertext)?,
+            decode(&sealed.iv)?,
+            context.to_vec(),
+            decode(&sealed.tag)?,
+            crypto::WrapType::Aes256Gcm,
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| Error::SessionStore("session not sealed with this key for this KBS".into()))?;
+
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| Error::SessionStore(format!("parse session: {e}")))
+    }
+}
+
+/// Write a file only readable by the owner.
+fn write_private(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    file.write_all(content)?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use attester::TeeEvidence;
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use jwt_simple::prelude::{Clock, Duration};
+    use kbs_types::Tee;
+    use reqwest::{
+        cookie::{CookieStore, Jar},
+        Url,
+    };
+    use serde_json::json;
+
+    use super::SessionStore;
+    use crate::{
+        evidence_provider::{EvidenceProvider, MockedEvidenceProvider},
+        keypair::TeeKeyPair,
+        token_provider::Token,
+    };
+
+    fn token(exp: u64) -> Token {
+        let claims = URL_SAFE_NO_PAD.encode(json!({ "exp": exp }).to_string());
+        Token::new(format!("e30.{claims}.c2lnbmF0dXJl")).unwrap()
+    }
+
+    #[test]
+    fn test_session_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session");
+        let store = SessionStore::new(&path, &[1; 32]).unwrap();
+        let url = Url::parse("https://kbs.io/kbs/v0/session").unwrap();
+        let context = b"https://kbs.io";
+
+        let jar = Jar::default();
+        assert!(store.resume(context, &jar, &url).unwrap().is_none());
+
+        let exp = (Clock::now_since_epoch() + Duration::from_hours(1)).as_secs();
+        let tee_key = TeeKeyPair::new().unwrap();
+        jar.add_cookie_str("kbs-session-id=1234", &url);
+        store
+            .save(context, &token(exp), &tee_key, &jar, &url)
+            .unwrap();
+
+        let jar = Jar::default();
+        let session = store.resume(context, &jar, &url).unwrap().unwrap();
+        assert_eq!(session.token.content, token(exp).content);
+        assert_eq!(
+            *session.tee_key.to_pem().unwrap(),
+            *tee_key.to_pem().unwrap()
+        );
+        assert_eq!(jar.cookies(&url).unwrap(), "kbs-session-id=1234");
+
+        // Sealed for another KBS, or with another sealing key
+        assert!(store.resume(b"https://other.io", &jar, &url).is_err());
+        let other = SessionStore::new(&path, &[2; 32]).unwrap();
+        assert!(other.resume(context, &jar, &url).is_err());
+
+        // Expired token
+        let exp = Clock::now_since_epoch().as_secs() - 60;
+        store
+            .save(context, &token(exp), &tee_key, &jar, &url)
+            .unwrap();
+        assert!(store.resume(context, &jar, &url).unwrap().is_none());
+
+        store.clear().unwrap();
+        store.clear().unwrap();
+        assert!(!path.exists());
+        assert!(SessionStore::new(&path, &[1; 16]).is_err());
+    }
+
+    /// An evidence provider which cannot derive sealing keys.
+    struct NoSealingKeyEvidenceProvider;
+
+    #[async_trait::async_trait]
+    impl EvidenceProvider for NoSealingKeyEvidenceProvider {
+        async fn primary_evidence(&self, _runtime_data: Vec<u8>) -> crate::Result<TeeEvidence> {
+            Ok("test evidence".into())
+        }
+
+        async fn get_additional_evidence(&self, _runtime_data: Vec<u8>) -> crate::Result<String> {
+            Ok("".into())
+        }
+
+        async fn get_tee_type(&self) -> crate::Result<Tee> {
+            Ok(Tee::Sample)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_store_with_evidence_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session");
+        let url = Url::parse("https://kbs.io/kbs/v0/session").unwrap();
+        let context = b"https://kbs.io";
+
+        let store = SessionStore::with_evidence_provider(&path, &MockedEvidenceProvider::default())
+            .await
+            .unwrap();
+        let exp = (Clock::now_since_epoch() + Duration::from_hours(1)).as_secs();
+        store
+            .save(
+                context,
+                &token(exp),
+                &TeeKeyPair::new().unwrap(),
+                &Jar::default(),
+                &url,
+            )
+            .unwrap();
+
+        // The key is derived again after a restart
+        let store = SessionStore::with_evidence_provider(&path, &MockedEvidenceProvider::default())
+            .await
+            .unwrap();
+        assert!(store
+            .resume(context, &Jar::default(), &url)
+            .unwrap()
+            .is_some());
+
+        assert!(
+            SessionStore::with_evidence_provider(&path, &NoSealingKeyEvidenceProvider)
+                .await
+                .is_err()
+        );
+    }
+}
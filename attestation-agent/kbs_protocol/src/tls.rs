@@ -0,0 +1,410 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Pinning of the KBS certificate.
+//!
+//! The KBS certificate can be pinned by the SHA-256 hash of its
+//! SubjectPublicKeyInfo (SPKI), in the same `sha256/<base64>` format as
+//! [HPKP](https://datatracker.ietf.org/doc/html/rfc7469) pins. The pins are
+//! either given to the [`crate::KbsClientBuilder`] directly, or delivered
+//! through the initdata in the attested TLS mode. As the initdata is bound to
+//! the TEE evidence, a KBS accepting the evidence also vouches for the pins.
+//!
+//! The pins are checked during the TLS handshake, after the usual
+//! verification of the certificate, so no request is sent to a KBS whose
+//! certificate matches none of them. This needs the rustls backend of the
+//! `rust-crypto` feature.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+#[cfg(feature = "rust-crypto")]
+use std::{error::Error as StdError, sync::Arc};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use x509_cert::{
+    der::{Decode, DecodePem, Encode},
+    Certificate,
+};
+
+#[cfg(feature = "rust-crypto")]
+use rustls::{
+    client::{
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        WebPkiServerVerifier,
+    },
+    crypto::ring,
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    CertificateError, ClientConfig, DigitallySignedStruct, OtherError, RootCertStore,
+    SignatureScheme,
+};
+
+use crate::{Error, Result};
+
+/// Key of the initdata `data` entry carrying the SPKI pins of the KBS
+/// certificate in the attested TLS mode. The pins are separated by commas
+/// or whitespaces.
+pub const INITDATA_KBS_SPKI_PINS: &str = "kbs_spki_pins";
+
+const SPKI_PIN_PREFIX: &str = "sha256/";
+
+/// SHA-256 hash of the SubjectPublicKeyInfo of a certificate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpkiPin([u8; 32]);
+
+impl SpkiPin {
+    /// The pin of a DER encoded certificate.
+    pub fn from_cert_der(cert_der: &[u8]) -> Result<Self> {
+        let cert = Certificate::from_der(cert_der)
+            .map_err(|e| Error::InvalidCertificate(format!("parse certificate: {e}")))?;
+        Self::from_cert(&cert)
+    }
+
+    /// The pin of a PEM encoded certificate.
+    pub fn from_cert_pem(cert_pem: &str) -> Result<Self> {
+        let cert = Certificate::from_pem(cert_pem)
+            .map_err(|e| Error::InvalidCertificate(format!("parse certificate: {e}")))?;
+        Self::from_cert(&cert)
+    }
+
+    fn from_cert(cert: &Certificate) -> Result<Self> {
+        let spki = cert
+            .tbs_certificate
+            .subject_public_key_info
+            .to_der()
+            .map_err(|e| Error::InvalidCertificate(format!("encode SPKI: {e}")))?;
+        Ok(Self(Sha256::digest(spki).into()))
+    }
+}
+
+impl FromStr for SpkiPin {
+    type Err = Error;
+
+    fn from_str(pin: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            Error::InvalidCertificate(format!(
+                "invalid SPKI pin `{pin}`, expected `{SPKI_PIN_PREFIX}<base64>`: {reason}"
+            ))
+        };
+        let hash = pin
+            .strip_prefix(SPKI_PIN_PREFIX)
+            .ok_or_else(|| invalid("unsupported hash algorithm"))?;
+        let hash = STANDARD
+            .decode(hash)
+            .map_err(|_| invalid("not base64 encoded"))?;
+        let hash = hash.try_into().map_err(|_| invalid("not a SHA-256 hash"))?;
+        Ok(Self(hash))
+    }
+}
+
+impl fmt::Display for SpkiPin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{SPKI_PIN_PREFIX}{}", STANDARD.encode(self.0))
+    }
+}
+
+#[derive(Deserialize)]
+struct InitdataEntries {
+    #[serde(default)]
+    data: HashMap<String, String>,
+}
+
+/// Parse the SPKI pins delivered through the given initdata TOML.
+pub fn spki_pins_from_initdata(initdata: &str) -> Result<Vec<SpkiPin>> {
+    let initdata: InitdataEntries = toml::from_str(initdata)
+        .map_err(|e| Error::InvalidCertificate(format!("parse initdata: {e}")))?;
+    let Some(pins) = initdata.data.get(INITDATA_KBS_SPKI_PINS) else {
+        return Ok(vec![]);
+    };
+
+    pins.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|pin| !pin.is_empty())
+        .map(SpkiPin::from_str)
+        .collect()
+}
+
+/// The KBS certificate matches none of the pins. The TLS handshake fails
+/// with this error.
+#[cfg(feature = "rust-crypto")]
+#[derive(Debug, thiserror::Error)]
+#[error("KBS certificate SPKI {0} matches none of the pins")]
+pub(crate) struct SpkiPinMismatch(SpkiPin);
+
+/// Verifies the KBS certificate with the root certificates, then checks it
+/// against the pins.
+#[cfg(feature = "rust-crypto")]
+#[derive(Debug)]
+struct SpkiPinVerifier {
+    verifier: Arc<WebPkiServerVerifier>,
+    pins: Vec<SpkiPin>,
+}
+
+#[cfg(feature = "rust-crypto")]
+impl ServerCertVerifier for SpkiPinVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        let verified = self.verifier.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+
+        let pin = SpkiPin::from_cert_der(end_entity)
+            .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+        if !self.pins.contains(&pin) {
+            return Err(rustls::Error::InvalidCertificate(CertificateError::Other(
+                OtherError(Arc::new(SpkiPinMismatch(pin))),
+            )));
+        }
+
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.verifier.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.verifier.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.verifier.supported_verify_schemes()
+    }
+}
+
+/// The TLS configuration of the connections to a pinned KBS. The KBS
+/// certificate is verified with the webpki roots and the given root
+/// certificates, like reqwest does, and then checked against the pins.
+#[cfg(feature = "rust-crypto")]
+pub(crate) fn pinned_tls_config(
+    kbs_certs: &[String],
+    client_identity: Option<(&str, &str)>,
+    pins: Vec<SpkiPin>,
+) -> Result<ClientConfig> {
+    let provider = Arc::new(ring::default_provider());
+
+    let mut roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    for cert_pem in kbs_certs {
+        for cert in CertificateDer::pem_slice_iter(cert_pem.as_bytes()) {
+            let cert =
+                cert.map_err(|e| Error::InvalidCertificate(format!("read KBS certificate: {e}")))?;
+            roots
+                .add(cert)
+                .map_err(|e| Error::InvalidCertificate(format!("add KBS certificate: {e}")))?;
+        }
+    }
+
+    let verifier = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+        .build()
+        .map_err(|e| Error::InvalidCertificate(format!("build certificate verifier: {e}")))?;
+    let builder = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| Error::InvalidCertificate(format!("set TLS versions: {e}")))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(SpkiPinVerifier { verifier, pins }));
+
+    let mut config = match client_identity {
+        Some((cert_pem, key_pem)) => {
+            let certs = CertificateDer::pem_slice_iter(cert_pem.as_bytes())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| Error::InvalidCertificate(format!("read client certificate: {e}")))?;
+            let key = PrivateKeyDer::from_pem_slice(key_pem.as_bytes())
+                .map_err(|e| Error::InvalidCertificate(format!("read client key: {e}")))?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| Error::InvalidCertificate(format!("set client identity: {e}")))?
+        }
+        None => builder.with_no_client_auth(),
+    };
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(config)
+}
+
+/// The pin mismatch which failed the TLS handshake of a request, if any.
+#[cfg(feature = "rust-crypto")]
+pub(crate) fn spki_pin_mismatch(error: &reqwest::Error) -> Option<&SpkiPinMismatch> {
+    let mut source: Option<&(dyn StdError + 'static)> = Some(error);
+    while let Some(error) = source {
+        // The TLS error is wrapped in an `io::Error`, which does not return
+        // it as its source
+        let inner: &(dyn StdError + 'static) = match error
+            .downcast_ref::<std::io::Error>()
+            .and_then(|e| e.get_ref())
+        {
+            Some(inner) => inner,
+            None => error,
+        };
+
+        if let Some(rustls::Error::InvalidCertificate(CertificateError::Other(other))) =
+            inner.downcast_ref::<rustls::Error>()
+        {
+            return other.0.downcast_ref::<SpkiPinMismatch>();
+        }
+
+        source = error.source();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[cfg(feature = "rust-crypto")]
+    use crate::{evidence_provider::MockedEvidenceProvider, KbsClientBuilder};
+
+    const CERT: &str = r"-----BEGIN CERTIFICATE-----
+MIIBzTCCAX+gAwIBAgIUOGdGRmt/IDSVIem7iFwsuxnV62AwBQYDK2VwMGkxCzAJ
+BgNVBAYTAkNOMREwDwYDVQQIDAhTaGFuZ2hhaTERMA8GA1UEBwwIU2hhbmdoYWkx
+EDAOBgNVBAoMB0FsaWJhYmExDzANBgNVBAsMBkFsaXl1bjERMA8GA1UEAwwIS0JT
+LXJvb3QwHhcNMjMwNzE0MDYzMzA1WhcNMjMwODEzMDYzMzA1WjBpMQswCQYDVQQG
+EwJDTjERMA8GA1UECAwIU2hhbmdoYWkxETAPBgNVBAcMCFNoYW5naGFpMRAwDgYD
+VQQKDAdBbGliYWJhMQ8wDQYDVQQLDAZBbGl5dW4xETAPBgNVBAMMCEtCUy1yb290
+MCowBQYDK2VwAyEAOo8z6/Ul3XvNBf2Oa7qDevljyhGSKyGMjV+4qneVNr+jOTA3
+MAkGA1UdEwQCMAAwCwYDVR0PBAQDAgXgMB0GA1UdDgQWBBREKNLFRe7fCBKRffTv
+x13TMfDeczAFBgMrZXADQQBpP6ABBkzVj3mF55nWUtP5vxwq3t91wqQJ6NyC7WsT
+3Z29bFfJn7C280JfkCqiqeSZjYV/JjTepATH659kktcA
+-----END CERTIFICATE-----";
+
+    #[test]
+    fn test_spki_pin_of_cert() {
+        let pin = SpkiPin::from_cert_pem(CERT).unwrap();
+        assert_eq!(pin, pin.to_string().parse::<SpkiPin>().unwrap());
+        assert!(SpkiPin::from_cert_pem("not a certificate").is_err());
+    }
+
+    #[rstest]
+    #[case("sha256/47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=", true)]
+    #[case("sha384/47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=", false)]
+    #[case("sha256/not-base64", false)]
+    #[case("sha256/AAAA", false)]
+    fn test_parse_spki_pin(#[case] pin: &str, #[case] valid: bool) {
+        assert_eq!(pin.parse::<SpkiPin>().is_ok(), valid);
+    }
+
+    #[test]
+    fn test_spki_pins_from_initdata() {
+        let pin = "sha256/47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=";
+        let initdata = format!(
+            r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"kbs_spki_pins" = "{pin}, {pin}"
+"#
+        );
+        let pins = spki_pins_from_initdata(&initdata).unwrap();
+        assert_eq!(pins, vec![pin.parse::<SpkiPin>().unwrap(); 2]);
+
+        let initdata = r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"#;
+        assert!(spki_pins_from_initdata(initdata).unwrap().is_empty());
+
+        let initdata = r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"kbs_spki_pins" = "sha256/AAAA"
+"#;
+        assert!(spki_pins_from_initdata(initdata).is_err());
+    }
+
+    /// Serve one HTTPS connection with the test KBS certificate, returning
+    /// the number of bytes of the request received.
+    #[cfg(feature = "rust-crypto")]
+    async fn serve_once(listener: tokio::net::TcpListener) -> usize {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let certs =
+            vec![CertificateDer::from_pem_slice(include_bytes!("../test/tls/kbs.crt")).unwrap()];
+        let key = PrivateKeyDer::from_pem_slice(include_bytes!("../test/tls/kbs.key")).unwrap();
+        let config =
+            rustls::ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .unwrap()
+                .with_no_client_auth()
+                .with_single_cert(certs, key)
+                .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let Ok(mut stream) = acceptor.accept(stream).await else {
+            return 0;
+        };
+        let mut request = [0; 4096];
+        let received = stream.read(&mut request).await.unwrap_or(0);
+        let _ = stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+            .await;
+        received
+    }
+
+    #[cfg(feature = "rust-crypto")]
+    #[rstest]
+    #[case("sha256/7hz1U0vKmxC1E+Hmoemf74g5t6XAW1dJnqnqckj05Rc=", true)]
+    #[case("sha256/47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=", false)]
+    #[tokio::test]
+    async fn test_spki_pin_checked_in_handshake(#[case] pin: &str, #[case] matches: bool) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "https://127.0.0.1:{}",
+            listener.local_addr().unwrap().port()
+        );
+        let server = tokio::spawn(serve_once(listener));
+
+        let client = KbsClientBuilder::with_evidence_provider(
+            Box::<MockedEvidenceProvider>::default(),
+            &url,
+        )
+        .add_kbs_cert(include_str!("../test/tls/ca.crt"))
+        .add_kbs_spki_pin(pin)
+        .build()
+        .unwrap();
+        let res = client
+            .send(client.http_client.get(format!("{url}/kbs/v0/auth")))
+            .await;
+        let received = server.await.unwrap();
+
+        if matches {
+            assert!(res.is_ok());
+            assert!(received > 0);
+        } else {
+            // The handshake fails before the request is sent
+            assert!(matches!(res, Err(Error::KbsCertificateMismatch(_))));
+            assert_eq!(received, 0);
+        }
+    }
+}
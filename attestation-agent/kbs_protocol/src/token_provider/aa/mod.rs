@@ -11,7 +11,8 @@ use ttrpc::context;
 
 use crate::{Error, Result, TeeKeyPair, Token};
 use protos::ttrpc::aa::{
-    attestation_agent::GetTokenRequest, attestation_agent_ttrpc::AttestationAgentServiceClient,
+    attestation_agent::{GetInitdataRequest, GetTokenRequest},
+    attestation_agent_ttrpc::AttestationAgentServiceClient,
 };
 
 use super::TokenProvider;
@@ -39,13 +40,31 @@ impl AATokenProvider {
         let client = AttestationAgentServiceClient::new(c);
         Ok(Self { client })
     }
-}
 
-#[async_trait]
-impl TokenProvider for AATokenProvider {
-    async fn get_token(&self) -> Result<(Token, TeeKeyPair)> {
+    /// Get the initdata TOML bound to the platform by the attestation-agent,
+    /// if any.
+    pub async fn get_initdata(&self) -> Result<Option<String>> {
+        let res = self
+            .client
+            .get_initdata(
+                context::with_timeout(10 * 1000 * 1000 * 1000),
+                &GetInitdataRequest::new(),
+            )
+            .await
+            .map_err(|e| Error::AATokenProvider(format!("get initdata failed: {e:?}")))?;
+        if res.Toml.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(res.Toml))
+    }
+
+    /// Get the token from the attestation-agent. With `refresh`, AA gets a
+    /// new token rather than returning the cached one.
+    async fn request_token(&self, refresh: bool) -> Result<(Token, TeeKeyPair)> {
         let req = GetTokenRequest {
             TokenType: TOKEN_TYPE.to_string(),
+            Refresh: refresh,
             ..Default::default()
         };
         let bytes = self
@@ -64,3 +83,14 @@ impl TokenProvider for AATokenProvider {
         Ok((token, tee_keypair))
     }
 }
+
+#[async_trait]
+impl TokenProvider for AATokenProvider {
+    async fn get_token(&self) -> Result<(Token, TeeKeyPair)> {
+        self.request_token(false).await
+    }
+
+    async fn refresh_token(&self) -> Result<(Token, TeeKeyPair)> {
+        self.request_token(true).await
+    }
+}
@@ -0,0 +1,322 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! This is a token provider which attests to the RESTful API of a CoCo
+//! attestation service, e.g. the standalone AS of a Trustee deployment in
+//! passport mode. The token it issues is then presented to the resource
+//! KBS.
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use attester::TeeEvidence;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use kbs_types::{HashAlgorithm, RuntimeData, Tee};
+use log::{debug, warn};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::{
+    builder::TOKEN_REFRESH_MARGIN_SEC,
+    client::rcar_client::{primary_runtime_data, serialize_json_canonically},
+    evidence_provider::EvidenceProvider,
+    Error, Result, TeeKeyAlgorithm, TeeKeyPair, Token,
+};
+
+use super::TokenProvider;
+
+/// Hash algorithm used to bind the runtime data into the evidence.
+const RUNTIME_DATA_HASH_ALGORITHM: HashAlgorithm = HashAlgorithm::Sha384;
+
+/// The initdata entry naming the hash algorithm of the initdata digest.
+#[derive(Deserialize)]
+struct InitdataAlgorithm {
+    algorithm: String,
+}
+
+pub struct CoCoAsTokenProvider {
+    provider: Box<dyn EvidenceProvider>,
+    as_url: String,
+    http_client: reqwest::Client,
+    policy_ids: Vec<String>,
+    initdata: Option<String>,
+    tee_key_algorithm: TeeKeyAlgorithm,
+    token_refresh_margin: Duration,
+    token: Mutex<Option<(Token, TeeKeyPair)>>,
+}
+
+impl CoCoAsTokenProvider {
+    /// A provider getting the tokens from the attestation service at
+    /// `as_url` with the given http client. The token is cached and a new
+    /// one is got once it expires within the refresh margin.
+    pub fn new(
+        provider: Box<dyn EvidenceProvider>,
+        as_url: &str,
+        http_client: reqwest::Client,
+    ) -> Self {
+        Self {
+            provider,
+            as_url: as_url.trim_end_matches('/').to_string(),
+            http_client,
+            policy_ids: Vec::new(),
+            initdata: None,
+            tee_key_algorithm: TeeKeyAlgorithm::default(),
+            token_refresh_margin: Duration::from_secs(TOKEN_REFRESH_MARGIN_SEC),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Set the ids of the policies the attestation service evaluates.
+    pub fn set_policy_ids(mut self, policy_ids: Vec<String>) -> Self {
+        self.policy_ids = policy_ids;
+        self
+    }
+
+    /// Set the initdata TOML, which the attestation service checks against
+    /// the digest bound into the evidence.
+    pub fn add_initdata(mut self, initdata: String) -> Self {
+        self.initdata = Some(initdata);
+        self
+    }
+
+    /// Set the algorithm of the TEE key certified by the tokens.
+    pub fn set_tee_key_algorithm(mut self, algorithm: TeeKeyAlgorithm) -> Self {
+        self.tee_key_algorithm = algorithm;
+        self
+    }
+
+    /// Set how long before its expiry a token is refreshed. The margin is
+    /// capped to a quarter of the token lifetime.
+    pub fn set_token_refresh_margin(mut self, margin: Duration) -> Self {
+        self.token_refresh_margin = margin;
+        self
+    }
+
+    /// Get a challenge nonce from the AS, which is bound into the evidence
+    /// to prevent it from being replayed.
+    async fn get_nonce(&self, tee: Tee) -> Result<String> {
+        let res = self
+            .http_client
+            .post(format!("{}/challenge", self.as_url))
+            .json(&json!({
+                "tee": tee,
+                "tee_params": "",
+            }))
+            .send()
+            .await
+            .map_err(|e| Error::HttpError(format!("get AS challenge: {e}")))?;
+
+        if res.status() != reqwest::StatusCode::OK {
+            return Err(Error::GetTokenFailed(format!(
+                "get AS challenge: HTTP {}: {}",
+                res.status(),
+                res.text().await.unwrap_or_default()
+            )));
+        }
+
+        let nonce = res
+            .text()
+            .await
+            .map_err(|e| Error::HttpError(format!("read AS challenge: {e}")))?;
+        Ok(nonce.trim().trim_matches('"').to_string())
+    }
+
+    /// Collect the evidence of the primary TEE and of the additional
+    /// devices, and turn them into the verification requests of the AS.
+    async fn verification_requests(
+        &self,
+        tee: Tee,
+        runtime_data: &RuntimeData,
+    ) -> anyhow::Result<Vec<Value>> {
+        let device_runtime_data = serialize_json_canonically(runtime_data)?;
+        let additional_evidence = self
+            .provider
+            .get_additional_evidence(RUNTIME_DATA_HASH_ALGORITHM.digest(&device_runtime_data))
+            .await?;
+
+        let mut requests = vec![];
+        if !additional_evidence.is_empty() {
+            let devices: HashMap<Tee, TeeEvidence> =
+                serde_json::from_str(&additional_evidence).context("parse additional evidence")?;
+            for (device, evidence) in &devices {
+                let mut request = evidence_request(*device, evidence)?;
+                request["runtime_data"] = json!({ "structured": runtime_data });
+                request["runtime_data_hash_algorithm"] = json!(RUNTIME_DATA_HASH_ALGORITHM);
+                requests.push(request);
+            }
+        }
+
+        let (structured, report_data) = primary_runtime_data(
+            tee,
+            runtime_data,
+            &additional_evidence,
+            RUNTIME_DATA_HASH_ALGORITHM,
+        )?;
+        let evidence = self.provider.primary_evidence(report_data).await?;
+        let mut request = evidence_request(tee, &evidence)?;
+        if let Some(structured) = structured {
+            request["runtime_data"] = json!({ "structured": structured });
+            request["runtime_data_hash_algorithm"] = json!(RUNTIME_DATA_HASH_ALGORITHM);
+        }
+        if let Some(initdata) = &self.initdata {
+            let parsed: InitdataAlgorithm =
+                toml::from_str(initdata).context("parse initdata algorithm")?;
+            let algorithm = parsed
+                .algorithm
+                .to_lowercase()
+                .parse::<HashAlgorithm>()
+                .map_err(|_| Error::InvalidHashAlgorithm(parsed.algorithm))?;
+            request["init_data"] = json!({ "toml": initdata });
+            request["init_data_hash_algorithm"] = json!(algorithm);
+        }
+        requests.insert(0, request);
+
+        Ok(requests)
+    }
+
+    /// Attest to the AS and get a token certifying a new TEE key.
+    async fn attest(&self) -> Result<(Token, TeeKeyPair)> {
+        let tee = self.provider.get_tee_type().await?;
+        let nonce = self.get_nonce(tee).await?;
+
+        let tee_key = TeeKeyPair::generate(self.tee_key_algorithm)
+            .map_err(|e| Error::GenerateKeyPairFailed(format!("{e:#}")))?;
+        let tee_pubkey = tee_key
+            .export_pubkey()
+            .map_err(|e| Error::GetTokenFailed(format!("export TEE public key failed: {e:#}")))?;
+        let runtime_data = RuntimeData { nonce, tee_pubkey };
+
+        let verification_requests = self
+            .verification_requests(tee, &runtime_data)
+            .await
+            .map_err(|e| Error::GetEvidence(format!("{e:#}")))?;
+
+        debug!("send attestation request to {}", self.as_url);
+        let res = self
+            .http_client
+            .post(format!("{}/attestation", self.as_url))
+            .json(&json!({
+                "verification_requests": verification_requests,
+                "policy_ids": self.policy_ids,
+            }))
+            .send()
+            .await
+            .map_err(|e| Error::HttpError(format!("attest to AS: {e}")))?;
+
+        if res.status() != reqwest::StatusCode::OK {
+            return Err(Error::GetTokenFailed(format!(
+                "attest to AS: HTTP {}: {}",
+                res.status(),
+                res.text().await.unwrap_or_default()
+            )));
+        }
+
+        let token = res
+            .text()
+            .await
+            .map_err(|e| Error::HttpError(format!("read AS token: {e}")))?;
+        let token = Token::new(token.trim().to_string())
+            .map_err(|e| Error::GetTokenFailed(format!("invalid AS token: {e:#}")))?;
+        Ok((token, tee_key))
+    }
+}
+
+/// The verification request of an evidence, without its runtime data.
+fn evidence_request(tee: Tee, evidence: &TeeEvidence) -> anyhow::Result<Value> {
+    Ok(json!({
+        "tee": tee,
+        "evidence": URL_SAFE_NO_PAD.encode(serde_json::to_string(evidence)?),
+    }))
+}
+
+#[async_trait]
+impl TokenProvider for CoCoAsTokenProvider {
+    async fn get_token(&self) -> Result<(Token, TeeKeyPair)> {
+        let mut cached = self.token.lock().await;
+        match &*cached {
+            Some((token, _)) if !token.expires_within(self.token_refresh_margin) => {}
+            Some((token, _)) if token.check_valid().is_ok() => match self.attest().await {
+                Ok(attested) => *cached = Some(attested),
+                Err(e) => warn!("refresh the token before it expires failed: {e}"),
+            },
+            _ => *cached = Some(self.attest().await?),
+        }
+
+        Ok(cached.clone().expect("token must have been got"))
+    }
+
+    async fn refresh_token(&self) -> Result<(Token, TeeKeyPair)> {
+        let mut cached = self.token.lock().await;
+        let attested = self.attest().await?;
+        *cached = Some(attested.clone());
+        Ok(attested)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mock_kbs::{Endpoint, MockKbs};
+
+    use super::CoCoAsTokenProvider;
+    use crate::{
+        evidence_provider::MockedEvidenceProvider, token_provider::TokenProvider, KbsClientBuilder,
+        KbsClientCapabilities,
+    };
+
+    const CONTENT: &[u8] = b"test content";
+
+    #[tokio::test]
+    async fn test_passport_with_coco_as_token_provider() {
+        let issuer = MockKbs::start().await.unwrap();
+        let kbs = MockKbs::start().await.unwrap();
+        kbs.trust_token_issuer(&issuer.token_public_key_pem())
+            .unwrap();
+        kbs.add_resource("default/key/1", CONTENT);
+
+        let provider = CoCoAsTokenProvider::new(
+            Box::<MockedEvidenceProvider>::default(),
+            &issuer.url(),
+            reqwest::Client::new(),
+        )
+        .set_policy_ids(vec!["default".into()])
+        .add_initdata("version = \"0.1.0\"\nalgorithm = \"sha384\"\n[data]\n".into());
+
+        // The token is cached while valid
+        let (token, _) = provider.get_token().await.unwrap();
+        let (cached, _) = provider.get_token().await.unwrap();
+        assert_eq!(token.content, cached.content);
+        assert_eq!(issuer.requests(Endpoint::AsAttestation), 1);
+
+        // A refresh always attests again
+        provider.refresh_token().await.unwrap();
+        assert_eq!(issuer.requests(Endpoint::AsChallenge), 2);
+        assert_eq!(issuer.requests(Endpoint::AsAttestation), 2);
+
+        let mut client = KbsClientBuilder::with_token_provider(Box::new(provider), &kbs.url())
+            .build()
+            .unwrap();
+
+        // A token rejected by the KBS is refreshed
+        kbs.inject_error(Endpoint::Resource, 401);
+        let resource = client
+            .get_resource("kbs:///default/key/1".try_into().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resource, CONTENT);
+        assert_eq!(issuer.requests(Endpoint::AsAttestation), 3);
+        assert_eq!(kbs.requests(Endpoint::Attest), 0);
+
+        // A denied attestation fails the token
+        issuer.inject_error(Endpoint::AsAttestation, 403);
+        let provider = CoCoAsTokenProvider::new(
+            Box::<MockedEvidenceProvider>::default(),
+            &issuer.url(),
+            reqwest::Client::new(),
+        );
+        assert!(provider.get_token().await.is_err());
+    }
+}
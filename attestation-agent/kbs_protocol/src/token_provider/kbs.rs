@@ -0,0 +1,125 @@
+// Copyright (c) 2025 Alibaba Cloud
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! This is a token provider which attests to an attestation service with
+//! the KBS attestation protocol, e.g. the issuer KBS of a Trustee deployment
+//! in passport mode. The token it issues is then presented to the resource
+//! KBS.
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::{client::KbsClient, evidence_provider::EvidenceProvider, Result, TeeKeyPair, Token};
+
+use super::TokenProvider;
+
+pub struct KbsTokenProvider {
+    client: Mutex<KbsClient<Box<dyn EvidenceProvider>>>,
+}
+
+impl KbsTokenProvider {
+    /// A provider getting the tokens with the client of the attestation
+    /// service. The token is cached by the client and refreshed with a new
+    /// RCAR handshake once it expires within the refresh margin of the
+    /// client.
+    pub fn new(client: KbsClient<Box<dyn EvidenceProvider>>) -> Self {
+        Self {
+            client: Mutex::new(client),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenProvider for KbsTokenProvider {
+    async fn get_token(&self) -> Result<(Token, TeeKeyPair)> {
+        self.client.lock().await.get_token().await
+    }
+
+    async fn refresh_token(&self) -> Result<(Token, TeeKeyPair)> {
+        self.client.lock().await.refresh_token().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use mock_kbs::{Endpoint, MockKbs};
+
+    use super::KbsTokenProvider;
+    use crate::{
+        client::KbsClient, evidence_provider::MockedEvidenceProvider,
+        token_provider::TokenProvider, KbsClientBuilder, KbsClientCapabilities,
+    };
+
+    const CONTENT: &[u8] = b"test content";
+
+    async fn get_resource(client: &mut KbsClient<Box<dyn TokenProvider>>) -> Vec<u8> {
+        client
+            .get_resource("kbs:///default/key/1".try_into().unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_passport_with_kbs_token_provider() {
+        let issuer = MockKbs::start().await.unwrap();
+        let kbs = MockKbs::start().await.unwrap();
+        kbs.trust_token_issuer(&issuer.token_public_key_pem())
+            .unwrap();
+        kbs.add_resource("default/key/1", CONTENT);
+
+        let issuer_client = KbsClientBuilder::with_evidence_provider(
+            Box::<MockedEvidenceProvider>::default(),
+            &issuer.url(),
+        )
+        .build()
+        .unwrap();
+        let mut client = KbsClientBuilder::with_token_provider(
+            Box::new(KbsTokenProvider::new(issuer_client)),
+            &kbs.url(),
+        )
+        .build()
+        .unwrap();
+
+        // The token is cached while valid
+        for _ in 0..2 {
+            assert_eq!(get_resource(&mut client).await, CONTENT);
+        }
+        assert_eq!(issuer.requests(Endpoint::Attest), 1);
+        assert_eq!(kbs.requests(Endpoint::Attest), 0);
+
+        // A rejected token is refreshed
+        kbs.inject_error(Endpoint::Resource, 401);
+        issuer.set_token_validity(jwt_simple::prelude::Duration::from_secs(30));
+        assert_eq!(get_resource(&mut client).await, CONTENT);
+        assert_eq!(issuer.requests(Endpoint::Attest), 2);
+
+        // A short-lived token is not refreshed at every request, as the
+        // refresh margin is capped to a quarter of its lifetime
+        assert_eq!(get_resource(&mut client).await, CONTENT);
+        assert_eq!(issuer.requests(Endpoint::Attest), 2);
+
+        let mut client = KbsClientBuilder::with_token_provider(
+            Box::new(KbsTokenProvider::new(
+                KbsClientBuilder::with_evidence_provider(
+                    Box::<MockedEvidenceProvider>::default(),
+                    &issuer.url(),
+                )
+                .set_token_refresh_margin(Duration::from_secs(10))
+                .build()
+                .unwrap(),
+            )),
+            &kbs.url(),
+        )
+        .set_token_refresh_margin(Duration::from_secs(10))
+        .build()
+        .unwrap();
+        for _ in 0..2 {
+            assert_eq!(get_resource(&mut client).await, CONTENT);
+        }
+        assert_eq!(issuer.requests(Endpoint::Attest), 3);
+    }
+}
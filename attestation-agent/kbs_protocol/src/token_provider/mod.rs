@@ -11,6 +11,18 @@ pub mod aa;
 #[cfg(feature = "aa_ttrpc")]
 pub use aa::*;
 
+#[cfg(feature = "background_check")]
+pub mod kbs;
+#[cfg(feature = "background_check")]
+pub use kbs::*;
+
+#[cfg(feature = "background_check")]
+pub mod coco_as;
+#[cfg(feature = "background_check")]
+pub use coco_as::*;
+
+use std::time::Duration;
+
 use anyhow::*;
 use async_trait::async_trait;
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
@@ -22,12 +34,22 @@ use serde_json::Value;
 
 use crate::keypair::TeeKeyPair;
 
+/// The refresh margin of a token is at most its lifetime divided by this,
+/// so that a short-lived token is not refreshed at every request.
+const MAX_REFRESH_MARGIN_DIVISOR: u64 = 4;
+
 #[async_trait]
 pub trait TokenProvider: Send + Sync {
     /// Get token provisioned by Kbs and the tee pub key
     ///
     /// The returned value is a (Token, Private key) pair.
     async fn get_token(&self) -> crate::Result<(Token, TeeKeyPair)>;
+
+    /// Get a new token rather than a cached one, e.g. when the KBS rejected
+    /// the previous token. Providers caching the tokens should override it.
+    async fn refresh_token(&self) -> crate::Result<(Token, TeeKeyPair)> {
+        self.get_token().await
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -35,6 +57,7 @@ pub struct Token {
     pub content: String,
     exp: Option<UnixTimeStamp>,
     nbf: Option<UnixTimeStamp>,
+    iat: Option<UnixTimeStamp>,
 }
 
 impl Token {
@@ -49,6 +72,7 @@ impl Token {
             content: token,
             exp: claims.expires_at,
             nbf: claims.invalid_before,
+            iat: claims.issued_at,
         })
     }
 
@@ -68,4 +92,65 @@ impl Token {
 
         Ok(())
     }
+
+    /// Whether the token expires within the margin, s.t. it should be
+    /// refreshed before being used. The margin is capped to a quarter of
+    /// the lifetime of the token, from its `iat` or `nbf` claim to its `exp`
+    /// claim.
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        let Some(exp) = self.exp else {
+            return false;
+        };
+
+        let mut margin = margin.as_secs();
+        if let Some(start) = self.iat.or(self.nbf) {
+            let lifetime = exp.as_secs().saturating_sub(start.as_secs());
+            margin = margin.min(lifetime / MAX_REFRESH_MARGIN_DIVISOR);
+        }
+
+        let deadline = Clock::now_since_epoch() + jwt_simple::prelude::Duration::from_secs(margin);
+        exp < deadline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use jwt_simple::prelude::Clock;
+    use rstest::rstest;
+    use serde_json::json;
+
+    use super::Token;
+
+    fn now() -> u64 {
+        Clock::now_since_epoch().as_secs()
+    }
+
+    fn token(claims: serde_json::Value) -> Token {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(claims.to_string());
+        Token::new(format!("{header}.{claims}.signature")).unwrap()
+    }
+
+    #[rstest]
+    // A long-lived token is refreshed within the margin
+    #[case(json!({"iat": now(), "exp": now() + 3600}), 60, false)]
+    #[case(json!({"iat": now() - 3570, "exp": now() + 30}), 60, true)]
+    // The margin is capped to a quarter of the lifetime
+    #[case(json!({"iat": now(), "exp": now() + 30}), 60, false)]
+    #[case(json!({"iat": now() - 25, "exp": now() + 5}), 60, true)]
+    #[case(json!({"nbf": now(), "exp": now() + 30}), 60, false)]
+    // Without `iat` nor `nbf` the margin is not capped
+    #[case(json!({"exp": now() + 30}), 60, true)]
+    #[case(json!({}), 60, false)]
+    fn test_expires_within(
+        #[case] claims: serde_json::Value,
+        #[case] margin: u64,
+        #[case] expected: bool,
+    ) {
+        let token = token(claims);
+        assert_eq!(token.expires_within(Duration::from_secs(margin)), expected);
+    }
 }
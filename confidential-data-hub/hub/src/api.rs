@@ -28,6 +28,13 @@ pub trait DataHub {
     /// <https://github.com/confidential-containers/guest-components/blob/main/attestation-agent/docs/KBS_URI.md>
     async fn get_resource(&self, uri: String) -> Result<Vec<u8>>;
 
+    /// Get the resource due to the given KBS Resource URI and write it into
+    /// a new file at the given path, relative to the configured
+    /// `resource_output_dir`. The resource is streamed into the file range
+    /// by range if the KBS serves ranges, so it is not held in memory as a
+    /// whole. Returns the size of the resource.
+    async fn get_resource_to_file(&self, uri: String, path: String) -> Result<u64>;
+
     async fn secure_mount(&self, storage: Storage) -> Result<String>;
 
     /// Pull image of image url (reference), and place the merged layers in the `bundle_path/rootfs`
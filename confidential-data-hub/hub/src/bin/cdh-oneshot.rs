@@ -95,8 +95,14 @@ struct PullImageArgs {
 #[tokio::main]
 async fn main() {
     let args = Cli::parse();
-    let config = CdhConfig::new(args.config).expect("failed to initialize cdh config");
-    config.set_configuration_envs();
+    let config = CdhConfig::new(args.config)
+        .expect("failed to initialize cdh config")
+        .with_initdata_from_aa()
+        .await
+        .expect("failed to apply initdata to cdh config");
+    config
+        .set_configuration_envs()
+        .expect("failed to set cdh configuration");
 
     let cdh = Hub::new(config).await.expect("failed to start CDH");
 
@@ -73,6 +73,11 @@ struct GetResourceArgs {
     /// KBS Resource URI to the target resource
     #[arg(short, long)]
     resource_uri: String,
+
+    /// Path of the new file CDH writes the resource into, relative to its
+    /// `resource_output_dir`, instead of returning it
+    #[arg(short, long)]
+    output_path: Option<String>,
 }
 
 #[derive(Args)]
@@ -116,12 +121,22 @@ async fn main() {
             let mut client = GetResourceServiceClient::connect(args.socket)
                 .await
                 .expect("initialize client");
+            let output_path = arg.output_path.unwrap_or_default();
+            let to_file = !output_path.is_empty();
             let req = tonic::Request::new(GetResourceRequest {
                 resource_path: arg.resource_uri,
+                output_path,
             });
-            let res = client.get_resource(req).await.expect("request to CDH");
-            let res = STANDARD.encode(res.into_inner().resource);
-            println!("{res}");
+            let res = client
+                .get_resource(req)
+                .await
+                .expect("request to CDH")
+                .into_inner();
+            if to_file {
+                println!("{} bytes written", res.size);
+            } else {
+                println!("{}", STANDARD.encode(res.resource));
+            }
         }
         Operation::SecureMount(arg) => {
             let mut client = SecureMountServiceClient::connect(args.socket)
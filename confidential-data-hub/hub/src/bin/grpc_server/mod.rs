@@ -7,11 +7,11 @@ use anyhow::*;
 
 use confidential_data_hub::{
     storage::volume_type::Storage,
-    {hub::Hub, DataHub},
+    {hub::Hub, DataHub, ErrorKind},
 };
 use log::{debug, error};
 use std::{error::Error as _, net::SocketAddr, sync::Arc};
-use tonic::{transport::Server, Request, Response, Status};
+use tonic::{transport::Server, Code, Request, Response, Status};
 
 use crate::{
     format_error,
@@ -37,6 +37,16 @@ pub struct Cdh {
     inner: Arc<Hub>,
 }
 
+fn error_code(kind: ErrorKind) -> Code {
+    match kind {
+        ErrorKind::InvalidArgument => Code::InvalidArgument,
+        ErrorKind::NotFound => Code::NotFound,
+        ErrorKind::PermissionDenied => Code::PermissionDenied,
+        ErrorKind::Unavailable => Code::Unavailable,
+        ErrorKind::Internal => Code::Internal,
+    }
+}
+
 #[tonic::async_trait]
 impl SealedSecretService for Cdh {
     async fn unseal_secret(
@@ -53,7 +63,7 @@ impl SealedSecretService for Cdh {
             .map_err(|e| {
                 let detailed_error = format_error!(e);
                 error!("[gRPC CDH] Call CDH to unseal secret failed:\n{detailed_error}");
-                Status::internal(format!("[CDH] [ERROR]: {e}"))
+                Status::new(error_code(e.kind()), format!("[CDH] [ERROR]: {e}"))
             })?;
 
         debug!("[gRPC CDH] Unseal secret successfully!");
@@ -73,19 +83,35 @@ impl GetResourceService for Cdh {
         debug!("[gRPC CDH] get new GetResource request");
         let request = request.into_inner();
 
-        let resource = self
-            .inner
-            .get_resource(request.resource_path)
-            .await
-            .map_err(|e| {
-                let detailed_error = format_error!(e);
-                error!("[gRPC CDH] Call CDH to get resource failed:\n{detailed_error}");
-                Status::internal(format!("[CDH] [ERROR]: {e}"))
-            })?;
+        let map_error = |e: confidential_data_hub::Error| {
+            let detailed_error = format_error!(e);
+            error!("[gRPC CDH] Call CDH to get resource failed:\n{detailed_error}");
+            Status::new(error_code(e.kind()), format!("[CDH] [ERROR]: {e}"))
+        };
 
-        debug!("[gRPC CDH] Get resource successfully!");
+        let reply = if request.output_path.is_empty() {
+            let resource = self
+                .inner
+                .get_resource(request.resource_path)
+                .await
+                .map_err(map_error)?;
+            GetResourceResponse {
+                size: resource.len() as u64,
+                resource,
+            }
+        } else {
+            let size = self
+                .inner
+                .get_resource_to_file(request.resource_path, request.output_path)
+                .await
+                .map_err(map_error)?;
+            GetResourceResponse {
+                resource: vec![],
+                size,
+            }
+        };
 
-        let reply = GetResourceResponse { resource };
+        debug!("[gRPC CDH] Get resource successfully!");
 
         Result::Ok(Response::new(reply))
     }
@@ -109,7 +135,7 @@ impl SecureMountService for Cdh {
         let mount_path = self.inner.secure_mount(storage).await.map_err(|e| {
             let detailed_error = format_error!(e);
             error!("[gRPC CDH] Call CDH to secure mount failed:\n{detailed_error}");
-            Status::internal(format!("[CDH] [ERROR]: {e}"))
+            Status::new(error_code(e.kind()), format!("[CDH] [ERROR]: {e}"))
         })?;
 
         debug!("[gRPC CDH] Secure mount successfully!");
@@ -136,7 +162,7 @@ impl ImagePullService for Cdh {
             .map_err(|e| {
                 let detailed_error = format_error!(e);
                 error!("[gRPC CDH] Call CDH to pull image failed:\n{detailed_error}");
-                Status::internal(format!("[CDH] [ERROR]: {e}"))
+                Status::new(error_code(e.kind()), format!("[CDH] [ERROR]: {e}"))
             })?;
 
         debug!("[gRPC CDH] Pull image successfully!");
@@ -185,7 +211,7 @@ impl KeyProviderService for Cdh {
             .map_err(|e| {
                 let detailed_error = format_error!(e);
                 error!("[gRPC CDH] Call CDH to Unwrap Key failed:\n{detailed_error}");
-                Status::internal(format!("[CDH] [ERROR]: {e}"))
+                Status::new(error_code(e.kind()), format!("[CDH] [ERROR]: {e}"))
             })?;
 
         // Construct output structure and serialize it as the return value of gRPC
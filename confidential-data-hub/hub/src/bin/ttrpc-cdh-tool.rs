@@ -82,6 +82,11 @@ struct GetResourceArgs {
     /// KBS Resource URI to the target resource
     #[arg(short, long)]
     resource_uri: String,
+
+    /// Path of the new file CDH writes the resource into, relative to its
+    /// `resource_output_dir`, instead of returning it
+    #[arg(short, long)]
+    output_path: Option<String>,
 }
 
 #[derive(Args)]
@@ -144,16 +149,22 @@ async fn main() {
         }
         Operation::GetResource(arg) => {
             let client = GetResourceServiceClient::new(inner);
+            let output_path = arg.output_path.unwrap_or_default();
+            let to_file = !output_path.is_empty();
             let req = GetResourceRequest {
                 ResourcePath: arg.resource_uri,
+                OutputPath: output_path,
                 ..Default::default()
             };
             let res = client
                 .get_resource(context::with_timeout(args.timeout * NANO_PER_SECOND), &req)
                 .await
                 .expect("request to CDH");
-            let res = STANDARD.encode(res.Resource);
-            println!("{res}");
+            if to_file {
+                println!("{} bytes written", res.Size);
+            } else {
+                println!("{}", STANDARD.encode(res.Resource));
+            }
         }
         Operation::SecureMount(arg) => {
             let client = SecureMountServiceClient::new(inner);
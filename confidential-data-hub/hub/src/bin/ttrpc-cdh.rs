@@ -46,7 +46,7 @@ async fn main() -> Result<()> {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
     let cli = Cli::parse();
 
-    let config = CdhConfig::new(cli.config)?;
+    let config = CdhConfig::new(cli.config)?.with_initdata_from_aa().await?;
 
     let unix_socket_path = config
         .socket
@@ -9,7 +9,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use confidential_data_hub::{
     storage::volume_type::Storage,
-    {hub::Hub, CdhConfig, DataHub},
+    {hub::Hub, CdhConfig, DataHub, ErrorKind},
 };
 use log::{debug, error};
 use ttrpc::{asynchronous::TtrpcContext, Code, Error, Status};
@@ -33,6 +33,16 @@ pub struct Server {
     hub: Hub,
 }
 
+fn error_code(kind: ErrorKind) -> Code {
+    match kind {
+        ErrorKind::InvalidArgument => Code::INVALID_ARGUMENT,
+        ErrorKind::NotFound => Code::NOT_FOUND,
+        ErrorKind::PermissionDenied => Code::PERMISSION_DENIED,
+        ErrorKind::Unavailable => Code::UNAVAILABLE,
+        ErrorKind::Internal => Code::INTERNAL,
+    }
+}
+
 impl Server {
     pub async fn new(config: &CdhConfig) -> Result<Self> {
         let hub = Hub::new(config.clone()).await?;
@@ -53,7 +63,7 @@ impl SealedSecretService for Server {
             let detailed_error = format_error!(e);
             error!("[ttRPC CDH] UnsealSecret :\n{detailed_error}");
             let mut status = Status::new();
-            status.set_code(Code::INTERNAL);
+            status.set_code(error_code(e.kind()));
             status.set_message(format!("[CDH] [ERROR]: {e}"));
             Error::RpcStatus(status)
         })?;
@@ -73,18 +83,34 @@ impl GetResourceService for Server {
         req: GetResourceRequest,
     ) -> ::ttrpc::Result<GetResourceResponse> {
         debug!("[ttRPC CDH] get new GetResource request");
-        let resource = self.hub.get_resource(req.ResourcePath).await.map_err(|e| {
+        let map_error = |e: confidential_data_hub::Error| {
             let detailed_error = format_error!(e);
             error!("[ttRPC CDH] GetResource :\n{detailed_error}");
             let mut status = Status::new();
-            status.set_code(Code::INTERNAL);
+            status.set_code(error_code(e.kind()));
             status.set_message(format!("[CDH] [ERROR]: {e}"));
             Error::RpcStatus(status)
-        })?;
+        };
 
         let mut reply = GetResourceResponse::new();
-        reply.Resource = resource;
-        debug!("[ttRPC CDH] send back the resource");
+        if req.OutputPath.is_empty() {
+            let resource = self
+                .hub
+                .get_resource(req.ResourcePath)
+                .await
+                .map_err(map_error)?;
+            reply.Size = resource.len() as u64;
+            reply.Resource = resource;
+            debug!("[ttRPC CDH] send back the resource");
+        } else {
+            reply.Size = self
+                .hub
+                .get_resource_to_file(req.ResourcePath, req.OutputPath)
+                .await
+                .map_err(map_error)?;
+            debug!("[ttRPC CDH] resource written to file");
+        }
+
         Ok(reply)
     }
 }
@@ -119,7 +145,7 @@ impl KeyProviderService for Server {
             let detailed_error = format_error!(e);
             error!("[ttRPC CDH] UnWrapKey :\n{detailed_error}");
             let mut status = Status::new();
-            status.set_code(Code::INTERNAL);
+            status.set_code(error_code(e.kind()));
             status.set_message(format!("[CDH] [ERROR]: {e}"));
             Error::RpcStatus(status)
         })?;
@@ -165,7 +191,7 @@ impl SecureMountService for Server {
             let detailed_error = format_error!(e);
             error!("[ttRPC CDH] Secure Mount :\n{detailed_error}");
             let mut status = Status::new();
-            status.set_code(Code::INTERNAL);
+            status.set_code(error_code(e.kind()));
             status.set_message(format!("[CDH] [ERROR]: {e}"));
             Error::RpcStatus(status)
         })?;
@@ -193,7 +219,7 @@ impl ImagePullService for Server {
                 let detailed_error = format_error!(e);
                 error!("[ttRPC CDH] Pull Image :\n{detailed_error}");
                 let mut status = Status::new();
-                status.set_code(Code::INTERNAL);
+                status.set_code(error_code(e.kind()));
                 status.set_message(format!("[CDH] [ERROR]: {e}"));
                 Error::RpcStatus(status)
             })?;
@@ -6,10 +6,11 @@
 use std::{env, fs, path::Path};
 
 use anyhow::*;
-use attestation_agent::config::aa_kbc_params::AaKbcParams;
-use config::{Config, File};
+use attestation_agent::{config::aa_kbc_params::AaKbcParams, initdata::Initdata};
+use config::{Config, File, FileFormat, Source};
 use image_rs::config::ImageConfig;
-use log::{debug, info};
+use kbs_protocol::ProxyConfig;
+use log::{debug, info, warn};
 use serde::Deserialize;
 
 cfg_if::cfg_if! {
@@ -23,13 +24,53 @@ cfg_if::cfg_if! {
 const CDH_DEFAULT_IMAGE_AUTHENTICATED_REGISTRY_CREDENTIALS: &str =
     "CDH_DEFAULT_IMAGE_AUTHENTICATED_REGISTRY_CREDENTIALS";
 
-#[derive(Clone, Deserialize, Debug, PartialEq)]
+#[derive(Clone, Default, Deserialize, Debug, PartialEq)]
 pub struct KbsConfig {
     pub name: String,
 
     pub url: String,
 
     pub kbs_cert: Option<String>,
+
+    /// Client certificate (PEM format) to authenticate to the KBS with
+    /// mutual TLS. Must be given together with `client_key`.
+    #[serde(default)]
+    pub client_cert: Option<String>,
+
+    /// PKCS#8 private key (PEM format) of the client certificate.
+    #[serde(default)]
+    pub client_key: Option<String>,
+
+    /// SHA-256 hashes of the SubjectPublicKeyInfo of the KBS certificate, in
+    /// `sha256/<base64>` format.
+    #[serde(default)]
+    pub spki_pins: Vec<String>,
+
+    /// Pin the KBS certificate by the `kbs_spki_pins` entry of the initdata
+    /// bound by AA rather than by `spki_pins`.
+    #[serde(default)]
+    pub attested_tls: bool,
+
+    /// URLs of replicas of the KBS at `url`, tried in turn when it fails.
+    #[serde(default)]
+    pub replica_urls: Vec<String>,
+
+    /// Other KBSes to get resources from, by their address in the resource
+    /// URI, s.t. `kbs://<host>:<port>/<repository>/<type>/<tag>`. Each one is
+    /// given by the URLs of its replicas.
+    #[serde(default)]
+    pub additional_kbses: Vec<Vec<String>>,
+
+    /// Proxies to reach the KBSes.
+    #[serde(default)]
+    pub proxy: Option<ProxyConfig>,
+
+    /// Directory persisting the sessions with the KBSes, one file per KBS
+    /// URL, so they are resumed by a restarted CDH or the next `cdh-oneshot`
+    /// run while their tokens are valid. The sessions are sealed with a key
+    /// derived from the TEE by AA, and not persisted if AA cannot derive one.
+    #[serde(default)]
+    pub session_dir: Option<String>,
 }
 
 impl KbsConfig {
@@ -40,7 +81,7 @@ impl KbsConfig {
         Ok(KbsConfig {
             name: aa_kbc_params.kbc,
             url: aa_kbc_params.uri,
-            kbs_cert: None,
+            ..Default::default()
         })
     }
 }
@@ -65,6 +106,12 @@ pub struct CdhConfig {
     pub image: ImageConfig,
 
     pub socket: String,
+
+    /// The directory `GetResource` requests may write resources into. The
+    /// output path of a request is relative to it. If not given, resources
+    /// are only returned in the response.
+    #[serde(default)]
+    pub resource_output_dir: Option<String>,
 }
 
 impl CdhConfig {
@@ -77,7 +124,7 @@ impl CdhConfig {
             None
         });
 
-        let mut config = match config_path {
+        let config = match config_path {
             Some(path) => {
                 info!("Use configuration file {path}");
                 if !Path::new(&path).exists() {
@@ -93,34 +140,91 @@ impl CdhConfig {
                     credentials: Vec::new(),
                     socket: DEFAULT_CDH_SOCKET_ADDR.into(),
                     image: ImageConfig::from_kernel_cmdline(),
+                    resource_output_dir: None,
                 }
             }
         };
 
-        if let std::result::Result::Ok(env) =
-            env::var(CDH_DEFAULT_IMAGE_AUTHENTICATED_REGISTRY_CREDENTIALS)
+        config.extend_from_env_and_kernel_cmdline()
+    }
+
+    /// Create the `CdhConfig` from the `cdh.toml` section of the given
+    /// initdata TOML. `None` is returned if the initdata has no such section.
+    ///
+    /// The initdata is supposed to be the one bound to the platform by AA,
+    /// so the configuration it carries takes precedence over the
+    /// configuration file.
+    pub fn from_initdata(initdata_toml: &str) -> Result<Option<Self>> {
+        let (initdata, _) =
+            Initdata::parse_and_get_digest(initdata_toml).context("invalid initdata")?;
+        let Some(cdh_config) = initdata.cdh_config()? else {
+            return Ok(None);
+        };
+
+        let config = Self::from_source(File::from_str(cdh_config, FileFormat::Toml))
+            .context("invalid `cdh.toml` in initdata")?;
+        config.extend_from_env_and_kernel_cmdline().map(Some)
+    }
+
+    /// Replace the configuration with the `cdh.toml` section of the initdata
+    /// bound by AA, if any, see [`CdhConfig::from_initdata`]. If AA is not
+    /// running, is not reachable or does not support `GetInitdata`, the
+    /// configuration is kept. The initdata is got from AA over ttRPC, so it is
+    /// only applied with the `ttrpc` feature.
+    pub async fn with_initdata_from_aa(self) -> Result<Self> {
+        #[cfg(feature = "ttrpc")]
         {
-            info!("Read authenticated registry credentials URI from env: {env}");
-            config.image.authenticated_registry_credentials_uri = Some(env);
+            let initdata = match crate::hub::get_initdata_from_aa().await {
+                std::result::Result::Ok(initdata) => initdata,
+                Err(e) => {
+                    warn!("Failed to get initdata from AA, starting without it: {e:?}");
+                    None
+                }
+            };
+
+            if let Some(initdata) = initdata {
+                if let Some(config) = Self::from_initdata(&initdata)? {
+                    info!("Use configuration from initdata");
+                    return Ok(config);
+                }
+            }
         }
 
-        config.extend_credentials_from_kernel_cmdline()?;
-        Ok(config)
+        Ok(self)
     }
 
     /// Load `CdhConfig` from a configuration file. Supported formats are all formats supported by the
     /// `config` crate.
     fn from_file(config_path: &str) -> Result<Self> {
+        Self::from_source(File::with_name(config_path))
+    }
+
+    fn from_source<T>(source: T) -> Result<Self>
+    where
+        T: Source + Send + Sync + 'static,
+    {
         let c = Config::builder()
             .set_default("socket", DEFAULT_CDH_SOCKET_ADDR)?
             .set_default("kbc.url", "")?
-            .add_source(File::with_name(config_path))
+            .add_source(source)
             .build()?;
 
         let res = c.try_deserialize().context("invalid config")?;
         Ok(res)
     }
 
+    fn extend_from_env_and_kernel_cmdline(mut self) -> Result<Self> {
+        if let std::result::Result::Ok(env) =
+            env::var(CDH_DEFAULT_IMAGE_AUTHENTICATED_REGISTRY_CREDENTIALS)
+        {
+            info!("Read authenticated registry credentials URI from env: {env}");
+            self.image.authenticated_registry_credentials_uri = Some(env);
+        }
+
+        self.extend_credentials_from_kernel_cmdline()?;
+        Ok(self)
+    }
+
     /// all the resource ids can be from the kernel commandline in the following format:
     /// ```shell
     /// cdh.kbs_resources=<resource id 1>::<target path 1>,<resource id 2>::<target path 2>...
@@ -158,7 +262,7 @@ impl CdhConfig {
 }
 
 impl CdhConfig {
-    pub fn set_configuration_envs(&self) {
+    pub fn set_configuration_envs(&self) -> Result<()> {
         if env::var("AA_KBC_PARAMS").is_err() {
             env::set_var(
                 "AA_KBC_PARAMS",
@@ -169,6 +273,49 @@ impl CdhConfig {
         if let Some(kbs_cert) = &self.kbc.kbs_cert {
             env::set_var("KBS_CERT", kbs_cert);
         }
+
+        match (&self.kbc.client_cert, &self.kbc.client_key) {
+            (Some(client_cert), Some(client_key)) => {
+                // Not put into the environment, to keep the private key
+                // from the child processes
+                kms::plugins::kbs::set_client_identity(client_cert.clone(), client_key.clone());
+            }
+            (None, None) => {}
+            _ => bail!("`kbc.client_cert` and `kbc.client_key` must be given together"),
+        }
+
+        if !self.kbc.spki_pins.is_empty() {
+            env::set_var("KBS_SPKI_PINS", self.kbc.spki_pins.join(","));
+        }
+
+        if self.kbc.attested_tls {
+            env::set_var("KBS_ATTESTED_TLS", "true");
+        }
+
+        if !self.kbc.replica_urls.is_empty() {
+            env::set_var("KBS_REPLICA_URLS", self.kbc.replica_urls.join(","));
+        }
+
+        if !self.kbc.additional_kbses.is_empty() {
+            let additional_kbses: Vec<String> = self
+                .kbc
+                .additional_kbses
+                .iter()
+                .map(|urls| urls.join(","))
+                .collect();
+            env::set_var("KBS_ADDITIONAL_URLS", additional_kbses.join(";"));
+        }
+
+        if let Some(session_dir) = &self.kbc.session_dir {
+            env::set_var("KBS_SESSION_DIR", session_dir);
+        }
+
+        #[cfg(feature = "kbs")]
+        if let Some(proxy) = &self.kbc.proxy {
+            kms::plugins::kbs::set_proxy(proxy.clone());
+        }
+
+        Ok(())
     }
 }
 
@@ -223,6 +370,7 @@ https_proxy = "http://127.0.0.1:8080"
                 name: "offline_fs_kbc".to_string(),
                 url: "".to_string(),
                 kbs_cert: Some("".to_string()),
+                ..Default::default()
             },
             credentials: vec![],
             image: ImageConfig {
@@ -256,6 +404,7 @@ https_proxy = "http://127.0.0.1:8080"
                 ..Default::default()
             },
             socket: "unix:///run/confidential-containers/cdh.sock".to_string(),
+            resource_output_dir: None,
         })
     )]
     #[case(
@@ -281,6 +430,49 @@ name = "offline_fs_kbc"
             name: "offline_fs_kbc".to_string(),
             url: "".to_string(),
             kbs_cert: None,
+            ..Default::default()
+        },
+        credentials: vec![],
+        image: ImageConfig {
+                sigstore_config_uri: None,
+                image_security_policy_uri: None,
+                authenticated_registry_credentials_uri: None,
+                image_pull_proxy: None,
+                ..Default::default()
+        },
+        socket: DEFAULT_CDH_SOCKET_ADDR.to_string(),
+        resource_output_dir: None,
+    })
+    )]
+    #[case(
+        r#"
+[kbc]
+name = "cc_kbc"
+url = "http://kbs-eu-1:8080"
+replica_urls = ["http://kbs-eu-2:8080"]
+additional_kbses = [["http://kbs-us-1:8080", "http://kbs-us-2:8080"]]
+session_dir = "/run/confidential-containers/cdh/kbs-sessions"
+
+[kbc.proxy]
+https_proxy = "http://127.0.0.1:3128"
+no_proxy = "kbs-eu-1,kbs-eu-2"
+"#,
+    Some(CdhConfig {
+        kbc: KbsConfig {
+            name: "cc_kbc".to_string(),
+            url: "http://kbs-eu-1:8080".to_string(),
+            replica_urls: vec!["http://kbs-eu-2:8080".to_string()],
+            additional_kbses: vec![vec![
+                "http://kbs-us-1:8080".to_string(),
+                "http://kbs-us-2:8080".to_string(),
+            ]],
+            proxy: Some(kbs_protocol::ProxyConfig {
+                https_proxy: Some("http://127.0.0.1:3128".to_string()),
+                http_proxy: None,
+                no_proxy: Some("kbs-eu-1,kbs-eu-2".to_string()),
+            }),
+            session_dir: Some("/run/confidential-containers/cdh/kbs-sessions".to_string()),
+            ..Default::default()
         },
         credentials: vec![],
         image: ImageConfig {
@@ -291,6 +483,7 @@ name = "offline_fs_kbc"
                 ..Default::default()
         },
         socket: DEFAULT_CDH_SOCKET_ADDR.to_string(),
+        resource_output_dir: None,
     })
     )]
     #[case(
@@ -306,6 +499,7 @@ some_undefined_field = "unknown value"
             name: "offline_fs_kbc".to_string(),
             url: "".to_string(),
             kbs_cert: None,
+            ..Default::default()
         },
         credentials: vec![],
         image: ImageConfig {
@@ -316,6 +510,7 @@ some_undefined_field = "unknown value"
                 ..Default::default()
         },
         socket: DEFAULT_CDH_SOCKET_ADDR.to_string(),
+        resource_output_dir: None,
     })
     )]
     #[serial]
@@ -345,9 +540,11 @@ some_undefined_field = "unknown value"
                 name: "offline_fs_kbc".into(),
                 url: "".into(),
                 kbs_cert: None,
+                ..Default::default()
             },
             credentials: Vec::new(),
             socket: DEFAULT_CDH_SOCKET_ADDR.into(),
+            resource_output_dir: None,
             image: ImageConfig::from_kernel_cmdline(),
         };
         assert_eq!(config, expected);
@@ -411,4 +608,57 @@ authenticated_registry_credentials_uri = "kbs:///default/auth/1"
             Some("kbs:///default/auth/1".into())
         );
     }
+
+    #[test]
+    #[serial]
+    fn test_config_from_initdata() {
+        let initdata = r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"cdh.toml" = '''
+[kbc]
+name = "cc_kbc"
+url = "https://127.0.0.1:8080"
+attested_tls = true
+
+[[credentials]]
+resource_uri = "kbs:///default/key/1"
+path = "/run/confidential-containers/cdh/key"
+'''
+"#;
+        let config = CdhConfig::from_initdata(initdata)
+            .expect("Must be successful")
+            .expect("cdh.toml is given");
+        assert_eq!(
+            config.kbc,
+            KbsConfig {
+                name: "cc_kbc".into(),
+                url: "https://127.0.0.1:8080".into(),
+                attested_tls: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(config.socket, DEFAULT_CDH_SOCKET_ADDR);
+        assert_eq!(config.credentials.len(), 1);
+
+        let initdata = r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"#;
+        assert!(CdhConfig::from_initdata(initdata).unwrap().is_none());
+
+        let initdata = r#"
+version = "0.1.0"
+algorithm = "sha384"
+
+[data]
+"cdh.toml" = "socket = \"unix:///run/cdh.sock\""
+"#;
+        // `kbc` is missing
+        assert!(CdhConfig::from_initdata(initdata).is_err());
+    }
 }
@@ -22,6 +22,15 @@ pub enum Error {
         source: kms::Error,
     },
 
+    #[error("Invalid resource output path: {0}")]
+    InvalidResourceOutputPath(String),
+
+    #[error("Write resource to file failed")]
+    WriteResource {
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error("Decrypt Image (UnwrapKey) failed")]
     ImageDecryption(#[from] image::Error),
 
@@ -46,6 +55,58 @@ pub enum Error {
         source: anyhow::Error,
     },
 }
+
+/// Coarse classification of [`Error`]s, mapped to the status codes of the
+/// CDH RPC services.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    InvalidArgument,
+    NotFound,
+    PermissionDenied,
+    Unavailable,
+    Internal,
+}
+
+impl Error {
+    /// Classify the error of any CDH API. Errors caused by a KBS request,
+    /// e.g. when unsealing a secret or unwrapping an image key, are
+    /// classified by the response of the KBS.
+    pub fn kind(&self) -> ErrorKind {
+        if matches!(
+            self,
+            Error::InvalidResourceOutputPath(_)
+                | Error::UnsealSecret(
+                    secret::SecretError::VersionError | secret::SecretError::ParseFailed(_)
+                )
+                | Error::SecureMount(storage::Error::StorageTypeNotRecognized(_))
+        ) {
+            return ErrorKind::InvalidArgument;
+        }
+
+        #[cfg(feature = "kbs")]
+        {
+            let mut source: Option<&(dyn std::error::Error + 'static)> = Some(self);
+            while let Some(error) = source {
+                if let Some(e) = error
+                    .downcast_ref::<kms::Error>()
+                    .and_then(kms::Error::kbs_error)
+                {
+                    return match e.http_status() {
+                        Some(404) => ErrorKind::NotFound,
+                        Some(401 | 403) => ErrorKind::PermissionDenied,
+                        Some(502..=504) => ErrorKind::Unavailable,
+                        None if e.is_connection_error() => ErrorKind::Unavailable,
+                        _ => ErrorKind::Internal,
+                    };
+                }
+                source = error.source();
+            }
+        }
+
+        ErrorKind::Internal
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,4 +132,95 @@ mod tests {
         let brief_message = error.to_string();
         assert_eq!(brief_message, expected);
     }
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(
+            Error::InvalidResourceOutputPath("relative/path".into()).kind(),
+            ErrorKind::InvalidArgument
+        );
+        assert_eq!(
+            Error::GetResource {
+                source: kms::Error::KbsClientError("details".into())
+            }
+            .kind(),
+            ErrorKind::Internal
+        );
+        assert_eq!(
+            Error::UnsealSecret(secret::SecretError::VersionError).kind(),
+            ErrorKind::InvalidArgument
+        );
+        assert_eq!(
+            Error::SecureMount(storage::Error::StorageTypeNotRecognized(
+                strum::ParseError::VariantNotFound
+            ))
+            .kind(),
+            ErrorKind::InvalidArgument
+        );
+    }
+
+    #[cfg(feature = "kbs")]
+    #[test]
+    fn test_error_kind_of_kbs_error() {
+        use crate::secret::layout::vault::VaultError;
+
+        let kbs_error = |status| kms::Error::KbsRequest {
+            context: "get resource failed".into(),
+            source: kbs_protocol::Error::ResourceNotFound(kbs_protocol::KbsErrorResponse {
+                status,
+                error_type: None,
+                detail: "details".into(),
+            }),
+        };
+
+        assert_eq!(
+            Error::GetResource {
+                source: kbs_error(404)
+            }
+            .kind(),
+            ErrorKind::NotFound
+        );
+
+        // The KBS error is found through the sources of other errors
+        let error = Error::UnsealSecret(secret::SecretError::UnsealVaultFailed(
+            VaultError::KmsError {
+                source: kbs_error(404),
+                context: "get secret",
+            },
+        ));
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+    }
+
+    #[cfg(feature = "kbs")]
+    #[tokio::test]
+    async fn test_error_kind_with_mock_kbs() {
+        use kbs_protocol::evidence_provider::{EvidenceProvider, MockedEvidenceProvider};
+        use kms::plugins::kbs::{CcKbc, Kbc};
+        use mock_kbs::{Endpoint, MockKbs};
+
+        let kbs = MockKbs::start().await.unwrap();
+        kbs.add_resource("default/key/1", b"test content");
+        let mut kbc = CcKbc::with_evidence_provider(&kbs.url(), None, || async {
+            Ok(Box::<MockedEvidenceProvider>::default() as Box<dyn EvidenceProvider>)
+        })
+        .await
+        .unwrap();
+
+        // The errors of the KBS responses, as returned by the secret getter
+        let source = kbc
+            .get_resource("kbs:///default/key/2".try_into().unwrap())
+            .await
+            .unwrap_err();
+        assert_eq!(Error::GetResource { source }.kind(), ErrorKind::NotFound);
+
+        kbs.inject_error(Endpoint::Resource, 403);
+        let source = kbc
+            .get_resource("kbs:///default/key/1".try_into().unwrap())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            Error::GetResource { source }.kind(),
+            ErrorKind::PermissionDenied
+        );
+    }
 }
@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
 
 use async_trait::async_trait;
 use image_rs::{builder::ClientBuilder, config::ImageConfig, image::ImageClient};
@@ -13,7 +16,9 @@ use tokio::sync::{Mutex, OnceCell};
 
 #[cfg(feature = "ttrpc")]
 use protos::ttrpc::aa::attestation_agent::{
-    ExtendRuntimeMeasurementRequest, RuntimeMeasurementResult,
+    ExtendRuntimeMeasurementRequest, ExtendRuntimeMeasurementResponse,
+    ExtendRuntimeMeasurementsRequest, GetInitdataRequest, PullImageEvent, RuntimeMeasurementEvent,
+    RuntimeMeasurementResult,
 };
 #[cfg(feature = "ttrpc")]
 use protos::ttrpc::aa::attestation_agent_ttrpc::AttestationAgentServiceClient;
@@ -32,7 +37,9 @@ pub struct Hub {
 
 impl Hub {
     pub async fn new(config: CdhConfig) -> Result<Self> {
-        config.set_configuration_envs();
+        config
+            .set_configuration_envs()
+            .map_err(|e| Error::InitializationFailed(format!("{e:#}")))?;
         let credentials = config
             .credentials
             .iter()
@@ -84,6 +91,36 @@ impl DataHub for Hub {
         Ok(res)
     }
 
+    async fn get_resource_to_file(&self, uri: String, path: String) -> Result<u64> {
+        info!("get resource to file called: {uri}");
+        let dir = self.config.resource_output_dir.as_deref().ok_or_else(|| {
+            Error::InvalidResourceOutputPath(format!(
+                "{path}: no resource output directory is configured"
+            ))
+        })?;
+        let path = resource_output_path(Path::new(dir), &path)?;
+
+        let client = kms::plugins::kbs::KbcClient::new()
+            .await
+            .map_err(|e| Error::KbsClient { source: e })?;
+        let mut file = create_resource_file(&path).await?;
+        let written: Result<u64> = async {
+            let size = client
+                .get_secret_to_writer(&uri, &mut file)
+                .await
+                .map_err(|e| Error::GetResource { source: e })?;
+            file.sync_all()
+                .await
+                .map_err(|source| Error::WriteResource { source })?;
+            Ok(size)
+        }
+        .await;
+        if written.is_err() {
+            let _ = tokio::fs::remove_file(&path).await;
+        }
+        written
+    }
+
     async fn secure_mount(&self, storage: Storage) -> Result<String> {
         info!("secure mount called");
         let res = storage.mount().await?;
@@ -112,10 +149,7 @@ impl DataHub for Hub {
         #[cfg(feature = "ttrpc")]
         {
             use anyhow::anyhow;
-            use ttrpc::context::with_timeout;
-
-            // 10 seconds in nanoseconds
-            const EXTEND_RUNTIME_MEASUREMENT_TIMEOUT: i64 = 10 * 1000 * 1000 * 1000;
+            use ttrpc::{context::with_timeout, proto::Code};
 
             let aa_client = self
                 .aa_client
@@ -129,23 +163,30 @@ impl DataHub for Hub {
 
             info!("Extend image pull event via AA's runtime measurement API...");
             debug!("The pulled image information: {image_info:?}");
-            // The event follows definition in
-            // https://github.com/confidential-containers/trustee/blob/main/kbs/docs/confidential-containers-eventlog.md#confidential-containers-event-spec
-            let req = ExtendRuntimeMeasurementRequest {
-                Domain: "github.com/confidential-containers".to_string(),
-                Operation: "PullImage".to_string(),
-                Content: format!(
-                    r#"{{"image":"{image_url}", "digest":"{}"}}"#,
-                    image_info.manifest_digest
-                ),
+            let mut event = RuntimeMeasurementEvent::new();
+            event.set_PullImage(PullImageEvent {
+                Image: image_url.to_string(),
+                Digest: image_info.manifest_digest.clone(),
+                ..Default::default()
+            });
+            let req = ExtendRuntimeMeasurementsRequest {
+                Events: vec![event],
                 ..Default::default()
             };
-            let res = aa_client
-                .extend_runtime_measurement(with_timeout(EXTEND_RUNTIME_MEASUREMENT_TIMEOUT), &req)
+            let res = match aa_client
+                .extend_runtime_measurements(with_timeout(EXTEND_RUNTIME_MEASUREMENT_TIMEOUT), &req)
                 .await
-                .map_err(|e| Error::AttestationAgentClientError {
-                    source: anyhow!("failed to extend runtime measurement: {e:?}"),
-                })?;
+            {
+                Err(ttrpc::Error::RpcStatus(status)) if status.code() == Code::UNIMPLEMENTED => {
+                    debug!("AA does not support ExtendRuntimeMeasurements, fall back to ExtendRuntimeMeasurement");
+                    extend_pull_image_event(aa_client, image_url, &image_info.manifest_digest)
+                        .await
+                }
+                res => res,
+            }
+            .map_err(|e| Error::AttestationAgentClientError {
+                source: anyhow!("failed to extend runtime measurement: {e:?}"),
+            })?;
 
             match res
                 .Result
@@ -177,6 +218,16 @@ async fn initialize_image_client(config: ImageConfig) -> Result<Mutex<ImageClien
     Ok(Mutex::new(image_client))
 }
 
+/// The socket file of a `unix://` ttRPC address.
+#[cfg(feature = "ttrpc")]
+fn unix_socket_file(address: &str) -> Option<&Path> {
+    address.strip_prefix("unix://").map(Path::new)
+}
+
+/// 10 seconds in nanoseconds
+#[cfg(feature = "ttrpc")]
+const EXTEND_RUNTIME_MEASUREMENT_TIMEOUT: i64 = 10 * 1000 * 1000 * 1000;
+
 #[cfg(feature = "ttrpc")]
 async fn initialize_aa_client() -> Result<Option<AttestationAgentServiceClient>> {
     use anyhow::anyhow;
@@ -184,7 +235,9 @@ async fn initialize_aa_client() -> Result<Option<AttestationAgentServiceClient>>
     const AA_SOCKET_FILE: &str =
         "unix:///run/confidential-containers/attestation-agent/attestation-agent.sock";
 
-    if !Path::new(AA_SOCKET_FILE).exists() {
+    // The address itself is not a path, so it never exists
+    let socket_file = unix_socket_file(AA_SOCKET_FILE).expect("must be a unix socket");
+    if !socket_file.exists() {
         return Ok(None);
     }
 
@@ -196,3 +249,233 @@ async fn initialize_aa_client() -> Result<Option<AttestationAgentServiceClient>>
     let client = AttestationAgentServiceClient::new(c);
     Ok(Some(client))
 }
+
+/// Extend the image pull event with the single event API of AA, for the
+/// versions of AA without `ExtendRuntimeMeasurements`.
+#[cfg(feature = "ttrpc")]
+async fn extend_pull_image_event(
+    aa_client: &AttestationAgentServiceClient,
+    image_url: &str,
+    digest: &str,
+) -> ttrpc::Result<ExtendRuntimeMeasurementResponse> {
+    use serde::Serialize;
+    use ttrpc::context::with_timeout;
+
+    /// The content of the event, the same as AA records for the typed event.
+    #[derive(Serialize)]
+    struct PullImage<'a> {
+        image: &'a str,
+        digest: &'a str,
+    }
+
+    let content = serde_json::to_string(&PullImage {
+        image: image_url,
+        digest,
+    })
+    .expect("serialize pull image event");
+    let req = ExtendRuntimeMeasurementRequest {
+        Domain: "github.com/confidential-containers".to_string(),
+        Operation: "PullImage".to_string(),
+        Content: content,
+        ..Default::default()
+    };
+    aa_client
+        .extend_runtime_measurement(with_timeout(EXTEND_RUNTIME_MEASUREMENT_TIMEOUT), &req)
+        .await
+}
+
+/// Get the initdata TOML bound to the platform by AA. `None` is returned if
+/// AA is not running or no initdata is given to AA.
+#[cfg(feature = "ttrpc")]
+pub async fn get_initdata_from_aa() -> Result<Option<String>> {
+    use anyhow::anyhow;
+    use ttrpc::context::with_timeout;
+
+    // 10 seconds in nanoseconds
+    const GET_INITDATA_TIMEOUT: i64 = 10 * 1000 * 1000 * 1000;
+
+    let Some(aa_client) = initialize_aa_client().await? else {
+        return Ok(None);
+    };
+
+    let res = aa_client
+        .get_initdata(
+            with_timeout(GET_INITDATA_TIMEOUT),
+            &GetInitdataRequest::new(),
+        )
+        .await
+        .map_err(|e| Error::AttestationAgentClientError {
+            source: anyhow!("failed to get initdata: {e:?}"),
+        })?;
+
+    if res.Toml.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(res.Toml))
+}
+
+/// Resolve the output path of a `GetResource` request under `dir`. The path
+/// must be relative and must not contain `.` or `..`, and the directories it
+/// goes through must not be symlinks.
+fn resource_output_path(dir: &Path, path: &str) -> Result<PathBuf> {
+    let invalid = |reason: String| Error::InvalidResourceOutputPath(format!("{path}: {reason}"));
+    let relative = Path::new(path);
+    if relative.as_os_str().is_empty()
+        || !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(invalid(
+            "must be a relative path without `.` or `..`".into(),
+        ));
+    }
+
+    let mut output = dir.to_path_buf();
+    let mut components = relative.components().peekable();
+    while let Some(component) = components.next() {
+        output.push(component);
+        if components.peek().is_none() {
+            break;
+        }
+
+        // `symlink_metadata` does not follow symlinks, so a symlink is not
+        // a directory here
+        let metadata = std::fs::symlink_metadata(&output)
+            .map_err(|e| invalid(format!("{}: {e}", output.display())))?;
+        if !metadata.is_dir() {
+            return Err(invalid(format!("{} is not a directory", output.display())));
+        }
+    }
+
+    Ok(output)
+}
+
+/// Create a new file readable only by CDH to stream a resource into. The
+/// file must not exist and must not be a symlink.
+async fn create_resource_file(path: &Path) -> Result<tokio::fs::File> {
+    tokio::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .custom_flags(nix::libc::O_NOFOLLOW)
+        .open(path)
+        .await
+        .map_err(|e| Error::InvalidResourceOutputPath(format!("{}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    use tokio::io::AsyncWriteExt;
+
+    use super::{create_resource_file, resource_output_path};
+
+    #[test]
+    fn test_resource_output_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        symlink(dir.path().join("sub"), dir.path().join("link")).unwrap();
+
+        assert_eq!(
+            resource_output_path(dir.path(), "sub/resource").unwrap(),
+            dir.path().join("sub/resource")
+        );
+        assert_eq!(
+            resource_output_path(dir.path(), "resource").unwrap(),
+            dir.path().join("resource")
+        );
+
+        for path in [
+            "",
+            "/etc/passwd",
+            "../resource",
+            "sub/../../resource",
+            "./resource",
+            "link/resource",
+            "missing/resource",
+        ] {
+            assert!(resource_output_path(dir.path(), path).is_err(), "{path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_resource_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("resource");
+        let mut file = create_resource_file(&path).await.unwrap();
+        file.write_all(b"secret").await.unwrap();
+        file.flush().await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"secret");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // An existing file is not overwritten
+        assert!(create_resource_file(&path).await.is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"secret");
+
+        // A symlink is not followed
+        let link = dir.path().join("link");
+        let target = dir.path().join("target");
+        symlink(&target, &link).unwrap();
+        assert!(create_resource_file(&link).await.is_err());
+        assert!(!target.exists());
+    }
+
+    /// The resource is streamed into the file range by range, so it is never
+    /// held in memory as a whole.
+    #[cfg(feature = "kbs")]
+    #[tokio::test]
+    async fn test_stream_resource_file_with_mock_kbs() {
+        use kbs_protocol::{
+            evidence_provider::{EvidenceProvider, MockedEvidenceProvider},
+            RESOURCE_CHUNK_SIZE,
+        };
+        use kms::plugins::kbs::{CcKbc, Kbc};
+        use mock_kbs::{Endpoint, MockKbs};
+
+        let kbs = MockKbs::start().await.unwrap();
+        let content: Vec<u8> = (0..2 * RESOURCE_CHUNK_SIZE + 1)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        kbs.add_resource("default/model/1", &content);
+        let mut kbc = CcKbc::with_evidence_provider(&kbs.url(), None, || async {
+            Ok(Box::<MockedEvidenceProvider>::default() as Box<dyn EvidenceProvider>)
+        })
+        .await
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model");
+        let mut file = create_resource_file(&path).await.unwrap();
+        let size = kbc
+            .get_resource_to_writer("kbs:///default/model/1".try_into().unwrap(), &mut file)
+            .await
+            .unwrap();
+        drop(file);
+        assert_eq!(size, content.len() as u64);
+        assert_eq!(kbs.requests(Endpoint::Resource), 3);
+        assert_eq!(std::fs::read(&path).unwrap(), content);
+    }
+
+    #[cfg(feature = "ttrpc")]
+    #[test]
+    fn test_unix_socket_file() {
+        use std::path::Path;
+
+        use super::unix_socket_file;
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("attestation-agent.sock");
+        std::fs::write(&socket, b"").unwrap();
+
+        let address = format!("unix://{}", socket.display());
+        assert!(!Path::new(&address).exists());
+        let socket_file = unix_socket_file(&address).unwrap();
+        assert_eq!(socket_file, socket);
+        assert!(socket_file.exists());
+
+        assert!(unix_socket_file("127.0.0.1:50002").is_none());
+    }
+}
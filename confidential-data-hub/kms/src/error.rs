@@ -16,6 +16,14 @@ pub enum Error {
     #[error("Kbs client error: {0}")]
     KbsClientError(String),
 
+    #[cfg(feature = "kbs")]
+    #[error("KBS request failed: {context}")]
+    KbsRequest {
+        context: String,
+        #[source]
+        source: kbs_protocol::Error,
+    },
+
     #[cfg(feature = "ehsm")]
     #[error("eHSM-KMS client error: {0}")]
     EhsmKmsError(String),
@@ -23,3 +31,14 @@ pub enum Error {
     #[error("Unsupported provider: {0}")]
     UnsupportedProvider(String),
 }
+
+impl Error {
+    /// The error of the KBS protocol client which caused the error, if any.
+    #[cfg(feature = "kbs")]
+    pub fn kbs_error(&self) -> Option<&kbs_protocol::Error> {
+        match self {
+            Error::KbsRequest { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
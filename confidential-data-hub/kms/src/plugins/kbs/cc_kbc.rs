@@ -3,35 +3,120 @@
 // SPDX-License-Identifier: Apache-2.0
 //
 
-use std::env;
+use std::{env, future::Future, path::Path};
 
 use async_trait::async_trait;
 use kbs_protocol::{
     client::KbsClient as KbsProtocolClient,
-    token_provider::{AATokenProvider, TokenProvider},
-    KbsClientCapabilities, ResourceUri,
+    evidence_provider::{AAEvidenceProvider, EvidenceProvider},
+    token_provider::AATokenProvider,
+    KbsClientCapabilities, KbsClientPool, ResourceUri, SessionStore,
 };
 use log::{info, warn};
+use tokio::io::AsyncWrite;
 
 use crate::{Error, Result};
 
 use super::Kbc;
 
+/// Replicas of the KBS given by `AA_KBC_PARAMS`, separated by commas.
+const KBS_REPLICA_URLS: &str = "KBS_REPLICA_URLS";
+
+/// Other KBSes to get resources from by their address, separated by
+/// semicolons. Each one is given by the URLs of its replicas, separated by
+/// commas.
+const KBS_ADDITIONAL_URLS: &str = "KBS_ADDITIONAL_URLS";
+
+/// Directory persisting the sessions with the KBSes, one file per KBS URL.
+const KBS_SESSION_DIR: &str = "KBS_SESSION_DIR";
+
 pub struct CcKbc {
-    client: KbsProtocolClient<Box<dyn TokenProvider>>,
+    client: KbsClientPool<Box<dyn EvidenceProvider>>,
 }
 
 impl CcKbc {
     pub async fn new(kbs_host_url: &str) -> Result<Self> {
-        let token_provider = AATokenProvider::new().await.map_err(|e| {
-            Error::KbsClientError(format!("create AA token provider failed: {e:?}"))
-        })?;
-        let client = kbs_protocol::KbsClientBuilder::with_token_provider(
-            Box::new(token_provider),
-            kbs_host_url,
-        );
-
-        let client = match env::var("KBS_CERT") {
+        let initdata = match env::var("KBS_ATTESTED_TLS").as_deref() {
+            Ok("true") => {
+                let token_provider = AATokenProvider::new().await.map_err(|e| {
+                    Error::KbsClientError(format!("create AA token provider failed: {e:?}"))
+                })?;
+                let initdata = token_provider.get_initdata().await.map_err(|e| {
+                    Error::KbsClientError(format!("get initdata for attested TLS failed: {e:?}"))
+                })?;
+                Some(initdata.ok_or_else(|| {
+                    Error::KbsClientError("attested TLS requires initdata".into())
+                })?)
+            }
+            _ => None,
+        };
+
+        Self::with_evidence_provider(kbs_host_url, initdata.as_deref(), || async {
+            let evidence_provider = AAEvidenceProvider::new().await.map_err(|e| {
+                Error::KbsClientError(format!("create AA evidence provider failed: {e:?}"))
+            })?;
+            Ok(Box::new(evidence_provider) as Box<dyn EvidenceProvider>)
+        })
+        .await
+    }
+
+    /// Create the clients of the KBS at the URL, of its replicas and of the
+    /// additional KBSes. Each replica attests its own session with the
+    /// evidence of its own evidence provider, created by
+    /// `new_evidence_provider`.
+    pub async fn with_evidence_provider<F, Fut>(
+        kbs_host_url: &str,
+        initdata: Option<&str>,
+        new_evidence_provider: F,
+    ) -> Result<Self>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<Box<dyn EvidenceProvider>>>,
+    {
+        let mut default_kbs = vec![kbs_host_url.to_string()];
+        if let Ok(urls) = env::var(KBS_REPLICA_URLS) {
+            default_kbs.extend(split_urls(&urls));
+        }
+
+        let additional_kbses = env::var(KBS_ADDITIONAL_URLS).unwrap_or_default();
+        let additional_kbses = additional_kbses
+            .split(';')
+            .map(split_urls)
+            .filter(|urls| !urls.is_empty());
+
+        let mut client = KbsClientPool::new();
+        for urls in [default_kbs].into_iter().chain(additional_kbses) {
+            let mut replicas = Vec::new();
+            for url in urls {
+                let evidence_provider = new_evidence_provider().await?;
+                let session_store = session_store(&url, evidence_provider.as_ref()).await;
+                replicas.push(Self::build_client(
+                    &url,
+                    evidence_provider,
+                    session_store,
+                    initdata,
+                )?);
+            }
+            client
+                .add_kbs(replicas)
+                .map_err(|e| Error::KbsClientError(format!("create kbs client failed: {e:?}")))?;
+        }
+
+        Ok(Self { client })
+    }
+
+    /// Build the client of a KBS replica, which attests to it with the
+    /// background check mode of the KBS protocol.
+    fn build_client(
+        kbs_host_url: &str,
+        evidence_provider: Box<dyn EvidenceProvider>,
+        session_store: Option<SessionStore>,
+        initdata: Option<&str>,
+    ) -> Result<KbsProtocolClient<Box<dyn EvidenceProvider>>> {
+        let client =
+            kbs_protocol::KbsClientBuilder::with_evidence_provider(evidence_provider, kbs_host_url);
+
+        let mut client = match env::var("KBS_CERT") {
             Ok(cert_pem) => {
                 info!("Use KBS public key cert");
                 client.add_kbs_cert(&cert_pem)
@@ -42,14 +127,66 @@ impl CcKbc {
             }
         };
 
-        let client = client
-            .build()
-            .map_err(|e| Error::KbsClientError(format!("create kbs client failed: {e:?}")))?;
+        if let Some((cert_pem, key_pem)) = super::client_identity() {
+            info!("Use KBS client certificate");
+            client = client.set_client_identity(&cert_pem, &key_pem);
+        }
 
-        Ok(Self { client })
+        if let Some(proxy) = super::proxy() {
+            client = client.set_proxy(proxy);
+        }
+
+        if let Ok(pins) = env::var("KBS_SPKI_PINS") {
+            for pin in pins.split(',').filter(|pin| !pin.is_empty()) {
+                client = client.add_kbs_spki_pin(pin);
+            }
+        }
+
+        if let Some(session_store) = session_store {
+            client = client.set_session_store(session_store);
+        }
+
+        if let Some(initdata) = initdata {
+            info!("Use attested TLS with KBS {kbs_host_url}");
+            client = client
+                .add_initdata(initdata.to_string())
+                .set_attested_tls(true);
+        }
+
+        client
+            .build()
+            .map_err(|e| Error::KbsClientError(format!("create kbs client failed: {e:?}")))
     }
 }
 
+/// The store persisting the session with the KBS at the URL, if the
+/// sessions are persisted. The session is sealed with a key derived from
+/// the TEE by the evidence provider, and not persisted if it cannot derive
+/// one.
+async fn session_store(
+    kbs_host_url: &str,
+    evidence_provider: &dyn EvidenceProvider,
+) -> Option<SessionStore> {
+    let session_dir = env::var(KBS_SESSION_DIR).ok()?;
+
+    let file_name: String = kbs_host_url
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    SessionStore::with_evidence_provider(Path::new(&session_dir).join(file_name), evidence_provider)
+        .await
+        .inspect_err(|e| warn!("session with KBS {kbs_host_url} is not persisted: {e}"))
+        .ok()
+}
+
+fn split_urls(urls: &str) -> Vec<String> {
+    urls.split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 #[async_trait]
 impl Kbc for CcKbc {
     async fn get_resource(&mut self, rid: ResourceUri) -> Result<Vec<u8>> {
@@ -57,7 +194,99 @@ impl Kbc for CcKbc {
             .client
             .get_resource(rid)
             .await
-            .map_err(|e| Error::KbsClientError(format!("get resource failed: {e:?}")))?;
+            .map_err(|source| Error::KbsRequest {
+                context: "get resource failed".into(),
+                source,
+            })?;
         Ok(secret)
     }
+
+    async fn get_resource_to_writer(
+        &mut self,
+        rid: ResourceUri,
+        writer: &mut (dyn AsyncWrite + Unpin + Send),
+    ) -> Result<u64> {
+        let size = self
+            .client
+            .get_resource_to_writer(rid, writer)
+            .await
+            .map_err(|source| Error::KbsRequest {
+                context: "stream resource failed".into(),
+                source,
+            })?;
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kbs_protocol::{
+        evidence_provider::{EvidenceProvider, MockedEvidenceProvider},
+        RESOURCE_CHUNK_SIZE,
+    };
+    use mock_kbs::{Endpoint, MockKbs};
+
+    use super::{CcKbc, Kbc};
+
+    const CONTENT: &[u8] = b"test content";
+
+    #[tokio::test]
+    async fn test_cc_kbc_with_mock_kbs() {
+        let kbs = MockKbs::start().await.unwrap();
+        kbs.add_resource("default/key/1", CONTENT);
+
+        let mut kbc = CcKbc::with_evidence_provider(&kbs.url(), None, || async {
+            Ok(Box::<MockedEvidenceProvider>::default() as Box<dyn EvidenceProvider>)
+        })
+        .await
+        .unwrap();
+
+        for _ in 0..2 {
+            let resource = kbc
+                .get_resource("kbs:///default/key/1".try_into().unwrap())
+                .await
+                .unwrap();
+            assert_eq!(resource, CONTENT);
+        }
+        assert_eq!(kbs.requests(Endpoint::Attest), 1);
+
+        // The response of the KBS is kept as source of the error
+        let error = kbc
+            .get_resource("kbs:///default/key/2".try_into().unwrap())
+            .await
+            .unwrap_err();
+        let kbs_error = error.kbs_error().expect("KBS request error");
+        assert_eq!(kbs_error.http_status(), Some(404));
+
+        kbs.inject_error(Endpoint::Resource, 403);
+        let error = kbc
+            .get_resource("kbs:///default/key/1".try_into().unwrap())
+            .await
+            .unwrap_err();
+        assert_eq!(error.kbs_error().and_then(|e| e.http_status()), Some(403));
+    }
+
+    #[tokio::test]
+    async fn test_cc_kbc_streams_resource_with_mock_kbs() {
+        let kbs = MockKbs::start().await.unwrap();
+        let content: Vec<u8> = (0..RESOURCE_CHUNK_SIZE + 1)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        kbs.add_resource("default/model/1", &content);
+
+        let mut kbc = CcKbc::with_evidence_provider(&kbs.url(), None, || async {
+            Ok(Box::<MockedEvidenceProvider>::default() as Box<dyn EvidenceProvider>)
+        })
+        .await
+        .unwrap();
+
+        let mut streamed = Vec::new();
+        let size = kbc
+            .get_resource_to_writer("kbs:///default/model/1".try_into().unwrap(), &mut streamed)
+            .await
+            .unwrap();
+        assert_eq!(size, content.len() as u64);
+        assert_eq!(streamed, content);
+        assert_eq!(kbs.requests(Endpoint::Resource), 2);
+    }
 }
@@ -7,18 +7,26 @@
 
 #[cfg(feature = "kbs")]
 mod cc_kbc;
+#[cfg(feature = "kbs")]
+pub use cc_kbc::CcKbc;
 
 #[cfg(feature = "sev")]
 mod sev;
 
 mod offline_fs;
 
-use std::{env, sync::Arc, sync::LazyLock};
+use std::{
+    env,
+    sync::{Arc, LazyLock, Mutex as StdMutex},
+};
 
 use async_trait::async_trait;
 use attestation_agent::config::aa_kbc_params::AaKbcParams;
 pub use resource_uri::ResourceUri;
-use tokio::sync::Mutex;
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    sync::Mutex,
+};
 
 use crate::{Annotations, Error, Getter, Result};
 
@@ -52,9 +60,57 @@ impl RealClient {
 static KBS_CLIENT: LazyLock<Arc<Mutex<Option<RealClient>>>> =
     LazyLock::new(|| Arc::new(Mutex::new(None)));
 
+/// The certificate (chain) and PKCS#8 private key, both in PEM format, with
+/// which the KBS clients authenticate with mutual TLS. Unlike the other KBS
+/// configurations they are kept in memory rather than in the environment,
+/// which would expose the private key to child processes.
+static KBS_CLIENT_IDENTITY: StdMutex<Option<(String, String)>> = StdMutex::new(None);
+
+/// Set the client certificate and private key with which the KBS clients
+/// created afterwards authenticate with mutual TLS.
+pub fn set_client_identity(cert_pem: String, key_pem: String) {
+    *KBS_CLIENT_IDENTITY.lock().expect("poisoned lock") = Some((cert_pem, key_pem));
+}
+
+#[cfg(feature = "kbs")]
+fn client_identity() -> Option<(String, String)> {
+    KBS_CLIENT_IDENTITY.lock().expect("poisoned lock").clone()
+}
+
+/// The proxies with which the KBS clients reach the KBSes.
+#[cfg(feature = "kbs")]
+static KBS_PROXY: StdMutex<Option<kbs_protocol::ProxyConfig>> = StdMutex::new(None);
+
+/// Set the proxies of the KBS clients created afterwards.
+#[cfg(feature = "kbs")]
+pub fn set_proxy(proxy: kbs_protocol::ProxyConfig) {
+    *KBS_PROXY.lock().expect("poisoned lock") = Some(proxy);
+}
+
+#[cfg(feature = "kbs")]
+fn proxy() -> Option<kbs_protocol::ProxyConfig> {
+    KBS_PROXY.lock().expect("poisoned lock").clone()
+}
+
 #[async_trait]
 pub trait Kbc: Send + Sync {
     async fn get_resource(&mut self, _rid: ResourceUri) -> Result<Vec<u8>>;
+
+    /// Write the resource into the writer, returning its size. KBCs able to
+    /// stream the resource should override this, as by default the whole
+    /// resource is got into memory first.
+    async fn get_resource_to_writer(
+        &mut self,
+        rid: ResourceUri,
+        writer: &mut (dyn AsyncWrite + Unpin + Send),
+    ) -> Result<u64> {
+        let resource = self.get_resource(rid).await?;
+        writer
+            .write_all(&resource)
+            .await
+            .map_err(|e| Error::KbsClientError(format!("write resource failed: {e}")))?;
+        Ok(resource.len() as u64)
+    }
 }
 
 /// A fake KbcClient to carry the [`Getter`] semantics. The real `new()`
@@ -92,6 +148,34 @@ impl Getter for KbcClient {
 }
 
 impl KbcClient {
+    /// Write the resource of the given KBS Resource URI into the writer
+    /// rather than returning it. Returns the size of the resource.
+    pub async fn get_secret_to_writer(
+        &self,
+        name: &str,
+        writer: &mut (dyn AsyncWrite + Unpin + Send),
+    ) -> Result<u64> {
+        let resource_uri = ResourceUri::try_from(name)
+            .map_err(|_| Error::KbsClientError(format!("illegal kbs resource uri: {name}")))?;
+        let real_client = KBS_CLIENT.clone();
+        let mut client = real_client.lock().await;
+
+        if client.is_none() {
+            let c = RealClient::new().await?;
+            *client = Some(c);
+        }
+
+        let client = client.as_mut().expect("must be initialized");
+
+        match client {
+            #[cfg(feature = "kbs")]
+            RealClient::Cc(c) => c.get_resource_to_writer(resource_uri, writer).await,
+            #[cfg(feature = "sev")]
+            RealClient::Sev(c) => c.get_resource_to_writer(resource_uri, writer).await,
+            RealClient::OfflineFs(c) => c.get_resource_to_writer(resource_uri, writer).await,
+        }
+    }
+
     pub async fn new() -> Result<Self> {
         let client = KBS_CLIENT.clone();
         let mut client = client.lock().await;
@@ -129,3 +129,30 @@ impl SecureChannel {
         Ok(res)
     }
 }
+
+#[cfg(all(test, feature = "keywrap-native", not(feature = "keywrap-ttrpc")))]
+mod tests {
+    use mock_kbs::{Endpoint, MockKbs};
+
+    use super::SecureChannel;
+
+    const CONTENT: &[u8] = b"test content";
+
+    #[tokio::test]
+    async fn test_native_secure_channel_with_mock_kbs() {
+        let kbs = MockKbs::start().await.unwrap();
+        kbs.add_resource("default/key/1", CONTENT);
+        let work_dir = tempfile::tempdir().unwrap();
+
+        let channel = SecureChannel::new("cc_kbc", &kbs.url(), work_dir.path()).unwrap();
+        for _ in 0..2 {
+            let resource = channel.get_resource("kbs:///default/key/1").await.unwrap();
+            assert_eq!(resource, CONTENT);
+        }
+
+        // The resource is kept in the storage path once fetched
+        assert_eq!(kbs.requests(Endpoint::Resource), 1);
+
+        assert!(channel.get_resource("kbs:///default/key/2").await.is_err());
+    }
+}
@@ -19,6 +19,9 @@ pub struct GetEvidenceResponse {
 pub struct GetTokenRequest {
     #[prost(string, tag = "1")]
     pub token_type: ::prost::alloc::string::String,
+    /// Get a new token rather than the cached one, e.g. once the KBS rejected it.
+    #[prost(bool, tag = "2")]
+    pub refresh: bool,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct GetTokenResponse {
@@ -47,6 +50,81 @@ pub struct ExtendRuntimeMeasurementResponse {
     #[prost(enumeration = "RuntimeMeasurementResult", tag = "1")]
     pub result: i32,
 }
+/// A runtime measurement event with free-form domain, operation and content.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GenericEvent {
+    #[prost(string, tag = "1")]
+    pub domain: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub operation: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub content: ::prost::alloc::string::String,
+}
+/// An image is pulled.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct PullImageEvent {
+    /// Reference of the image, e.g. `docker.io/library/busybox:latest`.
+    #[prost(string, tag = "1")]
+    pub image: ::prost::alloc::string::String,
+    /// Digest of the image manifest, e.g. `sha256:...`.
+    #[prost(string, tag = "2")]
+    pub digest: ::prost::alloc::string::String,
+}
+/// A secure storage is mounted.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SecureMountEvent {
+    /// Type of the storage, e.g. `block-device`.
+    #[prost(string, tag = "1")]
+    pub volume_type: ::prost::alloc::string::String,
+    /// Absolute path where the storage is mounted.
+    #[prost(string, tag = "2")]
+    pub mount_point: ::prost::alloc::string::String,
+}
+/// A sealed secret is unsealed.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UnsealSecretEvent {
+    /// Id of the key used to unseal the secret.
+    #[prost(string, tag = "1")]
+    pub key_id: ::prost::alloc::string::String,
+}
+/// A confidential resource is got from the KBS.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetResourceEvent {
+    /// The resource uri, e.g. `kbs:///default/key/1`.
+    #[prost(string, tag = "1")]
+    pub resource_uri: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RuntimeMeasurementEvent {
+    /// Which PCR will be extended with the hash of this entry.
+    #[prost(uint64, optional, tag = "6")]
+    pub register_index: ::core::option::Option<u64>,
+    #[prost(oneof = "runtime_measurement_event::Event", tags = "1, 2, 3, 4, 5")]
+    pub event: ::core::option::Option<runtime_measurement_event::Event>,
+}
+/// Nested message and enum types in `RuntimeMeasurementEvent`.
+pub mod runtime_measurement_event {
+    #[derive(Clone, PartialEq, Eq, Hash, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag = "1")]
+        Generic(super::GenericEvent),
+        #[prost(message, tag = "2")]
+        PullImage(super::PullImageEvent),
+        #[prost(message, tag = "3")]
+        SecureMount(super::SecureMountEvent),
+        #[prost(message, tag = "4")]
+        UnsealSecret(super::UnsealSecretEvent),
+        #[prost(message, tag = "5")]
+        GetResource(super::GetResourceEvent),
+    }
+}
+/// Extend several events atomically. Either all of them or none of them
+/// are recorded in the eventlog.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExtendRuntimeMeasurementsRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<RuntimeMeasurementEvent>,
+}
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct BindInitDataRequest {
     #[prost(bytes = "vec", tag = "1")]
@@ -54,6 +132,20 @@ pub struct BindInitDataRequest {
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct BindInitDataResponse {}
+/// Derive a key bound to the TEE and the guest measurement, e.g. to seal
+/// data persisted by a guest component. Every caller must use its own
+/// purpose, as the key is the same for the same purpose.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct DeriveSealingKeyRequest {
+    #[prost(string, tag = "1")]
+    pub purpose: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct DeriveSealingKeyResponse {
+    /// 32 bytes key.
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: ::prost::alloc::vec::Vec<u8>,
+}
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct GetTeeTypeRequest {}
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
@@ -61,6 +153,144 @@ pub struct GetTeeTypeResponse {
     #[prost(string, tag = "1")]
     pub tee: ::prost::alloc::string::String,
 }
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetEventlogRequest {}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetEventlogResponse {
+    /// The CCEL followed by the AAEL entries, in TCG2 crypto agile format.
+    #[prost(bytes = "vec", tag = "1")]
+    pub eventlog: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct VerifyEventlogRequest {}
+/// The replay result of a single CC measurement register.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RegisterReplayResult {
+    /// The CC measurement register index recorded in the eventlog.
+    #[prost(uint32, tag = "1")]
+    pub register_index: u32,
+    /// The register value calculated from the eventlog.
+    #[prost(bytes = "vec", tag = "2")]
+    pub replayed: ::prost::alloc::vec::Vec<u8>,
+    /// The register value read from the platform. Not set if no PCR maps to the register.
+    #[prost(bytes = "vec", optional, tag = "3")]
+    pub actual: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bool, tag = "4")]
+    pub matched: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VerifyEventlogResponse {
+    /// Whether all the readable registers match the replayed values. False
+    /// if no register could be read to compare.
+    #[prost(bool, tag = "1")]
+    pub verified: bool,
+    /// The hash algorithm used to replay the eventlog, e.g. sha384.
+    #[prost(string, tag = "2")]
+    pub hash_algorithm: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub registers: ::prost::alloc::vec::Vec<RegisterReplayResult>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetInitdataRequest {}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetInitdataResponse {
+    /// The initdata TOML given to AA at launch. Empty if no initdata is given.
+    #[prost(string, tag = "1")]
+    pub toml: ::prost::alloc::string::String,
+    /// The digest of the initdata TOML bound to the platform.
+    #[prost(bytes = "vec", tag = "2")]
+    pub digest: ::prost::alloc::vec::Vec<u8>,
+    /// The hash algorithm of the initdata, e.g. sha384.
+    #[prost(string, tag = "3")]
+    pub algorithm: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateConfigRequest {
+    /// The new AA configuration in TOML format, the same as the configuration file.
+    #[prost(string, tag = "1")]
+    pub config: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateConfigResponse {}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetPlatformReportRequest {}
+/// A path checked while probing a platform, e.g. a device node, a sysfs or a configfs entry.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct CheckedPath {
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub exists: bool,
+}
+/// The diagnosis of a compiled in attester.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlatformDiagnosis {
+    /// The TEE of the attester, e.g. tdx.
+    #[prost(string, tag = "1")]
+    pub tee: ::prost::alloc::string::String,
+    /// Whether the platform was probed. Primary platforms after the detected one are not probed.
+    #[prost(bool, tag = "2")]
+    pub probed: bool,
+    #[prost(bool, tag = "3")]
+    pub detected: bool,
+    #[prost(message, repeated, tag = "4")]
+    pub checked: ::prost::alloc::vec::Vec<CheckedPath>,
+    /// Why the platform was not detected. Not set if it was.
+    #[prost(string, optional, tag = "5")]
+    pub failure: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPlatformReportResponse {
+    /// The detected primary platform.
+    #[prost(string, tag = "1")]
+    pub tee: ::prost::alloc::string::String,
+    /// Whether no primary platform was detected, so the sample attester is used when no primary
+    /// attester is configured.
+    #[prost(bool, tag = "2")]
+    pub sample_fallback: bool,
+    /// The primary platforms, in the order they are probed.
+    #[prost(message, repeated, tag = "3")]
+    pub platforms: ::prost::alloc::vec::Vec<PlatformDiagnosis>,
+    /// The additional devices, in the order they are probed.
+    #[prost(message, repeated, tag = "4")]
+    pub devices: ::prost::alloc::vec::Vec<PlatformDiagnosis>,
+    /// The attesters which are not compiled in.
+    #[prost(string, repeated, tag = "5")]
+    pub not_compiled_in: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The primary attester in use, which may be configured instead of detected.
+    #[prost(string, tag = "6")]
+    pub primary_tee: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetEvidenceMetricsRequest {}
+/// Metrics of the evidence generations of an attester.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct EvidenceMetrics {
+    /// The TEE of the attester, e.g. tdx.
+    #[prost(string, tag = "1")]
+    pub tee: ::prost::alloc::string::String,
+    /// Number of evidence generations, i.e. calls to the hardware.
+    #[prost(uint64, tag = "2")]
+    pub generations: u64,
+    /// Number of failed evidence generations.
+    #[prost(uint64, tag = "3")]
+    pub failures: u64,
+    /// Number of evidence returned from the cache.
+    #[prost(uint64, tag = "4")]
+    pub cache_hits: u64,
+    /// Total latency of the evidence generations, in milliseconds.
+    #[prost(uint64, tag = "5")]
+    pub total_latency_ms: u64,
+    /// Maximum latency of an evidence generation, in milliseconds.
+    #[prost(uint64, tag = "6")]
+    pub max_latency_ms: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetEvidenceMetricsResponse {
+    /// The primary attester and the additional ones.
+    #[prost(message, repeated, tag = "1")]
+    pub attesters: ::prost::alloc::vec::Vec<EvidenceMetrics>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum RuntimeMeasurementResult {
@@ -299,6 +529,35 @@ pub mod attestation_agent_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn extend_runtime_measurements(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExtendRuntimeMeasurementsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExtendRuntimeMeasurementResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/attestation_agent.AttestationAgentService/ExtendRuntimeMeasurements",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "attestation_agent.AttestationAgentService",
+                        "ExtendRuntimeMeasurements",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn bind_init_data(
             &mut self,
             request: impl tonic::IntoRequest<super::BindInitDataRequest>,
@@ -328,6 +587,35 @@ pub mod attestation_agent_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn derive_sealing_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeriveSealingKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeriveSealingKeyResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/attestation_agent.AttestationAgentService/DeriveSealingKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "attestation_agent.AttestationAgentService",
+                        "DeriveSealingKey",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn get_tee_type(
             &mut self,
             request: impl tonic::IntoRequest<super::GetTeeTypeRequest>,
@@ -357,63 +645,293 @@ pub mod attestation_agent_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod attestation_agent_service_server {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value,
-    )]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with AttestationAgentServiceServer.
-    #[async_trait]
-    pub trait AttestationAgentService: std::marker::Send + std::marker::Sync + 'static {
-        async fn get_evidence(
-            &self,
-            request: tonic::Request<super::GetEvidenceRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::GetEvidenceResponse>,
-            tonic::Status,
-        >;
-        async fn get_additional_evidence(
-            &self,
-            request: tonic::Request<super::GetAdditionalEvidenceRequest>,
+        pub async fn get_eventlog(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetEventlogRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetEvidenceResponse>,
+            tonic::Response<super::GetEventlogResponse>,
             tonic::Status,
-        >;
-        async fn get_token(
-            &self,
-            request: tonic::Request<super::GetTokenRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/attestation_agent.AttestationAgentService/GetEventlog",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "attestation_agent.AttestationAgentService",
+                        "GetEventlog",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn verify_eventlog(
+            &mut self,
+            request: impl tonic::IntoRequest<super::VerifyEventlogRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetTokenResponse>,
+            tonic::Response<super::VerifyEventlogResponse>,
             tonic::Status,
-        >;
-        async fn extend_runtime_measurement(
-            &self,
-            request: tonic::Request<super::ExtendRuntimeMeasurementRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/attestation_agent.AttestationAgentService/VerifyEventlog",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "attestation_agent.AttestationAgentService",
+                        "VerifyEventlog",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_initdata(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetInitdataRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ExtendRuntimeMeasurementResponse>,
+            tonic::Response<super::GetInitdataResponse>,
             tonic::Status,
-        >;
-        async fn bind_init_data(
-            &self,
-            request: tonic::Request<super::BindInitDataRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/attestation_agent.AttestationAgentService/GetInitdata",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "attestation_agent.AttestationAgentService",
+                        "GetInitdata",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn update_config(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateConfigRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::BindInitDataResponse>,
+            tonic::Response<super::UpdateConfigResponse>,
             tonic::Status,
-        >;
-        async fn get_tee_type(
-            &self,
-            request: tonic::Request<super::GetTeeTypeRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/attestation_agent.AttestationAgentService/UpdateConfig",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "attestation_agent.AttestationAgentService",
+                        "UpdateConfig",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_platform_report(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPlatformReportRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetTeeTypeResponse>,
+            tonic::Response<super::GetPlatformReportResponse>,
             tonic::Status,
-        >;
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/attestation_agent.AttestationAgentService/GetPlatformReport",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "attestation_agent.AttestationAgentService",
+                        "GetPlatformReport",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_evidence_metrics(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetEvidenceMetricsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEvidenceMetricsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/attestation_agent.AttestationAgentService/GetEvidenceMetrics",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "attestation_agent.AttestationAgentService",
+                        "GetEvidenceMetrics",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod attestation_agent_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with AttestationAgentServiceServer.
+    #[async_trait]
+    pub trait AttestationAgentService: std::marker::Send + std::marker::Sync + 'static {
+        async fn get_evidence(
+            &self,
+            request: tonic::Request<super::GetEvidenceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEvidenceResponse>,
+            tonic::Status,
+        >;
+        async fn get_additional_evidence(
+            &self,
+            request: tonic::Request<super::GetAdditionalEvidenceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEvidenceResponse>,
+            tonic::Status,
+        >;
+        async fn get_token(
+            &self,
+            request: tonic::Request<super::GetTokenRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetTokenResponse>,
+            tonic::Status,
+        >;
+        async fn extend_runtime_measurement(
+            &self,
+            request: tonic::Request<super::ExtendRuntimeMeasurementRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExtendRuntimeMeasurementResponse>,
+            tonic::Status,
+        >;
+        async fn extend_runtime_measurements(
+            &self,
+            request: tonic::Request<super::ExtendRuntimeMeasurementsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExtendRuntimeMeasurementResponse>,
+            tonic::Status,
+        >;
+        async fn bind_init_data(
+            &self,
+            request: tonic::Request<super::BindInitDataRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BindInitDataResponse>,
+            tonic::Status,
+        >;
+        async fn derive_sealing_key(
+            &self,
+            request: tonic::Request<super::DeriveSealingKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeriveSealingKeyResponse>,
+            tonic::Status,
+        >;
+        async fn get_tee_type(
+            &self,
+            request: tonic::Request<super::GetTeeTypeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetTeeTypeResponse>,
+            tonic::Status,
+        >;
+        async fn get_eventlog(
+            &self,
+            request: tonic::Request<super::GetEventlogRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEventlogResponse>,
+            tonic::Status,
+        >;
+        async fn verify_eventlog(
+            &self,
+            request: tonic::Request<super::VerifyEventlogRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::VerifyEventlogResponse>,
+            tonic::Status,
+        >;
+        async fn get_initdata(
+            &self,
+            request: tonic::Request<super::GetInitdataRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetInitdataResponse>,
+            tonic::Status,
+        >;
+        async fn update_config(
+            &self,
+            request: tonic::Request<super::UpdateConfigRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateConfigResponse>,
+            tonic::Status,
+        >;
+        async fn get_platform_report(
+            &self,
+            request: tonic::Request<super::GetPlatformReportRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPlatformReportResponse>,
+            tonic::Status,
+        >;
+        async fn get_evidence_metrics(
+            &self,
+            request: tonic::Request<super::GetEvidenceMetricsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEvidenceMetricsResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct AttestationAgentServiceServer<T> {
@@ -691,6 +1209,60 @@ pub mod attestation_agent_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/attestation_agent.AttestationAgentService/ExtendRuntimeMeasurements" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExtendRuntimeMeasurementsSvc<T: AttestationAgentService>(
+                        pub Arc<T>,
+                    );
+                    impl<
+                        T: AttestationAgentService,
+                    > tonic::server::UnaryService<
+                        super::ExtendRuntimeMeasurementsRequest,
+                    > for ExtendRuntimeMeasurementsSvc<T> {
+                        type Response = super::ExtendRuntimeMeasurementResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::ExtendRuntimeMeasurementsRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AttestationAgentService>::extend_runtime_measurements(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ExtendRuntimeMeasurementsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/attestation_agent.AttestationAgentService/BindInitData" => {
                     #[allow(non_camel_case_types)]
                     struct BindInitDataSvc<T: AttestationAgentService>(pub Arc<T>);
@@ -740,6 +1312,55 @@ pub mod attestation_agent_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/attestation_agent.AttestationAgentService/DeriveSealingKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeriveSealingKeySvc<T: AttestationAgentService>(pub Arc<T>);
+                    impl<
+                        T: AttestationAgentService,
+                    > tonic::server::UnaryService<super::DeriveSealingKeyRequest>
+                    for DeriveSealingKeySvc<T> {
+                        type Response = super::DeriveSealingKeyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeriveSealingKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AttestationAgentService>::derive_sealing_key(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeriveSealingKeySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/attestation_agent.AttestationAgentService/GetTeeType" => {
                     #[allow(non_camel_case_types)]
                     struct GetTeeTypeSvc<T: AttestationAgentService>(pub Arc<T>);
@@ -789,6 +1410,300 @@ pub mod attestation_agent_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/attestation_agent.AttestationAgentService/GetEventlog" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetEventlogSvc<T: AttestationAgentService>(pub Arc<T>);
+                    impl<
+                        T: AttestationAgentService,
+                    > tonic::server::UnaryService<super::GetEventlogRequest>
+                    for GetEventlogSvc<T> {
+                        type Response = super::GetEventlogResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetEventlogRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AttestationAgentService>::get_eventlog(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetEventlogSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/attestation_agent.AttestationAgentService/VerifyEventlog" => {
+                    #[allow(non_camel_case_types)]
+                    struct VerifyEventlogSvc<T: AttestationAgentService>(pub Arc<T>);
+                    impl<
+                        T: AttestationAgentService,
+                    > tonic::server::UnaryService<super::VerifyEventlogRequest>
+                    for VerifyEventlogSvc<T> {
+                        type Response = super::VerifyEventlogResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::VerifyEventlogRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AttestationAgentService>::verify_eventlog(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = VerifyEventlogSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/attestation_agent.AttestationAgentService/GetInitdata" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetInitdataSvc<T: AttestationAgentService>(pub Arc<T>);
+                    impl<
+                        T: AttestationAgentService,
+                    > tonic::server::UnaryService<super::GetInitdataRequest>
+                    for GetInitdataSvc<T> {
+                        type Response = super::GetInitdataResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetInitdataRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AttestationAgentService>::get_initdata(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetInitdataSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/attestation_agent.AttestationAgentService/UpdateConfig" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateConfigSvc<T: AttestationAgentService>(pub Arc<T>);
+                    impl<
+                        T: AttestationAgentService,
+                    > tonic::server::UnaryService<super::UpdateConfigRequest>
+                    for UpdateConfigSvc<T> {
+                        type Response = super::UpdateConfigResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateConfigRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AttestationAgentService>::update_config(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateConfigSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/attestation_agent.AttestationAgentService/GetPlatformReport" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPlatformReportSvc<T: AttestationAgentService>(pub Arc<T>);
+                    impl<
+                        T: AttestationAgentService,
+                    > tonic::server::UnaryService<super::GetPlatformReportRequest>
+                    for GetPlatformReportSvc<T> {
+                        type Response = super::GetPlatformReportResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPlatformReportRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AttestationAgentService>::get_platform_report(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetPlatformReportSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/attestation_agent.AttestationAgentService/GetEvidenceMetrics" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetEvidenceMetricsSvc<T: AttestationAgentService>(pub Arc<T>);
+                    impl<
+                        T: AttestationAgentService,
+                    > tonic::server::UnaryService<super::GetEvidenceMetricsRequest>
+                    for GetEvidenceMetricsSvc<T> {
+                        type Response = super::GetEvidenceMetricsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetEvidenceMetricsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AttestationAgentService>::get_evidence_metrics(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetEvidenceMetricsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
@@ -14,11 +14,19 @@ pub struct UnsealSecretOutput {
 pub struct GetResourceRequest {
     #[prost(string, tag = "1")]
     pub resource_path: ::prost::alloc::string::String,
+    /// If set, the resource is written into a new file at this path, relative
+    /// to the `resource_output_dir` of CDH, instead of being returned in the
+    /// response.
+    #[prost(string, tag = "2")]
+    pub output_path: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct GetResourceResponse {
     #[prost(bytes = "vec", tag = "1")]
     pub resource: ::prost::alloc::vec::Vec<u8>,
+    /// The size of the resource.
+    #[prost(uint64, tag = "2")]
+    pub size: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SecureMountRequest {
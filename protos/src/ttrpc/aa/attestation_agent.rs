@@ -396,6 +396,8 @@ pub struct GetTokenRequest {
     // message fields
     // @@protoc_insertion_point(field:attestation_agent.GetTokenRequest.TokenType)
     pub TokenType: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.GetTokenRequest.Refresh)
+    pub Refresh: bool,
     // special fields
     // @@protoc_insertion_point(special_field:attestation_agent.GetTokenRequest.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -413,13 +415,18 @@ impl GetTokenRequest {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut fields = ::std::vec::Vec::with_capacity(2);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
             "TokenType",
             |m: &GetTokenRequest| { &m.TokenType },
             |m: &mut GetTokenRequest| { &mut m.TokenType },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Refresh",
+            |m: &GetTokenRequest| { &m.Refresh },
+            |m: &mut GetTokenRequest| { &mut m.Refresh },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetTokenRequest>(
             "GetTokenRequest",
             fields,
@@ -441,6 +448,9 @@ impl ::protobuf::Message for GetTokenRequest {
                 10 => {
                     self.TokenType = is.read_string()?;
                 },
+                16 => {
+                    self.Refresh = is.read_bool()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -456,6 +466,9 @@ impl ::protobuf::Message for GetTokenRequest {
         if !self.TokenType.is_empty() {
             my_size += ::protobuf::rt::string_size(1, &self.TokenType);
         }
+        if self.Refresh != false {
+            my_size += 1 + 1;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -465,6 +478,9 @@ impl ::protobuf::Message for GetTokenRequest {
         if !self.TokenType.is_empty() {
             os.write_string(1, &self.TokenType)?;
         }
+        if self.Refresh != false {
+            os.write_bool(2, self.Refresh)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -483,12 +499,14 @@ impl ::protobuf::Message for GetTokenRequest {
 
     fn clear(&mut self) {
         self.TokenType.clear();
+        self.Refresh = false;
         self.special_fields.clear();
     }
 
     fn default_instance() -> &'static GetTokenRequest {
         static instance: GetTokenRequest = GetTokenRequest {
             TokenType: ::std::string::String::new(),
+            Refresh: false,
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -932,46 +950,60 @@ impl ::protobuf::reflect::ProtobufValue for ExtendRuntimeMeasurementResponse {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-// @@protoc_insertion_point(message:attestation_agent.BindInitDataRequest)
+// @@protoc_insertion_point(message:attestation_agent.GenericEvent)
 #[derive(PartialEq,Clone,Default,Debug)]
-pub struct BindInitDataRequest {
+pub struct GenericEvent {
     // message fields
-    // @@protoc_insertion_point(field:attestation_agent.BindInitDataRequest.Digest)
-    pub Digest: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:attestation_agent.GenericEvent.Domain)
+    pub Domain: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.GenericEvent.Operation)
+    pub Operation: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.GenericEvent.Content)
+    pub Content: ::std::string::String,
     // special fields
-    // @@protoc_insertion_point(special_field:attestation_agent.BindInitDataRequest.special_fields)
+    // @@protoc_insertion_point(special_field:attestation_agent.GenericEvent.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
 }
 
-impl<'a> ::std::default::Default for &'a BindInitDataRequest {
-    fn default() -> &'a BindInitDataRequest {
-        <BindInitDataRequest as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a GenericEvent {
+    fn default() -> &'a GenericEvent {
+        <GenericEvent as ::protobuf::Message>::default_instance()
     }
 }
 
-impl BindInitDataRequest {
-    pub fn new() -> BindInitDataRequest {
+impl GenericEvent {
+    pub fn new() -> GenericEvent {
         ::std::default::Default::default()
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut fields = ::std::vec::Vec::with_capacity(3);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
-            "Digest",
-            |m: &BindInitDataRequest| { &m.Digest },
-            |m: &mut BindInitDataRequest| { &mut m.Digest },
+            "Domain",
+            |m: &GenericEvent| { &m.Domain },
+            |m: &mut GenericEvent| { &mut m.Domain },
         ));
-        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<BindInitDataRequest>(
-            "BindInitDataRequest",
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Operation",
+            |m: &GenericEvent| { &m.Operation },
+            |m: &mut GenericEvent| { &mut m.Operation },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Content",
+            |m: &GenericEvent| { &m.Content },
+            |m: &mut GenericEvent| { &mut m.Content },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GenericEvent>(
+            "GenericEvent",
             fields,
             oneofs,
         )
     }
 }
 
-impl ::protobuf::Message for BindInitDataRequest {
-    const NAME: &'static str = "BindInitDataRequest";
+impl ::protobuf::Message for GenericEvent {
+    const NAME: &'static str = "GenericEvent";
 
     fn is_initialized(&self) -> bool {
         true
@@ -981,7 +1013,13 @@ impl ::protobuf::Message for BindInitDataRequest {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 10 => {
-                    self.Digest = is.read_bytes()?;
+                    self.Domain = is.read_string()?;
+                },
+                18 => {
+                    self.Operation = is.read_string()?;
+                },
+                26 => {
+                    self.Content = is.read_string()?;
                 },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -995,8 +1033,14 @@ impl ::protobuf::Message for BindInitDataRequest {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u64 {
         let mut my_size = 0;
-        if !self.Digest.is_empty() {
-            my_size += ::protobuf::rt::bytes_size(1, &self.Digest);
+        if !self.Domain.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.Domain);
+        }
+        if !self.Operation.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.Operation);
+        }
+        if !self.Content.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.Content);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
@@ -1004,8 +1048,14 @@ impl ::protobuf::Message for BindInitDataRequest {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
-        if !self.Digest.is_empty() {
-            os.write_bytes(1, &self.Digest)?;
+        if !self.Domain.is_empty() {
+            os.write_string(1, &self.Domain)?;
+        }
+        if !self.Operation.is_empty() {
+            os.write_string(2, &self.Operation)?;
+        }
+        if !self.Content.is_empty() {
+            os.write_string(3, &self.Content)?;
         }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -1019,73 +1069,92 @@ impl ::protobuf::Message for BindInitDataRequest {
         &mut self.special_fields
     }
 
-    fn new() -> BindInitDataRequest {
-        BindInitDataRequest::new()
+    fn new() -> GenericEvent {
+        GenericEvent::new()
     }
 
     fn clear(&mut self) {
-        self.Digest.clear();
+        self.Domain.clear();
+        self.Operation.clear();
+        self.Content.clear();
         self.special_fields.clear();
     }
 
-    fn default_instance() -> &'static BindInitDataRequest {
-        static instance: BindInitDataRequest = BindInitDataRequest {
-            Digest: ::std::vec::Vec::new(),
+    fn default_instance() -> &'static GenericEvent {
+        static instance: GenericEvent = GenericEvent {
+            Domain: ::std::string::String::new(),
+            Operation: ::std::string::String::new(),
+            Content: ::std::string::String::new(),
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
     }
 }
 
-impl ::protobuf::MessageFull for BindInitDataRequest {
+impl ::protobuf::MessageFull for GenericEvent {
     fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
-        descriptor.get(|| file_descriptor().message_by_package_relative_name("BindInitDataRequest").unwrap()).clone()
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GenericEvent").unwrap()).clone()
     }
 }
 
-impl ::std::fmt::Display for BindInitDataRequest {
+impl ::std::fmt::Display for GenericEvent {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for BindInitDataRequest {
+impl ::protobuf::reflect::ProtobufValue for GenericEvent {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-// @@protoc_insertion_point(message:attestation_agent.BindInitDataResponse)
+// @@protoc_insertion_point(message:attestation_agent.PullImageEvent)
 #[derive(PartialEq,Clone,Default,Debug)]
-pub struct BindInitDataResponse {
+pub struct PullImageEvent {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.PullImageEvent.Image)
+    pub Image: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.PullImageEvent.Digest)
+    pub Digest: ::std::string::String,
     // special fields
-    // @@protoc_insertion_point(special_field:attestation_agent.BindInitDataResponse.special_fields)
+    // @@protoc_insertion_point(special_field:attestation_agent.PullImageEvent.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
 }
 
-impl<'a> ::std::default::Default for &'a BindInitDataResponse {
-    fn default() -> &'a BindInitDataResponse {
-        <BindInitDataResponse as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a PullImageEvent {
+    fn default() -> &'a PullImageEvent {
+        <PullImageEvent as ::protobuf::Message>::default_instance()
     }
 }
 
-impl BindInitDataResponse {
-    pub fn new() -> BindInitDataResponse {
+impl PullImageEvent {
+    pub fn new() -> PullImageEvent {
         ::std::default::Default::default()
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut fields = ::std::vec::Vec::with_capacity(2);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
-        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<BindInitDataResponse>(
-            "BindInitDataResponse",
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Image",
+            |m: &PullImageEvent| { &m.Image },
+            |m: &mut PullImageEvent| { &mut m.Image },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Digest",
+            |m: &PullImageEvent| { &m.Digest },
+            |m: &mut PullImageEvent| { &mut m.Digest },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<PullImageEvent>(
+            "PullImageEvent",
             fields,
             oneofs,
         )
     }
 }
 
-impl ::protobuf::Message for BindInitDataResponse {
-    const NAME: &'static str = "BindInitDataResponse";
+impl ::protobuf::Message for PullImageEvent {
+    const NAME: &'static str = "PullImageEvent";
 
     fn is_initialized(&self) -> bool {
         true
@@ -1094,6 +1163,12 @@ impl ::protobuf::Message for BindInitDataResponse {
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
+                10 => {
+                    self.Image = is.read_string()?;
+                },
+                18 => {
+                    self.Digest = is.read_string()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -1106,12 +1181,24 @@ impl ::protobuf::Message for BindInitDataResponse {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u64 {
         let mut my_size = 0;
+        if !self.Image.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.Image);
+        }
+        if !self.Digest.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.Digest);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Image.is_empty() {
+            os.write_string(1, &self.Image)?;
+        }
+        if !self.Digest.is_empty() {
+            os.write_string(2, &self.Digest)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1124,71 +1211,90 @@ impl ::protobuf::Message for BindInitDataResponse {
         &mut self.special_fields
     }
 
-    fn new() -> BindInitDataResponse {
-        BindInitDataResponse::new()
+    fn new() -> PullImageEvent {
+        PullImageEvent::new()
     }
 
     fn clear(&mut self) {
+        self.Image.clear();
+        self.Digest.clear();
         self.special_fields.clear();
     }
 
-    fn default_instance() -> &'static BindInitDataResponse {
-        static instance: BindInitDataResponse = BindInitDataResponse {
+    fn default_instance() -> &'static PullImageEvent {
+        static instance: PullImageEvent = PullImageEvent {
+            Image: ::std::string::String::new(),
+            Digest: ::std::string::String::new(),
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
     }
 }
 
-impl ::protobuf::MessageFull for BindInitDataResponse {
+impl ::protobuf::MessageFull for PullImageEvent {
     fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
-        descriptor.get(|| file_descriptor().message_by_package_relative_name("BindInitDataResponse").unwrap()).clone()
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("PullImageEvent").unwrap()).clone()
     }
 }
 
-impl ::std::fmt::Display for BindInitDataResponse {
+impl ::std::fmt::Display for PullImageEvent {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for BindInitDataResponse {
+impl ::protobuf::reflect::ProtobufValue for PullImageEvent {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-// @@protoc_insertion_point(message:attestation_agent.GetTeeTypeRequest)
+// @@protoc_insertion_point(message:attestation_agent.SecureMountEvent)
 #[derive(PartialEq,Clone,Default,Debug)]
-pub struct GetTeeTypeRequest {
+pub struct SecureMountEvent {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.SecureMountEvent.VolumeType)
+    pub VolumeType: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.SecureMountEvent.MountPoint)
+    pub MountPoint: ::std::string::String,
     // special fields
-    // @@protoc_insertion_point(special_field:attestation_agent.GetTeeTypeRequest.special_fields)
+    // @@protoc_insertion_point(special_field:attestation_agent.SecureMountEvent.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
 }
 
-impl<'a> ::std::default::Default for &'a GetTeeTypeRequest {
-    fn default() -> &'a GetTeeTypeRequest {
-        <GetTeeTypeRequest as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a SecureMountEvent {
+    fn default() -> &'a SecureMountEvent {
+        <SecureMountEvent as ::protobuf::Message>::default_instance()
     }
 }
 
-impl GetTeeTypeRequest {
-    pub fn new() -> GetTeeTypeRequest {
+impl SecureMountEvent {
+    pub fn new() -> SecureMountEvent {
         ::std::default::Default::default()
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut fields = ::std::vec::Vec::with_capacity(2);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
-        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetTeeTypeRequest>(
-            "GetTeeTypeRequest",
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "VolumeType",
+            |m: &SecureMountEvent| { &m.VolumeType },
+            |m: &mut SecureMountEvent| { &mut m.VolumeType },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "MountPoint",
+            |m: &SecureMountEvent| { &m.MountPoint },
+            |m: &mut SecureMountEvent| { &mut m.MountPoint },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<SecureMountEvent>(
+            "SecureMountEvent",
             fields,
             oneofs,
         )
     }
 }
 
-impl ::protobuf::Message for GetTeeTypeRequest {
-    const NAME: &'static str = "GetTeeTypeRequest";
+impl ::protobuf::Message for SecureMountEvent {
+    const NAME: &'static str = "SecureMountEvent";
 
     fn is_initialized(&self) -> bool {
         true
@@ -1197,6 +1303,12 @@ impl ::protobuf::Message for GetTeeTypeRequest {
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
+                10 => {
+                    self.VolumeType = is.read_string()?;
+                },
+                18 => {
+                    self.MountPoint = is.read_string()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -1209,12 +1321,24 @@ impl ::protobuf::Message for GetTeeTypeRequest {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u64 {
         let mut my_size = 0;
+        if !self.VolumeType.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.VolumeType);
+        }
+        if !self.MountPoint.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.MountPoint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.VolumeType.is_empty() {
+            os.write_string(1, &self.VolumeType)?;
+        }
+        if !self.MountPoint.is_empty() {
+            os.write_string(2, &self.MountPoint)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1227,58 +1351,62 @@ impl ::protobuf::Message for GetTeeTypeRequest {
         &mut self.special_fields
     }
 
-    fn new() -> GetTeeTypeRequest {
-        GetTeeTypeRequest::new()
+    fn new() -> SecureMountEvent {
+        SecureMountEvent::new()
     }
 
     fn clear(&mut self) {
+        self.VolumeType.clear();
+        self.MountPoint.clear();
         self.special_fields.clear();
     }
 
-    fn default_instance() -> &'static GetTeeTypeRequest {
-        static instance: GetTeeTypeRequest = GetTeeTypeRequest {
+    fn default_instance() -> &'static SecureMountEvent {
+        static instance: SecureMountEvent = SecureMountEvent {
+            VolumeType: ::std::string::String::new(),
+            MountPoint: ::std::string::String::new(),
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
     }
 }
 
-impl ::protobuf::MessageFull for GetTeeTypeRequest {
+impl ::protobuf::MessageFull for SecureMountEvent {
     fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
-        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetTeeTypeRequest").unwrap()).clone()
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("SecureMountEvent").unwrap()).clone()
     }
 }
 
-impl ::std::fmt::Display for GetTeeTypeRequest {
+impl ::std::fmt::Display for SecureMountEvent {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for GetTeeTypeRequest {
+impl ::protobuf::reflect::ProtobufValue for SecureMountEvent {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-// @@protoc_insertion_point(message:attestation_agent.GetTeeTypeResponse)
+// @@protoc_insertion_point(message:attestation_agent.UnsealSecretEvent)
 #[derive(PartialEq,Clone,Default,Debug)]
-pub struct GetTeeTypeResponse {
+pub struct UnsealSecretEvent {
     // message fields
-    // @@protoc_insertion_point(field:attestation_agent.GetTeeTypeResponse.tee)
-    pub tee: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.UnsealSecretEvent.KeyId)
+    pub KeyId: ::std::string::String,
     // special fields
-    // @@protoc_insertion_point(special_field:attestation_agent.GetTeeTypeResponse.special_fields)
+    // @@protoc_insertion_point(special_field:attestation_agent.UnsealSecretEvent.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
 }
 
-impl<'a> ::std::default::Default for &'a GetTeeTypeResponse {
-    fn default() -> &'a GetTeeTypeResponse {
-        <GetTeeTypeResponse as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a UnsealSecretEvent {
+    fn default() -> &'a UnsealSecretEvent {
+        <UnsealSecretEvent as ::protobuf::Message>::default_instance()
     }
 }
 
-impl GetTeeTypeResponse {
-    pub fn new() -> GetTeeTypeResponse {
+impl UnsealSecretEvent {
+    pub fn new() -> UnsealSecretEvent {
         ::std::default::Default::default()
     }
 
@@ -1286,20 +1414,20 @@ impl GetTeeTypeResponse {
         let mut fields = ::std::vec::Vec::with_capacity(1);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
-            "tee",
-            |m: &GetTeeTypeResponse| { &m.tee },
-            |m: &mut GetTeeTypeResponse| { &mut m.tee },
+            "KeyId",
+            |m: &UnsealSecretEvent| { &m.KeyId },
+            |m: &mut UnsealSecretEvent| { &mut m.KeyId },
         ));
-        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetTeeTypeResponse>(
-            "GetTeeTypeResponse",
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<UnsealSecretEvent>(
+            "UnsealSecretEvent",
             fields,
             oneofs,
         )
     }
 }
 
-impl ::protobuf::Message for GetTeeTypeResponse {
-    const NAME: &'static str = "GetTeeTypeResponse";
+impl ::protobuf::Message for UnsealSecretEvent {
+    const NAME: &'static str = "UnsealSecretEvent";
 
     fn is_initialized(&self) -> bool {
         true
@@ -1309,7 +1437,7 @@ impl ::protobuf::Message for GetTeeTypeResponse {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 10 => {
-                    self.tee = is.read_string()?;
+                    self.KeyId = is.read_string()?;
                 },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -1323,8 +1451,8 @@ impl ::protobuf::Message for GetTeeTypeResponse {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u64 {
         let mut my_size = 0;
-        if !self.tee.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.tee);
+        if !self.KeyId.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.KeyId);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
@@ -1332,8 +1460,8 @@ impl ::protobuf::Message for GetTeeTypeResponse {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
-        if !self.tee.is_empty() {
-            os.write_string(1, &self.tee)?;
+        if !self.KeyId.is_empty() {
+            os.write_string(1, &self.KeyId)?;
         }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -1347,38 +1475,3721 @@ impl ::protobuf::Message for GetTeeTypeResponse {
         &mut self.special_fields
     }
 
-    fn new() -> GetTeeTypeResponse {
-        GetTeeTypeResponse::new()
+    fn new() -> UnsealSecretEvent {
+        UnsealSecretEvent::new()
     }
 
     fn clear(&mut self) {
-        self.tee.clear();
+        self.KeyId.clear();
         self.special_fields.clear();
     }
 
-    fn default_instance() -> &'static GetTeeTypeResponse {
-        static instance: GetTeeTypeResponse = GetTeeTypeResponse {
-            tee: ::std::string::String::new(),
+    fn default_instance() -> &'static UnsealSecretEvent {
+        static instance: UnsealSecretEvent = UnsealSecretEvent {
+            KeyId: ::std::string::String::new(),
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
     }
 }
 
-impl ::protobuf::MessageFull for GetTeeTypeResponse {
+impl ::protobuf::MessageFull for UnsealSecretEvent {
     fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
-        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetTeeTypeResponse").unwrap()).clone()
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("UnsealSecretEvent").unwrap()).clone()
     }
 }
 
-impl ::std::fmt::Display for GetTeeTypeResponse {
+impl ::std::fmt::Display for UnsealSecretEvent {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for GetTeeTypeResponse {
+impl ::protobuf::reflect::ProtobufValue for UnsealSecretEvent {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetResourceEvent)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetResourceEvent {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.GetResourceEvent.ResourceUri)
+    pub ResourceUri: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetResourceEvent.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetResourceEvent {
+    fn default() -> &'a GetResourceEvent {
+        <GetResourceEvent as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetResourceEvent {
+    pub fn new() -> GetResourceEvent {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "ResourceUri",
+            |m: &GetResourceEvent| { &m.ResourceUri },
+            |m: &mut GetResourceEvent| { &mut m.ResourceUri },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetResourceEvent>(
+            "GetResourceEvent",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetResourceEvent {
+    const NAME: &'static str = "GetResourceEvent";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.ResourceUri = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.ResourceUri.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.ResourceUri);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.ResourceUri.is_empty() {
+            os.write_string(1, &self.ResourceUri)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetResourceEvent {
+        GetResourceEvent::new()
+    }
+
+    fn clear(&mut self) {
+        self.ResourceUri.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetResourceEvent {
+        static instance: GetResourceEvent = GetResourceEvent {
+            ResourceUri: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetResourceEvent {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetResourceEvent").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetResourceEvent {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetResourceEvent {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.RuntimeMeasurementEvent)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct RuntimeMeasurementEvent {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.RuntimeMeasurementEvent.RegisterIndex)
+    pub RegisterIndex: ::std::option::Option<u64>,
+    // message oneof groups
+    pub Event: ::std::option::Option<runtime_measurement_event::Event>,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.RuntimeMeasurementEvent.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a RuntimeMeasurementEvent {
+    fn default() -> &'a RuntimeMeasurementEvent {
+        <RuntimeMeasurementEvent as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl RuntimeMeasurementEvent {
+    pub fn new() -> RuntimeMeasurementEvent {
+        ::std::default::Default::default()
+    }
+
+    // .attestation_agent.GenericEvent Generic = 1;
+
+    pub fn Generic(&self) -> &GenericEvent {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::Generic(ref v)) => v,
+            _ => <GenericEvent as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_Generic(&mut self) {
+        self.Event = ::std::option::Option::None;
+    }
+
+    pub fn has_Generic(&self) -> bool {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::Generic(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_Generic(&mut self, v: GenericEvent) {
+        self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::Generic(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_Generic(&mut self) -> &mut GenericEvent {
+        if let ::std::option::Option::Some(runtime_measurement_event::Event::Generic(_)) = self.Event {
+        } else {
+            self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::Generic(GenericEvent::new()));
+        }
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::Generic(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_Generic(&mut self) -> GenericEvent {
+        if self.has_Generic() {
+            match self.Event.take() {
+                ::std::option::Option::Some(runtime_measurement_event::Event::Generic(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            GenericEvent::new()
+        }
+    }
+
+    // .attestation_agent.PullImageEvent PullImage = 2;
+
+    pub fn PullImage(&self) -> &PullImageEvent {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::PullImage(ref v)) => v,
+            _ => <PullImageEvent as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_PullImage(&mut self) {
+        self.Event = ::std::option::Option::None;
+    }
+
+    pub fn has_PullImage(&self) -> bool {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::PullImage(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_PullImage(&mut self, v: PullImageEvent) {
+        self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::PullImage(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_PullImage(&mut self) -> &mut PullImageEvent {
+        if let ::std::option::Option::Some(runtime_measurement_event::Event::PullImage(_)) = self.Event {
+        } else {
+            self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::PullImage(PullImageEvent::new()));
+        }
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::PullImage(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_PullImage(&mut self) -> PullImageEvent {
+        if self.has_PullImage() {
+            match self.Event.take() {
+                ::std::option::Option::Some(runtime_measurement_event::Event::PullImage(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            PullImageEvent::new()
+        }
+    }
+
+    // .attestation_agent.SecureMountEvent SecureMount = 3;
+
+    pub fn SecureMount(&self) -> &SecureMountEvent {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::SecureMount(ref v)) => v,
+            _ => <SecureMountEvent as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_SecureMount(&mut self) {
+        self.Event = ::std::option::Option::None;
+    }
+
+    pub fn has_SecureMount(&self) -> bool {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::SecureMount(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_SecureMount(&mut self, v: SecureMountEvent) {
+        self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::SecureMount(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_SecureMount(&mut self) -> &mut SecureMountEvent {
+        if let ::std::option::Option::Some(runtime_measurement_event::Event::SecureMount(_)) = self.Event {
+        } else {
+            self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::SecureMount(SecureMountEvent::new()));
+        }
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::SecureMount(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_SecureMount(&mut self) -> SecureMountEvent {
+        if self.has_SecureMount() {
+            match self.Event.take() {
+                ::std::option::Option::Some(runtime_measurement_event::Event::SecureMount(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            SecureMountEvent::new()
+        }
+    }
+
+    // .attestation_agent.UnsealSecretEvent UnsealSecret = 4;
+
+    pub fn UnsealSecret(&self) -> &UnsealSecretEvent {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::UnsealSecret(ref v)) => v,
+            _ => <UnsealSecretEvent as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_UnsealSecret(&mut self) {
+        self.Event = ::std::option::Option::None;
+    }
+
+    pub fn has_UnsealSecret(&self) -> bool {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::UnsealSecret(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_UnsealSecret(&mut self, v: UnsealSecretEvent) {
+        self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::UnsealSecret(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_UnsealSecret(&mut self) -> &mut UnsealSecretEvent {
+        if let ::std::option::Option::Some(runtime_measurement_event::Event::UnsealSecret(_)) = self.Event {
+        } else {
+            self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::UnsealSecret(UnsealSecretEvent::new()));
+        }
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::UnsealSecret(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_UnsealSecret(&mut self) -> UnsealSecretEvent {
+        if self.has_UnsealSecret() {
+            match self.Event.take() {
+                ::std::option::Option::Some(runtime_measurement_event::Event::UnsealSecret(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            UnsealSecretEvent::new()
+        }
+    }
+
+    // .attestation_agent.GetResourceEvent GetResource = 5;
+
+    pub fn GetResource(&self) -> &GetResourceEvent {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::GetResource(ref v)) => v,
+            _ => <GetResourceEvent as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_GetResource(&mut self) {
+        self.Event = ::std::option::Option::None;
+    }
+
+    pub fn has_GetResource(&self) -> bool {
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::GetResource(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_GetResource(&mut self, v: GetResourceEvent) {
+        self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::GetResource(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_GetResource(&mut self) -> &mut GetResourceEvent {
+        if let ::std::option::Option::Some(runtime_measurement_event::Event::GetResource(_)) = self.Event {
+        } else {
+            self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::GetResource(GetResourceEvent::new()));
+        }
+        match self.Event {
+            ::std::option::Option::Some(runtime_measurement_event::Event::GetResource(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_GetResource(&mut self) -> GetResourceEvent {
+        if self.has_GetResource() {
+            match self.Event.take() {
+                ::std::option::Option::Some(runtime_measurement_event::Event::GetResource(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            GetResourceEvent::new()
+        }
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(6);
+        let mut oneofs = ::std::vec::Vec::with_capacity(1);
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, GenericEvent>(
+            "Generic",
+            RuntimeMeasurementEvent::has_Generic,
+            RuntimeMeasurementEvent::Generic,
+            RuntimeMeasurementEvent::mut_Generic,
+            RuntimeMeasurementEvent::set_Generic,
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, PullImageEvent>(
+            "PullImage",
+            RuntimeMeasurementEvent::has_PullImage,
+            RuntimeMeasurementEvent::PullImage,
+            RuntimeMeasurementEvent::mut_PullImage,
+            RuntimeMeasurementEvent::set_PullImage,
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, SecureMountEvent>(
+            "SecureMount",
+            RuntimeMeasurementEvent::has_SecureMount,
+            RuntimeMeasurementEvent::SecureMount,
+            RuntimeMeasurementEvent::mut_SecureMount,
+            RuntimeMeasurementEvent::set_SecureMount,
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, UnsealSecretEvent>(
+            "UnsealSecret",
+            RuntimeMeasurementEvent::has_UnsealSecret,
+            RuntimeMeasurementEvent::UnsealSecret,
+            RuntimeMeasurementEvent::mut_UnsealSecret,
+            RuntimeMeasurementEvent::set_UnsealSecret,
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, GetResourceEvent>(
+            "GetResource",
+            RuntimeMeasurementEvent::has_GetResource,
+            RuntimeMeasurementEvent::GetResource,
+            RuntimeMeasurementEvent::mut_GetResource,
+            RuntimeMeasurementEvent::set_GetResource,
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+            "RegisterIndex",
+            |m: &RuntimeMeasurementEvent| { &m.RegisterIndex },
+            |m: &mut RuntimeMeasurementEvent| { &mut m.RegisterIndex },
+        ));
+        oneofs.push(runtime_measurement_event::Event::generated_oneof_descriptor_data());
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<RuntimeMeasurementEvent>(
+            "RuntimeMeasurementEvent",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for RuntimeMeasurementEvent {
+    const NAME: &'static str = "RuntimeMeasurementEvent";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::Generic(is.read_message()?));
+                },
+                18 => {
+                    self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::PullImage(is.read_message()?));
+                },
+                26 => {
+                    self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::SecureMount(is.read_message()?));
+                },
+                34 => {
+                    self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::UnsealSecret(is.read_message()?));
+                },
+                42 => {
+                    self.Event = ::std::option::Option::Some(runtime_measurement_event::Event::GetResource(is.read_message()?));
+                },
+                48 => {
+                    self.RegisterIndex = ::std::option::Option::Some(is.read_uint64()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let Some(v) = self.RegisterIndex {
+            my_size += ::protobuf::rt::uint64_size(6, v);
+        }
+        if let ::std::option::Option::Some(ref v) = self.Event {
+            match v {
+                &runtime_measurement_event::Event::Generic(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
+                &runtime_measurement_event::Event::PullImage(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
+                &runtime_measurement_event::Event::SecureMount(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
+                &runtime_measurement_event::Event::UnsealSecret(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
+                &runtime_measurement_event::Event::GetResource(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
+            };
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let Some(v) = self.RegisterIndex {
+            os.write_uint64(6, v)?;
+        }
+        if let ::std::option::Option::Some(ref v) = self.Event {
+            match v {
+                &runtime_measurement_event::Event::Generic(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+                },
+                &runtime_measurement_event::Event::PullImage(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+                },
+                &runtime_measurement_event::Event::SecureMount(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
+                },
+                &runtime_measurement_event::Event::UnsealSecret(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(4, v, os)?;
+                },
+                &runtime_measurement_event::Event::GetResource(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(5, v, os)?;
+                },
+            };
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> RuntimeMeasurementEvent {
+        RuntimeMeasurementEvent::new()
+    }
+
+    fn clear(&mut self) {
+        self.Event = ::std::option::Option::None;
+        self.Event = ::std::option::Option::None;
+        self.Event = ::std::option::Option::None;
+        self.Event = ::std::option::Option::None;
+        self.Event = ::std::option::Option::None;
+        self.RegisterIndex = ::std::option::Option::None;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static RuntimeMeasurementEvent {
+        static instance: RuntimeMeasurementEvent = RuntimeMeasurementEvent {
+            RegisterIndex: ::std::option::Option::None,
+            Event: ::std::option::Option::None,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for RuntimeMeasurementEvent {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("RuntimeMeasurementEvent").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for RuntimeMeasurementEvent {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RuntimeMeasurementEvent {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+/// Nested message and enums of message `RuntimeMeasurementEvent`
+pub mod runtime_measurement_event {
+
+    #[derive(Clone,PartialEq,Debug)]
+    #[non_exhaustive]
+    // @@protoc_insertion_point(oneof:attestation_agent.RuntimeMeasurementEvent.Event)
+    pub enum Event {
+        // @@protoc_insertion_point(oneof_field:attestation_agent.RuntimeMeasurementEvent.Generic)
+        Generic(super::GenericEvent),
+        // @@protoc_insertion_point(oneof_field:attestation_agent.RuntimeMeasurementEvent.PullImage)
+        PullImage(super::PullImageEvent),
+        // @@protoc_insertion_point(oneof_field:attestation_agent.RuntimeMeasurementEvent.SecureMount)
+        SecureMount(super::SecureMountEvent),
+        // @@protoc_insertion_point(oneof_field:attestation_agent.RuntimeMeasurementEvent.UnsealSecret)
+        UnsealSecret(super::UnsealSecretEvent),
+        // @@protoc_insertion_point(oneof_field:attestation_agent.RuntimeMeasurementEvent.GetResource)
+        GetResource(super::GetResourceEvent),
+    }
+
+    impl ::protobuf::Oneof for Event {
+    }
+
+    impl ::protobuf::OneofFull for Event {
+        fn descriptor() -> ::protobuf::reflect::OneofDescriptor {
+            static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::OneofDescriptor> = ::protobuf::rt::Lazy::new();
+            descriptor.get(|| <super::RuntimeMeasurementEvent as ::protobuf::MessageFull>::descriptor().oneof_by_name("Event").unwrap()).clone()
+        }
+    }
+
+    impl Event {
+        pub(in super) fn generated_oneof_descriptor_data() -> ::protobuf::reflect::GeneratedOneofDescriptorData {
+            ::protobuf::reflect::GeneratedOneofDescriptorData::new::<Event>("Event")
+        }
+    }
+}
+
+// @@protoc_insertion_point(message:attestation_agent.ExtendRuntimeMeasurementsRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct ExtendRuntimeMeasurementsRequest {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.ExtendRuntimeMeasurementsRequest.Events)
+    pub Events: ::std::vec::Vec<RuntimeMeasurementEvent>,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.ExtendRuntimeMeasurementsRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ExtendRuntimeMeasurementsRequest {
+    fn default() -> &'a ExtendRuntimeMeasurementsRequest {
+        <ExtendRuntimeMeasurementsRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ExtendRuntimeMeasurementsRequest {
+    pub fn new() -> ExtendRuntimeMeasurementsRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "Events",
+            |m: &ExtendRuntimeMeasurementsRequest| { &m.Events },
+            |m: &mut ExtendRuntimeMeasurementsRequest| { &mut m.Events },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ExtendRuntimeMeasurementsRequest>(
+            "ExtendRuntimeMeasurementsRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ExtendRuntimeMeasurementsRequest {
+    const NAME: &'static str = "ExtendRuntimeMeasurementsRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Events.push(is.read_message()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.Events {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.Events {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ExtendRuntimeMeasurementsRequest {
+        ExtendRuntimeMeasurementsRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.Events.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ExtendRuntimeMeasurementsRequest {
+        static instance: ExtendRuntimeMeasurementsRequest = ExtendRuntimeMeasurementsRequest {
+            Events: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ExtendRuntimeMeasurementsRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ExtendRuntimeMeasurementsRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ExtendRuntimeMeasurementsRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ExtendRuntimeMeasurementsRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.BindInitDataRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct BindInitDataRequest {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.BindInitDataRequest.Digest)
+    pub Digest: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.BindInitDataRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a BindInitDataRequest {
+    fn default() -> &'a BindInitDataRequest {
+        <BindInitDataRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl BindInitDataRequest {
+    pub fn new() -> BindInitDataRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Digest",
+            |m: &BindInitDataRequest| { &m.Digest },
+            |m: &mut BindInitDataRequest| { &mut m.Digest },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<BindInitDataRequest>(
+            "BindInitDataRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for BindInitDataRequest {
+    const NAME: &'static str = "BindInitDataRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Digest = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Digest.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.Digest);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Digest.is_empty() {
+            os.write_bytes(1, &self.Digest)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> BindInitDataRequest {
+        BindInitDataRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.Digest.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static BindInitDataRequest {
+        static instance: BindInitDataRequest = BindInitDataRequest {
+            Digest: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for BindInitDataRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("BindInitDataRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for BindInitDataRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BindInitDataRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.BindInitDataResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct BindInitDataResponse {
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.BindInitDataResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a BindInitDataResponse {
+    fn default() -> &'a BindInitDataResponse {
+        <BindInitDataResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl BindInitDataResponse {
+    pub fn new() -> BindInitDataResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<BindInitDataResponse>(
+            "BindInitDataResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for BindInitDataResponse {
+    const NAME: &'static str = "BindInitDataResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> BindInitDataResponse {
+        BindInitDataResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static BindInitDataResponse {
+        static instance: BindInitDataResponse = BindInitDataResponse {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for BindInitDataResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("BindInitDataResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for BindInitDataResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BindInitDataResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.DeriveSealingKeyRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct DeriveSealingKeyRequest {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.DeriveSealingKeyRequest.Purpose)
+    pub Purpose: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.DeriveSealingKeyRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a DeriveSealingKeyRequest {
+    fn default() -> &'a DeriveSealingKeyRequest {
+        <DeriveSealingKeyRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DeriveSealingKeyRequest {
+    pub fn new() -> DeriveSealingKeyRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Purpose",
+            |m: &DeriveSealingKeyRequest| { &m.Purpose },
+            |m: &mut DeriveSealingKeyRequest| { &mut m.Purpose },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<DeriveSealingKeyRequest>(
+            "DeriveSealingKeyRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for DeriveSealingKeyRequest {
+    const NAME: &'static str = "DeriveSealingKeyRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Purpose = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Purpose.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.Purpose);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Purpose.is_empty() {
+            os.write_string(1, &self.Purpose)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> DeriveSealingKeyRequest {
+        DeriveSealingKeyRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.Purpose.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static DeriveSealingKeyRequest {
+        static instance: DeriveSealingKeyRequest = DeriveSealingKeyRequest {
+            Purpose: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for DeriveSealingKeyRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("DeriveSealingKeyRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for DeriveSealingKeyRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DeriveSealingKeyRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.DeriveSealingKeyResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct DeriveSealingKeyResponse {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.DeriveSealingKeyResponse.Key)
+    pub Key: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.DeriveSealingKeyResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a DeriveSealingKeyResponse {
+    fn default() -> &'a DeriveSealingKeyResponse {
+        <DeriveSealingKeyResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DeriveSealingKeyResponse {
+    pub fn new() -> DeriveSealingKeyResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Key",
+            |m: &DeriveSealingKeyResponse| { &m.Key },
+            |m: &mut DeriveSealingKeyResponse| { &mut m.Key },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<DeriveSealingKeyResponse>(
+            "DeriveSealingKeyResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for DeriveSealingKeyResponse {
+    const NAME: &'static str = "DeriveSealingKeyResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Key = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Key.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.Key);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Key.is_empty() {
+            os.write_bytes(1, &self.Key)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> DeriveSealingKeyResponse {
+        DeriveSealingKeyResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.Key.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static DeriveSealingKeyResponse {
+        static instance: DeriveSealingKeyResponse = DeriveSealingKeyResponse {
+            Key: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for DeriveSealingKeyResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("DeriveSealingKeyResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for DeriveSealingKeyResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DeriveSealingKeyResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetTeeTypeRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetTeeTypeRequest {
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetTeeTypeRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetTeeTypeRequest {
+    fn default() -> &'a GetTeeTypeRequest {
+        <GetTeeTypeRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetTeeTypeRequest {
+    pub fn new() -> GetTeeTypeRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetTeeTypeRequest>(
+            "GetTeeTypeRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetTeeTypeRequest {
+    const NAME: &'static str = "GetTeeTypeRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetTeeTypeRequest {
+        GetTeeTypeRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetTeeTypeRequest {
+        static instance: GetTeeTypeRequest = GetTeeTypeRequest {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetTeeTypeRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetTeeTypeRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetTeeTypeRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetTeeTypeRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetTeeTypeResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetTeeTypeResponse {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.GetTeeTypeResponse.tee)
+    pub tee: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetTeeTypeResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetTeeTypeResponse {
+    fn default() -> &'a GetTeeTypeResponse {
+        <GetTeeTypeResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetTeeTypeResponse {
+    pub fn new() -> GetTeeTypeResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "tee",
+            |m: &GetTeeTypeResponse| { &m.tee },
+            |m: &mut GetTeeTypeResponse| { &mut m.tee },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetTeeTypeResponse>(
+            "GetTeeTypeResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetTeeTypeResponse {
+    const NAME: &'static str = "GetTeeTypeResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.tee = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.tee.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.tee);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.tee.is_empty() {
+            os.write_string(1, &self.tee)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetTeeTypeResponse {
+        GetTeeTypeResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.tee.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetTeeTypeResponse {
+        static instance: GetTeeTypeResponse = GetTeeTypeResponse {
+            tee: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetTeeTypeResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetTeeTypeResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetTeeTypeResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetTeeTypeResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetEventlogRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetEventlogRequest {
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetEventlogRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetEventlogRequest {
+    fn default() -> &'a GetEventlogRequest {
+        <GetEventlogRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetEventlogRequest {
+    pub fn new() -> GetEventlogRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetEventlogRequest>(
+            "GetEventlogRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetEventlogRequest {
+    const NAME: &'static str = "GetEventlogRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetEventlogRequest {
+        GetEventlogRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetEventlogRequest {
+        static instance: GetEventlogRequest = GetEventlogRequest {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetEventlogRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetEventlogRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetEventlogRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetEventlogRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetEventlogResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetEventlogResponse {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.GetEventlogResponse.Eventlog)
+    pub Eventlog: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetEventlogResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetEventlogResponse {
+    fn default() -> &'a GetEventlogResponse {
+        <GetEventlogResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetEventlogResponse {
+    pub fn new() -> GetEventlogResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Eventlog",
+            |m: &GetEventlogResponse| { &m.Eventlog },
+            |m: &mut GetEventlogResponse| { &mut m.Eventlog },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetEventlogResponse>(
+            "GetEventlogResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetEventlogResponse {
+    const NAME: &'static str = "GetEventlogResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Eventlog = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Eventlog.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.Eventlog);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Eventlog.is_empty() {
+            os.write_bytes(1, &self.Eventlog)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetEventlogResponse {
+        GetEventlogResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.Eventlog.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetEventlogResponse {
+        static instance: GetEventlogResponse = GetEventlogResponse {
+            Eventlog: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetEventlogResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetEventlogResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetEventlogResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetEventlogResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.VerifyEventlogRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct VerifyEventlogRequest {
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.VerifyEventlogRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a VerifyEventlogRequest {
+    fn default() -> &'a VerifyEventlogRequest {
+        <VerifyEventlogRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl VerifyEventlogRequest {
+    pub fn new() -> VerifyEventlogRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<VerifyEventlogRequest>(
+            "VerifyEventlogRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for VerifyEventlogRequest {
+    const NAME: &'static str = "VerifyEventlogRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> VerifyEventlogRequest {
+        VerifyEventlogRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static VerifyEventlogRequest {
+        static instance: VerifyEventlogRequest = VerifyEventlogRequest {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for VerifyEventlogRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("VerifyEventlogRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for VerifyEventlogRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for VerifyEventlogRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.RegisterReplayResult)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct RegisterReplayResult {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.RegisterReplayResult.RegisterIndex)
+    pub RegisterIndex: u32,
+    // @@protoc_insertion_point(field:attestation_agent.RegisterReplayResult.Replayed)
+    pub Replayed: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:attestation_agent.RegisterReplayResult.Actual)
+    pub Actual: ::std::option::Option<::std::vec::Vec<u8>>,
+    // @@protoc_insertion_point(field:attestation_agent.RegisterReplayResult.Matched)
+    pub Matched: bool,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.RegisterReplayResult.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a RegisterReplayResult {
+    fn default() -> &'a RegisterReplayResult {
+        <RegisterReplayResult as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl RegisterReplayResult {
+    pub fn new() -> RegisterReplayResult {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(4);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "RegisterIndex",
+            |m: &RegisterReplayResult| { &m.RegisterIndex },
+            |m: &mut RegisterReplayResult| { &mut m.RegisterIndex },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Replayed",
+            |m: &RegisterReplayResult| { &m.Replayed },
+            |m: &mut RegisterReplayResult| { &mut m.Replayed },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+            "Actual",
+            |m: &RegisterReplayResult| { &m.Actual },
+            |m: &mut RegisterReplayResult| { &mut m.Actual },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Matched",
+            |m: &RegisterReplayResult| { &m.Matched },
+            |m: &mut RegisterReplayResult| { &mut m.Matched },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<RegisterReplayResult>(
+            "RegisterReplayResult",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for RegisterReplayResult {
+    const NAME: &'static str = "RegisterReplayResult";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.RegisterIndex = is.read_uint32()?;
+                },
+                18 => {
+                    self.Replayed = is.read_bytes()?;
+                },
+                26 => {
+                    self.Actual = ::std::option::Option::Some(is.read_bytes()?);
+                },
+                32 => {
+                    self.Matched = is.read_bool()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.RegisterIndex != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.RegisterIndex);
+        }
+        if !self.Replayed.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.Replayed);
+        }
+        if let Some(v) = self.Actual.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(3, &v);
+        }
+        if self.Matched != false {
+            my_size += 1 + 1;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.RegisterIndex != 0 {
+            os.write_uint32(1, self.RegisterIndex)?;
+        }
+        if !self.Replayed.is_empty() {
+            os.write_bytes(2, &self.Replayed)?;
+        }
+        if let Some(v) = self.Actual.as_ref() {
+            os.write_bytes(3, v)?;
+        }
+        if self.Matched != false {
+            os.write_bool(4, self.Matched)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> RegisterReplayResult {
+        RegisterReplayResult::new()
+    }
+
+    fn clear(&mut self) {
+        self.RegisterIndex = 0;
+        self.Replayed.clear();
+        self.Actual = ::std::option::Option::None;
+        self.Matched = false;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static RegisterReplayResult {
+        static instance: RegisterReplayResult = RegisterReplayResult {
+            RegisterIndex: 0,
+            Replayed: ::std::vec::Vec::new(),
+            Actual: ::std::option::Option::None,
+            Matched: false,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for RegisterReplayResult {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("RegisterReplayResult").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for RegisterReplayResult {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RegisterReplayResult {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.VerifyEventlogResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct VerifyEventlogResponse {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.VerifyEventlogResponse.Verified)
+    pub Verified: bool,
+    // @@protoc_insertion_point(field:attestation_agent.VerifyEventlogResponse.HashAlgorithm)
+    pub HashAlgorithm: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.VerifyEventlogResponse.Registers)
+    pub Registers: ::std::vec::Vec<RegisterReplayResult>,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.VerifyEventlogResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a VerifyEventlogResponse {
+    fn default() -> &'a VerifyEventlogResponse {
+        <VerifyEventlogResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl VerifyEventlogResponse {
+    pub fn new() -> VerifyEventlogResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Verified",
+            |m: &VerifyEventlogResponse| { &m.Verified },
+            |m: &mut VerifyEventlogResponse| { &mut m.Verified },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "HashAlgorithm",
+            |m: &VerifyEventlogResponse| { &m.HashAlgorithm },
+            |m: &mut VerifyEventlogResponse| { &mut m.HashAlgorithm },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "Registers",
+            |m: &VerifyEventlogResponse| { &m.Registers },
+            |m: &mut VerifyEventlogResponse| { &mut m.Registers },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<VerifyEventlogResponse>(
+            "VerifyEventlogResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for VerifyEventlogResponse {
+    const NAME: &'static str = "VerifyEventlogResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.Verified = is.read_bool()?;
+                },
+                18 => {
+                    self.HashAlgorithm = is.read_string()?;
+                },
+                26 => {
+                    self.Registers.push(is.read_message()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.Verified != false {
+            my_size += 1 + 1;
+        }
+        if !self.HashAlgorithm.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.HashAlgorithm);
+        }
+        for value in &self.Registers {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.Verified != false {
+            os.write_bool(1, self.Verified)?;
+        }
+        if !self.HashAlgorithm.is_empty() {
+            os.write_string(2, &self.HashAlgorithm)?;
+        }
+        for v in &self.Registers {
+            ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> VerifyEventlogResponse {
+        VerifyEventlogResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.Verified = false;
+        self.HashAlgorithm.clear();
+        self.Registers.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static VerifyEventlogResponse {
+        static instance: VerifyEventlogResponse = VerifyEventlogResponse {
+            Verified: false,
+            HashAlgorithm: ::std::string::String::new(),
+            Registers: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for VerifyEventlogResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("VerifyEventlogResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for VerifyEventlogResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for VerifyEventlogResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetInitdataRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetInitdataRequest {
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetInitdataRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetInitdataRequest {
+    fn default() -> &'a GetInitdataRequest {
+        <GetInitdataRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetInitdataRequest {
+    pub fn new() -> GetInitdataRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetInitdataRequest>(
+            "GetInitdataRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetInitdataRequest {
+    const NAME: &'static str = "GetInitdataRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetInitdataRequest {
+        GetInitdataRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetInitdataRequest {
+        static instance: GetInitdataRequest = GetInitdataRequest {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetInitdataRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetInitdataRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetInitdataRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetInitdataRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetInitdataResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetInitdataResponse {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.GetInitdataResponse.Toml)
+    pub Toml: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.GetInitdataResponse.Digest)
+    pub Digest: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:attestation_agent.GetInitdataResponse.Algorithm)
+    pub Algorithm: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetInitdataResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetInitdataResponse {
+    fn default() -> &'a GetInitdataResponse {
+        <GetInitdataResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetInitdataResponse {
+    pub fn new() -> GetInitdataResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Toml",
+            |m: &GetInitdataResponse| { &m.Toml },
+            |m: &mut GetInitdataResponse| { &mut m.Toml },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Digest",
+            |m: &GetInitdataResponse| { &m.Digest },
+            |m: &mut GetInitdataResponse| { &mut m.Digest },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Algorithm",
+            |m: &GetInitdataResponse| { &m.Algorithm },
+            |m: &mut GetInitdataResponse| { &mut m.Algorithm },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetInitdataResponse>(
+            "GetInitdataResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetInitdataResponse {
+    const NAME: &'static str = "GetInitdataResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Toml = is.read_string()?;
+                },
+                18 => {
+                    self.Digest = is.read_bytes()?;
+                },
+                26 => {
+                    self.Algorithm = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Toml.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.Toml);
+        }
+        if !self.Digest.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.Digest);
+        }
+        if !self.Algorithm.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.Algorithm);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Toml.is_empty() {
+            os.write_string(1, &self.Toml)?;
+        }
+        if !self.Digest.is_empty() {
+            os.write_bytes(2, &self.Digest)?;
+        }
+        if !self.Algorithm.is_empty() {
+            os.write_string(3, &self.Algorithm)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetInitdataResponse {
+        GetInitdataResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.Toml.clear();
+        self.Digest.clear();
+        self.Algorithm.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetInitdataResponse {
+        static instance: GetInitdataResponse = GetInitdataResponse {
+            Toml: ::std::string::String::new(),
+            Digest: ::std::vec::Vec::new(),
+            Algorithm: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetInitdataResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetInitdataResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetInitdataResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetInitdataResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.UpdateConfigRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct UpdateConfigRequest {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.UpdateConfigRequest.Config)
+    pub Config: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.UpdateConfigRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a UpdateConfigRequest {
+    fn default() -> &'a UpdateConfigRequest {
+        <UpdateConfigRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl UpdateConfigRequest {
+    pub fn new() -> UpdateConfigRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Config",
+            |m: &UpdateConfigRequest| { &m.Config },
+            |m: &mut UpdateConfigRequest| { &mut m.Config },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<UpdateConfigRequest>(
+            "UpdateConfigRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for UpdateConfigRequest {
+    const NAME: &'static str = "UpdateConfigRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Config = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Config.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.Config);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Config.is_empty() {
+            os.write_string(1, &self.Config)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> UpdateConfigRequest {
+        UpdateConfigRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.Config.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static UpdateConfigRequest {
+        static instance: UpdateConfigRequest = UpdateConfigRequest {
+            Config: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for UpdateConfigRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("UpdateConfigRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for UpdateConfigRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for UpdateConfigRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.UpdateConfigResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct UpdateConfigResponse {
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.UpdateConfigResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a UpdateConfigResponse {
+    fn default() -> &'a UpdateConfigResponse {
+        <UpdateConfigResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl UpdateConfigResponse {
+    pub fn new() -> UpdateConfigResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<UpdateConfigResponse>(
+            "UpdateConfigResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for UpdateConfigResponse {
+    const NAME: &'static str = "UpdateConfigResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> UpdateConfigResponse {
+        UpdateConfigResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static UpdateConfigResponse {
+        static instance: UpdateConfigResponse = UpdateConfigResponse {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for UpdateConfigResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("UpdateConfigResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for UpdateConfigResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for UpdateConfigResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetPlatformReportRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetPlatformReportRequest {
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetPlatformReportRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetPlatformReportRequest {
+    fn default() -> &'a GetPlatformReportRequest {
+        <GetPlatformReportRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetPlatformReportRequest {
+    pub fn new() -> GetPlatformReportRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetPlatformReportRequest>(
+            "GetPlatformReportRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetPlatformReportRequest {
+    const NAME: &'static str = "GetPlatformReportRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetPlatformReportRequest {
+        GetPlatformReportRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetPlatformReportRequest {
+        static instance: GetPlatformReportRequest = GetPlatformReportRequest {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetPlatformReportRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetPlatformReportRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetPlatformReportRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetPlatformReportRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.CheckedPath)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct CheckedPath {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.CheckedPath.Path)
+    pub Path: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.CheckedPath.Exists)
+    pub Exists: bool,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.CheckedPath.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a CheckedPath {
+    fn default() -> &'a CheckedPath {
+        <CheckedPath as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl CheckedPath {
+    pub fn new() -> CheckedPath {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Path",
+            |m: &CheckedPath| { &m.Path },
+            |m: &mut CheckedPath| { &mut m.Path },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Exists",
+            |m: &CheckedPath| { &m.Exists },
+            |m: &mut CheckedPath| { &mut m.Exists },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<CheckedPath>(
+            "CheckedPath",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for CheckedPath {
+    const NAME: &'static str = "CheckedPath";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Path = is.read_string()?;
+                },
+                16 => {
+                    self.Exists = is.read_bool()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Path.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.Path);
+        }
+        if self.Exists != false {
+            my_size += 1 + 1;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Path.is_empty() {
+            os.write_string(1, &self.Path)?;
+        }
+        if self.Exists != false {
+            os.write_bool(2, self.Exists)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> CheckedPath {
+        CheckedPath::new()
+    }
+
+    fn clear(&mut self) {
+        self.Path.clear();
+        self.Exists = false;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static CheckedPath {
+        static instance: CheckedPath = CheckedPath {
+            Path: ::std::string::String::new(),
+            Exists: false,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for CheckedPath {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("CheckedPath").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for CheckedPath {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for CheckedPath {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.PlatformDiagnosis)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct PlatformDiagnosis {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.PlatformDiagnosis.Tee)
+    pub Tee: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.PlatformDiagnosis.Probed)
+    pub Probed: bool,
+    // @@protoc_insertion_point(field:attestation_agent.PlatformDiagnosis.Detected)
+    pub Detected: bool,
+    // @@protoc_insertion_point(field:attestation_agent.PlatformDiagnosis.Checked)
+    pub Checked: ::std::vec::Vec<CheckedPath>,
+    // @@protoc_insertion_point(field:attestation_agent.PlatformDiagnosis.Failure)
+    pub Failure: ::std::option::Option<::std::string::String>,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.PlatformDiagnosis.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a PlatformDiagnosis {
+    fn default() -> &'a PlatformDiagnosis {
+        <PlatformDiagnosis as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl PlatformDiagnosis {
+    pub fn new() -> PlatformDiagnosis {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(5);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Tee",
+            |m: &PlatformDiagnosis| { &m.Tee },
+            |m: &mut PlatformDiagnosis| { &mut m.Tee },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Probed",
+            |m: &PlatformDiagnosis| { &m.Probed },
+            |m: &mut PlatformDiagnosis| { &mut m.Probed },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Detected",
+            |m: &PlatformDiagnosis| { &m.Detected },
+            |m: &mut PlatformDiagnosis| { &mut m.Detected },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "Checked",
+            |m: &PlatformDiagnosis| { &m.Checked },
+            |m: &mut PlatformDiagnosis| { &mut m.Checked },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_option_accessor::<_, _>(
+            "Failure",
+            |m: &PlatformDiagnosis| { &m.Failure },
+            |m: &mut PlatformDiagnosis| { &mut m.Failure },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<PlatformDiagnosis>(
+            "PlatformDiagnosis",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for PlatformDiagnosis {
+    const NAME: &'static str = "PlatformDiagnosis";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Tee = is.read_string()?;
+                },
+                16 => {
+                    self.Probed = is.read_bool()?;
+                },
+                24 => {
+                    self.Detected = is.read_bool()?;
+                },
+                34 => {
+                    self.Checked.push(is.read_message()?);
+                },
+                42 => {
+                    self.Failure = ::std::option::Option::Some(is.read_string()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Tee.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.Tee);
+        }
+        if self.Probed != false {
+            my_size += 1 + 1;
+        }
+        if self.Detected != false {
+            my_size += 1 + 1;
+        }
+        for value in &self.Checked {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        if let Some(v) = self.Failure.as_ref() {
+            my_size += ::protobuf::rt::string_size(5, &v);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Tee.is_empty() {
+            os.write_string(1, &self.Tee)?;
+        }
+        if self.Probed != false {
+            os.write_bool(2, self.Probed)?;
+        }
+        if self.Detected != false {
+            os.write_bool(3, self.Detected)?;
+        }
+        for v in &self.Checked {
+            ::protobuf::rt::write_message_field_with_cached_size(4, v, os)?;
+        };
+        if let Some(v) = self.Failure.as_ref() {
+            os.write_string(5, v)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> PlatformDiagnosis {
+        PlatformDiagnosis::new()
+    }
+
+    fn clear(&mut self) {
+        self.Tee.clear();
+        self.Probed = false;
+        self.Detected = false;
+        self.Checked.clear();
+        self.Failure = ::std::option::Option::None;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static PlatformDiagnosis {
+        static instance: PlatformDiagnosis = PlatformDiagnosis {
+            Tee: ::std::string::String::new(),
+            Probed: false,
+            Detected: false,
+            Checked: ::std::vec::Vec::new(),
+            Failure: ::std::option::Option::None,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for PlatformDiagnosis {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("PlatformDiagnosis").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for PlatformDiagnosis {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PlatformDiagnosis {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetPlatformReportResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetPlatformReportResponse {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.GetPlatformReportResponse.Tee)
+    pub Tee: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.GetPlatformReportResponse.SampleFallback)
+    pub SampleFallback: bool,
+    // @@protoc_insertion_point(field:attestation_agent.GetPlatformReportResponse.Platforms)
+    pub Platforms: ::std::vec::Vec<PlatformDiagnosis>,
+    // @@protoc_insertion_point(field:attestation_agent.GetPlatformReportResponse.Devices)
+    pub Devices: ::std::vec::Vec<PlatformDiagnosis>,
+    // @@protoc_insertion_point(field:attestation_agent.GetPlatformReportResponse.NotCompiledIn)
+    pub NotCompiledIn: ::std::vec::Vec<::std::string::String>,
+    // @@protoc_insertion_point(field:attestation_agent.GetPlatformReportResponse.PrimaryTee)
+    pub PrimaryTee: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetPlatformReportResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetPlatformReportResponse {
+    fn default() -> &'a GetPlatformReportResponse {
+        <GetPlatformReportResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetPlatformReportResponse {
+    pub fn new() -> GetPlatformReportResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(6);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Tee",
+            |m: &GetPlatformReportResponse| { &m.Tee },
+            |m: &mut GetPlatformReportResponse| { &mut m.Tee },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "SampleFallback",
+            |m: &GetPlatformReportResponse| { &m.SampleFallback },
+            |m: &mut GetPlatformReportResponse| { &mut m.SampleFallback },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "Platforms",
+            |m: &GetPlatformReportResponse| { &m.Platforms },
+            |m: &mut GetPlatformReportResponse| { &mut m.Platforms },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "Devices",
+            |m: &GetPlatformReportResponse| { &m.Devices },
+            |m: &mut GetPlatformReportResponse| { &mut m.Devices },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "NotCompiledIn",
+            |m: &GetPlatformReportResponse| { &m.NotCompiledIn },
+            |m: &mut GetPlatformReportResponse| { &mut m.NotCompiledIn },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "PrimaryTee",
+            |m: &GetPlatformReportResponse| { &m.PrimaryTee },
+            |m: &mut GetPlatformReportResponse| { &mut m.PrimaryTee },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetPlatformReportResponse>(
+            "GetPlatformReportResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetPlatformReportResponse {
+    const NAME: &'static str = "GetPlatformReportResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Tee = is.read_string()?;
+                },
+                16 => {
+                    self.SampleFallback = is.read_bool()?;
+                },
+                26 => {
+                    self.Platforms.push(is.read_message()?);
+                },
+                34 => {
+                    self.Devices.push(is.read_message()?);
+                },
+                42 => {
+                    self.NotCompiledIn.push(is.read_string()?);
+                },
+                50 => {
+                    self.PrimaryTee = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Tee.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.Tee);
+        }
+        if self.SampleFallback != false {
+            my_size += 1 + 1;
+        }
+        for value in &self.Platforms {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        for value in &self.Devices {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        for value in &self.NotCompiledIn {
+            my_size += ::protobuf::rt::string_size(5, &value);
+        };
+        if !self.PrimaryTee.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.PrimaryTee);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Tee.is_empty() {
+            os.write_string(1, &self.Tee)?;
+        }
+        if self.SampleFallback != false {
+            os.write_bool(2, self.SampleFallback)?;
+        }
+        for v in &self.Platforms {
+            ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
+        };
+        for v in &self.Devices {
+            ::protobuf::rt::write_message_field_with_cached_size(4, v, os)?;
+        };
+        for v in &self.NotCompiledIn {
+            os.write_string(5, &v)?;
+        };
+        if !self.PrimaryTee.is_empty() {
+            os.write_string(6, &self.PrimaryTee)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetPlatformReportResponse {
+        GetPlatformReportResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.Tee.clear();
+        self.SampleFallback = false;
+        self.Platforms.clear();
+        self.Devices.clear();
+        self.NotCompiledIn.clear();
+        self.PrimaryTee.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetPlatformReportResponse {
+        static instance: GetPlatformReportResponse = GetPlatformReportResponse {
+            Tee: ::std::string::String::new(),
+            SampleFallback: false,
+            Platforms: ::std::vec::Vec::new(),
+            Devices: ::std::vec::Vec::new(),
+            NotCompiledIn: ::std::vec::Vec::new(),
+            PrimaryTee: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetPlatformReportResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetPlatformReportResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetPlatformReportResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetPlatformReportResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetEvidenceMetricsRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetEvidenceMetricsRequest {
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetEvidenceMetricsRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetEvidenceMetricsRequest {
+    fn default() -> &'a GetEvidenceMetricsRequest {
+        <GetEvidenceMetricsRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetEvidenceMetricsRequest {
+    pub fn new() -> GetEvidenceMetricsRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetEvidenceMetricsRequest>(
+            "GetEvidenceMetricsRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetEvidenceMetricsRequest {
+    const NAME: &'static str = "GetEvidenceMetricsRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetEvidenceMetricsRequest {
+        GetEvidenceMetricsRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetEvidenceMetricsRequest {
+        static instance: GetEvidenceMetricsRequest = GetEvidenceMetricsRequest {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetEvidenceMetricsRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetEvidenceMetricsRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetEvidenceMetricsRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetEvidenceMetricsRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.EvidenceMetrics)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct EvidenceMetrics {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.EvidenceMetrics.Tee)
+    pub Tee: ::std::string::String,
+    // @@protoc_insertion_point(field:attestation_agent.EvidenceMetrics.Generations)
+    pub Generations: u64,
+    // @@protoc_insertion_point(field:attestation_agent.EvidenceMetrics.Failures)
+    pub Failures: u64,
+    // @@protoc_insertion_point(field:attestation_agent.EvidenceMetrics.CacheHits)
+    pub CacheHits: u64,
+    // @@protoc_insertion_point(field:attestation_agent.EvidenceMetrics.TotalLatencyMs)
+    pub TotalLatencyMs: u64,
+    // @@protoc_insertion_point(field:attestation_agent.EvidenceMetrics.MaxLatencyMs)
+    pub MaxLatencyMs: u64,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.EvidenceMetrics.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a EvidenceMetrics {
+    fn default() -> &'a EvidenceMetrics {
+        <EvidenceMetrics as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl EvidenceMetrics {
+    pub fn new() -> EvidenceMetrics {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(6);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Tee",
+            |m: &EvidenceMetrics| { &m.Tee },
+            |m: &mut EvidenceMetrics| { &mut m.Tee },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Generations",
+            |m: &EvidenceMetrics| { &m.Generations },
+            |m: &mut EvidenceMetrics| { &mut m.Generations },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Failures",
+            |m: &EvidenceMetrics| { &m.Failures },
+            |m: &mut EvidenceMetrics| { &mut m.Failures },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "CacheHits",
+            |m: &EvidenceMetrics| { &m.CacheHits },
+            |m: &mut EvidenceMetrics| { &mut m.CacheHits },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "TotalLatencyMs",
+            |m: &EvidenceMetrics| { &m.TotalLatencyMs },
+            |m: &mut EvidenceMetrics| { &mut m.TotalLatencyMs },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "MaxLatencyMs",
+            |m: &EvidenceMetrics| { &m.MaxLatencyMs },
+            |m: &mut EvidenceMetrics| { &mut m.MaxLatencyMs },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<EvidenceMetrics>(
+            "EvidenceMetrics",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for EvidenceMetrics {
+    const NAME: &'static str = "EvidenceMetrics";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Tee = is.read_string()?;
+                },
+                16 => {
+                    self.Generations = is.read_uint64()?;
+                },
+                24 => {
+                    self.Failures = is.read_uint64()?;
+                },
+                32 => {
+                    self.CacheHits = is.read_uint64()?;
+                },
+                40 => {
+                    self.TotalLatencyMs = is.read_uint64()?;
+                },
+                48 => {
+                    self.MaxLatencyMs = is.read_uint64()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.Tee.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.Tee);
+        }
+        if self.Generations != 0 {
+            my_size += ::protobuf::rt::uint64_size(2, self.Generations);
+        }
+        if self.Failures != 0 {
+            my_size += ::protobuf::rt::uint64_size(3, self.Failures);
+        }
+        if self.CacheHits != 0 {
+            my_size += ::protobuf::rt::uint64_size(4, self.CacheHits);
+        }
+        if self.TotalLatencyMs != 0 {
+            my_size += ::protobuf::rt::uint64_size(5, self.TotalLatencyMs);
+        }
+        if self.MaxLatencyMs != 0 {
+            my_size += ::protobuf::rt::uint64_size(6, self.MaxLatencyMs);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.Tee.is_empty() {
+            os.write_string(1, &self.Tee)?;
+        }
+        if self.Generations != 0 {
+            os.write_uint64(2, self.Generations)?;
+        }
+        if self.Failures != 0 {
+            os.write_uint64(3, self.Failures)?;
+        }
+        if self.CacheHits != 0 {
+            os.write_uint64(4, self.CacheHits)?;
+        }
+        if self.TotalLatencyMs != 0 {
+            os.write_uint64(5, self.TotalLatencyMs)?;
+        }
+        if self.MaxLatencyMs != 0 {
+            os.write_uint64(6, self.MaxLatencyMs)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> EvidenceMetrics {
+        EvidenceMetrics::new()
+    }
+
+    fn clear(&mut self) {
+        self.Tee.clear();
+        self.Generations = 0;
+        self.Failures = 0;
+        self.CacheHits = 0;
+        self.TotalLatencyMs = 0;
+        self.MaxLatencyMs = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static EvidenceMetrics {
+        static instance: EvidenceMetrics = EvidenceMetrics {
+            Tee: ::std::string::String::new(),
+            Generations: 0,
+            Failures: 0,
+            CacheHits: 0,
+            TotalLatencyMs: 0,
+            MaxLatencyMs: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for EvidenceMetrics {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("EvidenceMetrics").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for EvidenceMetrics {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EvidenceMetrics {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:attestation_agent.GetEvidenceMetricsResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct GetEvidenceMetricsResponse {
+    // message fields
+    // @@protoc_insertion_point(field:attestation_agent.GetEvidenceMetricsResponse.Attesters)
+    pub Attesters: ::std::vec::Vec<EvidenceMetrics>,
+    // special fields
+    // @@protoc_insertion_point(special_field:attestation_agent.GetEvidenceMetricsResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a GetEvidenceMetricsResponse {
+    fn default() -> &'a GetEvidenceMetricsResponse {
+        <GetEvidenceMetricsResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GetEvidenceMetricsResponse {
+    pub fn new() -> GetEvidenceMetricsResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "Attesters",
+            |m: &GetEvidenceMetricsResponse| { &m.Attesters },
+            |m: &mut GetEvidenceMetricsResponse| { &mut m.Attesters },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetEvidenceMetricsResponse>(
+            "GetEvidenceMetricsResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for GetEvidenceMetricsResponse {
+    const NAME: &'static str = "GetEvidenceMetricsResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.Attesters.push(is.read_message()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.Attesters {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.Attesters {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> GetEvidenceMetricsResponse {
+        GetEvidenceMetricsResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.Attesters.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static GetEvidenceMetricsResponse {
+        static instance: GetEvidenceMetricsResponse = GetEvidenceMetricsResponse {
+            Attesters: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for GetEvidenceMetricsResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("GetEvidenceMetricsResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for GetEvidenceMetricsResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetEvidenceMetricsResponse {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
@@ -1454,30 +5265,101 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     ceRequest\x12\x20\n\x0bRuntimeData\x18\x01\x20\x01(\x0cR\x0bRuntimeData\
     \"@\n\x1cGetAdditionalEvidenceRequest\x12\x20\n\x0bRuntimeData\x18\x01\
     \x20\x01(\x0cR\x0bRuntimeData\"1\n\x13GetEvidenceResponse\x12\x1a\n\x08E\
-    vidence\x18\x01\x20\x01(\x0cR\x08Evidence\"/\n\x0fGetTokenRequest\x12\
-    \x1c\n\tTokenType\x18\x01\x20\x01(\tR\tTokenType\"(\n\x10GetTokenRespons\
-    e\x12\x14\n\x05Token\x18\x01\x20\x01(\x0cR\x05Token\"\xae\x01\n\x1fExten\
-    dRuntimeMeasurementRequest\x12\x16\n\x06Domain\x18\x01\x20\x01(\tR\x06Do\
-    main\x12\x1c\n\tOperation\x18\x02\x20\x01(\tR\tOperation\x12\x18\n\x07Co\
-    ntent\x18\x03\x20\x01(\tR\x07Content\x12)\n\rRegisterIndex\x18\x04\x20\
-    \x01(\x04H\0R\rRegisterIndex\x88\x01\x01B\x10\n\x0e_RegisterIndex\"g\n\
-    \x20ExtendRuntimeMeasurementResponse\x12C\n\x06Result\x18\x01\x20\x01(\
-    \x0e2+.attestation_agent.RuntimeMeasurementResultR\x06Result\"-\n\x13Bin\
-    dInitDataRequest\x12\x16\n\x06Digest\x18\x01\x20\x01(\x0cR\x06Digest\"\
-    \x16\n\x14BindInitDataResponse\"\x13\n\x11GetTeeTypeRequest\"&\n\x12GetT\
-    eeTypeResponse\x12\x10\n\x03tee\x18\x01\x20\x01(\tR\x03tee*F\n\x18Runtim\
-    eMeasurementResult\x12\x06\n\x02OK\x10\0\x12\x11\n\rNOT_SUPPORTED\x10\
-    \x01\x12\x0f\n\x0bNOT_ENABLED\x10\x022\x80\x05\n\x17AttestationAgentServ\
-    ice\x12\\\n\x0bGetEvidence\x12%.attestation_agent.GetEvidenceRequest\x1a\
-    &.attestation_agent.GetEvidenceResponse\x12p\n\x15GetAdditionalEvidence\
-    \x12/.attestation_agent.GetAdditionalEvidenceRequest\x1a&.attestation_ag\
-    ent.GetEvidenceResponse\x12S\n\x08GetToken\x12\".attestation_agent.GetTo\
-    kenRequest\x1a#.attestation_agent.GetTokenResponse\x12\x83\x01\n\x18Exte\
-    ndRuntimeMeasurement\x122.attestation_agent.ExtendRuntimeMeasurementRequ\
-    est\x1a3.attestation_agent.ExtendRuntimeMeasurementResponse\x12_\n\x0cBi\
-    ndInitData\x12&.attestation_agent.BindInitDataRequest\x1a'.attestation_a\
-    gent.BindInitDataResponse\x12Y\n\nGetTeeType\x12$.attestation_agent.GetT\
-    eeTypeRequest\x1a%.attestation_agent.GetTeeTypeResponseb\x06proto3\
+    vidence\x18\x01\x20\x01(\x0cR\x08Evidence\"I\n\x0fGetTokenRequest\x12\
+    \x1c\n\tTokenType\x18\x01\x20\x01(\tR\tTokenType\x12\x18\n\x07Refresh\
+    \x18\x02\x20\x01(\x08R\x07Refresh\"(\n\x10GetTokenResponse\x12\x14\n\x05\
+    Token\x18\x01\x20\x01(\x0cR\x05Token\"\xae\x01\n\x1fExtendRuntimeMeasure\
+    mentRequest\x12\x16\n\x06Domain\x18\x01\x20\x01(\tR\x06Domain\x12\x1c\n\
+    \tOperation\x18\x02\x20\x01(\tR\tOperation\x12\x18\n\x07Content\x18\x03\
+    \x20\x01(\tR\x07Content\x12)\n\rRegisterIndex\x18\x04\x20\x01(\x04H\0R\r\
+    RegisterIndex\x88\x01\x01B\x10\n\x0e_RegisterIndex\"g\n\x20ExtendRuntime\
+    MeasurementResponse\x12C\n\x06Result\x18\x01\x20\x01(\x0e2+.attestation_\
+    agent.RuntimeMeasurementResultR\x06Result\"^\n\x0cGenericEvent\x12\x16\n\
+    \x06Domain\x18\x01\x20\x01(\tR\x06Domain\x12\x1c\n\tOperation\x18\x02\
+    \x20\x01(\tR\tOperation\x12\x18\n\x07Content\x18\x03\x20\x01(\tR\x07Cont\
+    ent\">\n\x0ePullImageEvent\x12\x14\n\x05Image\x18\x01\x20\x01(\tR\x05Ima\
+    ge\x12\x16\n\x06Digest\x18\x02\x20\x01(\tR\x06Digest\"R\n\x10SecureMount\
+    Event\x12\x1e\n\nVolumeType\x18\x01\x20\x01(\tR\nVolumeType\x12\x1e\n\nM\
+    ountPoint\x18\x02\x20\x01(\tR\nMountPoint\")\n\x11UnsealSecretEvent\x12\
+    \x14\n\x05KeyId\x18\x01\x20\x01(\tR\x05KeyId\"4\n\x10GetResourceEvent\
+    \x12\x20\n\x0bResourceUri\x18\x01\x20\x01(\tR\x0bResourceUri\"\xbd\x03\n\
+    \x17RuntimeMeasurementEvent\x12;\n\x07Generic\x18\x01\x20\x01(\x0b2\x1f.\
+    attestation_agent.GenericEventH\0R\x07Generic\x12A\n\tPullImage\x18\x02\
+    \x20\x01(\x0b2!.attestation_agent.PullImageEventH\0R\tPullImage\x12G\n\
+    \x0bSecureMount\x18\x03\x20\x01(\x0b2#.attestation_agent.SecureMountEven\
+    tH\0R\x0bSecureMount\x12J\n\x0cUnsealSecret\x18\x04\x20\x01(\x0b2$.attes\
+    tation_agent.UnsealSecretEventH\0R\x0cUnsealSecret\x12G\n\x0bGetResource\
+    \x18\x05\x20\x01(\x0b2#.attestation_agent.GetResourceEventH\0R\x0bGetRes\
+    ource\x12)\n\rRegisterIndex\x18\x06\x20\x01(\x04H\x01R\rRegisterIndex\
+    \x88\x01\x01B\x07\n\x05EventB\x10\n\x0e_RegisterIndex\"f\n\x20ExtendRunt\
+    imeMeasurementsRequest\x12B\n\x06Events\x18\x01\x20\x03(\x0b2*.attestati\
+    on_agent.RuntimeMeasurementEventR\x06Events\"-\n\x13BindInitDataRequest\
+    \x12\x16\n\x06Digest\x18\x01\x20\x01(\x0cR\x06Digest\"\x16\n\x14BindInit\
+    DataResponse\"3\n\x17DeriveSealingKeyRequest\x12\x18\n\x07Purpose\x18\
+    \x01\x20\x01(\tR\x07Purpose\",\n\x18DeriveSealingKeyResponse\x12\x10\n\
+    \x03Key\x18\x01\x20\x01(\x0cR\x03Key\"\x13\n\x11GetTeeTypeRequest\"&\n\
+    \x12GetTeeTypeResponse\x12\x10\n\x03tee\x18\x01\x20\x01(\tR\x03tee\"\x14\
+    \n\x12GetEventlogRequest\"1\n\x13GetEventlogResponse\x12\x1a\n\x08Eventl\
+    og\x18\x01\x20\x01(\x0cR\x08Eventlog\"\x17\n\x15VerifyEventlogRequest\"\
+    \x9a\x01\n\x14RegisterReplayResult\x12$\n\rRegisterIndex\x18\x01\x20\x01\
+    (\rR\rRegisterIndex\x12\x1a\n\x08Replayed\x18\x02\x20\x01(\x0cR\x08Repla\
+    yed\x12\x1b\n\x06Actual\x18\x03\x20\x01(\x0cH\0R\x06Actual\x88\x01\x01\
+    \x12\x18\n\x07Matched\x18\x04\x20\x01(\x08R\x07MatchedB\t\n\x07_Actual\"\
+    \xa1\x01\n\x16VerifyEventlogResponse\x12\x1a\n\x08Verified\x18\x01\x20\
+    \x01(\x08R\x08Verified\x12$\n\rHashAlgorithm\x18\x02\x20\x01(\tR\rHashAl\
+    gorithm\x12E\n\tRegisters\x18\x03\x20\x03(\x0b2'.attestation_agent.Regis\
+    terReplayResultR\tRegisters\"\x14\n\x12GetInitdataRequest\"_\n\x13GetIni\
+    tdataResponse\x12\x12\n\x04Toml\x18\x01\x20\x01(\tR\x04Toml\x12\x16\n\
+    \x06Digest\x18\x02\x20\x01(\x0cR\x06Digest\x12\x1c\n\tAlgorithm\x18\x03\
+    \x20\x01(\tR\tAlgorithm\"-\n\x13UpdateConfigRequest\x12\x16\n\x06Config\
+    \x18\x01\x20\x01(\tR\x06Config\"\x16\n\x14UpdateConfigResponse\"\x1a\n\
+    \x18GetPlatformReportRequest\"9\n\x0bCheckedPath\x12\x12\n\x04Path\x18\
+    \x01\x20\x01(\tR\x04Path\x12\x16\n\x06Exists\x18\x02\x20\x01(\x08R\x06Ex\
+    ists\"\xbe\x01\n\x11PlatformDiagnosis\x12\x10\n\x03Tee\x18\x01\x20\x01(\
+    \tR\x03Tee\x12\x16\n\x06Probed\x18\x02\x20\x01(\x08R\x06Probed\x12\x1a\n\
+    \x08Detected\x18\x03\x20\x01(\x08R\x08Detected\x128\n\x07Checked\x18\x04\
+    \x20\x03(\x0b2\x1e.attestation_agent.CheckedPathR\x07Checked\x12\x1d\n\
+    \x07Failure\x18\x05\x20\x01(\tH\0R\x07Failure\x88\x01\x01B\n\n\x08_Failu\
+    re\"\x9f\x02\n\x19GetPlatformReportResponse\x12\x10\n\x03Tee\x18\x01\x20\
+    \x01(\tR\x03Tee\x12&\n\x0eSampleFallback\x18\x02\x20\x01(\x08R\x0eSample\
+    Fallback\x12B\n\tPlatforms\x18\x03\x20\x03(\x0b2$.attestation_agent.Plat\
+    formDiagnosisR\tPlatforms\x12>\n\x07Devices\x18\x04\x20\x03(\x0b2$.attes\
+    tation_agent.PlatformDiagnosisR\x07Devices\x12$\n\rNotCompiledIn\x18\x05\
+    \x20\x03(\tR\rNotCompiledIn\x12\x1e\n\nPrimaryTee\x18\x06\x20\x01(\tR\nP\
+    rimaryTee\"\x1b\n\x19GetEvidenceMetricsRequest\"\xcb\x01\n\x0fEvidenceMe\
+    trics\x12\x10\n\x03Tee\x18\x01\x20\x01(\tR\x03Tee\x12\x20\n\x0bGeneratio\
+    ns\x18\x02\x20\x01(\x04R\x0bGenerations\x12\x1a\n\x08Failures\x18\x03\
+    \x20\x01(\x04R\x08Failures\x12\x1c\n\tCacheHits\x18\x04\x20\x01(\x04R\tC\
+    acheHits\x12&\n\x0eTotalLatencyMs\x18\x05\x20\x01(\x04R\x0eTotalLatencyM\
+    s\x12\"\n\x0cMaxLatencyMs\x18\x06\x20\x01(\x04R\x0cMaxLatencyMs\"^\n\x1a\
+    GetEvidenceMetricsResponse\x12@\n\tAttesters\x18\x01\x20\x03(\x0b2\".att\
+    estation_agent.EvidenceMetricsR\tAttesters*F\n\x18RuntimeMeasurementResu\
+    lt\x12\x06\n\x02OK\x10\0\x12\x11\n\rNOT_SUPPORTED\x10\x01\x12\x0f\n\x0bN\
+    OT_ENABLED\x10\x022\xdc\x0b\n\x17AttestationAgentService\x12\\\n\x0bGetE\
+    vidence\x12%.attestation_agent.GetEvidenceRequest\x1a&.attestation_agent\
+    .GetEvidenceResponse\x12p\n\x15GetAdditionalEvidence\x12/.attestation_ag\
+    ent.GetAdditionalEvidenceRequest\x1a&.attestation_agent.GetEvidenceRespo\
+    nse\x12S\n\x08GetToken\x12\".attestation_agent.GetTokenRequest\x1a#.atte\
+    station_agent.GetTokenResponse\x12\x83\x01\n\x18ExtendRuntimeMeasurement\
+    \x122.attestation_agent.ExtendRuntimeMeasurementRequest\x1a3.attestation\
+    _agent.ExtendRuntimeMeasurementResponse\x12\x85\x01\n\x19ExtendRuntimeMe\
+    asurements\x123.attestation_agent.ExtendRuntimeMeasurementsRequest\x1a3.\
+    attestation_agent.ExtendRuntimeMeasurementResponse\x12_\n\x0cBindInitDat\
+    a\x12&.attestation_agent.BindInitDataRequest\x1a'.attestation_agent.Bind\
+    InitDataResponse\x12k\n\x10DeriveSealingKey\x12*.attestation_agent.Deriv\
+    eSealingKeyRequest\x1a+.attestation_agent.DeriveSealingKeyResponse\x12Y\
+    \n\nGetTeeType\x12$.attestation_agent.GetTeeTypeRequest\x1a%.attestation\
+    _agent.GetTeeTypeResponse\x12\\\n\x0bGetEventlog\x12%.attestation_agent.\
+    GetEventlogRequest\x1a&.attestation_agent.GetEventlogResponse\x12e\n\x0e\
+    VerifyEventlog\x12(.attestation_agent.VerifyEventlogRequest\x1a).attesta\
+    tion_agent.VerifyEventlogResponse\x12\\\n\x0bGetInitdata\x12%.attestatio\
+    n_agent.GetInitdataRequest\x1a&.attestation_agent.GetInitdataResponse\
+    \x12_\n\x0cUpdateConfig\x12&.attestation_agent.UpdateConfigRequest\x1a'.\
+    attestation_agent.UpdateConfigResponse\x12n\n\x11GetPlatformReport\x12+.\
+    attestation_agent.GetPlatformReportRequest\x1a,.attestation_agent.GetPla\
+    tformReportResponse\x12q\n\x12GetEvidenceMetrics\x12,.attestation_agent.\
+    GetEvidenceMetricsRequest\x1a-.attestation_agent.GetEvidenceMetricsRespo\
+    nseb\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -1495,7 +5377,7 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
     file_descriptor.get(|| {
         let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
             let mut deps = ::std::vec::Vec::with_capacity(0);
-            let mut messages = ::std::vec::Vec::with_capacity(11);
+            let mut messages = ::std::vec::Vec::with_capacity(36);
             messages.push(GetEvidenceRequest::generated_message_descriptor_data());
             messages.push(GetAdditionalEvidenceRequest::generated_message_descriptor_data());
             messages.push(GetEvidenceResponse::generated_message_descriptor_data());
@@ -1503,10 +5385,35 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
             messages.push(GetTokenResponse::generated_message_descriptor_data());
             messages.push(ExtendRuntimeMeasurementRequest::generated_message_descriptor_data());
             messages.push(ExtendRuntimeMeasurementResponse::generated_message_descriptor_data());
+            messages.push(GenericEvent::generated_message_descriptor_data());
+            messages.push(PullImageEvent::generated_message_descriptor_data());
+            messages.push(SecureMountEvent::generated_message_descriptor_data());
+            messages.push(UnsealSecretEvent::generated_message_descriptor_data());
+            messages.push(GetResourceEvent::generated_message_descriptor_data());
+            messages.push(RuntimeMeasurementEvent::generated_message_descriptor_data());
+            messages.push(ExtendRuntimeMeasurementsRequest::generated_message_descriptor_data());
             messages.push(BindInitDataRequest::generated_message_descriptor_data());
             messages.push(BindInitDataResponse::generated_message_descriptor_data());
+            messages.push(DeriveSealingKeyRequest::generated_message_descriptor_data());
+            messages.push(DeriveSealingKeyResponse::generated_message_descriptor_data());
             messages.push(GetTeeTypeRequest::generated_message_descriptor_data());
             messages.push(GetTeeTypeResponse::generated_message_descriptor_data());
+            messages.push(GetEventlogRequest::generated_message_descriptor_data());
+            messages.push(GetEventlogResponse::generated_message_descriptor_data());
+            messages.push(VerifyEventlogRequest::generated_message_descriptor_data());
+            messages.push(RegisterReplayResult::generated_message_descriptor_data());
+            messages.push(VerifyEventlogResponse::generated_message_descriptor_data());
+            messages.push(GetInitdataRequest::generated_message_descriptor_data());
+            messages.push(GetInitdataResponse::generated_message_descriptor_data());
+            messages.push(UpdateConfigRequest::generated_message_descriptor_data());
+            messages.push(UpdateConfigResponse::generated_message_descriptor_data());
+            messages.push(GetPlatformReportRequest::generated_message_descriptor_data());
+            messages.push(CheckedPath::generated_message_descriptor_data());
+            messages.push(PlatformDiagnosis::generated_message_descriptor_data());
+            messages.push(GetPlatformReportResponse::generated_message_descriptor_data());
+            messages.push(GetEvidenceMetricsRequest::generated_message_descriptor_data());
+            messages.push(EvidenceMetrics::generated_message_descriptor_data());
+            messages.push(GetEvidenceMetricsResponse::generated_message_descriptor_data());
             let mut enums = ::std::vec::Vec::with_capacity(1);
             enums.push(RuntimeMeasurementResult::generated_enum_descriptor_data());
             ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
@@ -51,15 +51,55 @@ impl AttestationAgentServiceClient {
         ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "ExtendRuntimeMeasurement", cres);
     }
 
+    pub async fn extend_runtime_measurements(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::ExtendRuntimeMeasurementsRequest) -> ::ttrpc::Result<super::attestation_agent::ExtendRuntimeMeasurementResponse> {
+        let mut cres = super::attestation_agent::ExtendRuntimeMeasurementResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "ExtendRuntimeMeasurements", cres);
+    }
+
     pub async fn bind_init_data(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::BindInitDataRequest) -> ::ttrpc::Result<super::attestation_agent::BindInitDataResponse> {
         let mut cres = super::attestation_agent::BindInitDataResponse::new();
         ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "BindInitData", cres);
     }
 
+    pub async fn derive_sealing_key(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::DeriveSealingKeyRequest) -> ::ttrpc::Result<super::attestation_agent::DeriveSealingKeyResponse> {
+        let mut cres = super::attestation_agent::DeriveSealingKeyResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "DeriveSealingKey", cres);
+    }
+
     pub async fn get_tee_type(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::GetTeeTypeRequest) -> ::ttrpc::Result<super::attestation_agent::GetTeeTypeResponse> {
         let mut cres = super::attestation_agent::GetTeeTypeResponse::new();
         ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "GetTeeType", cres);
     }
+
+    pub async fn get_eventlog(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::GetEventlogRequest) -> ::ttrpc::Result<super::attestation_agent::GetEventlogResponse> {
+        let mut cres = super::attestation_agent::GetEventlogResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "GetEventlog", cres);
+    }
+
+    pub async fn verify_eventlog(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::VerifyEventlogRequest) -> ::ttrpc::Result<super::attestation_agent::VerifyEventlogResponse> {
+        let mut cres = super::attestation_agent::VerifyEventlogResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "VerifyEventlog", cres);
+    }
+
+    pub async fn get_initdata(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::GetInitdataRequest) -> ::ttrpc::Result<super::attestation_agent::GetInitdataResponse> {
+        let mut cres = super::attestation_agent::GetInitdataResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "GetInitdata", cres);
+    }
+
+    pub async fn update_config(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::UpdateConfigRequest) -> ::ttrpc::Result<super::attestation_agent::UpdateConfigResponse> {
+        let mut cres = super::attestation_agent::UpdateConfigResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "UpdateConfig", cres);
+    }
+
+    pub async fn get_platform_report(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::GetPlatformReportRequest) -> ::ttrpc::Result<super::attestation_agent::GetPlatformReportResponse> {
+        let mut cres = super::attestation_agent::GetPlatformReportResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "GetPlatformReport", cres);
+    }
+
+    pub async fn get_evidence_metrics(&self, ctx: ttrpc::context::Context, req: &super::attestation_agent::GetEvidenceMetricsRequest) -> ::ttrpc::Result<super::attestation_agent::GetEvidenceMetricsResponse> {
+        let mut cres = super::attestation_agent::GetEvidenceMetricsResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "attestation_agent.AttestationAgentService", "GetEvidenceMetrics", cres);
+    }
 }
 
 struct GetEvidenceMethod {
@@ -106,6 +146,17 @@ impl ::ttrpc::r#async::MethodHandler for ExtendRuntimeMeasurementMethod {
     }
 }
 
+struct ExtendRuntimeMeasurementsMethod {
+    service: Arc<dyn AttestationAgentService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for ExtendRuntimeMeasurementsMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, attestation_agent, ExtendRuntimeMeasurementsRequest, extend_runtime_measurements);
+    }
+}
+
 struct BindInitDataMethod {
     service: Arc<dyn AttestationAgentService + Send + Sync>,
 }
@@ -117,6 +168,17 @@ impl ::ttrpc::r#async::MethodHandler for BindInitDataMethod {
     }
 }
 
+struct DeriveSealingKeyMethod {
+    service: Arc<dyn AttestationAgentService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for DeriveSealingKeyMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, attestation_agent, DeriveSealingKeyRequest, derive_sealing_key);
+    }
+}
+
 struct GetTeeTypeMethod {
     service: Arc<dyn AttestationAgentService + Send + Sync>,
 }
@@ -128,6 +190,72 @@ impl ::ttrpc::r#async::MethodHandler for GetTeeTypeMethod {
     }
 }
 
+struct GetEventlogMethod {
+    service: Arc<dyn AttestationAgentService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for GetEventlogMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, attestation_agent, GetEventlogRequest, get_eventlog);
+    }
+}
+
+struct VerifyEventlogMethod {
+    service: Arc<dyn AttestationAgentService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for VerifyEventlogMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, attestation_agent, VerifyEventlogRequest, verify_eventlog);
+    }
+}
+
+struct GetInitdataMethod {
+    service: Arc<dyn AttestationAgentService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for GetInitdataMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, attestation_agent, GetInitdataRequest, get_initdata);
+    }
+}
+
+struct UpdateConfigMethod {
+    service: Arc<dyn AttestationAgentService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for UpdateConfigMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, attestation_agent, UpdateConfigRequest, update_config);
+    }
+}
+
+struct GetPlatformReportMethod {
+    service: Arc<dyn AttestationAgentService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for GetPlatformReportMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, attestation_agent, GetPlatformReportRequest, get_platform_report);
+    }
+}
+
+struct GetEvidenceMetricsMethod {
+    service: Arc<dyn AttestationAgentService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for GetEvidenceMetricsMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, attestation_agent, GetEvidenceMetricsRequest, get_evidence_metrics);
+    }
+}
+
 #[async_trait]
 pub trait AttestationAgentService: Sync {
     async fn get_evidence(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::GetEvidenceRequest) -> ::ttrpc::Result<super::attestation_agent::GetEvidenceResponse> {
@@ -142,12 +270,36 @@ pub trait AttestationAgentService: Sync {
     async fn extend_runtime_measurement(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::ExtendRuntimeMeasurementRequest) -> ::ttrpc::Result<super::attestation_agent::ExtendRuntimeMeasurementResponse> {
         Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/ExtendRuntimeMeasurement is not supported".to_string())))
     }
+    async fn extend_runtime_measurements(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::ExtendRuntimeMeasurementsRequest) -> ::ttrpc::Result<super::attestation_agent::ExtendRuntimeMeasurementResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/ExtendRuntimeMeasurements is not supported".to_string())))
+    }
     async fn bind_init_data(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::BindInitDataRequest) -> ::ttrpc::Result<super::attestation_agent::BindInitDataResponse> {
         Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/BindInitData is not supported".to_string())))
     }
+    async fn derive_sealing_key(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::DeriveSealingKeyRequest) -> ::ttrpc::Result<super::attestation_agent::DeriveSealingKeyResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/DeriveSealingKey is not supported".to_string())))
+    }
     async fn get_tee_type(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::GetTeeTypeRequest) -> ::ttrpc::Result<super::attestation_agent::GetTeeTypeResponse> {
         Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/GetTeeType is not supported".to_string())))
     }
+    async fn get_eventlog(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::GetEventlogRequest) -> ::ttrpc::Result<super::attestation_agent::GetEventlogResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/GetEventlog is not supported".to_string())))
+    }
+    async fn verify_eventlog(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::VerifyEventlogRequest) -> ::ttrpc::Result<super::attestation_agent::VerifyEventlogResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/VerifyEventlog is not supported".to_string())))
+    }
+    async fn get_initdata(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::GetInitdataRequest) -> ::ttrpc::Result<super::attestation_agent::GetInitdataResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/GetInitdata is not supported".to_string())))
+    }
+    async fn update_config(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::UpdateConfigRequest) -> ::ttrpc::Result<super::attestation_agent::UpdateConfigResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/UpdateConfig is not supported".to_string())))
+    }
+    async fn get_platform_report(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::GetPlatformReportRequest) -> ::ttrpc::Result<super::attestation_agent::GetPlatformReportResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/GetPlatformReport is not supported".to_string())))
+    }
+    async fn get_evidence_metrics(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::attestation_agent::GetEvidenceMetricsRequest) -> ::ttrpc::Result<super::attestation_agent::GetEvidenceMetricsResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/attestation_agent.AttestationAgentService/GetEvidenceMetrics is not supported".to_string())))
+    }
 }
 
 pub fn create_attestation_agent_service(service: Arc<dyn AttestationAgentService + Send + Sync>) -> HashMap<String, ::ttrpc::r#async::Service> {
@@ -167,12 +319,36 @@ pub fn create_attestation_agent_service(service: Arc<dyn AttestationAgentService
     methods.insert("ExtendRuntimeMeasurement".to_string(),
                     Box::new(ExtendRuntimeMeasurementMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
 
+    methods.insert("ExtendRuntimeMeasurements".to_string(),
+                    Box::new(ExtendRuntimeMeasurementsMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
     methods.insert("BindInitData".to_string(),
                     Box::new(BindInitDataMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
 
+    methods.insert("DeriveSealingKey".to_string(),
+                    Box::new(DeriveSealingKeyMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
     methods.insert("GetTeeType".to_string(),
                     Box::new(GetTeeTypeMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
 
+    methods.insert("GetEventlog".to_string(),
+                    Box::new(GetEventlogMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("VerifyEventlog".to_string(),
+                    Box::new(VerifyEventlogMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("GetInitdata".to_string(),
+                    Box::new(GetInitdataMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("UpdateConfig".to_string(),
+                    Box::new(UpdateConfigMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("GetPlatformReport".to_string(),
+                    Box::new(GetPlatformReportMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("GetEvidenceMetrics".to_string(),
+                    Box::new(GetEvidenceMetricsMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
     ret.insert("attestation_agent.AttestationAgentService".to_string(), ::ttrpc::r#async::Service{ methods, streams });
     ret
 }
@@ -274,6 +274,8 @@ pub struct GetResourceRequest {
     // message fields
     // @@protoc_insertion_point(field:api.GetResourceRequest.ResourcePath)
     pub ResourcePath: ::std::string::String,
+    // @@protoc_insertion_point(field:api.GetResourceRequest.OutputPath)
+    pub OutputPath: ::std::string::String,
     // special fields
     // @@protoc_insertion_point(special_field:api.GetResourceRequest.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -291,13 +293,18 @@ impl GetResourceRequest {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut fields = ::std::vec::Vec::with_capacity(2);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
             "ResourcePath",
             |m: &GetResourceRequest| { &m.ResourcePath },
             |m: &mut GetResourceRequest| { &mut m.ResourcePath },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "OutputPath",
+            |m: &GetResourceRequest| { &m.OutputPath },
+            |m: &mut GetResourceRequest| { &mut m.OutputPath },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetResourceRequest>(
             "GetResourceRequest",
             fields,
@@ -319,6 +326,9 @@ impl ::protobuf::Message for GetResourceRequest {
                 10 => {
                     self.ResourcePath = is.read_string()?;
                 },
+                18 => {
+                    self.OutputPath = is.read_string()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -334,6 +344,9 @@ impl ::protobuf::Message for GetResourceRequest {
         if !self.ResourcePath.is_empty() {
             my_size += ::protobuf::rt::string_size(1, &self.ResourcePath);
         }
+        if !self.OutputPath.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.OutputPath);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -343,6 +356,9 @@ impl ::protobuf::Message for GetResourceRequest {
         if !self.ResourcePath.is_empty() {
             os.write_string(1, &self.ResourcePath)?;
         }
+        if !self.OutputPath.is_empty() {
+            os.write_string(2, &self.OutputPath)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -361,12 +377,14 @@ impl ::protobuf::Message for GetResourceRequest {
 
     fn clear(&mut self) {
         self.ResourcePath.clear();
+        self.OutputPath.clear();
         self.special_fields.clear();
     }
 
     fn default_instance() -> &'static GetResourceRequest {
         static instance: GetResourceRequest = GetResourceRequest {
             ResourcePath: ::std::string::String::new(),
+            OutputPath: ::std::string::String::new(),
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -396,6 +414,8 @@ pub struct GetResourceResponse {
     // message fields
     // @@protoc_insertion_point(field:api.GetResourceResponse.Resource)
     pub Resource: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:api.GetResourceResponse.Size)
+    pub Size: u64,
     // special fields
     // @@protoc_insertion_point(special_field:api.GetResourceResponse.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -413,13 +433,18 @@ impl GetResourceResponse {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut fields = ::std::vec::Vec::with_capacity(2);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
             "Resource",
             |m: &GetResourceResponse| { &m.Resource },
             |m: &mut GetResourceResponse| { &mut m.Resource },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "Size",
+            |m: &GetResourceResponse| { &m.Size },
+            |m: &mut GetResourceResponse| { &mut m.Size },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<GetResourceResponse>(
             "GetResourceResponse",
             fields,
@@ -441,6 +466,9 @@ impl ::protobuf::Message for GetResourceResponse {
                 10 => {
                     self.Resource = is.read_bytes()?;
                 },
+                16 => {
+                    self.Size = is.read_uint64()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -456,6 +484,9 @@ impl ::protobuf::Message for GetResourceResponse {
         if !self.Resource.is_empty() {
             my_size += ::protobuf::rt::bytes_size(1, &self.Resource);
         }
+        if self.Size != 0 {
+            my_size += ::protobuf::rt::uint64_size(2, self.Size);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -465,6 +496,9 @@ impl ::protobuf::Message for GetResourceResponse {
         if !self.Resource.is_empty() {
             os.write_bytes(1, &self.Resource)?;
         }
+        if self.Size != 0 {
+            os.write_uint64(2, self.Size)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -483,12 +517,14 @@ impl ::protobuf::Message for GetResourceResponse {
 
     fn clear(&mut self) {
         self.Resource.clear();
+        self.Size = 0;
         self.special_fields.clear();
     }
 
     fn default_instance() -> &'static GetResourceResponse {
         static instance: GetResourceResponse = GetResourceResponse {
             Resource: ::std::vec::Vec::new(),
+            Size: 0,
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -1090,27 +1126,29 @@ impl ::protobuf::reflect::ProtobufValue for ImagePullResponse {
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\tapi.proto\x12\x03api\"+\n\x11UnsealSecretInput\x12\x16\n\x06secret\
     \x18\x01\x20\x01(\x0cR\x06secret\"2\n\x12UnsealSecretOutput\x12\x1c\n\tp\
-    laintext\x18\x01\x20\x01(\x0cR\tplaintext\"8\n\x12GetResourceRequest\x12\
-    \"\n\x0cResourcePath\x18\x01\x20\x01(\tR\x0cResourcePath\"1\n\x13GetReso\
-    urceResponse\x12\x1a\n\x08Resource\x18\x01\x20\x01(\x0cR\x08Resource\"\
-    \xe8\x01\n\x12SecureMountRequest\x12\x1f\n\x0bvolume_type\x18\x01\x20\
-    \x01(\tR\nvolumeType\x12>\n\x07options\x18\x02\x20\x03(\x0b2$.api.Secure\
-    MountRequest.OptionsEntryR\x07options\x12\x14\n\x05flags\x18\x03\x20\x03\
-    (\tR\x05flags\x12\x1f\n\x0bmount_point\x18\x04\x20\x01(\tR\nmountPoint\
-    \x1a:\n\x0cOptionsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\
-    \x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"4\n\x13SecureMou\
-    ntResponse\x12\x1d\n\nmount_path\x18\x01\x20\x01(\tR\tmountPath\"P\n\x10\
-    ImagePullRequest\x12\x1b\n\timage_url\x18\x01\x20\x01(\tR\x08imageUrl\
-    \x12\x1f\n\x0bbundle_path\x18\x02\x20\x01(\tR\nbundlePath\"<\n\x11ImageP\
-    ullResponse\x12'\n\x0fmanifest_digest\x18\x01\x20\x01(\tR\x0emanifestDig\
-    est2V\n\x13SealedSecretService\x12?\n\x0cUnsealSecret\x12\x16.api.Unseal\
-    SecretInput\x1a\x17.api.UnsealSecretOutput2V\n\x12GetResourceService\x12\
-    @\n\x0bGetResource\x12\x17.api.GetResourceRequest\x1a\x18.api.GetResourc\
-    eResponse2V\n\x12SecureMountService\x12@\n\x0bSecureMount\x12\x17.api.Se\
-    cureMountRequest\x1a\x18.api.SecureMountResponse2N\n\x10ImagePullService\
-    \x12:\n\tPullImage\x12\x15.api.ImagePullRequest\x1a\x16.api.ImagePullRes\
-    ponseBaZ_github.com/confidential-containers/guest-components/confidentia\
-    l-data-hub/golang/pkg/api/cdhapib\x06proto3\
+    laintext\x18\x01\x20\x01(\x0cR\tplaintext\"X\n\x12GetResourceRequest\x12\
+    \"\n\x0cResourcePath\x18\x01\x20\x01(\tR\x0cResourcePath\x12\x1e\n\nOutp\
+    utPath\x18\x02\x20\x01(\tR\nOutputPath\"E\n\x13GetResourceResponse\x12\
+    \x1a\n\x08Resource\x18\x01\x20\x01(\x0cR\x08Resource\x12\x12\n\x04Size\
+    \x18\x02\x20\x01(\x04R\x04Size\"\xe8\x01\n\x12SecureMountRequest\x12\x1f\
+    \n\x0bvolume_type\x18\x01\x20\x01(\tR\nvolumeType\x12>\n\x07options\x18\
+    \x02\x20\x03(\x0b2$.api.SecureMountRequest.OptionsEntryR\x07options\x12\
+    \x14\n\x05flags\x18\x03\x20\x03(\tR\x05flags\x12\x1f\n\x0bmount_point\
+    \x18\x04\x20\x01(\tR\nmountPoint\x1a:\n\x0cOptionsEntry\x12\x10\n\x03key\
+    \x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05va\
+    lue:\x028\x01\"4\n\x13SecureMountResponse\x12\x1d\n\nmount_path\x18\x01\
+    \x20\x01(\tR\tmountPath\"P\n\x10ImagePullRequest\x12\x1b\n\timage_url\
+    \x18\x01\x20\x01(\tR\x08imageUrl\x12\x1f\n\x0bbundle_path\x18\x02\x20\
+    \x01(\tR\nbundlePath\"<\n\x11ImagePullResponse\x12'\n\x0fmanifest_digest\
+    \x18\x01\x20\x01(\tR\x0emanifestDigest2V\n\x13SealedSecretService\x12?\n\
+    \x0cUnsealSecret\x12\x16.api.UnsealSecretInput\x1a\x17.api.UnsealSecretO\
+    utput2V\n\x12GetResourceService\x12@\n\x0bGetResource\x12\x17.api.GetRes\
+    ourceRequest\x1a\x18.api.GetResourceResponse2V\n\x12SecureMountService\
+    \x12@\n\x0bSecureMount\x12\x17.api.SecureMountRequest\x1a\x18.api.Secure\
+    MountResponse2N\n\x10ImagePullService\x12:\n\tPullImage\x12\x15.api.Imag\
+    ePullRequest\x1a\x16.api.ImagePullResponseBaZ_github.com/confidential-co\
+    ntainers/guest-components/confidential-data-hub/golang/pkg/api/cdhapib\
+    \x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file